@@ -1,4 +1,4 @@
-//! This code will show statistics for all files in the `test-code` directory,
+//! This code will show parsing and printing statistics for all files in the `test-code` directory,
 //! and show percentage differences compared to the previous run (if it exists).
 //! For the highest and lowest runs this change may vary significantly..
 //! like 1000% faster/slower, so the main comparison is the total and average
@@ -11,22 +11,20 @@
 mod benchmark_results;
 
 use benchmark_results::{BenchMarkResult, BenchMarkResults};
-use luau_parser::prelude::Parser;
+use luau_parser::prelude::{Parser, ParserOptions, Print};
 use std::{
     fs::{self, File},
     io::{self, Read},
-    path::Path,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
 const ITERATIONS: usize = 1000;
 const WARM_UP_ITERATIONS: usize = 50;
 
-fn bench(parser: &mut Parser, uri: &str, content: &str) -> BenchMarkResult {
-    parser.set_input(content);
-
+fn bench(mut function: impl FnMut()) -> BenchMarkResult {
     for _ in 0..WARM_UP_ITERATIONS {
-        let _ = parser.parse(uri);
+        function();
     }
 
     let mut total = Duration::default();
@@ -35,7 +33,7 @@ fn bench(parser: &mut Parser, uri: &str, content: &str) -> BenchMarkResult {
 
     for _ in 0..ITERATIONS {
         let start = Instant::now();
-        let _ = parser.parse(uri);
+        function();
         let elapsed = start.elapsed();
 
         if elapsed < lowest {
@@ -66,9 +64,20 @@ fn process_files(src_dir: &Path, benchmark_results: &mut BenchMarkResults) -> io
             let mut content = String::new();
             file.read_to_string(&mut content)?;
 
-            let mut parser = Parser::new(&content);
-            let time_taken = bench(&mut parser, path.to_string_lossy().as_ref(), &content);
+            let uri = path.to_string_lossy().to_string();
+            let mut parser = Parser::new(&content, ParserOptions::default());
+            let time_taken = bench(|| {
+                parser.set_input(&content);
+                let _ = parser.parse(&uri);
+            });
+
+            parser.set_input(&content);
+            let cst = parser.parse(&uri);
+            let print_time_taken = bench(|| {
+                let _ = cst.block.print();
+            });
 
+            benchmark_results.insert(PathBuf::from(format!("{uri} (print)")), print_time_taken);
             benchmark_results.insert(path, time_taken);
         }
     }
@@ -89,12 +98,9 @@ fn print_single_results(path: &Path, benchmark_result: &BenchMarkResult) {
     let (average, difference_1, difference_2) = calculate_data(benchmark_result);
 
     eprintln!("File at '{}'", path.display());
+    eprintln!("Ran {} times in: {:?}", ITERATIONS, benchmark_result.total);
     eprintln!(
-        "Parsed {} times in: {:?}",
-        ITERATIONS, benchmark_result.total
-    );
-    eprintln!(
-        "Average time/parse: {:?} (-{:?}/+{:?})",
+        "Average time/run: {:?} (-{:?}/+{:?})",
         average, difference_1, difference_2
     );
 }
@@ -126,11 +132,11 @@ fn print_and_compare_results(
 
     eprintln!("File at '{}'", path.display());
     eprintln!(
-        "Parsed {} times in: {:?}({})",
+        "Ran {} times in: {:?}({})",
         ITERATIONS, benchmark_result.total, total_difference
     );
     eprintln!(
-        "Average time/parse: {:?}({}) (-{:?}({})/+{:?}({}))",
+        "Average time/run: {:?}({}) (-{:?}({})/+{:?}({}))",
         average,
         average_difference,
         difference_1,
@@ -0,0 +1,1015 @@
+//! # Builders
+//!
+//! Functions creating [`Cst`] nodes without fabricating [`Token`]s, trivia,
+//! [`Pointer`]s, and list separators by hand. All nodes get the spacing
+//! idiomatic Luau code has, so [`Print`](crate::types::Print)ing them gives
+//! readable code:
+//!
+//! * Tokens are separated by single spaces, except around `.`, `:`, brackets,
+//!   and `,` which is only followed by one.
+//! * Statements are on their own lines, and blocks are indented by 4 spaces.
+//! * Operands of operators are wrapped in parenthesis when needed to keep the
+//!   order of operations of the built tree.
+//!
+//! ```rust
+//! # use luau_parser::{build::{block, call, local, name, string}, types::Print};
+//! let code = block([
+//!     local("x", call("require", [string("Foo")])).into(),
+//!     call("print", [name("x")]).into(),
+//! ]);
+//!
+//! assert_eq!(code.print(), "local x = require(\"Foo\")\nprint(x)");
+//! ```
+//!
+//! ## Note
+//!
+//! Built tokens have no positions, they're [`Token::empty`]. Print and parse
+//! the code to get them.
+
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType, Trivia};
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::types::{
+    Associativity, BinaryOperator, Block, Bracketed, BracketedList, Closure, CompoundSetExpression,
+    Cst, DoBlock, ElseIfStatement, ElseStatement, EndOfFile, Expression, FunctionArgument,
+    FunctionArguments, FunctionCall, FunctionCallInvoked, GenericFor, GlobalFunction,
+    GlobalFunctionName, IfStatement, List, ListItem, LocalAssignment, LocalFunction, Name,
+    NumericalFor, Parameter, Pointer, PrefixExp, RepeatBlock, SetExpression, Statement, Table,
+    TableAccess, TableAccessKey, TableAccessPrefix, TableField, TableFieldValue, TableKey,
+    TerminationStatement, TypeDefinition, TypeFunction, UnaryOperator, Var, VisitMut, VisitorMut,
+    WhileLoop,
+};
+
+/// The indentation added for each nested block.
+const INDENT: &str = "    ";
+
+/// A [`VisitorMut`] replacing the leading trivia of the first visited token.
+struct LeadingTrivia<'a> {
+    /// The spaces to use, [`None`] once they were used.
+    spaces: Option<&'a str>,
+
+    /// The type of the first visited token.
+    token_type: Option<TokenType>,
+}
+
+impl VisitorMut for LeadingTrivia<'_> {
+    fn visit_token(&mut self, token: &mut Token) {
+        if let Some(spaces) = self.spaces.take() {
+            token.leading_trivia = trivia(spaces);
+            self.token_type = Some(token.token_type.clone());
+        }
+    }
+}
+
+/// A [`VisitorMut`] indenting every line break in leading trivia by one level.
+struct Indenter;
+
+impl VisitorMut for Indenter {
+    fn visit_token(&mut self, token: &mut Token) {
+        for trivia in &mut token.leading_trivia {
+            if let Trivia::Spaces(spaces) = trivia
+                && spaces.contains('\n')
+            {
+                *spaces = spaces.replace('\n', &format!("\n{INDENT}")).into();
+            }
+        }
+    }
+}
+
+/// Create trivia made of the passed spaces.
+#[inline]
+fn trivia(spaces: &str) -> Vec<Trivia> {
+    if spaces.is_empty() {
+        Vec::new()
+    } else {
+        vec![Trivia::Spaces(spaces.into())]
+    }
+}
+
+/// Create a token from the passed text with `spaces` before it.
+fn token(text: &str, spaces: &str) -> Token {
+    let mut token = Token::empty(Lexer::new(text).next_token().token_type);
+    token.leading_trivia = trivia(spaces);
+
+    token
+}
+
+/// Whether or not the passed text can be used as a name.
+fn is_name(text: &str) -> bool {
+    match Lexer::new(text).next_token().token_type {
+        TokenType::Identifier(name) => name == text,
+        TokenType::PartialKeyword(keyword) => {
+            TokenType::PartialKeyword(keyword)
+                .try_as_string()
+                .as_deref()
+                == Some(text)
+        }
+        _ => false,
+    }
+}
+
+/// Create a name token from the passed text with `spaces` before it.
+///
+/// # Panics
+///
+/// If `text` isn't a valid name.
+fn name_token(text: &str, spaces: &str) -> Token {
+    assert!(is_name(text), "`{text}` isn't a valid name");
+
+    token(text, spaces)
+}
+
+/// Replace the leading trivia of the first token of `node` with `spaces`,
+/// returning the type of that token.
+fn set_leading<N: VisitMut + ?Sized>(node: &mut N, spaces: &str) -> Option<TokenType> {
+    let mut visitor = LeadingTrivia {
+        spaces: Some(spaces),
+        token_type: None,
+    };
+    node.visit_mut(&mut visitor);
+
+    visitor.token_type
+}
+
+/// [`set_leading`], taking and returning the node.
+#[inline]
+fn spaced<N: VisitMut>(mut node: N, spaces: &str) -> N {
+    set_leading(&mut node, spaces);
+
+    node
+}
+
+/// Create a list of the passed items, with `first` before the first one and
+/// a space before the others.
+fn list<T: VisitMut>(items: impl IntoIterator<Item = T>, first: &str) -> List<T> {
+    let mut items = items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| ListItem::Trailing {
+            item: spaced(item, if index == 0 { first } else { " " }),
+            separator: token(",", ""),
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(ListItem::Trailing { item, .. }) = items.pop() {
+        items.push(ListItem::NonTrailing(item));
+    }
+
+    List { items }
+}
+
+/// Wrap the passed item in brackets with no spaces inside them.
+#[inline]
+fn bracketed<T>(opening: &str, item: T, closing: &str) -> Bracketed<T> {
+    Bracketed {
+        opening_bracket: token(opening, ""),
+        item,
+        closing_bracket: token(closing, ""),
+    }
+}
+
+/// Create a list of parameters from their names, which may be `...`.
+fn parameters<'a>(names: impl IntoIterator<Item = &'a str>) -> BracketedList<Parameter> {
+    let parameters = names.into_iter().map(|name| Parameter {
+        name: if name == "..." {
+            token(name, "")
+        } else {
+            name_token(name, "")
+        },
+        colon: None,
+        r#type: None,
+    });
+
+    bracketed("(", list(parameters, ""), ")")
+}
+
+/// Prepare `body` to be nested between two keywords, indenting it and putting
+/// it on its own lines.
+fn nested(mut body: Block) -> Block {
+    if !body.is_empty() {
+        set_leading(&mut body, "\n");
+        body.visit_mut(&mut Indenter);
+    }
+
+    body
+}
+
+/// Create the keyword closing the passed body, on its own line if the body
+/// isn't empty.
+#[inline]
+fn closing(keyword: &str, body: &Block) -> Token {
+    token(keyword, if body.is_empty() { " " } else { "\n" })
+}
+
+/// How tightly the passed expression binds when used as an operand.
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::BinaryExpression { operator, .. } => operator.precedence(),
+        Expression::UnaryExpression { operator, .. } => operator.precedence(),
+        Expression::IfExpression(_) => 0,
+        _ => u8::MAX,
+    }
+}
+
+/// Conversions to [`PrefixExp`]s, for functions being called and tables being
+/// indexed.
+pub trait IntoPrefixExp {
+    /// Convert this value into a [`PrefixExp`].
+    fn into_prefix_exp(self) -> PrefixExp;
+}
+
+impl IntoPrefixExp for PrefixExp {
+    #[inline]
+    fn into_prefix_exp(self) -> PrefixExp {
+        self
+    }
+}
+
+impl IntoPrefixExp for &str {
+    /// Use a variable with this name, see [`name`].
+    ///
+    /// # Panics
+    ///
+    /// If this isn't a valid name.
+    #[inline]
+    fn into_prefix_exp(self) -> PrefixExp {
+        PrefixExp::Var(Var::Name(name_token(self, "")))
+    }
+}
+
+impl IntoPrefixExp for FunctionCall {
+    #[inline]
+    fn into_prefix_exp(self) -> PrefixExp {
+        PrefixExp::FunctionCall(self)
+    }
+}
+
+impl IntoPrefixExp for Expression {
+    /// Variables, function calls, and expressions in parenthesis are used as
+    /// is, other expressions are wrapped in parenthesis.
+    fn into_prefix_exp(self) -> PrefixExp {
+        match self {
+            Self::Var(var) => PrefixExp::Var(var),
+            Self::FunctionCall(function_call) => PrefixExp::FunctionCall(function_call),
+            Self::ExpressionWrap(wrap) => PrefixExp::ExpressionWrap(wrap),
+            expression => PrefixExp::ExpressionWrap(bracketed("(", Pointer::new(expression), ")")),
+        }
+    }
+}
+
+impl From<PrefixExp> for Expression {
+    #[inline]
+    fn from(value: PrefixExp) -> Self {
+        match value {
+            PrefixExp::Var(var) => Self::Var(var),
+            PrefixExp::FunctionCall(function_call) => Self::FunctionCall(function_call),
+            PrefixExp::ExpressionWrap(wrap) => Self::ExpressionWrap(wrap),
+        }
+    }
+}
+
+impl From<FunctionCall> for Expression {
+    #[inline]
+    fn from(value: FunctionCall) -> Self {
+        Self::FunctionCall(value)
+    }
+}
+
+/// Helper macro to implement [`From`] for [`Statement`] for all nodes it can
+/// hold.
+macro_rules! impl_into_statement {
+    ($($name: ident),* $(,)?) => {
+        $(
+            impl From<$name> for Statement {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    Self::$name(Pointer::new(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_statement!(
+    LocalFunction,
+    LocalAssignment,
+    TypeDefinition,
+    IfStatement,
+    DoBlock,
+    GenericFor,
+    NumericalFor,
+    RepeatBlock,
+    WhileLoop,
+    SetExpression,
+    CompoundSetExpression,
+    FunctionCall,
+    GlobalFunction,
+    TypeFunction,
+);
+
+/// `nil`.
+#[inline]
+pub fn nil() -> Expression {
+    Expression::Nil(token("nil", ""))
+}
+
+/// `true` or `false`.
+#[inline]
+pub fn boolean(value: bool) -> Expression {
+    Expression::Boolean(token(if value { "true" } else { "false" }, ""))
+}
+
+/// A number, negative ones are negated with `-`, infinite ones use
+/// `math.huge`, and NaN is `0 / 0`.
+pub fn number(value: impl Display) -> Expression {
+    let text = value.to_string();
+
+    if let Some(positive) = text.strip_prefix('-') {
+        unary("-", number(positive))
+    } else if text == "inf" {
+        index("math", "huge")
+    } else if text == "NaN" {
+        paren(binary(number(0), "/", number(0)))
+    } else {
+        Expression::Number(token(&text, ""))
+    }
+}
+
+/// A double quoted string with the passed contents, escaping them as needed.
+pub fn string(value: &str) -> Expression {
+    let mut text = String::from('"');
+    for character in value.chars() {
+        match character {
+            '"' => text.push_str("\\\""),
+            '\\' => text.push_str("\\\\"),
+            '\n' => text.push_str("\\n"),
+            '\r' => text.push_str("\\r"),
+            '\t' => text.push_str("\\t"),
+            character if character.is_ascii_control() => {
+                text.push_str(&format!("\\{:03}", character as u8));
+            }
+            character => text.push(character),
+        }
+    }
+    text.push('"');
+
+    Expression::String(token(&text, ""))
+}
+
+/// A variable.
+///
+/// # Panics
+///
+/// If `name` isn't a valid name.
+#[inline]
+pub fn name(name: &str) -> Expression {
+    Expression::Var(Var::Name(name_token(name, "")))
+}
+
+/// Split the passed object into the prefix and keys of a [`TableAccess`].
+fn table_access(object: impl IntoPrefixExp) -> (TableAccessPrefix, Vec<TableAccessKey>) {
+    match object.into_prefix_exp() {
+        PrefixExp::Var(Var::Name(name)) => (TableAccessPrefix::Name(name), Vec::new()),
+        PrefixExp::Var(Var::TableAccess(access)) => (access.prefix, access.accessed_keys),
+        PrefixExp::Var(Var::ERROR) => (
+            TableAccessPrefix::ExpressionWrap(Pointer::new(bracketed(
+                "(",
+                Pointer::new(Expression::Var(Var::ERROR)),
+                ")",
+            ))),
+            Vec::new(),
+        ),
+        PrefixExp::FunctionCall(function_call) => (
+            TableAccessPrefix::FunctionCall(Pointer::new(function_call)),
+            Vec::new(),
+        ),
+        PrefixExp::ExpressionWrap(wrap) => (
+            TableAccessPrefix::ExpressionWrap(Pointer::new(wrap)),
+            Vec::new(),
+        ),
+    }
+}
+
+/// `object.key`, or `object["key"]` if `key` isn't a valid name.
+pub fn index(object: impl IntoPrefixExp, key: &str) -> Expression {
+    if !is_name(key) {
+        return index_with(object, string(key));
+    }
+
+    let (prefix, mut accessed_keys) = table_access(object);
+    accessed_keys.push(TableAccessKey::Name {
+        dot: Pointer::new(token(".", "")),
+        name: Pointer::new(name_token(key, "")),
+    });
+
+    Expression::Var(Var::TableAccess(TableAccess {
+        prefix,
+        accessed_keys,
+    }))
+}
+
+/// `object[key]`.
+pub fn index_with(object: impl IntoPrefixExp, key: impl Into<Expression>) -> Expression {
+    let (prefix, mut accessed_keys) = table_access(object);
+    accessed_keys.push(TableAccessKey::Expression(Pointer::new(
+        TableKey::Expression(bracketed("[", Pointer::new(key.into()), "]")),
+    )));
+
+    Expression::Var(Var::TableAccess(TableAccess {
+        prefix,
+        accessed_keys,
+    }))
+}
+
+/// Create the arguments of a function call.
+fn arguments(arguments: impl IntoIterator<Item = Expression>) -> FunctionArguments {
+    let arguments = arguments
+        .into_iter()
+        .map(|argument| Pointer::new(FunctionArgument::Expression(Pointer::new(argument))));
+
+    FunctionArguments::List(bracketed("(", list(arguments, ""), ")"))
+}
+
+/// `function(arguments)`.
+pub fn call(
+    function: impl IntoPrefixExp,
+    arguments: impl IntoIterator<Item = Expression>,
+) -> FunctionCall {
+    FunctionCall {
+        invoked: FunctionCallInvoked::Function(Pointer::new(function.into_prefix_exp())),
+        arguments: self::arguments(arguments),
+    }
+}
+
+/// `object:method(arguments)`.
+///
+/// # Panics
+///
+/// If `method` isn't a valid name.
+pub fn method_call(
+    object: impl IntoPrefixExp,
+    method: &str,
+    arguments: impl IntoIterator<Item = Expression>,
+) -> FunctionCall {
+    FunctionCall {
+        invoked: FunctionCallInvoked::TableMethod {
+            table: Pointer::new(object.into_prefix_exp()),
+            colon: Pointer::new(token(":", "")),
+            method: Pointer::new(name_token(method, "")),
+        },
+        arguments: self::arguments(arguments),
+    }
+}
+
+/// A table with the passed fields on one line, like `{ a = 1, 2 }`.
+/// Positional fields are numbered in order.
+pub fn table(fields: impl IntoIterator<Item = TableField>) -> Expression {
+    let mut position = 0;
+    let fields = fields.into_iter().map(|mut field| {
+        if let TableKey::UndefinedNumber(_) = *field.key {
+            position += 1;
+            field.key = Pointer::new(TableKey::UndefinedNumber(position));
+        }
+
+        field
+    });
+
+    let fields = list(fields, " ");
+    let spaces = if fields.items.is_empty() { "" } else { " " };
+
+    Expression::Table(Table(Bracketed {
+        opening_bracket: token("{", ""),
+        item: fields,
+        closing_bracket: token("}", spaces),
+    }))
+}
+
+/// A `key = value` table field, or `["key"] = value` if `key` isn't a valid
+/// name.
+pub fn field(key: &str, value: impl Into<Expression>) -> TableField {
+    if !is_name(key) {
+        return field_with(string(key), value);
+    }
+
+    TableField {
+        key: Pointer::new(TableKey::Simple(name_token(key, ""))),
+        equal_or_colon: Some(token("=", " ")),
+        value: Pointer::new(TableFieldValue::Expression(spaced(value.into(), " "))),
+    }
+}
+
+/// A `[key] = value` table field.
+pub fn field_with(key: impl Into<Expression>, value: impl Into<Expression>) -> TableField {
+    TableField {
+        key: Pointer::new(TableKey::Expression(bracketed(
+            "[",
+            Pointer::new(key.into()),
+            "]",
+        ))),
+        equal_or_colon: Some(token("=", " ")),
+        value: Pointer::new(TableFieldValue::Expression(spaced(value.into(), " "))),
+    }
+}
+
+/// A positional table field, without a key.
+pub fn item(value: impl Into<Expression>) -> TableField {
+    TableField {
+        key: Pointer::new(TableKey::UndefinedNumber(1)),
+        equal_or_colon: None,
+        value: Pointer::new(TableFieldValue::Expression(value.into())),
+    }
+}
+
+/// Create a [`BinaryOperator`] from its text.
+fn binary_operator(operator: &str) -> Option<BinaryOperator> {
+    let variant = match operator {
+        "+" => BinaryOperator::Plus,
+        "-" => BinaryOperator::Minus,
+        "*" => BinaryOperator::Multiplication,
+        "/" => BinaryOperator::Division,
+        "//" => BinaryOperator::FloorDivision,
+        "%" => BinaryOperator::Modulo,
+        "^" => BinaryOperator::Exponentiation,
+        ".." => BinaryOperator::Concatenation,
+        "==" => BinaryOperator::Equal,
+        "~=" => BinaryOperator::NotEqual,
+        "<" => BinaryOperator::LessThan,
+        "<=" => BinaryOperator::LessThanOrEqualTo,
+        ">" => BinaryOperator::GreaterThan,
+        ">=" => BinaryOperator::GreaterThanOrEqualTo,
+        "and" => BinaryOperator::And,
+        "or" => BinaryOperator::Or,
+        _ => return None,
+    };
+
+    Some(variant(token(operator, " ")))
+}
+
+/// `left operator right`, like `a + b`. Operands are wrapped in parenthesis
+/// if they'd otherwise be grouped differently when parsed.
+///
+/// # Panics
+///
+/// If `operator` isn't a binary operator.
+pub fn binary(
+    left: impl Into<Expression>,
+    operator: &str,
+    right: impl Into<Expression>,
+) -> Expression {
+    let operator =
+        binary_operator(operator).unwrap_or_else(|| panic!("`{operator}` isn't a binary operator"));
+    let (left, right) = (left.into(), right.into());
+
+    let left_associative = operator.associativity() == Associativity::Left;
+    let left = match precedence(&left).cmp(&operator.precedence()) {
+        Ordering::Less => paren(left),
+        Ordering::Equal if !left_associative => paren(left),
+        _ => left,
+    };
+    let right = match right {
+        // Unary operators only apply to what's after them.
+        Expression::UnaryExpression { .. } => right,
+        _ if precedence(&right) < operator.precedence()
+            || (precedence(&right) == operator.precedence() && left_associative) =>
+        {
+            paren(right)
+        }
+        _ => right,
+    };
+
+    Expression::BinaryExpression {
+        left: Pointer::new(left),
+        operator,
+        right: Pointer::new(spaced(right, " ")),
+    }
+}
+
+/// `operator expression`, like `-a` or `not a`. The expression is wrapped in
+/// parenthesis if it'd otherwise be grouped differently when parsed.
+///
+/// # Panics
+///
+/// If `operator` isn't `-`, `not`, or `#`.
+pub fn unary(operator: &str, expression: impl Into<Expression>) -> Expression {
+    let operator = match operator {
+        "-" => UnaryOperator::Minus(token(operator, "")),
+        "not" => UnaryOperator::Not(token(operator, "")),
+        "#" => UnaryOperator::Length(token(operator, "")),
+        _ => panic!("`{operator}` isn't a unary operator"),
+    };
+
+    let mut expression = expression.into();
+    if precedence(&expression) < operator.precedence() {
+        expression = paren(expression);
+    }
+
+    // `not` needs a space, and so does `- -a` to not be a comment.
+    let spaces = match (&operator, &expression) {
+        (UnaryOperator::Not(_), _)
+        | (
+            UnaryOperator::Minus(_),
+            Expression::UnaryExpression {
+                operator: UnaryOperator::Minus(_),
+                ..
+            },
+        ) => " ",
+        _ => "",
+    };
+
+    Expression::UnaryExpression {
+        operator,
+        expression: Pointer::new(spaced(expression, spaces)),
+    }
+}
+
+/// `(expression)`.
+#[inline]
+pub fn paren(expression: impl Into<Expression>) -> Expression {
+    Expression::ExpressionWrap(bracketed("(", Pointer::new(expression.into()), ")"))
+}
+
+/// `function(parameters) body end`. Parameters can be `...`.
+///
+/// # Panics
+///
+/// If one of the `parameters` isn't a valid name.
+pub fn closure<'a>(parameters: impl IntoIterator<Item = &'a str>, body: Block) -> Expression {
+    let body = nested(body);
+    let end_keyword = closing("end", &body);
+
+    Expression::Closure(Pointer::new(Closure {
+        attributes: Vec::new(),
+        function_keyword: token("function", ""),
+        generics: None,
+        parameters: self::parameters(parameters),
+        colon: None,
+        return_type: None,
+        body,
+        end_keyword,
+    }))
+}
+
+/// `local name = value`.
+///
+/// # Panics
+///
+/// If `name` isn't a valid name.
+#[inline]
+pub fn local(name: &str, value: impl Into<Expression>) -> LocalAssignment {
+    locals([name], [value.into()])
+}
+
+/// `local names = values`, the `=` is omitted if there are no values.
+///
+/// # Panics
+///
+/// If one of the `names` isn't a valid name.
+pub fn locals<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    values: impl IntoIterator<Item = Expression>,
+) -> LocalAssignment {
+    let names = names.into_iter().map(|name| Name {
+        name: name_token(name, ""),
+        colon: None,
+        r#type: None,
+    });
+    let expressions = list(values.into_iter().map(Pointer::new), " ");
+
+    LocalAssignment {
+        local_token: token("local", ""),
+        name_list: list(names, " "),
+        equal_token: (!expressions.items.is_empty()).then(|| token("=", " ")),
+        expressions,
+    }
+}
+
+/// `variable = value`.
+///
+/// # Panics
+///
+/// If `variable` isn't an [`Expression::Var`].
+pub fn assign(variable: Expression, value: impl Into<Expression>) -> SetExpression {
+    let Expression::Var(variable) = variable else {
+        panic!("only variables can be assigned to");
+    };
+
+    SetExpression {
+        variables: list([variable], ""),
+        equal: token("=", " "),
+        values: list([Pointer::new(value.into())], " "),
+    }
+}
+
+/// `local function name(parameters) body end`. Parameters can be `...`.
+///
+/// # Panics
+///
+/// If `name` or one of the `parameters` isn't a valid name.
+pub fn local_function<'a>(
+    name: &str,
+    parameters: impl IntoIterator<Item = &'a str>,
+    body: Block,
+) -> LocalFunction {
+    let body = nested(body);
+    let end_keyword = closing("end", &body);
+
+    LocalFunction {
+        attributes: Vec::new(),
+        local_keyword: token("local", ""),
+        function_keyword: token("function", " "),
+        function_name: name_token(name, " "),
+        generics: None,
+        parameters: self::parameters(parameters),
+        colon: None,
+        return_type: None,
+        body,
+        end_keyword,
+    }
+}
+
+/// `function name(parameters) body end`. The name can be a path like
+/// `Foo.bar:baz`, and parameters can be `...`.
+///
+/// # Panics
+///
+/// If a part of `name` or one of the `parameters` isn't a valid name.
+pub fn function<'a>(
+    name: &str,
+    parameters: impl IntoIterator<Item = &'a str>,
+    body: Block,
+) -> GlobalFunction {
+    let (path, method) = match name.split_once(':') {
+        Some((path, method)) => (path, Some(method)),
+        None => (name, None),
+    };
+    let mut keys = path.split('.');
+    let table = name_token(keys.next().unwrap_or_default(), " ");
+    let keys = keys
+        .map(|key| TableAccessKey::Name {
+            dot: Pointer::new(token(".", "")),
+            name: Pointer::new(name_token(key, "")),
+        })
+        .collect::<Vec<_>>();
+
+    let function_name = if keys.is_empty() && method.is_none() {
+        GlobalFunctionName::SimpleName(table)
+    } else {
+        GlobalFunctionName::Table {
+            table,
+            keys,
+            method: method.map(|method| Pointer::new((token(":", ""), name_token(method, "")))),
+        }
+    };
+
+    let body = nested(body);
+    let end_keyword = closing("end", &body);
+
+    GlobalFunction {
+        attributes: Vec::new(),
+        function_keyword: token("function", ""),
+        function_name,
+        generics: None,
+        parameters: self::parameters(parameters),
+        colon: None,
+        return_type: None,
+        body,
+        end_keyword,
+    }
+}
+
+/// `if condition then body end`. Use [`with_else_if`] and [`with_else`] to
+/// add more branches.
+pub fn if_statement(condition: impl Into<Expression>, body: Block) -> IfStatement {
+    let body = nested(body);
+    let end_keyword = closing("end", &body);
+
+    IfStatement {
+        if_keyword: token("if", ""),
+        condition: Pointer::new(spaced(condition.into(), " ")),
+        then_keyword: token("then", " "),
+        body,
+        else_if_statements: Vec::new(),
+        else_statement: None,
+        end_keyword,
+    }
+}
+
+/// Add an `elseif condition then body` branch to the passed if statement,
+/// after its other `elseif` branches.
+pub fn with_else_if(
+    mut statement: IfStatement,
+    condition: impl Into<Expression>,
+    body: Block,
+) -> IfStatement {
+    statement.else_if_statements.push(ElseIfStatement {
+        elseif_keyword: token("elseif", "\n"),
+        condition: Pointer::new(spaced(condition.into(), " ")),
+        then_keyword: token("then", " "),
+        body: nested(body),
+    });
+    statement.end_keyword = closing("end", last_body(&statement));
+
+    statement
+}
+
+/// Set the `else body` branch of the passed if statement.
+pub fn with_else(mut statement: IfStatement, body: Block) -> IfStatement {
+    statement.else_statement = Some(ElseStatement {
+        else_keyword: token("else", "\n"),
+        body: nested(body),
+    });
+    statement.end_keyword = closing("end", last_body(&statement));
+
+    statement
+}
+
+/// The body of the last branch of the passed if statement.
+fn last_body(statement: &IfStatement) -> &Block {
+    if let Some(else_statement) = &statement.else_statement {
+        &else_statement.body
+    } else if let Some(else_if_statement) = statement.else_if_statements.last() {
+        &else_if_statement.body
+    } else {
+        &statement.body
+    }
+}
+
+/// Create a `do body end` block with `do` preceded by `spaces`.
+fn do_end(body: Block, spaces: &str) -> DoBlock {
+    let body = nested(body);
+    let end_keyword = closing("end", &body);
+
+    DoBlock {
+        do_keyword: token("do", spaces),
+        body,
+        end_keyword,
+    }
+}
+
+/// `do body end`.
+#[inline]
+pub fn do_block(body: Block) -> DoBlock {
+    do_end(body, "")
+}
+
+/// `while condition do body end`.
+pub fn while_loop(condition: impl Into<Expression>, body: Block) -> WhileLoop {
+    WhileLoop {
+        while_keyword: token("while", ""),
+        condition: Pointer::new(spaced(condition.into(), " ")),
+        do_block: do_end(body, " "),
+    }
+}
+
+/// `repeat body until condition`.
+pub fn repeat_until(body: Block, condition: impl Into<Expression>) -> RepeatBlock {
+    let body = nested(body);
+    let until_keyword = closing("until", &body);
+
+    RepeatBlock {
+        repeat_keyword: token("repeat", ""),
+        body,
+        until_keyword,
+        condition: Pointer::new(spaced(condition.into(), " ")),
+    }
+}
+
+/// `for name = start, end, step do body end`, the step is omitted if it's
+/// [`None`].
+///
+/// # Panics
+///
+/// If `name` isn't a valid name.
+pub fn numerical_for(
+    name: &str,
+    start: impl Into<Expression>,
+    end: impl Into<Expression>,
+    step: Option<Expression>,
+    body: Block,
+) -> NumericalFor {
+    NumericalFor {
+        for_keyword: token("for", ""),
+        variable: Name {
+            name: name_token(name, " "),
+            colon: None,
+            r#type: None,
+        },
+        equal_keyword: token("=", " "),
+        start: Pointer::new(spaced(start.into(), " ")),
+        start_comma: token(",", ""),
+        end: Pointer::new(spaced(end.into(), " ")),
+        end_comma: step.is_some().then(|| token(",", "")),
+        step: step.map(|step| Pointer::new(spaced(step, " "))),
+        do_block: do_end(body, " "),
+    }
+}
+
+/// `for names in values do body end`.
+///
+/// # Panics
+///
+/// If one of the `names` isn't a valid name.
+pub fn generic_for<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    values: impl IntoIterator<Item = Expression>,
+    body: Block,
+) -> GenericFor {
+    let names = names.into_iter().map(|name| Name {
+        name: name_token(name, ""),
+        colon: None,
+        r#type: None,
+    });
+
+    GenericFor {
+        for_keyword: token("for", ""),
+        names: list(names, " "),
+        in_keyword: token("in", " "),
+        expressions: list(values.into_iter().map(Pointer::new), " "),
+        do_block: do_end(body, " "),
+    }
+}
+
+/// `return values`.
+pub fn return_statement(values: impl IntoIterator<Item = Expression>) -> TerminationStatement {
+    let expressions = list(values.into_iter().map(Pointer::new), " ");
+
+    TerminationStatement::Return {
+        return_keyword: token("return", ""),
+        expressions: (!expressions.items.is_empty()).then_some(expressions),
+    }
+}
+
+/// `break`.
+#[inline]
+pub fn break_statement() -> TerminationStatement {
+    TerminationStatement::Break(token("break", ""))
+}
+
+/// `continue`.
+#[inline]
+pub fn continue_statement() -> TerminationStatement {
+    TerminationStatement::Continue(token("continue", ""))
+}
+
+/// A block with each of the passed statements on its own line.
+#[inline]
+pub fn block(statements: impl IntoIterator<Item = Statement>) -> Block {
+    block_with_last(statements, None)
+}
+
+/// [`block`], ending with the passed `return`, `break`, or `continue`.
+pub fn block_with_last(
+    statements: impl IntoIterator<Item = Statement>,
+    last_statement: Option<TerminationStatement>,
+) -> Block {
+    let mut block = Block::default();
+
+    for mut statement in statements {
+        let spaces = if block.statements.is_empty() {
+            ""
+        } else {
+            "\n"
+        };
+        let first = set_leading(&mut statement, spaces);
+
+        // `f()\n(g)()` is a single statement.
+        if first == Some(TokenType::Symbol(Symbol::OpeningParenthesis))
+            && let Some((_, semicolon)) = block.statements.last_mut()
+        {
+            *semicolon = Some(token(";", ""));
+        }
+
+        block.statements.push((Pointer::new(statement), None));
+    }
+
+    if let Some(mut last_statement) = last_statement {
+        let spaces = if block.statements.is_empty() {
+            ""
+        } else {
+            "\n"
+        };
+        set_leading(&mut last_statement, spaces);
+
+        block.last_statement = Some((Pointer::new(last_statement), None));
+    }
+
+    block
+}
+
+/// A [`Cst`] holding the passed block, ending with a new line.
+pub fn cst(mut block: Block) -> Cst {
+    let mut end_of_file = Token::empty(TokenType::EndOfFile);
+    end_of_file.leading_trivia = trivia("\n");
+
+    block.statements.push((
+        Pointer::new(Statement::EndOfFile(Pointer::new(EndOfFile::new(
+            end_of_file,
+        )))),
+        None,
+    ));
+
+    Cst {
+        block,
+        ..Cst::default()
+    }
+}
@@ -0,0 +1,127 @@
+//! Finds the comments around each token, which is what the formatter attaches
+//! them to.
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Comment, Token, Trivia};
+use std::collections::HashMap;
+
+use crate::types::{Visit, Visitor};
+
+/// A comment on its own line.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct OwnLineComment<'a> {
+    /// The comment's text, including the `--`.
+    pub(crate) text: &'a str,
+
+    /// Whether or not there's an empty line before the comment.
+    pub(crate) blank_before: bool,
+}
+
+/// The comments around a token.
+#[derive(Clone, Debug)]
+pub(crate) struct TokenComments<'a> {
+    /// The token.
+    pub(crate) token: &'a Token,
+
+    /// Comments on their own lines before the token.
+    pub(crate) leading: Vec<OwnLineComment<'a>>,
+
+    /// Comments after the token, on the same line.
+    pub(crate) trailing: Vec<&'a str>,
+
+    /// Whether or not there's an empty line right before the token, after the
+    /// [`leading`](Self::leading) comments if there are any.
+    pub(crate) blank_before: bool,
+}
+
+/// A [`Visitor`] collecting all tokens.
+#[derive(Default)]
+struct TokenCollector<'a> {
+    /// The tokens found.
+    tokens: Vec<&'a Token>,
+}
+
+impl<'a> Visitor<'a> for TokenCollector<'a> {
+    #[inline]
+    fn visit_token(&mut self, token: &'a Token) {
+        self.tokens.push(token);
+    }
+}
+
+/// Get the text of the passed comment.
+#[inline]
+fn comment_text(comment: &Comment) -> &str {
+    match comment {
+        Comment::SingleLine(text) => text.trim_end(),
+        Comment::MultiLine(text) => text,
+    }
+}
+
+/// All tokens in the passed node, in the order they're in the code.
+pub(crate) fn sorted_tokens(node: &(impl Visit + ?Sized)) -> Vec<&Token> {
+    let mut collector = TokenCollector::default();
+    node.visit(&mut collector);
+
+    // The end of the file is visited before the termination statement of the
+    // main block, the rest is in order.
+    let mut tokens = collector.tokens;
+    tokens.sort_by_key(|token| token.start);
+
+    tokens
+}
+
+/// Find the comments around all tokens in the passed node, keyed by the
+/// tokens' start positions.
+///
+/// Each token's leading trivia holds everything between it and the previous
+/// token. Comments before the first new line in it are trailing comments of the
+/// previous token, the rest are leading comments of the token itself.
+pub(crate) fn collect<'a>(node: &'a (impl Visit + ?Sized)) -> HashMap<Position, TokenComments<'a>> {
+    let tokens = sorted_tokens(node);
+    let mut comments: HashMap<Position, TokenComments> = HashMap::with_capacity(tokens.len());
+    let mut previous: Option<Position> = None;
+
+    for token in tokens {
+        let mut leading = Vec::new();
+        let mut trailing = Vec::new();
+        let mut new_lines = 0;
+        let mut seen_new_line = previous.is_none();
+
+        for trivia in &token.leading_trivia {
+            match trivia {
+                Trivia::Spaces(spaces) => {
+                    let count = spaces.matches('\n').count();
+                    new_lines += count;
+                    seen_new_line |= count != 0;
+                }
+                Trivia::Comment(comment) if seen_new_line => {
+                    leading.push(OwnLineComment {
+                        text: comment_text(comment),
+                        blank_before: new_lines >= 2,
+                    });
+                    new_lines = 0;
+                }
+                Trivia::Comment(comment) => trailing.push(comment_text(comment)),
+            }
+        }
+
+        if let Some(previous) = previous
+            && let Some(previous) = comments.get_mut(&previous)
+        {
+            previous.trailing = trailing;
+        }
+
+        comments.insert(
+            token.start,
+            TokenComments {
+                token,
+                leading,
+                trailing: Vec::new(),
+                blank_before: new_lines >= 2,
+            },
+        );
+        previous = Some(token.start);
+    }
+
+    comments
+}
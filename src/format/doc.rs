@@ -0,0 +1,393 @@
+//! The document the formatter lowers nodes to, and the renderer laying it out
+//! within the configured line width.
+
+use std::mem;
+
+use crate::format::{FormatConfig, IndentStyle};
+
+/// A document describing the possible layouts of some code. The renderer picks
+/// the layout of each [`Doc::Group`] depending on whether or not it fits on the
+/// current line.
+#[derive(Clone, Debug)]
+pub(crate) enum Doc {
+    /// Text printed as is. It only has new lines in it if it's a multi-line
+    /// string or comment.
+    Text(String),
+
+    /// Documents printed one after the other.
+    Concat(Vec<Doc>),
+
+    /// A document printed flat if it fits on the current line, and broken
+    /// otherwise.
+    Group {
+        /// The grouped document.
+        doc: Box<Doc>,
+
+        /// Whether or not the document has a [`Doc::HardLine`] or a
+        /// [`Doc::BreakParent`] in it, which forces the group to break.
+        breaks: bool,
+    },
+
+    /// A document whose new lines are indented one more level.
+    Indent(Box<Doc>),
+
+    /// A space when flat, a new line when broken.
+    Line,
+
+    /// Nothing when flat, a new line when broken.
+    SoftLine,
+
+    /// A new line, always.
+    HardLine,
+
+    /// A new line, unless nothing was printed on the current line yet.
+    EnsureNewLine,
+
+    /// An empty line, unless there's already one or nothing was printed yet.
+    BlankLine,
+
+    /// Text printed right before the next new line, used for comments at the
+    /// end of lines.
+    LineSuffix(String),
+
+    /// Forces all enclosing groups to break.
+    BreakParent,
+
+    /// The first document when the enclosing group is broken, the second one
+    /// otherwise.
+    IfBreak(Box<Doc>, Box<Doc>),
+}
+
+impl Doc {
+    /// A document printing nothing.
+    pub(crate) const EMPTY: Self = Self::Concat(Vec::new());
+
+    /// Whether or not this document prints nothing.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) => text.is_empty(),
+            Self::Concat(docs) => docs.iter().all(Self::is_empty),
+            _ => false,
+        }
+    }
+
+    /// Whether or not this document forces the enclosing group to break.
+    fn forces_break(&self) -> bool {
+        match self {
+            Self::HardLine | Self::EnsureNewLine | Self::BlankLine | Self::BreakParent => true,
+            Self::Concat(docs) => docs.iter().any(Self::forces_break),
+            Self::Group { breaks, .. } => *breaks,
+            Self::Indent(doc) => doc.forces_break(),
+            Self::Text(_)
+            | Self::Line
+            | Self::SoftLine
+            | Self::LineSuffix(_)
+            | Self::IfBreak(_, _) => false,
+        }
+    }
+}
+
+impl From<&str> for Doc {
+    #[inline]
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for Doc {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl<const N: usize> From<[Doc; N]> for Doc {
+    #[inline]
+    fn from(docs: [Doc; N]) -> Self {
+        Self::Concat(docs.into())
+    }
+}
+
+impl From<Vec<Doc>> for Doc {
+    #[inline]
+    fn from(docs: Vec<Doc>) -> Self {
+        Self::Concat(docs)
+    }
+}
+
+/// Group the passed document, see [`Doc::Group`].
+#[inline]
+pub(crate) fn group(doc: impl Into<Doc>) -> Doc {
+    let doc = doc.into();
+
+    Doc::Group {
+        breaks: doc.forces_break(),
+        doc: Box::new(doc),
+    }
+}
+
+/// Indent the passed document, see [`Doc::Indent`].
+#[inline]
+pub(crate) fn indent(doc: impl Into<Doc>) -> Doc {
+    Doc::Indent(Box::new(doc.into()))
+}
+
+/// Pick a document depending on the enclosing group, see [`Doc::IfBreak`].
+#[inline]
+pub(crate) fn if_break(broken: impl Into<Doc>, flat: impl Into<Doc>) -> Doc {
+    Doc::IfBreak(Box::new(broken.into()), Box::new(flat.into()))
+}
+
+/// Join the passed documents with `separator`.
+pub(crate) fn join(docs: impl IntoIterator<Item = Doc>, separator: &Doc) -> Doc {
+    let mut joined = Vec::new();
+
+    for doc in docs {
+        if !joined.is_empty() {
+            joined.push(separator.clone());
+        }
+        joined.push(doc);
+    }
+
+    Doc::Concat(joined)
+}
+
+/// How the contents of a [`Doc::Group`] are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// Lines are new lines.
+    Break,
+
+    /// Lines are spaces or nothing.
+    Flat,
+}
+
+/// A document waiting to be printed, with its indentation level and mode.
+type Command<'a> = (usize, Mode, &'a Doc);
+
+/// Lays out a [`Doc`].
+struct Renderer<'a> {
+    /// The configuration to follow.
+    config: &'a FormatConfig,
+
+    /// The printed code.
+    out: String,
+
+    /// The column the next character will be printed at.
+    column: usize,
+
+    /// The indentation level of the current line, if nothing was printed on it
+    /// yet. The indentation is only printed with the line's first text, so
+    /// empty lines don't have trailing whitespace.
+    pending_indent: Option<usize>,
+
+    /// The [`Doc::LineSuffix`]es waiting for the next new line.
+    line_suffixes: Vec<&'a str>,
+}
+
+impl<'a> Renderer<'a> {
+    /// The width of the passed indentation level. Tabs count as
+    /// [`FormatConfig::indent_width`] columns.
+    #[inline]
+    fn indent_width(&self, level: usize) -> usize {
+        level * self.config.indent_width
+    }
+
+    /// Whether or not nothing was printed on the current line yet.
+    #[inline]
+    fn at_line_start(&self) -> bool {
+        self.pending_indent.is_some() || self.out.is_empty()
+    }
+
+    /// Print the passed text, starting with the line's indentation if needed.
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(level) = self.pending_indent.take() {
+            self.out.push_str(&indentation(self.config, level));
+        }
+
+        self.out.push_str(text);
+        match text.rfind('\n') {
+            Some(index) => self.column = text[index + 1..].chars().count(),
+            None => self.column += text.chars().count(),
+        }
+    }
+
+    /// Print the pending line suffixes and remove trailing whitespace from the
+    /// current line.
+    fn end_line(&mut self) {
+        for suffix in mem::take(&mut self.line_suffixes) {
+            self.write(suffix);
+        }
+
+        let length = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(length);
+    }
+
+    /// Start a new line with the passed indentation level.
+    fn new_line(&mut self, level: usize) {
+        self.end_line();
+        self.out.push('\n');
+        self.pending_indent = Some(level);
+        self.column = self.indent_width(level);
+    }
+
+    /// Whether or not the passed command fits in `width` columns, stopping at
+    /// the first new line of the commands after it.
+    fn fits(&self, next: Command<'a>, rest: &[Command<'a>], mut width: isize) -> bool {
+        let mut rest_index = rest.len();
+        let mut stack = vec![(next.1, next.2)];
+
+        // Comments at the end of a line can't be followed by more code in the
+        // group, the group is broken so the code goes on the next line instead
+        // of the comment being moved after it. It's fine when the code is
+        // after the group, there's no line to break there anyway.
+        let mut in_rest = false;
+        let mut has_line_suffix = false;
+
+        loop {
+            let (mode, doc) = match stack.pop() {
+                Some(command) => command,
+                None if rest_index == 0 => return true,
+                None => {
+                    rest_index -= 1;
+                    in_rest = true;
+
+                    let (_, mode, doc) = rest[rest_index];
+                    (mode, doc)
+                }
+            };
+
+            match doc {
+                Doc::Text(text) => {
+                    if text.is_empty() {
+                        continue;
+                    }
+                    if has_line_suffix && !in_rest {
+                        return false;
+                    }
+
+                    let first_line = text.split('\n').next().unwrap_or_default();
+                    width -= first_line.chars().count() as isize;
+
+                    if width < 0 {
+                        return false;
+                    }
+                    if first_line.len() != text.len() {
+                        return true;
+                    }
+                }
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (mode, doc))),
+                Doc::Group { doc, breaks } => {
+                    stack.push((if *breaks { Mode::Break } else { mode }, doc))
+                }
+                Doc::Indent(doc) => stack.push((mode, doc)),
+                Doc::Line if mode == Mode::Flat => {
+                    width -= 1;
+
+                    if width < 0 {
+                        return false;
+                    }
+                }
+                Doc::SoftLine if mode == Mode::Flat => (),
+                Doc::Line | Doc::SoftLine | Doc::HardLine | Doc::EnsureNewLine | Doc::BlankLine => {
+                    return true;
+                }
+                Doc::LineSuffix(_) => has_line_suffix = true,
+                Doc::BreakParent => (),
+                Doc::IfBreak(broken, flat) => {
+                    stack.push((mode, if mode == Mode::Break { broken } else { flat }))
+                }
+            }
+        }
+    }
+
+    /// Lay out the passed document.
+    fn render(mut self, doc: &'a Doc, level: usize) -> String {
+        let mut commands = vec![(level, Mode::Break, doc)];
+
+        while let Some((level, mode, doc)) = commands.pop() {
+            match doc {
+                Doc::Text(text) => self.write(text),
+                Doc::Concat(docs) => {
+                    commands.extend(docs.iter().rev().map(|doc| (level, mode, doc)))
+                }
+                Doc::Group { doc, breaks } => {
+                    let flat = (level, Mode::Flat, &**doc);
+                    let width = self.config.line_width as isize - self.column as isize;
+
+                    if mode == Mode::Flat || !*breaks && self.fits(flat, &commands, width) {
+                        commands.push(flat);
+                    } else {
+                        commands.push((level, Mode::Break, doc));
+                    }
+                }
+                Doc::Indent(doc) => commands.push((level + 1, mode, doc)),
+                Doc::Line => match mode {
+                    Mode::Break => self.new_line(level),
+                    Mode::Flat => self.write(" "),
+                },
+                Doc::SoftLine => {
+                    if mode == Mode::Break {
+                        self.new_line(level);
+                    }
+                }
+                Doc::HardLine => self.new_line(level),
+                Doc::EnsureNewLine => {
+                    if !self.at_line_start() {
+                        self.new_line(level);
+                    }
+                }
+                Doc::BlankLine => {
+                    if self.out.is_empty() {
+                        continue;
+                    }
+                    if !self.at_line_start() {
+                        self.new_line(level);
+                    }
+                    if !self.out.ends_with("\n\n") {
+                        self.out.push('\n');
+                    }
+                }
+                Doc::LineSuffix(suffix) => self.line_suffixes.push(suffix),
+                Doc::BreakParent => (),
+                Doc::IfBreak(broken, flat) => {
+                    commands.push((level, mode, if mode == Mode::Break { broken } else { flat }))
+                }
+            }
+        }
+
+        self.end_line();
+        let length = self.out.trim_end().len();
+        self.out.truncate(length);
+
+        self.out
+    }
+}
+
+/// The indentation of the passed level.
+pub(crate) fn indentation(config: &FormatConfig, level: usize) -> String {
+    match config.indent_style {
+        IndentStyle::Spaces => " ".repeat(level * config.indent_width),
+        IndentStyle::Tabs => "\t".repeat(level),
+    }
+}
+
+/// Lay out the passed document following `config`. `level` is the indentation
+/// level the document is at, the first line isn't indented as it's assumed to
+/// continue existing code.
+pub(crate) fn render(doc: &Doc, config: &FormatConfig, level: usize) -> String {
+    let renderer = Renderer {
+        config,
+        out: String::new(),
+        column: level * config.indent_width,
+        pending_indent: None,
+        line_suffixes: Vec::new(),
+    };
+
+    renderer.render(doc, level)
+}
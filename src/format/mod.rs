@@ -0,0 +1,232 @@
+//! # Formatter
+//!
+//! Re-layouts a [`Cst`], see [`format()`]. Unlike [`Print`],
+//! which reproduces the original code, the formatter only keeps the code's
+//! tokens and comments, and decides the whitespace between them itself:
+//!
+//! * Statements are put on their own lines, and at most one empty line is kept
+//!   between them. Semicolons are removed unless they're needed to separate a
+//!   statement from a following one starting with `(`.
+//! * Lists, tables, and binary expressions are kept on one line when they fit
+//!   in [`FormatConfig::line_width`], and are broken over multiple lines
+//!   otherwise.
+//! * Comments stay with the token they're next to. Comments at the end of a
+//!   line stay at the end of the line, which forces lists and tables they're in
+//!   to break, and comments on their own line stay on their own line.
+//!
+//! Formatting formatted code doesn't change it. Parts of the code can be
+//! formatted on their own using [`format_range`].
+//!
+//! ```rust
+//! # use luau_parser::{format::{self, FormatConfig}, prelude::{Parser, ParserOptions}};
+//! let cst = Parser::new("local t={1,2,3}", ParserOptions::default()).parse("");
+//!
+//! assert_eq!(
+//!     format::format(&cst, &FormatConfig::default()).unwrap(),
+//!     "local t = { 1, 2, 3 }\n"
+//! );
+//! ```
+
+pub(crate) mod comments;
+mod doc;
+mod nodes;
+mod range;
+
+use lsp_types::{Range, TextEdit};
+use std::{error::Error, fmt};
+
+use crate::{
+    format::{
+        doc::{indentation, render},
+        nodes::Formatter,
+    },
+    types::{Cst, LineIndex, Print},
+};
+
+/// The characters used to indent code.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum IndentStyle {
+    /// Indent using [`FormatConfig::indent_width`] spaces per level.
+    #[default]
+    Spaces,
+
+    /// Indent using one tab per level.
+    Tabs,
+}
+
+/// The quotes used for strings. Backtick and multi-line strings are never
+/// changed.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QuoteStyle {
+    /// Use double quotes, unless the string has more double quotes than single
+    /// ones in it.
+    #[default]
+    AutoPreferDouble,
+
+    /// Use single quotes, unless the string has more single quotes than double
+    /// ones in it.
+    AutoPreferSingle,
+
+    /// Always use double quotes.
+    ForceDouble,
+
+    /// Always use single quotes.
+    ForceSingle,
+
+    /// Keep the quotes as they are.
+    Keep,
+}
+
+/// When parentheses are used around the arguments of function calls.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum CallParentheses {
+    /// Always use parentheses, `f "foo"` becomes `f("foo")`.
+    #[default]
+    Always,
+
+    /// Remove the parentheses around a single string argument, `f("foo")`
+    /// becomes `f "foo"`.
+    NoSingleString,
+
+    /// Remove the parentheses around a single table argument, `f({})` becomes
+    /// `f {}`.
+    NoSingleTable,
+
+    /// Remove the parentheses around a single string or table argument.
+    None,
+
+    /// Keep the parentheses as they are.
+    Input,
+}
+
+/// When a separator is added after the last field of a table.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TrailingSeparators {
+    /// Always add one.
+    Always,
+
+    /// Only add one when the table is broken over multiple lines.
+    #[default]
+    Multiline,
+
+    /// Never add one.
+    Never,
+}
+
+/// Options changing how [`format()`] lays code out.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FormatConfig {
+    /// The characters used to indent code.
+    pub indent_style: IndentStyle,
+
+    /// The width of one indentation level, in columns. It's also how many
+    /// spaces are used per level with [`IndentStyle::Spaces`].
+    pub indent_width: usize,
+
+    /// The width lines should fit in. Lines may still be longer if there's no
+    /// place to break them at.
+    pub line_width: usize,
+
+    /// The quotes used for strings.
+    pub quote_style: QuoteStyle,
+
+    /// When parentheses are used around the arguments of function calls.
+    pub call_parentheses: CallParentheses,
+
+    /// When a separator is added after the last field of a table.
+    pub trailing_separators: TrailingSeparators,
+}
+
+impl Default for FormatConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            indent_style: IndentStyle::default(),
+            indent_width: 4,
+            line_width: 120,
+            quote_style: QuoteStyle::default(),
+            call_parentheses: CallParentheses::default(),
+            trailing_separators: TrailingSeparators::default(),
+        }
+    }
+}
+
+/// Errors that may occur in [`format()`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FormatError {
+    /// The [`Cst`] [has errors](Cst::has_errors) and is missing parts of the
+    /// code.
+    HasErrors,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HasErrors => f.write_str("can't format code with syntax errors"),
+        }
+    }
+}
+
+impl Error for FormatError {}
+
+/// Format the passed [`Cst`] following `config`. The output always ends with a
+/// new line, unless it's empty.
+pub fn format(cst: &Cst, config: &FormatConfig) -> Result<String, FormatError> {
+    if cst.has_errors() {
+        return Err(FormatError::HasErrors);
+    }
+
+    let formatter = Formatter::new(&cst.block, config);
+    let mut output = render(&formatter.main_block(&cst.block), config, 0);
+
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Format the statements touched by `range`, which uses the [`Cst`]'s
+/// [position encoding](Cst::position_encoding), following `config`.
+///
+/// The range is expanded to whole statements of the innermost block containing
+/// it, and only those statements, with the comments before them and the ones
+/// on the line of their end, are changed. The rest of the code is left as is.
+/// No edits are returned if the range doesn't touch any statement or if the
+/// statements are already formatted.
+pub fn format_range(
+    cst: &Cst,
+    range: Range,
+    config: &FormatConfig,
+) -> Result<Vec<TextEdit>, FormatError> {
+    if cst.has_errors() {
+        return Err(FormatError::HasErrors);
+    }
+    let Some(selection) = range::find(&cst.block, range, cst.position_encoding) else {
+        return Ok(Vec::new());
+    };
+
+    let formatter = Formatter::new(&cst.block, config);
+    let doc = formatter.statement_range(&selection.statements, selection.indices);
+    let mut new_text = render(&doc, config, selection.level);
+
+    if selection.at_line_start {
+        new_text.insert_str(0, &indentation(config, selection.level));
+    }
+
+    let code = cst.block.print();
+    let index = LineIndex::new(&code);
+    let start = index.offset(selection.edit.start, cst.position_encoding);
+    let end = index.offset(selection.edit.end, cst.position_encoding);
+
+    if code[start..end] == new_text {
+        Ok(Vec::new())
+    } else {
+        Ok(vec![TextEdit::new(selection.edit, new_text)])
+    }
+}
@@ -0,0 +1,1395 @@
+//! Lowers nodes to [`Doc`]s.
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Literal, LuauString, Symbol, Token, TokenType};
+use std::{collections::HashMap, ops::Range};
+
+use crate::{
+    format::{
+        CallParentheses, FormatConfig, QuoteStyle, TrailingSeparators,
+        comments::{self, TokenComments},
+        doc::{Doc, group, if_break, indent, join},
+    },
+    types::{
+        Attribute, Block, Bracketed, BracketedList, Closure, CompoundSetExpression, DoBlock,
+        Expression, FunctionArgument, FunctionArguments, FunctionCall, FunctionCallInvoked,
+        GenericDeclaration, GenericDeclarationParameter, GenericFor, GenericParameterInfo,
+        GenericParameterInfoDefault, GetRange, GetRangeError, GlobalFunction, GlobalFunctionName,
+        IfExpression, IfStatement, InterpolatedString, InterpolatedStringSegment, List, ListItem,
+        LocalAssignment, LocalFunction, Name, NumericalFor, Parameter, ParameterTypeName, Pointer,
+        PrefixExp, Print, RepeatBlock, SetExpression, Statement, Table, TableAccess,
+        TableAccessKey, TableAccessPrefix, TableField, TableFieldValue, TableKey,
+        TerminationStatement, TypeDefinition, TypeFunction, TypeValue, UnaryOperator, Var, Visit,
+        WhileLoop,
+    },
+};
+
+/// Lay out `docs`, which are joined by lines. Lines after the first one are
+/// indented when they don't fit.
+fn hanging(docs: Vec<Doc>) -> Doc {
+    let mut docs = docs.into_iter();
+    let Some(first) = docs.next() else {
+        return Doc::EMPTY;
+    };
+    let rest = docs.flat_map(|doc| [Doc::Line, doc]).collect::<Vec<_>>();
+
+    if rest.is_empty() {
+        first
+    } else {
+        group([first, indent(rest)])
+    }
+}
+
+/// Flatten a chain of binary expressions with the same precedence into their
+/// operands, each with the operator before it.
+fn flatten_binary<'b>(
+    expression: &'b Expression,
+    precedence: u8,
+    operator_before: Option<&'b Token>,
+    chain: &mut Vec<(Option<&'b Token>, &'b Expression)>,
+) {
+    match expression {
+        Expression::BinaryExpression {
+            left,
+            operator,
+            right,
+        } if operator.precedence() == precedence => {
+            flatten_binary(left, precedence, operator_before, chain);
+            flatten_binary(right, precedence, Some(operator.token()), chain);
+        }
+        _ => chain.push((operator_before, expression)),
+    }
+}
+
+/// Flatten a chain of union (`|`) or intersection (`&`) types into their
+/// operands, each with the operator before it.
+fn flatten_types<'b>(
+    type_value: &'b TypeValue,
+    is_union: bool,
+    operator_before: Option<&'b Token>,
+    chain: &mut Vec<(Option<&'b Token>, &'b TypeValue)>,
+) {
+    match type_value {
+        TypeValue::Union { left, pipe, right } if is_union => {
+            flatten_types(left, is_union, operator_before, chain);
+            flatten_types(right, is_union, Some(pipe), chain);
+        }
+        TypeValue::Intersection {
+            left,
+            ampersand,
+            right,
+        } if !is_union => {
+            flatten_types(left, is_union, operator_before, chain);
+            flatten_types(right, is_union, Some(ampersand), chain);
+        }
+        _ => chain.push((operator_before, type_value)),
+    }
+}
+
+/// A statement of a [`Block`], which may be its termination statement.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum BlockStatement<'b> {
+    /// A statement.
+    Statement(&'b Statement),
+
+    /// The termination statement.
+    Termination(&'b TerminationStatement),
+}
+
+impl GetRange for BlockStatement<'_> {
+    #[inline]
+    fn get_range(&self) -> Result<lsp_types::Range, GetRangeError> {
+        match self {
+            Self::Statement(statement) => statement.get_range(),
+            Self::Termination(statement) => statement.get_range(),
+        }
+    }
+}
+
+/// All statements of the passed block with their semicolons, excluding the end
+/// of the file.
+pub(crate) fn block_statements(block: &Block) -> Vec<(BlockStatement<'_>, &Option<Token>)> {
+    block
+        .statements
+        .iter()
+        .filter(|(statement, _)| !matches!(**statement, Statement::EndOfFile(_)))
+        .map(|(statement, semicolon)| (BlockStatement::Statement(statement), semicolon))
+        .chain(
+            block
+                .last_statement
+                .iter()
+                .map(|(statement, semicolon)| (BlockStatement::Termination(statement), semicolon)),
+        )
+        .collect()
+}
+
+/// Lowers nodes to [`Doc`]s, attaching the comments around each token to it.
+pub(crate) struct Formatter<'a> {
+    /// The configuration to follow.
+    config: &'a FormatConfig,
+
+    /// The comments around each token, keyed by the tokens' start positions.
+    comments: HashMap<Position, TokenComments<'a>>,
+}
+
+impl<'a> Formatter<'a> {
+    /// Create a new formatter for the passed node.
+    pub(crate) fn new(node: &'a (impl Visit + ?Sized), config: &'a FormatConfig) -> Self {
+        Self {
+            config,
+            comments: comments::collect(node),
+        }
+    }
+
+    /// Lay out the main block of a file, including the comments at its end.
+    pub(crate) fn main_block(&self, block: &Block) -> Doc {
+        let statements = self.statements(block);
+        let end_of_file = block
+            .statements
+            .iter()
+            .find_map(|(statement, _)| match &**statement {
+                Statement::EndOfFile(end_of_file) => Some(&***end_of_file),
+                _ => None,
+            });
+
+        match end_of_file {
+            Some(end_of_file) => {
+                let comments = self.closing_comments(end_of_file, !statements.is_empty());
+                [statements, comments].into()
+            }
+            None => statements,
+        }
+    }
+
+    /// Comments on their own lines before the passed token, each followed by a
+    /// new line.
+    fn leading_comments(&self, token: &Token) -> Doc {
+        let Some(comments) = self.comments.get(&token.start) else {
+            return Doc::EMPTY;
+        };
+        if comments.leading.is_empty() {
+            return Doc::EMPTY;
+        }
+
+        let mut docs = Vec::new();
+        for (index, comment) in comments.leading.iter().enumerate() {
+            if index != 0 && comment.blank_before {
+                docs.push(Doc::BlankLine);
+            }
+            docs.extend([
+                Doc::BreakParent,
+                Doc::EnsureNewLine,
+                comment.text.into(),
+                Doc::HardLine,
+            ]);
+        }
+        if comments.blank_before {
+            docs.push(Doc::BlankLine);
+        }
+
+        docs.into()
+    }
+
+    /// Comments on their own lines before the passed closing token, like `end`
+    /// or `}`. They're printed at the end of the body before the token, so
+    /// they aren't followed by a new line. `blank_first` is whether or not an
+    /// empty line may be kept before the first comment.
+    fn closing_comments(&self, token: &Token, blank_first: bool) -> Doc {
+        let Some(comments) = self.comments.get(&token.start) else {
+            return Doc::EMPTY;
+        };
+
+        let mut docs = Vec::new();
+        for (index, comment) in comments.leading.iter().enumerate() {
+            if comment.blank_before && (index != 0 || blank_first) {
+                docs.push(Doc::BlankLine);
+            }
+            docs.extend([Doc::BreakParent, Doc::EnsureNewLine, comment.text.into()]);
+        }
+
+        docs.into()
+    }
+
+    /// Comments after the passed token on the same line.
+    fn trailing_comments(&self, token: &Token) -> Doc {
+        match self.comments.get(&token.start) {
+            Some(comments) if !comments.trailing.is_empty() => {
+                Doc::LineSuffix(format!(" {}", comments.trailing.join(" ")))
+            }
+            _ => Doc::EMPTY,
+        }
+    }
+
+    /// Whether or not an empty line is kept before the statement starting with
+    /// the passed token.
+    fn blank_before(&self, token: &Token) -> bool {
+        self.comments.get(&token.start).is_some_and(|comments| {
+            comments
+                .leading
+                .first()
+                .map_or(comments.blank_before, |comment| comment.blank_before)
+        })
+    }
+
+    /// The first token in the passed node.
+    fn first_token(&self, node: &impl GetRange) -> Option<&'a Token> {
+        let range = node.get_range().ok()?;
+        self.comments
+            .get(&range.start)
+            .map(|comments| comments.token)
+    }
+
+    /// Put the passed string in the configured quotes.
+    fn requote(&self, text: &str) -> String {
+        let mut characters = text.chars();
+        let (Some(quote), Some(_)) = (characters.next(), characters.next_back()) else {
+            return text.to_string();
+        };
+        let content = characters.as_str();
+
+        let (mut doubles, mut singles) = (0, 0);
+        let mut characters = content.chars();
+        while let Some(character) = characters.next() {
+            match character {
+                '\\' => match characters.next() {
+                    Some('"') => doubles += 1,
+                    Some('\'') => singles += 1,
+                    _ => (),
+                },
+                '"' => doubles += 1,
+                '\'' => singles += 1,
+                _ => (),
+            }
+        }
+
+        let new_quote = match self.config.quote_style {
+            QuoteStyle::AutoPreferDouble if doubles > singles => '\'',
+            QuoteStyle::AutoPreferDouble | QuoteStyle::ForceDouble => '"',
+            QuoteStyle::AutoPreferSingle if singles > doubles => '"',
+            QuoteStyle::AutoPreferSingle | QuoteStyle::ForceSingle => '\'',
+            QuoteStyle::Keep => quote,
+        };
+        if new_quote == quote {
+            return text.to_string();
+        }
+
+        let mut string = String::with_capacity(text.len() + 2);
+        string.push(new_quote);
+
+        let mut characters = content.chars();
+        while let Some(character) = characters.next() {
+            match character {
+                '\\' => match characters.next() {
+                    // The old quote doesn't need escaping anymore.
+                    Some(character) if character == quote => string.push(character),
+                    Some(character) => {
+                        string.push('\\');
+                        string.push(character);
+                    }
+                    None => string.push('\\'),
+                },
+                character if character == new_quote => {
+                    string.push('\\');
+                    string.push(character);
+                }
+                character => string.push(character),
+            }
+        }
+
+        string.push(new_quote);
+        string
+    }
+
+    /// The text of the passed token.
+    fn token_text(&self, token: &Token) -> String {
+        match &token.token_type {
+            TokenType::Literal(Literal::String(
+                LuauString::SingleQuotes(text) | LuauString::DoubleQuotes(text),
+            )) => self.requote(text),
+            token_type => token_type.try_as_string().unwrap_or_default(),
+        }
+    }
+
+    /// The passed token with its comments.
+    #[inline]
+    fn token(&self, token: &Token) -> Doc {
+        self.token_as(token, self.token_text(token))
+    }
+
+    /// The passed token with its comments, printed as `text`.
+    fn token_as(&self, token: &Token, text: impl Into<Doc>) -> Doc {
+        [
+            self.leading_comments(token),
+            text.into(),
+            self.trailing_comments(token),
+        ]
+        .into()
+    }
+
+    /// The passed closing token with its trailing comments, its leading ones
+    /// must be printed using [`Formatter::closing_comments`].
+    fn closing_token(&self, token: &Token) -> Doc {
+        [self.token_text(token).into(), self.trailing_comments(token)].into()
+    }
+
+    /// Only the comments of a token that isn't printed.
+    fn dropped(&self, token: &Token) -> Doc {
+        [self.leading_comments(token), self.trailing_comments(token)].into()
+    }
+
+    /// Lay out the items of `list`, each one followed by its separator except
+    /// for the last one, whose separator is returned separately.
+    fn list<'b, T>(
+        &self,
+        list: &'b List<T>,
+        mut item: impl FnMut(&T) -> Doc,
+    ) -> (Vec<Doc>, Option<&'b Token>) {
+        let mut docs = Vec::with_capacity(list.items.len());
+        let mut last_separator = None;
+
+        for (index, list_item) in list.items.iter().enumerate() {
+            match list_item {
+                ListItem::Trailing {
+                    item: value,
+                    separator,
+                } if index + 1 != list.items.len() => {
+                    docs.push([item(value), self.token_as(separator, ",")].into())
+                }
+                ListItem::Trailing {
+                    item: value,
+                    separator,
+                } => {
+                    docs.push(item(value));
+                    last_separator = Some(separator);
+                }
+                ListItem::NonTrailing(value) => docs.push(item(value)),
+            }
+        }
+
+        (docs, last_separator)
+    }
+
+    /// Lay out the items of a comma separated `list`.
+    fn comma_list<T>(&self, list: &List<T>, item: impl FnMut(&T) -> Doc) -> Vec<Doc> {
+        let (mut docs, last_separator) = self.list(list, item);
+
+        if let (Some(last), Some(separator)) = (docs.last_mut(), last_separator) {
+            *last = [last.clone(), self.token(separator)].into();
+        }
+
+        docs
+    }
+
+    /// Lay out `items` between the passed brackets, on one line if they fit
+    /// and one per line otherwise. `trailing` is put after the last item and
+    /// `padding` is put inside the brackets.
+    fn delimited(
+        &self,
+        opening: &Token,
+        items: Vec<Doc>,
+        trailing: Doc,
+        closing: &Token,
+        padding: Doc,
+    ) -> Doc {
+        let comments = self.closing_comments(closing, !items.is_empty());
+
+        if items.is_empty() {
+            return if comments.is_empty() {
+                [self.token(opening), self.closing_token(closing)].into()
+            } else {
+                [
+                    self.token(opening),
+                    indent(comments),
+                    Doc::HardLine,
+                    self.closing_token(closing),
+                ]
+                .into()
+            };
+        }
+
+        group([
+            self.token(opening),
+            indent([padding.clone(), join(items, &Doc::Line), trailing, comments]),
+            padding,
+            self.closing_token(closing),
+        ])
+    }
+
+    /// Lay out a comma separated list between brackets.
+    fn bracketed_list<T>(&self, list: &BracketedList<T>, item: impl FnMut(&T) -> Doc) -> Doc {
+        let items = self.comma_list(&list.item, item);
+
+        self.delimited(
+            &list.opening_bracket,
+            items,
+            Doc::EMPTY,
+            &list.closing_bracket,
+            Doc::SoftLine,
+        )
+    }
+
+    /// Lay out a single item between brackets.
+    fn bracketed<T>(&self, bracketed: &Bracketed<T>, item: impl FnOnce(&T) -> Doc) -> Doc {
+        [
+            self.token(&bracketed.opening_bracket),
+            item(&bracketed.item),
+            self.token(&bracketed.closing_bracket),
+        ]
+        .into()
+    }
+
+    /// Lay out the statements of the passed block, excluding the end of the
+    /// file.
+    #[inline]
+    fn statements(&self, block: &Block) -> Doc {
+        let statements = block_statements(block);
+        self.statement_range(&statements, 0..statements.len())
+    }
+
+    /// Lay out the statements in `range`, indices into `statements`, which are
+    /// all the statements of a block. The ones around the range are only used
+    /// to decide whether or not semicolons are needed.
+    pub(crate) fn statement_range(
+        &self,
+        statements: &[(BlockStatement, &Option<Token>)],
+        range: Range<usize>,
+    ) -> Doc {
+        let first_tokens = statements
+            .iter()
+            .map(|(statement, _)| self.first_token(statement))
+            .collect::<Vec<_>>();
+
+        let mut docs = Vec::with_capacity(range.len() * 2);
+        for index in range.clone() {
+            let (statement, semicolon) = statements[index];
+
+            if index != range.start {
+                docs.push(Doc::HardLine);
+
+                if first_tokens[index].is_some_and(|token| self.blank_before(token)) {
+                    docs.push(Doc::BlankLine);
+                }
+            }
+            docs.push(match statement {
+                BlockStatement::Statement(statement) => self.statement(statement),
+                BlockStatement::Termination(statement) => self.termination_statement(statement),
+            });
+
+            if let Some(semicolon) = semicolon {
+                // `f()\n(g)()` would be a single call without the semicolon.
+                let is_needed =
+                    first_tokens
+                        .get(index + 1)
+                        .copied()
+                        .flatten()
+                        .is_some_and(|token| {
+                            token.token_type == TokenType::Symbol(Symbol::OpeningParenthesis)
+                        });
+
+                docs.push(if is_needed {
+                    self.token(semicolon)
+                } else {
+                    self.dropped(semicolon)
+                });
+            }
+        }
+
+        docs.into()
+    }
+
+    /// Lay out a body followed by the keyword closing it. Empty bodies are put
+    /// on the same line when `inline_empty` is set.
+    fn body(&self, block: &Block, closing: &Token, inline_empty: bool) -> Doc {
+        let statements = self.statements(block);
+        let comments = self.closing_comments(closing, !statements.is_empty());
+
+        if statements.is_empty() && comments.is_empty() {
+            return if inline_empty {
+                [" ".into(), self.closing_token(closing)].into()
+            } else {
+                [Doc::HardLine, self.closing_token(closing)].into()
+            };
+        }
+
+        [
+            indent([Doc::HardLine, statements, comments]),
+            Doc::HardLine,
+            self.closing_token(closing),
+        ]
+        .into()
+    }
+
+    /// Lay out the passed attributes, each followed by `separator`.
+    fn attributes(&self, attributes: &[Attribute], separator: &Doc) -> Doc {
+        attributes
+            .iter()
+            .flat_map(|attribute| {
+                [
+                    self.token(&attribute.at),
+                    self.token(&attribute.attribute),
+                    separator.clone(),
+                ]
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Lay out everything after the name of a function.
+    fn function_body(
+        &self,
+        generics: &Option<Pointer<GenericDeclaration>>,
+        parameters: &BracketedList<Parameter>,
+        colon: &Option<Pointer<Token>>,
+        return_type: &Option<Pointer<TypeValue>>,
+        body: &Block,
+        end_keyword: &Token,
+    ) -> Doc {
+        let mut docs = Vec::new();
+
+        if let Some(generics) = generics {
+            docs.push(self.generic_declaration(generics));
+        }
+        docs.push(self.bracketed_list(parameters, |parameter| self.parameter(parameter)));
+        if let (Some(colon), Some(return_type)) = (colon, return_type) {
+            docs.extend([self.token(colon), " ".into(), self.type_value(return_type)]);
+        }
+        docs.push(self.body(body, end_keyword, true));
+
+        docs.into()
+    }
+
+    /// Lay out a [`Parameter`].
+    fn parameter(&self, parameter: &Parameter) -> Doc {
+        self.typed_name(&parameter.name, &parameter.colon, &parameter.r#type)
+    }
+
+    /// Lay out a [`Name`].
+    fn name(&self, name: &Name) -> Doc {
+        self.typed_name(&name.name, &name.colon, &name.r#type)
+    }
+
+    /// Lay out a name with an optional type.
+    fn typed_name(
+        &self,
+        name: &Token,
+        colon: &Option<Token>,
+        r#type: &Option<Pointer<TypeValue>>,
+    ) -> Doc {
+        match (colon, r#type) {
+            (Some(colon), Some(r#type)) => [
+                self.token(name),
+                self.token(colon),
+                " ".into(),
+                self.type_value(r#type),
+            ]
+            .into(),
+            _ => self.token(name),
+        }
+    }
+
+    /// Lay out a [`GenericDeclaration`].
+    fn generic_declaration(&self, generics: &GenericDeclaration) -> Doc {
+        self.bracketed_list(generics, |parameter| {
+            self.generic_declaration_parameter(parameter)
+        })
+    }
+
+    /// Lay out a [`GenericDeclarationParameter`].
+    fn generic_declaration_parameter(&self, parameter: &GenericDeclarationParameter) -> Doc {
+        let info = match &parameter.parameter {
+            GenericParameterInfo::ERROR => Doc::EMPTY,
+            GenericParameterInfo::Name(name) => self.token(name),
+            GenericParameterInfo::Pack { name, ellipsis } => {
+                [self.token(name), self.token(ellipsis)].into()
+            }
+        };
+        let (Some(equal), Some(default)) = (&parameter.equal, &parameter.default) else {
+            return info;
+        };
+        let default = match default {
+            GenericParameterInfoDefault::ERROR => Doc::EMPTY,
+            GenericParameterInfoDefault::Name(name) => self.token(name),
+            GenericParameterInfoDefault::Pack(pack) => self.type_value(pack),
+        };
+
+        [info, " ".into(), self.token(equal), " ".into(), default].into()
+    }
+
+    /// Lay out a [`Statement`].
+    fn statement(&self, statement: &Statement) -> Doc {
+        match statement {
+            Statement::ERROR | Statement::EndOfFile(_) => Doc::EMPTY,
+            Statement::LocalFunction(node) => self.local_function(node),
+            Statement::LocalAssignment(node) => self.local_assignment(node),
+            Statement::TypeDefinition(node) => self.type_definition(node),
+            Statement::IfStatement(node) => self.if_statement(node),
+            Statement::DoBlock(node) => self.do_block(node),
+            Statement::GenericFor(node) => self.generic_for(node),
+            Statement::NumericalFor(node) => self.numerical_for(node),
+            Statement::RepeatBlock(node) => self.repeat_block(node),
+            Statement::WhileLoop(node) => self.while_loop(node),
+            Statement::SetExpression(node) => self.set_expression(node),
+            Statement::CompoundSetExpression(node) => self.compound_set_expression(node),
+            Statement::FunctionCall(node) => self.function_call(node),
+            Statement::GlobalFunction(node) => self.global_function(node),
+            Statement::TypeFunction(node) => self.type_function(node),
+        }
+    }
+
+    /// Lay out a [`TerminationStatement`].
+    fn termination_statement(&self, statement: &TerminationStatement) -> Doc {
+        match statement {
+            TerminationStatement::Break(token) | TerminationStatement::Continue(token) => {
+                self.token(token)
+            }
+            TerminationStatement::Return {
+                return_keyword,
+                expressions: Some(expressions),
+            } => [
+                self.token(return_keyword),
+                " ".into(),
+                hanging(self.comma_list(expressions, |expression| self.expression(expression))),
+            ]
+            .into(),
+            TerminationStatement::Return { return_keyword, .. } => self.token(return_keyword),
+        }
+    }
+
+    /// Lay out a [`LocalFunction`].
+    fn local_function(&self, node: &LocalFunction) -> Doc {
+        [
+            self.attributes(&node.attributes, &Doc::HardLine),
+            self.token(&node.local_keyword),
+            " ".into(),
+            self.token(&node.function_keyword),
+            " ".into(),
+            self.token(&node.function_name),
+            self.function_body(
+                &node.generics,
+                &node.parameters,
+                &node.colon,
+                &node.return_type,
+                &node.body,
+                &node.end_keyword,
+            ),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`GlobalFunction`].
+    fn global_function(&self, node: &GlobalFunction) -> Doc {
+        let name = match &node.function_name {
+            GlobalFunctionName::SimpleName(name) => self.token(name),
+            GlobalFunctionName::Table {
+                table,
+                keys,
+                method,
+            } => {
+                let mut docs = vec![self.token(table)];
+                docs.extend(keys.iter().map(|key| self.table_access_key(key)));
+                if let Some(method) = method {
+                    docs.extend([self.token(&method.0), self.token(&method.1)]);
+                }
+
+                docs.into()
+            }
+        };
+
+        [
+            self.attributes(&node.attributes, &Doc::HardLine),
+            self.token(&node.function_keyword),
+            " ".into(),
+            name,
+            self.function_body(
+                &node.generics,
+                &node.parameters,
+                &node.colon,
+                &node.return_type,
+                &node.body,
+                &node.end_keyword,
+            ),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`TypeFunction`].
+    fn type_function(&self, node: &TypeFunction) -> Doc {
+        [
+            self.export_keyword(&node.export_keyword),
+            self.token(&node.type_keyword),
+            " ".into(),
+            self.token(&node.function_keyword),
+            " ".into(),
+            self.token(&node.function_name),
+            self.function_body(
+                &node.generics,
+                &node.parameters,
+                &node.colon,
+                &node.return_type,
+                &node.body,
+                &node.end_keyword,
+            ),
+        ]
+        .into()
+    }
+
+    /// Lay out an optional `export` keyword.
+    fn export_keyword(&self, export_keyword: &Option<Token>) -> Doc {
+        match export_keyword {
+            Some(export_keyword) => [self.token(export_keyword), " ".into()].into(),
+            None => Doc::EMPTY,
+        }
+    }
+
+    /// Lay out a [`TypeDefinition`].
+    fn type_definition(&self, node: &TypeDefinition) -> Doc {
+        let generics = match &node.generics {
+            Some(generics) => self.generic_declaration(generics),
+            None => Doc::EMPTY,
+        };
+
+        [
+            self.export_keyword(&node.export_keyword),
+            self.token(&node.type_keyword),
+            " ".into(),
+            self.token(&node.type_name),
+            generics,
+            " ".into(),
+            self.token(&node.equal_sign),
+            " ".into(),
+            self.type_value(&node.type_value),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`LocalAssignment`].
+    fn local_assignment(&self, node: &LocalAssignment) -> Doc {
+        let mut docs = vec![
+            self.token(&node.local_token),
+            " ".into(),
+            hanging(self.comma_list(&node.name_list, |name| self.name(name))),
+        ];
+
+        if let Some(equal) = &node.equal_token {
+            docs.extend([
+                " ".into(),
+                self.token(equal),
+                " ".into(),
+                hanging(
+                    self.comma_list(&node.expressions, |expression| self.expression(expression)),
+                ),
+            ]);
+        }
+
+        docs.into()
+    }
+
+    /// Lay out a [`SetExpression`].
+    fn set_expression(&self, node: &SetExpression) -> Doc {
+        [
+            hanging(self.comma_list(&node.variables, |var| self.var(var))),
+            " ".into(),
+            self.token(&node.equal),
+            " ".into(),
+            hanging(self.comma_list(&node.values, |expression| self.expression(expression))),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`CompoundSetExpression`].
+    fn compound_set_expression(&self, node: &CompoundSetExpression) -> Doc {
+        [
+            self.var(&node.variable),
+            " ".into(),
+            self.token(&node.operation),
+            " ".into(),
+            self.expression(&node.value),
+        ]
+        .into()
+    }
+
+    /// Lay out an [`IfStatement`].
+    fn if_statement(&self, node: &IfStatement) -> Doc {
+        let mut closing_keywords = node
+            .else_if_statements
+            .iter()
+            .map(|else_if| &else_if.elseif_keyword)
+            .chain(node.else_statement.iter().map(|else_| &else_.else_keyword))
+            .chain([&node.end_keyword]);
+
+        let mut docs = vec![
+            self.token(&node.if_keyword),
+            " ".into(),
+            self.expression(&node.condition),
+            " ".into(),
+            self.token(&node.then_keyword),
+        ];
+        if let Some(closing) = closing_keywords.next() {
+            docs.push(self.body(&node.body, closing, false));
+        }
+
+        for else_if in &node.else_if_statements {
+            docs.extend([
+                " ".into(),
+                self.expression(&else_if.condition),
+                " ".into(),
+                self.token(&else_if.then_keyword),
+            ]);
+            if let Some(closing) = closing_keywords.next() {
+                docs.push(self.body(&else_if.body, closing, false));
+            }
+        }
+        if let Some(else_statement) = &node.else_statement
+            && let Some(closing) = closing_keywords.next()
+        {
+            docs.push(self.body(&else_statement.body, closing, false));
+        }
+
+        docs.into()
+    }
+
+    /// Lay out a [`DoBlock`].
+    fn do_block(&self, node: &DoBlock) -> Doc {
+        [
+            self.token(&node.do_keyword),
+            self.body(&node.body, &node.end_keyword, true),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`WhileLoop`].
+    fn while_loop(&self, node: &WhileLoop) -> Doc {
+        [
+            self.token(&node.while_keyword),
+            " ".into(),
+            self.expression(&node.condition),
+            " ".into(),
+            self.do_block(&node.do_block),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`RepeatBlock`].
+    fn repeat_block(&self, node: &RepeatBlock) -> Doc {
+        [
+            self.token(&node.repeat_keyword),
+            self.body(&node.body, &node.until_keyword, true),
+            " ".into(),
+            self.expression(&node.condition),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`NumericalFor`].
+    fn numerical_for(&self, node: &NumericalFor) -> Doc {
+        let mut docs = vec![
+            self.token(&node.for_keyword),
+            " ".into(),
+            self.name(&node.variable),
+            " ".into(),
+            self.token(&node.equal_keyword),
+            " ".into(),
+            self.expression(&node.start),
+            self.token(&node.start_comma),
+            " ".into(),
+            self.expression(&node.end),
+        ];
+        if let (Some(end_comma), Some(step)) = (&node.end_comma, &node.step) {
+            docs.extend([self.token(end_comma), " ".into(), self.expression(step)]);
+        }
+        docs.extend([" ".into(), self.do_block(&node.do_block)]);
+
+        docs.into()
+    }
+
+    /// Lay out a [`GenericFor`].
+    fn generic_for(&self, node: &GenericFor) -> Doc {
+        [
+            self.token(&node.for_keyword),
+            " ".into(),
+            hanging(self.comma_list(&node.names, |name| self.name(name))),
+            " ".into(),
+            self.token(&node.in_keyword),
+            " ".into(),
+            hanging(self.comma_list(&node.expressions, |expression| self.expression(expression))),
+            " ".into(),
+            self.do_block(&node.do_block),
+        ]
+        .into()
+    }
+
+    /// Lay out an [`Expression`].
+    fn expression(&self, expression: &Expression) -> Doc {
+        match expression {
+            Expression::ERROR => Doc::EMPTY,
+            Expression::Nil(token)
+            | Expression::Boolean(token)
+            | Expression::Number(token)
+            | Expression::String(token)
+            | Expression::VariadicValues(token) => self.token(token),
+            Expression::InterpolatedString(node) => self.interpolated_string(node),
+            Expression::Closure(node) => self.closure(node),
+            Expression::FunctionCall(node) => self.function_call(node),
+            Expression::ExpressionWrap(node) => {
+                self.bracketed(node, |expression| self.expression(expression))
+            }
+            Expression::Var(node) => self.var(node),
+            Expression::Table(node) => self.table(node),
+            Expression::UnaryExpression {
+                operator,
+                expression,
+            } => {
+                // `- -x` would be a comment without the space.
+                let needs_space = matches!(operator, UnaryOperator::Not(_))
+                    || matches!(operator, UnaryOperator::Minus(_))
+                        && matches!(
+                            **expression,
+                            Expression::UnaryExpression {
+                                operator: UnaryOperator::Minus(_),
+                                ..
+                            }
+                        );
+
+                [
+                    self.token(operator.token()),
+                    if needs_space { " " } else { "" }.into(),
+                    self.expression(expression),
+                ]
+                .into()
+            }
+            Expression::BinaryExpression { operator, .. } => {
+                let mut chain = Vec::new();
+                flatten_binary(expression, operator.precedence(), None, &mut chain);
+
+                self.chain(chain, |expression| self.expression(expression))
+            }
+            Expression::TypeCast {
+                expression,
+                operator,
+                cast_to,
+            } => [
+                self.expression(expression),
+                " ".into(),
+                self.token(operator),
+                " ".into(),
+                self.type_value(cast_to),
+            ]
+            .into(),
+            Expression::IfExpression(node) => self.if_expression(node),
+        }
+    }
+
+    /// Lay out operands joined by operators, breaking before the operators when
+    /// they don't fit.
+    fn chain<T>(&self, chain: Vec<(Option<&Token>, &T)>, item: impl Fn(&T) -> Doc) -> Doc {
+        let docs = chain
+            .into_iter()
+            .map(|(operator, operand)| match operator {
+                Some(operator) => [self.token(operator), " ".into(), item(operand)].into(),
+                None => item(operand),
+            })
+            .collect();
+
+        hanging(docs)
+    }
+
+    /// Lay out an [`InterpolatedString`]. It's kept as is since it can't be
+    /// broken over multiple lines.
+    fn interpolated_string(&self, node: &InterpolatedString) -> Doc {
+        let mut text = String::new();
+        for (index, segment) in node.segments.iter().enumerate() {
+            match segment {
+                InterpolatedStringSegment::String(token) if index == 0 => {
+                    text.push_str(&token.token_type.try_as_string().unwrap_or_default())
+                }
+                InterpolatedStringSegment::String(token) => {
+                    text.push_str(&token.print_without_final_trivia())
+                }
+                InterpolatedStringSegment::Expression(expression) => {
+                    text.push_str(&expression.print_without_final_trivia())
+                }
+            }
+        }
+
+        let first = node.segments.first().and_then(|segment| match segment {
+            InterpolatedStringSegment::String(token) => Some(token),
+            InterpolatedStringSegment::Expression(_) => None,
+        });
+        let last = node.segments.last().and_then(|segment| match segment {
+            InterpolatedStringSegment::String(token) => Some(token),
+            InterpolatedStringSegment::Expression(_) => None,
+        });
+
+        [
+            first.map_or(Doc::EMPTY, |token| self.leading_comments(token)),
+            text.into(),
+            last.map_or(Doc::EMPTY, |token| self.trailing_comments(token)),
+        ]
+        .into()
+    }
+
+    /// Lay out a [`Closure`].
+    fn closure(&self, node: &Closure) -> Doc {
+        [
+            self.attributes(&node.attributes, &" ".into()),
+            self.token(&node.function_keyword),
+            self.function_body(
+                &node.generics,
+                &node.parameters,
+                &node.colon,
+                &node.return_type,
+                &node.body,
+                &node.end_keyword,
+            ),
+        ]
+        .into()
+    }
+
+    /// Lay out an [`IfExpression`].
+    fn if_expression(&self, node: &IfExpression) -> Doc {
+        let mut branches = vec![
+            Doc::Line,
+            self.token(&node.then_keyword),
+            " ".into(),
+            self.expression(&node.if_expression),
+        ];
+        for else_if in node.else_if_expressions.iter() {
+            branches.extend([
+                Doc::Line,
+                self.token(&else_if.else_if_keyword),
+                " ".into(),
+                self.expression(&else_if.condition),
+                " ".into(),
+                self.token(&else_if.then_keyword),
+                " ".into(),
+                self.expression(&else_if.expression),
+            ]);
+        }
+        branches.extend([
+            Doc::Line,
+            self.token(&node.else_keyword),
+            " ".into(),
+            self.expression(&node.else_expression),
+        ]);
+
+        group([
+            self.token(&node.if_keyword),
+            " ".into(),
+            self.expression(&node.condition),
+            indent(branches),
+        ])
+    }
+
+    /// Lay out a [`Var`].
+    fn var(&self, var: &Var) -> Doc {
+        match var {
+            Var::ERROR => Doc::EMPTY,
+            Var::Name(name) => self.token(name),
+            Var::TableAccess(table_access) => self.table_access(table_access),
+        }
+    }
+
+    /// Lay out a [`TableAccess`].
+    fn table_access(&self, node: &TableAccess) -> Doc {
+        let mut docs = vec![match &node.prefix {
+            TableAccessPrefix::Name(name) => self.token(name),
+            TableAccessPrefix::FunctionCall(function_call) => self.function_call(function_call),
+            TableAccessPrefix::ExpressionWrap(expression_wrap) => {
+                self.bracketed(expression_wrap, |expression| self.expression(expression))
+            }
+        }];
+        docs.extend(
+            node.accessed_keys
+                .iter()
+                .map(|key| self.table_access_key(key)),
+        );
+
+        docs.into()
+    }
+
+    /// Lay out a [`TableAccessKey`].
+    fn table_access_key(&self, key: &TableAccessKey) -> Doc {
+        match key {
+            TableAccessKey::Expression(key) => self.table_key(key),
+            TableAccessKey::Name { dot, name } => [self.token(dot), self.token(name)].into(),
+        }
+    }
+
+    /// Lay out a [`TableKey`].
+    fn table_key(&self, key: &TableKey) -> Doc {
+        match key {
+            TableKey::ERROR | TableKey::UndefinedNumber(_) | TableKey::UndefinedString(_) => {
+                Doc::EMPTY
+            }
+            TableKey::Simple(token) => self.token(token),
+            TableKey::Expression(bracketed) => {
+                self.bracketed(bracketed, |expression| self.expression(expression))
+            }
+            TableKey::Type(bracketed) => {
+                self.bracketed(bracketed, |type_value| self.type_value(type_value))
+            }
+        }
+    }
+
+    /// Lay out a [`PrefixExp`].
+    fn prefix_exp(&self, prefix_exp: &PrefixExp) -> Doc {
+        match prefix_exp {
+            PrefixExp::Var(var) => self.var(var),
+            PrefixExp::FunctionCall(function_call) => self.function_call(function_call),
+            PrefixExp::ExpressionWrap(expression_wrap) => {
+                self.bracketed(expression_wrap, |expression| self.expression(expression))
+            }
+        }
+    }
+
+    /// Lay out a [`FunctionCall`].
+    fn function_call(&self, node: &FunctionCall) -> Doc {
+        let invoked = match &node.invoked {
+            FunctionCallInvoked::Function(prefix_exp) => self.prefix_exp(prefix_exp),
+            FunctionCallInvoked::TableMethod {
+                table,
+                colon,
+                method,
+            } => [
+                self.prefix_exp(table),
+                self.token(colon),
+                self.token(method),
+            ]
+            .into(),
+        };
+
+        [invoked, self.function_arguments(&node.arguments)].into()
+    }
+
+    /// Lay out [`FunctionArguments`].
+    fn function_arguments(&self, arguments: &FunctionArguments) -> Doc {
+        let (no_string_parentheses, no_table_parentheses) = match self.config.call_parentheses {
+            CallParentheses::Always => (false, false),
+            CallParentheses::NoSingleString => (true, false),
+            CallParentheses::NoSingleTable => (false, true),
+            CallParentheses::None | CallParentheses::Input => (true, true),
+        };
+
+        match arguments {
+            FunctionArguments::String(string) if no_string_parentheses => {
+                [" ".into(), self.token(string)].into()
+            }
+            // Laid out like an argument list would be, so formatting again doesn't
+            // change it.
+            FunctionArguments::String(string) => group([
+                "(".into(),
+                indent([Doc::SoftLine, self.token(string)]),
+                Doc::SoftLine,
+                ")".into(),
+            ]),
+            FunctionArguments::Table(table) if no_table_parentheses => {
+                [" ".into(), self.table(table)].into()
+            }
+            FunctionArguments::Table(table) => ["(".into(), self.table(table), ")".into()].into(),
+            FunctionArguments::List(list) => {
+                if self.config.call_parentheses != CallParentheses::Input
+                    && let [ListItem::NonTrailing(argument)] = &*list.item.items
+                    && let FunctionArgument::Expression(expression) = &**argument
+                {
+                    let remove_parentheses = match &**expression {
+                        Expression::String(token) => {
+                            no_string_parentheses
+                                && !matches!(
+                                    token.token_type,
+                                    TokenType::Literal(Literal::String(LuauString::Backticks(_)))
+                                )
+                        }
+                        Expression::Table(_) => no_table_parentheses,
+                        _ => false,
+                    };
+
+                    if remove_parentheses {
+                        return [
+                            self.dropped(&list.opening_bracket),
+                            " ".into(),
+                            self.expression(expression),
+                            self.dropped(&list.closing_bracket),
+                        ]
+                        .into();
+                    }
+                }
+
+                self.argument_list(list)
+            }
+        }
+    }
+
+    /// Lay out arguments between parentheses. A table or function as the last
+    /// argument is hugged by the parentheses, so only it is broken over
+    /// multiple lines.
+    fn argument_list(&self, list: &BracketedList<Pointer<FunctionArgument>>) -> Doc {
+        let items = self.comma_list(&list.item, |argument| match &**argument {
+            FunctionArgument::Expression(expression) => self.expression(expression),
+            FunctionArgument::VariadicValues(token) => self.token(token),
+        });
+        let hug = list.item.items.last().is_some_and(|item| {
+            let (ListItem::Trailing { item, .. } | ListItem::NonTrailing(item)) = item;
+
+            matches!(
+                &**item,
+                FunctionArgument::Expression(expression)
+                    if matches!(**expression, Expression::Table(_) | Expression::Closure(_))
+            )
+        });
+
+        if hug
+            && self
+                .closing_comments(&list.closing_bracket, true)
+                .is_empty()
+        {
+            [
+                self.token(&list.opening_bracket),
+                join(items, &" ".into()),
+                self.closing_token(&list.closing_bracket),
+            ]
+            .into()
+        } else {
+            self.delimited(
+                &list.opening_bracket,
+                items,
+                Doc::EMPTY,
+                &list.closing_bracket,
+                Doc::SoftLine,
+            )
+        }
+    }
+
+    /// Lay out a [`Table`], either a table constructor or a table type.
+    fn table(&self, table: &Table) -> Doc {
+        let list = &table.0;
+        let (items, last_separator) = self.list(&list.item, |field| self.table_field(field));
+
+        let trailing = match self.config.trailing_separators {
+            TrailingSeparators::Always => ",".into(),
+            TrailingSeparators::Multiline => if_break(",", ""),
+            TrailingSeparators::Never => Doc::EMPTY,
+        };
+        let trailing = match last_separator {
+            Some(separator) => [trailing, self.dropped(separator)].into(),
+            None => trailing,
+        };
+
+        self.delimited(
+            &list.opening_bracket,
+            items,
+            trailing,
+            &list.closing_bracket,
+            Doc::Line,
+        )
+    }
+
+    /// Lay out a [`TableField`].
+    fn table_field(&self, field: &TableField) -> Doc {
+        let value = match &*field.value {
+            TableFieldValue::ERROR => Doc::EMPTY,
+            TableFieldValue::Expression(expression) => self.expression(expression),
+            TableFieldValue::Type(type_value) => self.type_value(type_value),
+            TableFieldValue::VariadicValues(token) => self.token(token),
+        };
+        let key = self.table_key(&field.key);
+
+        match &field.equal_or_colon {
+            Some(equal) if *equal == TokenType::Symbol(Symbol::Equal) => {
+                [key, " ".into(), self.token(equal), " ".into(), value].into()
+            }
+            Some(colon) => [key, self.token(colon), " ".into(), value].into(),
+            None => [key, value].into(),
+        }
+    }
+
+    /// Lay out a [`TypeValue`].
+    fn type_value(&self, type_value: &TypeValue) -> Doc {
+        match type_value {
+            TypeValue::ERROR => Doc::EMPTY,
+            TypeValue::String(token) | TypeValue::Boolean(token) | TypeValue::Nil(token) => {
+                self.token(token)
+            }
+            TypeValue::Wrap(bracketed) => {
+                self.bracketed(bracketed, |type_value| self.type_value(type_value))
+            }
+            TypeValue::Function {
+                generics,
+                parameters,
+                arrow,
+                return_type,
+            } => [
+                generics
+                    .as_ref()
+                    .map_or(Doc::EMPTY, |generics| self.generic_declaration(generics)),
+                self.bracketed_list(parameters, |parameter| match parameter {
+                    ParameterTypeName::Normal(name) => self.name(name),
+                    ParameterTypeName::Type(type_value) => self.type_value(type_value),
+                }),
+                " ".into(),
+                self.token(arrow),
+                " ".into(),
+                self.type_value(return_type),
+            ]
+            .into(),
+            TypeValue::Basic { base, generics } => {
+                [self.token(base), self.type_generics(generics)].into()
+            }
+            TypeValue::GenericPack { name, ellipsis } => {
+                [self.token(name), self.token(ellipsis)].into()
+            }
+            TypeValue::Intersection { .. } | TypeValue::Union { .. } => {
+                let mut chain = Vec::new();
+                flatten_types(
+                    type_value,
+                    matches!(type_value, TypeValue::Union { .. }),
+                    None,
+                    &mut chain,
+                );
+
+                self.chain(chain, |type_value| self.type_value(type_value))
+            }
+            TypeValue::Module {
+                module,
+                dot,
+                name,
+                generics,
+            } => [
+                self.token(module),
+                self.token(dot),
+                self.token(name),
+                self.type_generics(generics),
+            ]
+            .into(),
+            TypeValue::Optional {
+                base,
+                question_mark,
+            } => [self.type_value(base), self.token(question_mark)].into(),
+            TypeValue::Table(table) => self.table(table),
+            TypeValue::Typeof {
+                typeof_token,
+                inner,
+            } => [
+                self.token(typeof_token),
+                self.bracketed(inner, |expression| self.expression(expression)),
+            ]
+            .into(),
+            TypeValue::Tuple(list) => {
+                self.bracketed_list(list, |type_value| self.type_value(type_value))
+            }
+            TypeValue::Variadic {
+                ellipsis,
+                type_value,
+            } => [self.token(ellipsis), self.type_value(type_value)].into(),
+            TypeValue::VariadicPack { ellipsis, name } => {
+                [self.token(ellipsis), self.token(name)].into()
+            }
+        }
+    }
+
+    /// Lay out the generics passed to a type.
+    fn type_generics(&self, generics: &Option<Pointer<BracketedList<Pointer<TypeValue>>>>) -> Doc {
+        generics.as_ref().map_or(Doc::EMPTY, |generics| {
+            self.bracketed_list(generics, |type_value| self.type_value(type_value))
+        })
+    }
+}
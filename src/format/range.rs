@@ -0,0 +1,211 @@
+//! Finds the statements [`format_range`](super::format_range) re-layouts and
+//! the code they replace.
+
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::{Comment, Token, Trivia};
+use std::{cmp::Reverse, ops};
+
+use crate::{
+    format::{
+        comments,
+        nodes::{BlockStatement, block_statements},
+    },
+    types::{Block, GetRange, PositionEncoding, Print, Visit, Visitor},
+};
+
+/// A [`Visitor`] collecting all blocks with their depths, the main block being
+/// at depth 0.
+#[derive(Default)]
+struct BlockCollector<'a> {
+    /// The blocks found.
+    blocks: Vec<(&'a Block, usize)>,
+
+    /// The depth of the current block.
+    depth: usize,
+}
+
+impl<'a> Visitor<'a> for BlockCollector<'a> {
+    #[inline]
+    fn visit_block(&mut self, node: &'a Block) {
+        self.blocks.push((node, self.depth));
+        self.depth += 1;
+    }
+
+    #[inline]
+    fn leave_block(&mut self, _: &'a Block) {
+        self.depth -= 1;
+    }
+}
+
+/// The statements of a block [`format_range`](super::format_range) re-layouts.
+pub(crate) struct Selection<'a> {
+    /// All statements of the block.
+    pub(crate) statements: Vec<(BlockStatement<'a>, &'a Option<Token>)>,
+
+    /// The indices of the re-layouted statements in
+    /// [`statements`](Self::statements).
+    pub(crate) indices: ops::Range<usize>,
+
+    /// The indentation level of the block.
+    pub(crate) level: usize,
+
+    /// The code replaced by the statements. It includes their comments and
+    /// starts at the beginning of the line when they're the first thing on it.
+    pub(crate) edit: Range,
+
+    /// Whether or not [`edit`](Self::edit) starts at the beginning of a line.
+    pub(crate) at_line_start: bool,
+}
+
+/// Whether or not the passed statement range overlaps with `range`. Empty
+/// ranges overlap with statements they touch.
+#[inline]
+fn overlaps(statement: Range, range: Range) -> bool {
+    if range.start == range.end {
+        statement.start <= range.start && range.start <= statement.end
+    } else {
+        statement.start < range.end && range.start < statement.end
+    }
+}
+
+/// Move `position` to the end of the passed text.
+fn advance(position: &mut Position, text: &str, encoding: PositionEncoding) {
+    for character in text.chars() {
+        if character == '\n' {
+            *position = Position::new(position.line + 1, 0);
+        } else {
+            position.character += encoding.len(character);
+        }
+    }
+}
+
+/// Where the code of a statement starting with the passed token starts, and
+/// whether or not it's at the beginning of a line. It follows the same rules
+/// as [`comments::collect`]: comments on their own lines before the token are
+/// part of it, ones on the line of the previous token aren't.
+fn edit_start(token: &Token, is_first: bool) -> (Position, bool) {
+    if is_first {
+        return (Position::new(0, 0), true);
+    }
+
+    let mut lines = 0;
+    let mut seen_new_line = false;
+    let mut first_comment_line = None;
+
+    for trivia in &token.leading_trivia {
+        if let Trivia::Comment(_) = trivia
+            && seen_new_line
+            && first_comment_line.is_none()
+        {
+            first_comment_line = Some(lines);
+        }
+
+        let new_lines = trivia.print().matches('\n').count() as u32;
+        lines += new_lines;
+        seen_new_line |= matches!(trivia, Trivia::Spaces(_)) && new_lines != 0;
+    }
+
+    match first_comment_line {
+        Some(line) => (Position::new(token.start.line - lines + line, 0), true),
+        None if seen_new_line => (Position::new(token.start.line, 0), true),
+        None => (token.start, false),
+    }
+}
+
+/// Where the code of a statement ending with `token` ends, after the comments
+/// on the same line. `next` is the token after it.
+fn edit_end(token: &Token, next: Option<&Token>, encoding: PositionEncoding) -> Position {
+    let mut end = token.end;
+    let Some(next) = next else {
+        return end;
+    };
+
+    let mut position = token.end;
+    for trivia in &next.leading_trivia {
+        match trivia {
+            Trivia::Spaces(spaces) if spaces.contains('\n') => break,
+            Trivia::Spaces(spaces) => advance(&mut position, spaces, encoding),
+            Trivia::Comment(Comment::SingleLine(text) | Comment::MultiLine(text)) => {
+                advance(&mut position, text, encoding);
+                end = position;
+            }
+        }
+    }
+
+    end
+}
+
+/// Find the statements touched by `range` in the innermost block whose
+/// statements cover all of it. Statements of the main block touched by
+/// `range` are used if there's no such block.
+pub(crate) fn find(
+    block: &Block,
+    range: Range,
+    encoding: PositionEncoding,
+) -> Option<Selection<'_>> {
+    let mut collector = BlockCollector::default();
+    block.visit(&mut collector);
+
+    let mut blocks = collector.blocks;
+    blocks.sort_by_key(|(_, depth)| Reverse(*depth));
+
+    let mut selection = None;
+    for (block, level) in blocks {
+        let statements = block_statements(block);
+        let ranges = statements
+            .iter()
+            .map(|(statement, semicolon)| {
+                let mut range = statement.get_range().ok()?;
+                if let Some(semicolon) = semicolon {
+                    range.end = semicolon.end;
+                }
+
+                Some(range)
+            })
+            .collect::<Vec<_>>();
+
+        let mut touched = ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, statement)| {
+                (**statement).is_some_and(|statement| overlaps(statement, range))
+            })
+            .map(|(index, _)| index);
+        let Some(first) = touched.next() else {
+            continue;
+        };
+        let last = touched.next_back().unwrap_or(first);
+
+        // Both are `Some` as they overlap with the range.
+        let (Some(start), Some(end)) = (ranges[first], ranges[last]) else {
+            continue;
+        };
+        let covers = start.start <= range.start && range.end <= end.end;
+
+        if covers || level == 0 {
+            selection = Some((statements, first..last + 1, level, start.start, end.end));
+        }
+        if covers {
+            break;
+        }
+    }
+
+    let (statements, indices, level, start, end) = selection?;
+
+    let tokens = comments::sorted_tokens(block);
+    let first = tokens.partition_point(|token| token.start < start);
+    let last = tokens
+        .partition_point(|token| token.start < end)
+        .checked_sub(1)?;
+
+    let (edit_start, at_line_start) = edit_start(tokens.get(first)?, first == 0);
+    let edit_end = edit_end(tokens[last], tokens.get(last + 1).copied(), encoding);
+
+    Some(Selection {
+        statements,
+        indices,
+        level,
+        edit: Range::new(edit_start, edit_end),
+        at_line_start,
+    })
+}
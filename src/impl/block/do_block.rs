@@ -1,14 +1,16 @@
 //! All `impl` blocks for [`DoBlock`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, Token, TokenType};
 
 use crate::{
-    types::{Block, DoBlock, Parse, TryParse, TryParseWithArgs},
+    types::{
+        Block, Diagnostic, DiagnosticCode, DoBlock, Parse, RelatedSpan, TryParse, TryParseWithArgs,
+    },
     utils::get_token_type_display_extended,
 };
 
 impl Parse for DoBlock {
-    fn parse(do_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(do_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if do_keyword != TokenType::Keyword(Keyword::Do) {
             return None;
         }
@@ -22,8 +24,10 @@ impl Parse for DoBlock {
             TokenType::Keyword(Keyword::End),
             TokenType::Keyword(Keyword::End),
             errors,
+            DiagnosticCode::MissingEnd,
             "Expected ".to_string()
-                + get_token_type_display_extended(&TokenType::Keyword(Keyword::End))
+                + get_token_type_display_extended(&TokenType::Keyword(Keyword::End)),
+            related: RelatedSpan::from_token(&do_keyword, "Unclosed `do` block starts here."),
         );
 
         Some(Self {
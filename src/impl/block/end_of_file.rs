@@ -1,12 +1,12 @@
 //! All `impl` blocks for [`EndOfFile`].
 
-use luau_lexer::prelude::{Lexer, ParseError, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Token, TokenType};
 
-use crate::types::{EndOfFile, Parse};
+use crate::types::{Diagnostic, EndOfFile, Parse};
 
 impl Parse for EndOfFile {
     #[inline]
-    fn parse(token: Token, _: &mut Lexer, _: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, _: &mut Lexer, _: &mut Vec<Diagnostic>) -> Option<Self> {
         (token == TokenType::EndOfFile).then(|| EndOfFile::new(token))
     }
 }
@@ -5,20 +5,20 @@
 //! * [`GlobalFunctionName`]
 
 use lsp_types::Range;
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, PartialKeyword, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, PartialKeyword, Symbol, Token, TokenType};
 
 use crate::{
     force_parse_bracketed, parse_bracketed,
     types::{
-        Attribute, Block, GetRange, GetRangeError, GlobalFunction, GlobalFunctionName,
-        LocalFunction, Parameter, Parse, ParseWithArgs, Pointer, TableAccessKey, TryParse,
-        TryParseWithArgs, TypeFunction, TypeValue,
+        Attribute, Block, Diagnostic, DiagnosticCode, GetRange, GetRangeError, GlobalFunction,
+        GlobalFunctionName, LocalFunction, Parameter, Parse, ParseWithArgs, Pointer,
+        TableAccessKey, TryParse, TryParseWithArgs, TypeFunction, TypeValue,
     },
-    utils::{get_token_type_display, get_token_type_display_extended},
+    utils::{get_token_type_display, get_token_type_display_extended, next_token},
 };
 
 impl Parse for LocalFunction {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let state = lexer.save_state();
         let attributes;
         let local_keyword;
@@ -32,10 +32,11 @@ impl Parse for LocalFunction {
                 attributes = safe_unwrap!(
                     lexer,
                     errors,
-                    "Expected <attribute>",
+                    DiagnosticCode::MissingAttribute,
+                    "<attribute>",
                     Vec::parse(token, lexer, errors)
                 );
-                local_keyword = lexer.next_token();
+                local_keyword = next_token(lexer);
             }
             _ => return None,
         }
@@ -47,7 +48,7 @@ impl Parse for LocalFunction {
 
         parse_function!(
             let attributes = attributes;
-            lexer.next_token(),
+            next_token(lexer),
             lexer,
             errors,
             let function_name = {
@@ -57,6 +58,7 @@ impl Parse for LocalFunction {
                     TokenType::Identifier(_) | TokenType::PartialKeyword(_),
                     TokenType::Identifier("*error*".into(),),
                     errors,
+                    DiagnosticCode::MissingIdentifier,
                     "Expected ".to_string()
                         + get_token_type_display(&TokenType::Identifier("".into(),))
                 );
@@ -70,7 +72,7 @@ impl Parse for LocalFunction {
 impl TryParse for LocalFunction {}
 
 impl Parse for GlobalFunctionName {
-    fn parse(name: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(name: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if !matches!(
             name.token_type,
             TokenType::Identifier(_) | TokenType::PartialKeyword(_)
@@ -78,51 +80,45 @@ impl Parse for GlobalFunctionName {
             return None;
         }
 
-        maybe_next_token!(
-            lexer,
-            dot_or_colon,
-            TokenType::Symbol(Symbol::Dot) | TokenType::Symbol(Symbol::Colon)
-        );
-        if let Some(dot_or_colon) = dot_or_colon {
-            let is_dot = dot_or_colon == TokenType::Symbol(Symbol::Dot);
-
-            let keys = if is_dot {
-                Vec::<TableAccessKey>::parse_with(dot_or_colon.clone(), lexer, errors, false)
-                    .unwrap_or_default()
-            } else {
-                Vec::new()
-            };
-
-            let method = if !is_dot {
-                next_token_recoverable!(
-                    lexer,
-                    parsed_method,
-                    TokenType::Identifier(_),
-                    TokenType::Identifier("*error*".into()),
-                    errors,
-                    "Expected ".to_string()
-                        + get_token_type_display(&TokenType::Identifier("".into()),)
-                );
+        maybe_next_token!(lexer, dot, TokenType::Symbol(Symbol::Dot));
+        let has_dot = dot.is_some();
+        let keys = dot
+            .and_then(|dot| Vec::<TableAccessKey>::parse_with(dot, lexer, errors, false))
+            .unwrap_or_default();
 
-                Some(Pointer::new((dot_or_colon, parsed_method)))
-            } else {
-                None
-            };
+        maybe_next_token!(lexer, colon, TokenType::Symbol(Symbol::Colon));
+        let method = if let Some(colon) = colon {
+            next_token_recoverable!(
+                lexer,
+                parsed_method,
+                TokenType::Identifier(_),
+                TokenType::Identifier("*error*".into()),
+                errors,
+                DiagnosticCode::MissingIdentifier,
+                "Expected ".to_string()
+                    + get_token_type_display(&TokenType::Identifier("".into()),)
+            );
+
+            Some(Pointer::new((colon, parsed_method)))
+        } else {
+            None
+        };
 
-            return Some(Self::Table {
-                table: name,
-                keys,
-                method,
-            });
+        if !has_dot && method.is_none() {
+            return Some(Self::SimpleName(name));
         }
 
-        Some(Self::SimpleName(name))
+        Some(Self::Table {
+            table: name,
+            keys,
+            method,
+        })
     }
 }
 impl TryParse for GlobalFunctionName {}
 
 impl Parse for GlobalFunction {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let attributes;
         let function_keyword;
 
@@ -135,10 +131,11 @@ impl Parse for GlobalFunction {
                 attributes = safe_unwrap!(
                     lexer,
                     errors,
-                    "Expected <attribute>",
+                    DiagnosticCode::MissingAttribute,
+                    "<attribute>",
                     Vec::parse(token, lexer, errors)
                 );
-                function_keyword = lexer.next_token();
+                function_keyword = next_token(lexer);
             }
             _ => return None,
         }
@@ -162,7 +159,7 @@ impl Parse for GlobalFunction {
 impl TryParse for GlobalFunction {}
 
 impl Parse for Parameter {
-    fn parse(name: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(name: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if !matches!(
             name.token_type,
             TokenType::Identifier(_)
@@ -189,7 +186,7 @@ impl Parse for Parameter {
 }
 
 impl Parse for Attribute {
-    fn parse(at: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(at: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if at != TokenType::Symbol(Symbol::At) {
             return None;
         }
@@ -200,6 +197,7 @@ impl Parse for Attribute {
             TokenType::Identifier(_) | TokenType::PartialKeyword(_),
             TokenType::Identifier("*error*".into()),
             errors,
+            DiagnosticCode::MissingIdentifier,
             "Expected ".to_string() + get_token_type_display(&TokenType::Identifier("".into()))
         );
 
@@ -233,24 +231,38 @@ impl GetRange for GlobalFunctionName {
     }
 }
 
+impl GetRange for LocalFunction {
+    #[inline]
+    fn get_range(&self) -> Result<Range, GetRangeError> {
+        function_range!(self, local_keyword)
+    }
+}
+
+impl GetRange for GlobalFunction {
+    #[inline]
+    fn get_range(&self) -> Result<Range, GetRangeError> {
+        function_range!(self, function_keyword)
+    }
+}
+
 impl Parse for TypeFunction {
     fn parse(
         mut type_keyword: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         let state = lexer.save_state();
 
         let export_keyword = if type_keyword == TokenType::PartialKeyword(PartialKeyword::Export) {
             let temp = type_keyword;
-            type_keyword = lexer.next_token();
+            type_keyword = next_token(lexer);
 
             Some(temp)
         } else {
             None
         };
 
-        let function_keyword = lexer.next_token();
+        let function_keyword = next_token(lexer);
 
         if type_keyword != TokenType::PartialKeyword(PartialKeyword::Type)
             || function_keyword != TokenType::Keyword(Keyword::Function)
@@ -271,6 +283,7 @@ impl Parse for TypeFunction {
                     TokenType::Identifier(_) | TokenType::PartialKeyword(_),
                     TokenType::Identifier("*error*".into(),),
                     errors,
+                    DiagnosticCode::MissingIdentifier,
                     "Expected ".to_string()
                         + get_token_type_display(&TokenType::Identifier("".into(),))
                 );
@@ -1,11 +1,11 @@
 //! All `impl` blocks for [`GenericFor`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, Token, TokenType};
 
-use crate::types::{DoBlock, GenericFor, List, Parse, TryParse};
+use crate::types::{Diagnostic, DoBlock, GenericFor, List, Parse, TryParse};
 
 impl Parse for GenericFor {
-    fn parse(for_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(for_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let state = lexer.save_state();
 
         if for_keyword != TokenType::Keyword(Keyword::For) {
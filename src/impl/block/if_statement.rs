@@ -4,14 +4,11 @@
 //! * [`ElseStatement`]
 //! * [`ElseIfStatement`]
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Token, TokenType};
-
-use crate::{
-    safe_unwrap,
-    types::{
-        Block, ElseIfStatement, ElseStatement, Expression, IfStatement, Parse, Pointer, TryParse,
-        TryParseWithArgs,
-    },
+use luau_lexer::prelude::{Keyword, Lexer, Token, TokenType};
+
+use crate::types::{
+    Block, Diagnostic, DiagnosticCode, ElseIfStatement, ElseStatement, Expression, IfStatement,
+    Parse, Pointer, RelatedSpan, TryParse, TryParseWithArgs,
 };
 
 /// All type of tokens that can end if/else/elseif blocks.
@@ -22,7 +19,7 @@ const END_TOKENS: [TokenType; 3] = [
 ];
 
 impl Parse for IfStatement {
-    fn parse(if_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(if_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if if_keyword != TokenType::Keyword(Keyword::If) {
             return None;
         }
@@ -30,7 +27,8 @@ impl Parse for IfStatement {
         let condition = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
@@ -40,7 +38,9 @@ impl Parse for IfStatement {
             TokenType::Keyword(Keyword::Then),
             TokenType::Keyword(Keyword::Then),
             errors,
-            "Expected `then`"
+            DiagnosticCode::MissingThen,
+            "Expected `then`",
+            related: RelatedSpan::from_token(&if_keyword, "`if` statement starts here."),
         );
 
         let body = Block::try_parse_with(lexer, errors, END_TOKENS).unwrap_or_default();
@@ -55,7 +55,9 @@ impl Parse for IfStatement {
             TokenType::Keyword(Keyword::End),
             TokenType::Keyword(Keyword::End),
             errors,
-            "Expected `end`"
+            DiagnosticCode::MissingEnd,
+            "Expected `end`",
+            related: RelatedSpan::from_token(&if_keyword, "Unclosed `if` statement starts here."),
         );
 
         Some(Self {
@@ -75,7 +77,7 @@ impl Parse for ElseIfStatement {
     fn parse(
         elseif_keyword: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         if elseif_keyword != TokenType::Keyword(Keyword::Elseif) {
             return None;
@@ -84,7 +86,8 @@ impl Parse for ElseIfStatement {
         let condition = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
@@ -94,7 +97,9 @@ impl Parse for ElseIfStatement {
             TokenType::Keyword(Keyword::Then),
             TokenType::Keyword(Keyword::Then),
             errors,
-            "Expected `then`"
+            DiagnosticCode::MissingThen,
+            "Expected `then`",
+            related: RelatedSpan::from_token(&elseif_keyword, "`elseif` starts here."),
         );
 
         let body = Block::try_parse_with(lexer, errors, END_TOKENS).unwrap_or_default();
@@ -110,7 +115,7 @@ impl Parse for ElseIfStatement {
 impl TryParse for ElseIfStatement {}
 
 impl Parse for ElseStatement {
-    fn parse(else_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(else_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if else_keyword != TokenType::Keyword(Keyword::Else) {
             return None;
         }
@@ -1,14 +1,14 @@
 //! All `impl` blocks for [`Block`].
 
-use lsp_types::Range;
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 
 use crate::{
     types::{
-        Block, GetRange, GetRangeError, Parse, ParseWithArgs, Pointer, Print, Statement,
-        TerminationStatement,
+        Block, Diagnostic, DiagnosticCode, GetRange, GetRangeError, Parse, ParseWithArgs, Pointer,
+        RelatedSpan, Statement, TerminationStatement,
     },
-    utils::get_token_type_display_extended,
+    utils::{get_token_type_display_extended, next_token},
 };
 
 /// A private helper trait for [`Block::parse_with`].
@@ -66,11 +66,27 @@ impl<const T: usize> MatchesToken for [TokenType; T] {
     }
 }
 
+/// Matches tokens starting at any of the positions, which must be sorted.
+impl MatchesToken for &[Position] {
+    #[inline]
+    fn matches(&self, token: &Token) -> bool {
+        self.binary_search(&token.start).is_ok()
+    }
+}
+
+/// Matches tokens matched by either item.
+impl<A: MatchesToken, B: MatchesToken> MatchesToken for (A, B) {
+    #[inline]
+    fn matches(&self, token: &Token) -> bool {
+        self.0.matches(token) || self.1.matches(token)
+    }
+}
+
 impl<T: MatchesToken> ParseWithArgs<T> for Block {
     fn parse_with(
         mut token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         stop_at: T,
     ) -> Option<Self> {
         let mut statements = Vec::new();
@@ -91,16 +107,30 @@ impl<T: MatchesToken> ParseWithArgs<T> for Block {
             let mut failed_parsing = false;
 
             if let Some(statement) = Statement::parse(token.clone(), lexer, errors) {
-                if last_statement.is_some() {
+                // The end of the file is a statement so it can hold the
+                // trivia there, it's fine to have it after termination ones.
+                if let Some((termination_statement, _)) = &last_statement
+                    && !matches!(statement, Statement::EndOfFile(_))
+                {
                     // We will still continue parsing so LSPs, formatters, etc.
                     // can still produce "correct" outputs.
 
                     if let Ok(range) = statement.get_range() {
-                        errors.push(ParseError::new(
+                        let mut error = Diagnostic::new(
+                            DiagnosticCode::StatementAfterTermination,
                             range.start,
-                            "Statements after a termination statement are not allowed.".to_string(),
-                            Some(range.end),
-                        ));
+                            range.end,
+                            "Statements after a termination statement are not allowed.",
+                        );
+                        if let Ok(termination_range) = termination_statement.get_range() {
+                            error = error.with_related(RelatedSpan::new(
+                                termination_range.start,
+                                termination_range.end,
+                                "Block is terminated here.",
+                            ));
+                        }
+
+                        errors.push(error);
                     }
                 }
 
@@ -120,21 +150,25 @@ impl<T: MatchesToken> ParseWithArgs<T> for Block {
             }
 
             let state = lexer.save_state();
-            let next_token = lexer.next_token();
+            let next_token = next_token(lexer);
 
             if stop_at.matches(&next_token) {
                 lexer.set_state(state);
 
                 break;
             } else if failed_parsing {
-                errors.push(ParseError::new(
-                    state.lexer_position(),
-                    format!(
-                        "Unexpected {}",
-                        get_token_type_display_extended(&token.token_type)
-                    ),
-                    Some(state.lexer_position()),
-                ));
+                errors.push(
+                    Diagnostic::new(
+                        DiagnosticCode::UnexpectedToken,
+                        state.lexer_position(),
+                        state.lexer_position(),
+                        format!(
+                            "Unexpected {}",
+                            get_token_type_display_extended(&token.token_type)
+                        ),
+                    )
+                    .with_found_token(&token.token_type),
+                );
             }
 
             token = next_token;
@@ -182,7 +216,7 @@ impl GetRange for Block {
                 Some((statement, semi_colon)) => get_range(statement, semi_colon),
                 None => self
                     .statements
-                    .first()
+                    .last()
                     .map(|(statement, semi_colon)| get_range(statement, semi_colon))
                     .unwrap(), // We're sure that at least one statement exists.
             };
@@ -200,35 +234,3 @@ impl GetRange for Block {
         }
     }
 }
-
-impl Print for Block {
-    fn print_final_trivia(&self) -> String {
-        if self.is_empty() {
-            String::new()
-        } else if let Some(last_statement) = self.last_statement.as_ref() {
-            last_statement.print_final_trivia()
-        } else {
-            self.statements.print_final_trivia()
-        }
-    }
-
-    fn print_without_final_trivia(&self) -> String {
-        if self.is_empty() {
-            String::new()
-        } else if self.statements.is_empty() {
-            self.last_statement
-                .as_ref()
-                .unwrap()
-                .print_without_final_trivia()
-        } else if self.last_statement.is_none() {
-            self.statements.print_without_final_trivia()
-        } else {
-            self.statements.print_without_final_trivia()
-                + &self
-                    .last_statement
-                    .as_ref()
-                    .unwrap()
-                    .print_without_final_trivia()
-        }
-    }
-}
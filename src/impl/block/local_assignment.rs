@@ -1,11 +1,15 @@
 //! All `impl` blocks for [`LocalAssignment`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Symbol, Token, TokenType};
+use lsp_types::Range;
+use luau_lexer::prelude::{Keyword, Lexer, Symbol, Token, TokenType};
 
-use crate::types::{Expression, List, LocalAssignment, Name, Parse, Pointer, TryParse};
+use crate::types::{
+    Diagnostic, Expression, GetRange, GetRangeError, List, LocalAssignment, Name, Parse, Pointer,
+    TryParse,
+};
 
 impl Parse for LocalAssignment {
-    fn parse(local_token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(local_token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if local_token != TokenType::Keyword(Keyword::Local) {
             return None;
         }
@@ -27,3 +31,18 @@ impl Parse for LocalAssignment {
         })
     }
 }
+
+impl GetRange for LocalAssignment {
+    fn get_range(&self) -> Result<Range, GetRangeError> {
+        // `expressions` is empty for declarations without values.
+        let end = match self.expressions.get_range() {
+            Ok(range) => range.end,
+            Err(_) => match &self.equal_token {
+                Some(equal_token) => equal_token.get_range()?.end,
+                None => self.name_list.get_range()?.end,
+            },
+        };
+
+        Ok(Range::new(self.local_token.get_range()?.start, end))
+    }
+}
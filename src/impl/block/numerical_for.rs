@@ -1,14 +1,17 @@
 //! All `impl` blocks for [`NumericalFor`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, Symbol, Token, TokenType};
 
 use crate::{
-    types::{DoBlock, Expression, Name, NumericalFor, Parse, Pointer, TryParse},
+    types::{
+        Diagnostic, DiagnosticCode, DoBlock, Expression, Name, NumericalFor, Parse, Pointer,
+        TryParse,
+    },
     utils::get_token_type_display_extended,
 };
 
 impl Parse for NumericalFor {
-    fn parse(for_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(for_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if for_keyword != TokenType::Keyword(Keyword::For) {
             return None;
         }
@@ -21,6 +24,7 @@ impl Parse for NumericalFor {
             TokenType::Symbol(Symbol::Equal),
             TokenType::Symbol(Symbol::Equal),
             errors,
+            DiagnosticCode::MissingEqual,
             "Expected ".to_string()
                 + get_token_type_display_extended(&TokenType::Symbol(Symbol::Equal))
         );
@@ -32,6 +36,7 @@ impl Parse for NumericalFor {
             TokenType::Symbol(Symbol::Comma),
             TokenType::Symbol(Symbol::Comma),
             errors,
+            DiagnosticCode::MissingComma,
             "Expected ".to_string()
                 + get_token_type_display_extended(&TokenType::Symbol(Symbol::Comma))
         );
@@ -1,10 +1,12 @@
 //! All `impl` blocks for [`RepeatBlock`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, Token, TokenType};
 
 use crate::{
-    safe_unwrap,
-    types::{Block, Expression, Parse, Pointer, RepeatBlock, TryParse, TryParseWithArgs},
+    types::{
+        Block, Diagnostic, DiagnosticCode, Expression, Parse, Pointer, RelatedSpan, RepeatBlock,
+        TryParse, TryParseWithArgs,
+    },
     utils::get_token_type_display_extended,
 };
 
@@ -12,7 +14,7 @@ impl Parse for RepeatBlock {
     fn parse(
         repeat_keyword: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         if repeat_keyword != TokenType::Keyword(Keyword::Repeat) {
             return None;
@@ -27,14 +29,17 @@ impl Parse for RepeatBlock {
             TokenType::Keyword(Keyword::Until),
             TokenType::Keyword(Keyword::Until),
             errors,
+            DiagnosticCode::MissingUntil,
             "Expected ".to_string()
-                + get_token_type_display_extended(&TokenType::Keyword(Keyword::Until))
+                + get_token_type_display_extended(&TokenType::Keyword(Keyword::Until)),
+            related: RelatedSpan::from_token(&repeat_keyword, "Unclosed `repeat` block starts here."),
         );
 
         let condition = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
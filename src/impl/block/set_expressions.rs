@@ -3,17 +3,15 @@
 //! * [`SetExpression`]
 //! * [`CompoundSetExpression`]
 
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 
-use crate::{
-    safe_unwrap,
-    types::{
-        CompoundSetExpression, Expression, List, Parse, Pointer, SetExpression, TryParse, Var,
-    },
+use crate::types::{
+    CompoundSetExpression, Diagnostic, DiagnosticCode, Expression, List, Parse, Pointer,
+    SetExpression, TryParse, Var,
 };
 
 impl Parse for SetExpression {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let state = lexer.save_state();
 
         if !matches!(
@@ -26,7 +24,8 @@ impl Parse for SetExpression {
         let variables = safe_unwrap!(
             lexer,
             errors,
-            "Expected <name>",
+            DiagnosticCode::MissingVariable,
+            "<name>",
             List::parse(token, lexer, errors)
         );
         maybe_next_token!(lexer, equal, TokenType::Symbol(Symbol::Equal));
@@ -39,7 +38,8 @@ impl Parse for SetExpression {
         let values = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             List::try_parse(lexer, errors)
         );
 
@@ -52,7 +52,7 @@ impl Parse for SetExpression {
 }
 
 impl Parse for CompoundSetExpression {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let state = lexer.save_state();
         if !matches!(
             token.token_type,
@@ -64,7 +64,8 @@ impl Parse for CompoundSetExpression {
         let variable = safe_unwrap!(
             lexer,
             errors,
-            "Expected <name>",
+            DiagnosticCode::MissingVariable,
+            "<name>",
             Var::parse(token, lexer, errors)
         );
         maybe_next_token!(lexer, operation, TokenType::CompoundOperator(_));
@@ -77,7 +78,8 @@ impl Parse for CompoundSetExpression {
         let value = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
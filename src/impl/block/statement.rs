@@ -1,14 +1,13 @@
 //! All `impl` blocks for [`Statement`] and [`TerminationStatement`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, PartialKeyword, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, PartialKeyword, Token, TokenType};
 
-use crate::{
-    handle_error_token,
-    types::{Expression, List, Parse, Pointer, Print, Statement, TerminationStatement, TryParse},
+use crate::types::{
+    Diagnostic, Expression, List, Parse, Pointer, Statement, TerminationStatement, TryParse,
 };
 
 impl Parse for Statement {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         match token.token_type {
             TokenType::Error(error) => handle_error_token!(errors, error),
             _ => Self::__parse(token, lexer, errors),
@@ -17,7 +16,7 @@ impl Parse for Statement {
 }
 
 impl Parse for TerminationStatement {
-    fn parse(keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if !matches!(
             keyword.token_type,
             TokenType::Keyword(Keyword::Break)
@@ -58,38 +57,3 @@ impl Parse for TerminationStatement {
         None
     }
 }
-
-impl Print for TerminationStatement {
-    fn print_final_trivia(&self) -> String {
-        match self {
-            TerminationStatement::Break(token) | TerminationStatement::Continue(token) => {
-                token.print_final_trivia()
-            }
-            TerminationStatement::Return {
-                return_keyword,
-                expressions,
-            } => {
-                if let Some(expressions) = expressions {
-                    expressions.print_final_trivia()
-                } else {
-                    return_keyword.print_final_trivia()
-                }
-            }
-        }
-    }
-
-    fn print_without_final_trivia(&self) -> String {
-        match self {
-            TerminationStatement::Break(token) | TerminationStatement::Continue(token) => {
-                token.print_without_final_trivia()
-            }
-            TerminationStatement::Return {
-                return_keyword,
-                expressions,
-            } => {
-                return_keyword.print_without_final_trivia()
-                    + &expressions.print_without_final_trivia()
-            }
-        }
-    }
-}
@@ -8,17 +8,20 @@
 //! * [`GenericParameterInfoDefault`]
 
 use luau_lexer::prelude::{
-    Keyword, Lexer, Literal, Operator, ParseError, PartialKeyword, Symbol, Token, TokenType,
+    Keyword, Lexer, Literal, Operator, PartialKeyword, Symbol, Token, TokenType,
 };
 
 use crate::{
-    force_parse_bracketed, handle_error_token, parse_bracketed, safe_unwrap,
+    force_parse_bracketed,
+    r#impl::literals::check_string_escapes,
+    parse_bracketed,
     types::{
-        Bracketed, BracketedList, GenericDeclaration, GenericDeclarationParameter,
-        GenericParameterInfo, GenericParameterInfoDefault, List, Name, ParameterTypeName, Parse,
-        ParseWithArgs, Pointer, Table, TryParse, TypeDefinition, TypeValue,
+        Bracketed, BracketedList, Diagnostic, DiagnosticCode, GenericDeclaration,
+        GenericDeclarationParameter, GenericParameterInfo, GenericParameterInfoDefault, List, Name,
+        ParameterTypeName, Parse, ParseWithArgs, Pointer, Table, TryParse, TypeDefinition,
+        TypeValue,
     },
-    utils::get_token_type_display,
+    utils::{get_token_type_display, next_token},
 };
 
 impl TypeValue {
@@ -26,10 +29,10 @@ impl TypeValue {
     fn parse_from_name(
         base: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         let state = lexer.save_state();
-        let maybe_dot = lexer.next_token();
+        let maybe_dot = next_token(lexer);
 
         let actual_type = if maybe_dot == TokenType::Symbol(Symbol::Dot) {
             next_token_recoverable!(
@@ -38,6 +41,7 @@ impl TypeValue {
                 TokenType::Identifier(_) | TokenType::PartialKeyword(_),
                 TokenType::Identifier("*error*".into()),
                 errors,
+                DiagnosticCode::MissingIdentifier,
                 "Expected ".to_string() + get_token_type_display(&TokenType::Identifier("".into()))
             );
 
@@ -50,6 +54,7 @@ impl TypeValue {
         let generics = parse_bracketed!(
             lexer,
             errors,
+            DiagnosticCode::MissingGenericDeclaration,
             "Expected <generic declaration>",
             TokenType::Symbol(Symbol::OpeningAngleBrackets),
             Symbol::ClosingAngleBrackets,
@@ -80,7 +85,7 @@ impl TypeValue {
     /// Parses a [`TypeValue::Function`]
     fn parse_function(
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         generics: Option<Pointer<GenericDeclaration>>,
         parameters: BracketedList<ParameterTypeName>,
         add_fake_arrow: bool,
@@ -93,6 +98,7 @@ impl TypeValue {
                 TokenType::Symbol(Symbol::Arrow),
                 TokenType::Symbol(Symbol::Arrow),
                 errors,
+                DiagnosticCode::MissingArrow,
                 "Expected ".to_string() + get_token_type_display(&TokenType::Symbol(Symbol::Arrow))
             );
             arrow = fake_arrow;
@@ -108,7 +114,8 @@ impl TypeValue {
         let return_type = safe_unwrap!(
             lexer,
             errors,
-            "Expected <type>",
+            DiagnosticCode::MissingType,
+            "<type>",
             Pointer::<TypeValue>::try_parse(lexer, errors)
         );
 
@@ -128,7 +135,7 @@ impl TypeValue {
     fn parse_bracketed(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         maybe_next_token!(
             lexer,
@@ -169,13 +176,15 @@ impl TypeValue {
             token.clone(),
             lexer,
             errors,
-            ("Expected <parameter>", Symbol::ClosingParenthesis),
-        ) {
-            if let type_value @ Some(_) =
-                Self::parse_function(lexer, errors, None, parameters, false)
-            {
-                return type_value;
-            };
+            (
+                DiagnosticCode::MissingParameters,
+                "Expected <parameter>",
+                Symbol::ClosingParenthesis,
+            ),
+        ) && let type_value @ Some(_) =
+            Self::parse_function(lexer, errors, None, parameters, false)
+        {
+            return type_value;
         }
 
         /*
@@ -202,7 +211,11 @@ impl TypeValue {
             token,
             lexer,
             errors,
-            ("Expected <type>", Symbol::ClosingParenthesis),
+            (
+                DiagnosticCode::MissingType,
+                "Expected <type>",
+                Symbol::ClosingParenthesis,
+            ),
         ) {
             if bracketed.items.len() == 1 {
                 Some(Self::Wrap(Bracketed {
@@ -221,12 +234,15 @@ impl TypeValue {
     /// Inner function for [`TypeValue::parse`]. This doesn't account for union
     /// nor intersection operations after the type, which [`TypeValue::parse`]
     /// handles.
-    fn parse_inner(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse_inner(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         match token.token_type {
             TokenType::Error(error) => handle_error_token!(errors, error),
             TokenType::Literal(ref literal) => match literal {
                 Literal::Number(_) => None,
-                Literal::String(_) => Some(Self::String(token)),
+                Literal::String(_) => {
+                    check_string_escapes(&token, errors);
+                    Some(Self::String(token))
+                }
                 Literal::Boolean(_) => Some(Self::Boolean(token)),
             },
             TokenType::Keyword(Keyword::Nil) => Some(Self::Nil(token)),
@@ -236,6 +252,7 @@ impl TypeValue {
                 inner: force_parse_bracketed!(
                     lexer,
                     errors,
+                    DiagnosticCode::MissingExpression,
                     "Expected <expr>",
                     (
                         TokenType::Symbol(Symbol::OpeningParenthesis),
@@ -257,20 +274,23 @@ impl TypeValue {
                 // I think is right, is the current method, switching to
                 // `try_parse` would cause the second.
                 let state = lexer.save_state();
-                let type_value = if let Some(type_value) =
-                    Self::parse_inner(lexer.next_token(), lexer, errors)
-                {
-                    type_value
-                } else {
-                    errors.push(ParseError::new(
-                        state.lexer_position(),
-                        "Expected <type>",
-                        Some(state.lexer_position()),
-                    ));
-                    lexer.set_state(state);
-
-                    TypeValue::ERROR
-                };
+                let type_value =
+                    if let Some(type_value) = Self::parse_inner(next_token(lexer), lexer, errors) {
+                        type_value
+                    } else {
+                        errors.push(
+                            Diagnostic::new(
+                                DiagnosticCode::MissingType,
+                                state.lexer_position(),
+                                state.lexer_position(),
+                                "Expected <type>",
+                            )
+                            .with_expected("<type>"),
+                        );
+                        lexer.set_state(state);
+
+                        TypeValue::ERROR
+                    };
 
                 match type_value {
                     TypeValue::Basic { base, generics } if generics.is_none() => {
@@ -290,13 +310,18 @@ impl TypeValue {
                     token,
                     lexer,
                     errors,
-                    ("Expected <generic parameter>", Symbol::ClosingAngleBrackets),
+                    (
+                        DiagnosticCode::MissingGenericDeclaration,
+                        "Expected <generic parameter>",
+                        Symbol::ClosingAngleBrackets,
+                    ),
                 )
                 .map(Pointer::new);
 
                 let parameters = force_parse_bracketed!(
                     lexer,
                     errors,
+                    DiagnosticCode::MissingParameters,
                     "Expected <parameter>",
                     (
                         TokenType::Symbol(Symbol::OpeningParenthesis),
@@ -316,10 +341,10 @@ impl TypeValue {
 }
 
 impl Parse for TypeValue {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let left = Self::parse_inner(token, lexer, errors)?;
         let state = lexer.save_state();
-        let maybe_operator = lexer.next_token();
+        let maybe_operator = next_token(lexer);
 
         match maybe_operator.token_type {
             TokenType::Operator(Operator::Optional) => Some(Self::Optional {
@@ -332,7 +357,8 @@ impl Parse for TypeValue {
                 right: safe_unwrap!(
                     lexer,
                     errors,
-                    "Expected <type>",
+                    DiagnosticCode::MissingType,
+                    "<type>",
                     Self::try_parse(lexer, errors).map(Pointer::new)
                 ),
             }),
@@ -342,7 +368,8 @@ impl Parse for TypeValue {
                 right: safe_unwrap!(
                     lexer,
                     errors,
-                    "Expected <type>",
+                    DiagnosticCode::MissingType,
+                    "<type>",
                     Self::try_parse(lexer, errors).map(Pointer::new)
                 ),
             }),
@@ -356,7 +383,7 @@ impl Parse for TypeValue {
 impl TryParse for TypeValue {}
 
 impl Parse for ParameterTypeName {
-    fn parse(name_or_type: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(name_or_type: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let state = lexer.save_state();
         maybe_next_token!(lexer, maybe_colon, TokenType::Symbol(Symbol::Colon));
         lexer.set_state(state);
@@ -371,32 +398,47 @@ impl Parse for ParameterTypeName {
 impl TryParse for ParameterTypeName {}
 
 impl Parse for TypeDefinition {
-    fn parse(mut token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(mut token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
+        let state = lexer.save_state();
+
         let export_keyword = if token == TokenType::PartialKeyword(PartialKeyword::Export) {
             let temp = token;
-            token = lexer.next_token();
+            token = next_token(lexer);
 
             Some(temp)
         } else {
             None
         };
         if token != TokenType::PartialKeyword(PartialKeyword::Type) {
+            lexer.set_state(state);
+
             return None;
         }
 
+        // `type function` is a `TypeFunction`.
+        let type_state = lexer.save_state();
+        if next_token(lexer) == TokenType::Keyword(Keyword::Function) {
+            lexer.set_state(state);
+
+            return None;
+        }
+        lexer.set_state(type_state);
+
         next_token_recoverable!(
             lexer,
             type_name,
             TokenType::Identifier(_) | TokenType::PartialKeyword(_),
             TokenType::Identifier("*error*".into()),
             errors,
+            DiagnosticCode::MissingIdentifier,
             "Expected ".to_string() + get_token_type_display(&TokenType::Identifier("".into()))
         );
 
         let generics = parse_bracketed!(
             lexer,
             errors,
-            "",
+            DiagnosticCode::MissingGenericDeclaration,
+            "Expected <generic declaration>",
             TokenType::Symbol(Symbol::OpeningAngleBrackets),
             Symbol::ClosingAngleBrackets,
         )
@@ -408,13 +450,15 @@ impl Parse for TypeDefinition {
             TokenType::Symbol(Symbol::Equal),
             TokenType::Symbol(Symbol::Equal),
             errors,
+            DiagnosticCode::MissingEqual,
             "Expected `=`"
         );
 
         let type_value = safe_unwrap!(
             lexer,
             errors,
-            "Expected <type>",
+            DiagnosticCode::MissingType,
+            "<type>",
             TypeValue::try_parse(lexer, errors).map(Pointer::new)
         );
 
@@ -431,7 +475,7 @@ impl Parse for TypeDefinition {
 impl TryParse for TypeDefinition {}
 
 impl Parse for GenericParameterInfo {
-    fn parse(name: Token, lexer: &mut Lexer, _: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(name: Token, lexer: &mut Lexer, _: &mut Vec<Diagnostic>) -> Option<Self> {
         if !matches!(
             name.token_type,
             TokenType::Identifier(_) | TokenType::PartialKeyword(_)
@@ -451,7 +495,7 @@ impl Parse for GenericParameterInfo {
 impl TryParse for GenericParameterInfo {}
 
 impl Parse for GenericDeclarationParameter {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let parameter = GenericParameterInfo::parse(token, lexer, errors)?;
 
         let (equal, default);
@@ -476,7 +520,7 @@ impl Parse for GenericDeclarationParameter {
 impl TryParse for GenericDeclarationParameter {}
 
 impl Parse for GenericParameterInfoDefault {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         match token.token_type {
             TokenType::PartialKeyword(_) | TokenType::Identifier(_) => Some(Self::Name(token)),
             _ => match TypeValue::parse(token, lexer, errors) {
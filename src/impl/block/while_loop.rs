@@ -1,17 +1,16 @@
 //! All `impl` blocks for [`WhileLoop`].
 
-use luau_lexer::prelude::{Keyword, Lexer, ParseError, Token, TokenType};
+use luau_lexer::prelude::{Keyword, Lexer, Token, TokenType};
 
-use crate::{
-    safe_unwrap,
-    types::{DoBlock, Expression, Parse, Pointer, TryParse, WhileLoop},
+use crate::types::{
+    Diagnostic, DiagnosticCode, DoBlock, Expression, Parse, Pointer, TryParse, WhileLoop,
 };
 
 impl Parse for WhileLoop {
     fn parse(
         while_keyword: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         if while_keyword != TokenType::Keyword(Keyword::While) {
             return None;
@@ -20,7 +19,8 @@ impl Parse for WhileLoop {
         let condition = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
         let do_block = DoBlock::try_parse(lexer, errors)?;
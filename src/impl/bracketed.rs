@@ -1,11 +1,11 @@
 //! All `impl` blocks for [`Bracketed`].
 
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 use std::ops::{Deref, DerefMut};
 
 use crate::{
-    types::{Bracketed, Parse, ParseWithArgs, Print},
-    utils::get_token_type_display_extended,
+    types::{Bracketed, Diagnostic, DiagnosticCode, Parse, ParseWithArgs, RelatedSpan},
+    utils::{get_token_type_display_extended, next_token},
 };
 
 impl<T: Default> Bracketed<T> {
@@ -14,13 +14,13 @@ impl<T: Default> Bracketed<T> {
         parse: F,
         opening_bracket: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
-        (error_message, stop_at): (&str, Symbol),
+        errors: &mut Vec<Diagnostic>,
+        (code, error_message, stop_at): (DiagnosticCode, &str, Symbol),
     ) -> Option<Self>
     where
-        F: FnOnce(Token, &mut Lexer, &mut Vec<ParseError>) -> Option<T>,
+        F: FnOnce(Token, &mut Lexer, &mut Vec<Diagnostic>) -> Option<T>,
     {
-        let token = lexer.next_token();
+        let token = next_token(lexer);
         if token == TokenType::Symbol(stop_at) {
             return Some(Self {
                 opening_bracket,
@@ -29,13 +29,17 @@ impl<T: Default> Bracketed<T> {
             });
         }
 
+        let found = get_token_type_display_extended(&token.token_type);
         let Some(item) = parse(token, lexer, errors) else {
-            let state = lexer.save_state();
-            errors.push(ParseError::new(
-                state.lexer_position(),
-                error_message.to_string(),
-                Some(state.lexer_position()),
-            ));
+            let position = lexer.save_state().lexer_position();
+            errors.push(
+                Diagnostic::new(code, position, position, error_message)
+                    .with_found(found)
+                    .with_related(RelatedSpan::from_token(
+                        &opening_bracket,
+                        "Opening bracket is here.",
+                    )),
+            );
 
             return None;
         };
@@ -46,10 +50,12 @@ impl<T: Default> Bracketed<T> {
             closing_bracket.token_type == TokenType::Symbol(stop_at),
             TokenType::Symbol(stop_at),
             errors,
+            DiagnosticCode::MissingClosingBracket,
             format!(
                 "Expected {}",
                 get_token_type_display_extended(&TokenType::Symbol(stop_at))
-            )
+            ),
+            related: RelatedSpan::from_token(&opening_bracket, "Unmatched bracket is here."),
         );
 
         Some(Self {
@@ -60,26 +66,26 @@ impl<T: Default> Bracketed<T> {
     }
 }
 
-impl<T: Parse + Default> ParseWithArgs<(&str, Symbol)> for Bracketed<T> {
+impl<T: Parse + Default> ParseWithArgs<(DiagnosticCode, &str, Symbol)> for Bracketed<T> {
     #[inline]
     fn parse_with(
         opening_bracket: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
-        (error_message, stop_at): (&str, Symbol),
+        errors: &mut Vec<Diagnostic>,
+        (code, error_message, stop_at): (DiagnosticCode, &str, Symbol),
     ) -> Option<Self> {
         Self::parse(
-            |token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>| {
+            |token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>| {
                 T::parse(token, lexer, errors)
             },
             opening_bracket,
             lexer,
             errors,
-            (error_message, stop_at),
+            (code, error_message, stop_at),
         )
     }
 }
-impl<A, T> ParseWithArgs<(&str, Symbol, A)> for Bracketed<T>
+impl<A, T> ParseWithArgs<(DiagnosticCode, &str, Symbol, A)> for Bracketed<T>
 where
     T: ParseWithArgs<A> + Default,
 {
@@ -87,17 +93,17 @@ where
     fn parse_with(
         opening_bracket: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
-        (error_message, stop_at, args): (&str, Symbol, A),
+        errors: &mut Vec<Diagnostic>,
+        (code, error_message, stop_at, args): (DiagnosticCode, &str, Symbol, A),
     ) -> Option<Self> {
         Self::parse(
-            |token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>| {
+            |token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>| {
                 T::parse_with(token, lexer, errors, args)
             },
             opening_bracket,
             lexer,
             errors,
-            (error_message, stop_at),
+            (code, error_message, stop_at),
         )
     }
 }
@@ -115,15 +121,3 @@ impl<T> DerefMut for Bracketed<T> {
         &mut self.item
     }
 }
-
-impl<T: Print> Print for Bracketed<T> {
-    fn print_final_trivia(&self) -> String {
-        self.closing_bracket.print_final_trivia()
-    }
-
-    fn print_without_final_trivia(&self) -> String {
-        self.opening_bracket.print_without_final_trivia()
-            + &self.item.print_without_final_trivia()
-            + &self.closing_bracket.print_without_final_trivia()
-    }
-}
@@ -1,9 +1,119 @@
 //! All `impl` blocks for [`Cst`].
 
+use lsp_types::{Position, Range};
 use luau_lexer::prelude::{Lexer, Token};
 use smol_str::SmolStr;
+use std::mem;
 
-use crate::types::{AstStatus, Block, Cst, ParseWithArgs, Print, PrintingError};
+use crate::types::{
+    AstStatus, Block, Cst, GetRange, GetRangeError, Node, ParseWithArgs, PositionEncoding, Print,
+    PrintingError, Visit, Visitor,
+};
+
+/// Whether or not `range` contains `position`, both ends are inclusive so that
+/// positions right after a node (like a cursor at the end of an identifier)
+/// still match it.
+#[inline]
+fn contains(range: Range, position: Position) -> bool {
+    range.start <= position && position <= range.end
+}
+
+/// Whether or not `inner` is completely within `outer`.
+#[inline]
+fn is_within(inner: Range, outer: Range) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// A [`Visitor`] finding the innermost node at a specific [`Position`].
+struct NodeFinder<'a> {
+    /// The position to look for.
+    position: Position,
+
+    /// The nodes containing the position, outermost first.
+    ancestors: Vec<Node<'a>>,
+
+    /// The number of nodes currently entered.
+    depth: usize,
+
+    /// The depth at which the last node of [`NodeFinder::ancestors`] was found.
+    last_depth: usize,
+
+    /// Whether or not the innermost node was found, no more nodes are added
+    /// to [`NodeFinder::ancestors`] after that.
+    is_done: bool,
+
+    /// Whether or not the children of the last visited node should be skipped.
+    skip: bool,
+}
+
+impl<'a> Visitor<'a> for NodeFinder<'a> {
+    fn visit_node(&mut self, node: Node<'a>) {
+        self.depth += 1;
+
+        if self.is_done {
+            self.skip = true;
+
+            return;
+        }
+
+        // Nodes without a range may still have children containing it.
+        match node.get_range() {
+            Ok(range) if contains(range, self.position) => {
+                self.ancestors.push(node);
+                self.last_depth = self.depth;
+            }
+            Ok(_) => self.skip = true,
+            Err(_) => (),
+        }
+    }
+
+    #[inline]
+    fn skip_children(&mut self) -> bool {
+        mem::take(&mut self.skip)
+    }
+
+    fn leave_node(&mut self, _: Node<'a>) {
+        if self.depth == self.last_depth {
+            self.is_done = true;
+        }
+
+        self.depth -= 1;
+    }
+}
+
+/// A [`Visitor`] collecting the outermost nodes within a specific [`Range`].
+struct RangeCollector<'a> {
+    /// The range to look in.
+    range: Range,
+
+    /// The nodes found so far.
+    nodes: Vec<Node<'a>>,
+
+    /// Whether or not the children of the last visited node should be skipped,
+    /// which is the case for found nodes and nodes outside of the range.
+    skip: bool,
+}
+
+impl<'a> Visitor<'a> for RangeCollector<'a> {
+    fn visit_node(&mut self, node: Node<'a>) {
+        // Nodes without a range may still have children within it.
+        let Ok(range) = node.get_range() else {
+            return;
+        };
+
+        if is_within(range, self.range) {
+            self.nodes.push(node);
+            self.skip = true;
+        } else if range.end < self.range.start || self.range.end < range.start {
+            self.skip = true;
+        }
+    }
+
+    #[inline]
+    fn skip_children(&mut self) -> bool {
+        mem::take(&mut self.skip)
+    }
+}
 
 impl Cst {
     /// The actual parsing logic for the [`Cst`].
@@ -22,6 +132,7 @@ impl Cst {
             block: block.unwrap_or_default(),
             errors,
             status,
+            position_encoding: PositionEncoding::Utf32,
         }
     }
 
@@ -34,6 +145,102 @@ impl Cst {
         self.status == AstStatus::HasErrors
     }
 
+    /// Get the innermost node at the passed [`Position`], along with all of its
+    /// ancestors. The returned nodes are ordered from the outermost (the
+    /// [`Cst`] itself) to the innermost, and the list is empty if the position
+    /// is outside of the code.
+    ///
+    /// The top-level statement containing the position is found using a binary
+    /// search over [`Block::statements`], nodes in it are only descended into
+    /// if they contain the position.
+    pub fn node_at(&self, position: Position) -> Vec<Node<'_>> {
+        let mut finder = NodeFinder {
+            position,
+            ancestors: Vec::new(),
+            depth: 0,
+            last_depth: 0,
+            is_done: false,
+            skip: false,
+        };
+
+        if !self
+            .block
+            .get_range()
+            .is_ok_and(|range| contains(range, position))
+        {
+            return finder.ancestors;
+        }
+
+        finder.ancestors.push(Node::Cst(self));
+        finder.ancestors.push(Node::Block(&self.block));
+
+        let statements = &self.block.statements;
+        let index = statements.partition_point(|(statement, _)| {
+            statement
+                .get_range()
+                .is_ok_and(|range| range.end < position)
+        });
+
+        if let Some(statement) = statements.get(index) {
+            statement.visit(&mut finder);
+        }
+        if !finder.is_done
+            && let Some(last_statement) = &self.block.last_statement
+        {
+            last_statement.visit(&mut finder);
+        }
+
+        finder.ancestors
+    }
+
+    /// Get all nodes that are completely within the passed [`Range`], in source
+    /// order. Only the outermost nodes are returned, their children can be
+    /// traversed using [`Visit`].
+    ///
+    /// Only the top-level statements overlapping the range are traversed,
+    /// the first one is found using a binary search over [`Block::statements`],
+    /// and nodes in them are only descended into if they overlap the range.
+    pub fn nodes_in(&self, range: Range) -> Vec<Node<'_>> {
+        let mut collector = RangeCollector {
+            range,
+            nodes: Vec::new(),
+            skip: false,
+        };
+
+        if self
+            .block
+            .get_range()
+            .is_ok_and(|block_range| is_within(block_range, range))
+        {
+            collector.nodes.push(Node::Block(&self.block));
+
+            return collector.nodes;
+        }
+
+        let statements = &self.block.statements;
+        let start = statements.partition_point(|(statement, _)| {
+            statement
+                .get_range()
+                .is_ok_and(|statement_range| statement_range.end < range.start)
+        });
+
+        for item @ (statement, _) in &statements[start..] {
+            if statement
+                .get_range()
+                .is_ok_and(|statement_range| statement_range.start > range.end)
+            {
+                return collector.nodes;
+            }
+
+            item.visit(&mut collector);
+        }
+        if let Some(last_statement) = &self.block.last_statement {
+            last_statement.visit(&mut collector);
+        }
+
+        collector.nodes
+    }
+
     /// Try printing the [`Cst`] back into source code. This'll only fail if
     /// [`Cst.has_errors()`](Self::has_errors()) returns `true`.
     pub fn try_print(&self) -> Result<String, PrintingError> {
@@ -44,3 +251,10 @@ impl Cst {
         }
     }
 }
+
+impl GetRange for Cst {
+    #[inline]
+    fn get_range(&self) -> Result<Range, GetRangeError> {
+        self.block.get_range()
+    }
+}
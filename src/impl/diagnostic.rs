@@ -0,0 +1,154 @@
+//! All `impl` blocks for [`Diagnostic`] and related types.
+
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::{ParseError, Token, TokenType};
+use smol_str::SmolStr;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    types::{Diagnostic, DiagnosticCode, GetRange, RelatedSpan, Severity},
+    utils::get_token_type_display_extended,
+};
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Information => "info",
+            Self::Hint => "hint",
+        })
+    }
+}
+
+impl Display for DiagnosticCode {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl RelatedSpan {
+    /// Create a new [`RelatedSpan`].
+    #[inline]
+    pub fn new(start: Position, end: Position, message: impl Into<SmolStr>) -> Self {
+        Self {
+            start,
+            end,
+            message: message.into(),
+        }
+    }
+
+    /// Create a new [`RelatedSpan`] covering the passed token.
+    #[inline]
+    pub fn from_token(token: &Token, message: impl Into<SmolStr>) -> Self {
+        let range = token
+            .get_range()
+            .unwrap_or_else(|_| Range::new(token.start, token.end));
+
+        Self::new(range.start, range.end, message)
+    }
+
+    /// The range of this span.
+    #[inline]
+    pub fn range(&self) -> Range {
+        Range::new(self.start, self.end)
+    }
+}
+
+impl Diagnostic {
+    /// Create a new [`Diagnostic`] with the default
+    /// [`severity`](DiagnosticCode::severity) of the passed code.
+    #[inline]
+    pub fn new(
+        code: DiagnosticCode,
+        start: Position,
+        end: Position,
+        message: impl Into<SmolStr>,
+    ) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            start,
+            end,
+            message: message.into(),
+            expected: None,
+            found: None,
+            related: Vec::new(),
+        }
+    }
+
+    /// Set the severity of this diagnostic.
+    #[inline]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set what was expected.
+    #[inline]
+    pub fn with_expected(mut self, expected: impl Into<SmolStr>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// Set what was found instead of what was expected.
+    #[inline]
+    pub fn with_found(mut self, found: impl Into<SmolStr>) -> Self {
+        self.found = Some(found.into());
+        self
+    }
+
+    /// Set what was found to the kind of the passed [`TokenType`].
+    #[inline]
+    pub fn with_found_token(self, token_type: &TokenType) -> Self {
+        self.with_found(get_token_type_display_extended(token_type))
+    }
+
+    /// Add a related span.
+    #[inline]
+    pub fn with_related(mut self, related: RelatedSpan) -> Self {
+        self.related.push(related);
+        self
+    }
+
+    /// The range of this diagnostic.
+    #[inline]
+    pub fn range(&self) -> Range {
+        Range::new(self.start, self.end)
+    }
+
+    /// Whether or not this diagnostic is an [`error`](Severity::Error).
+    #[inline]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    /// Convert errors reported by the lexer into
+    /// [`InvalidToken`](DiagnosticCode::InvalidToken) diagnostics.
+    #[inline]
+    fn from(error: ParseError) -> Self {
+        Self::new(
+            DiagnosticCode::InvalidToken,
+            error.start(),
+            error.end().unwrap_or(error.start()),
+            error.message(),
+        )
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: {} ({}:{})",
+            self.severity,
+            self.code,
+            self.message,
+            self.start.line + 1,
+            self.start.character + 1,
+        )
+    }
+}
@@ -6,20 +6,24 @@
 //! * [`FunctionArgument`]
 //! * [`Closure`]
 
-use luau_lexer::prelude::{Keyword, Lexer, Literal, ParseError, Symbol, Token, TokenType};
+use lsp_types::Range;
+use luau_lexer::prelude::{Keyword, Lexer, Literal, Symbol, Token, TokenType};
 
 use crate::{
-    force_parse_bracketed, parse_bracketed,
+    force_parse_bracketed,
+    r#impl::literals::check_string_escapes,
+    parse_bracketed,
     types::{
-        Block, BracketedList, Closure, Expression, FunctionArgument, FunctionArguments,
-        FunctionCall, FunctionCallInvoked, Parse, ParseWithArgs, Pointer, PrefixExp, Table,
-        TableAccessPrefix, TryParse, TryParseWithArgs, TypeValue,
+        Block, BracketedList, Closure, Diagnostic, DiagnosticCode, Expression, FunctionArgument,
+        FunctionArguments, FunctionCall, FunctionCallInvoked, GetRange, GetRangeError, Parse,
+        ParseWithArgs, Pointer, PrefixExp, Table, TableAccessPrefix, TryParse, TryParseWithArgs,
+        TypeValue,
     },
-    utils::{get_token_type_display, get_token_type_display_extended},
+    utils::{get_token_type_display, get_token_type_display_extended, next_token},
 };
 
 impl Parse for FunctionCallInvoked {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let prefix_exp = Pointer::new(PrefixExp::parse(token, lexer, errors)?);
 
         maybe_next_token!(lexer, colon, TokenType::Symbol(Symbol::Colon));
@@ -33,6 +37,7 @@ impl Parse for FunctionCallInvoked {
             TokenType::Identifier(_) | TokenType::PartialKeyword(_),
             TokenType::Identifier("*error*".into(),),
             errors,
+            DiagnosticCode::MissingIdentifier,
             "Expected ".to_string() + get_token_type_display(&TokenType::Identifier("".into(),))
         );
 
@@ -54,7 +59,7 @@ impl FunctionCall {
     /// ```
     pub fn try_parse_with_invoked(
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         mut invoked: FunctionCallInvoked,
     ) -> Option<Self> {
         let mut found_arguments = false;
@@ -71,10 +76,10 @@ impl FunctionCall {
             return None;
         }
 
-        if let FunctionCallInvoked::Function(pointer) = invoked {
-            if let PrefixExp::FunctionCall(function_call) = (*pointer).clone() {
-                return Some(function_call);
-            }
+        if let FunctionCallInvoked::Function(pointer) = invoked
+            && let PrefixExp::FunctionCall(function_call) = (*pointer).clone()
+        {
+            return Some(function_call);
         }
 
         None
@@ -82,24 +87,24 @@ impl FunctionCall {
 }
 
 impl Parse for FunctionCall {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let invoked = FunctionCallInvoked::parse(token, lexer, errors)?;
         let arguments = FunctionArguments::try_parse(lexer, errors);
 
         if let Some(arguments) = arguments {
             return Some(FunctionCall { invoked, arguments });
         }
-        if let FunctionCallInvoked::Function(pointer) = invoked {
-            if let PrefixExp::FunctionCall(call) = (*pointer).clone() {
-                return Some(call);
-            }
+        if let FunctionCallInvoked::Function(pointer) = invoked
+            && let PrefixExp::FunctionCall(call) = (*pointer).clone()
+        {
+            return Some(call);
         }
 
         None
     }
 }
 impl TryParse for FunctionCall {
-    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         Some(Self {
             invoked: FunctionCallInvoked::try_parse(lexer, errors)?,
             arguments: FunctionArguments::try_parse(lexer, errors)?,
@@ -108,7 +113,7 @@ impl TryParse for FunctionCall {
 }
 
 impl Parse<PrefixExp> for FunctionCall {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<PrefixExp> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<PrefixExp> {
         Self::parse(token, lexer, errors).map(PrefixExp::FunctionCall)
     }
 }
@@ -116,15 +121,16 @@ impl Parse<TableAccessPrefix> for FunctionCall {
     fn parse(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<TableAccessPrefix> {
         Pointer::<Self>::parse(token, lexer, errors).map(TableAccessPrefix::FunctionCall)
     }
 }
 
 impl Parse for FunctionArguments {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if matches!(token.token_type, TokenType::Literal(Literal::String(_))) {
+            check_string_escapes(&token, errors);
             return Some(Self::String(token));
         }
         if token.token_type == TokenType::Symbol(Symbol::OpeningParenthesis) {
@@ -132,7 +138,11 @@ impl Parse for FunctionArguments {
                 token,
                 lexer,
                 errors,
-                ("Expected <expr>", Symbol::ClosingParenthesis),
+                (
+                    DiagnosticCode::MissingExpression,
+                    "Expected <expr>",
+                    Symbol::ClosingParenthesis,
+                ),
             )
             .map(Self::List);
         }
@@ -143,20 +153,19 @@ impl Parse for FunctionArguments {
 impl TryParse for FunctionArguments {}
 
 impl Parse for FunctionArgument {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
-        if matches!(token.token_type, TokenType::Symbol(Symbol::Ellipses)) {
-            Some(Self::VariadicValues(token))
-        } else {
-            Expression::parse(token, lexer, errors)
-                .map(Pointer::new)
-                .map(Self::Expression)
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
+        // `...` on its own keeps its own variant, but it can still be used in
+        // bigger expressions, ex. `f(... + 1)`.
+        match Expression::parse(token, lexer, errors)? {
+            Expression::VariadicValues(token) => Some(Self::VariadicValues(token)),
+            expression => Some(Self::Expression(Pointer::new(expression))),
         }
     }
 }
 impl TryParse for FunctionArgument {}
 
 impl Parse for Closure {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let attributes;
         let function_keyword;
 
@@ -169,10 +178,11 @@ impl Parse for Closure {
                 attributes = safe_unwrap!(
                     lexer,
                     errors,
-                    "Expected <attribute>",
+                    DiagnosticCode::MissingAttribute,
+                    "<attribute>",
                     Vec::parse(token, lexer, errors)
                 );
-                function_keyword = lexer.next_token();
+                function_keyword = next_token(lexer);
             }
             _ => return None,
         }
@@ -186,3 +196,10 @@ impl Parse for Closure {
         )
     }
 }
+
+impl GetRange for Closure {
+    #[inline]
+    fn get_range(&self) -> Result<Range, GetRangeError> {
+        function_range!(self, function_keyword)
+    }
+}
@@ -1,26 +1,27 @@
 //! All `impl` blocks for expression-related types
 
 mod function;
+mod operator;
+mod string;
 mod table;
 mod var;
 
-use luau_lexer::prelude::{
-    Keyword, Lexer, Literal, Operator, ParseError, Symbol, Token, TokenType,
-};
+use luau_lexer::prelude::{Keyword, Lexer, Literal, Symbol, Token, TokenType};
 
 use crate::{
-    handle_error_token, safe_unwrap,
+    r#impl::literals::check_string_escapes,
     types::{
-        Bracketed, Closure, ElseIfExpression, Expression, FunctionCall, FunctionCallInvoked,
-        IfExpression, Parse, ParseWithArgs, Pointer, PrefixExp, Table, TableAccess,
-        TableAccessPrefix, TryParse, TypeValue, Var,
+        BinaryOperator, Bracketed, Closure, Diagnostic, DiagnosticCode, ElseIfExpression,
+        Expression, FunctionCall, FunctionCallInvoked, GetRange, IfExpression, InterpolatedString,
+        Parse, ParseWithArgs, Pointer, PrefixExp, RelatedSpan, Table, TableAccess,
+        TableAccessPrefix, TryParse, TypeValue, UnaryOperator, Var,
     },
-    utils::get_token_type_display,
+    utils::{get_token_type_display, next_token},
 };
 
 impl PrefixExp {
     /// Tries parsing more [`PrefixExp`]s starting with this one.
-    fn parse_more(&self, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse_more(&self, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         maybe_next_token!(lexer, colon, TokenType::Symbol(Symbol::Colon));
 
         let invoked = if let Some(colon) = colon {
@@ -30,6 +31,7 @@ impl PrefixExp {
                 TokenType::Identifier(_) | TokenType::PartialKeyword(_),
                 TokenType::Identifier("*error*".into(),),
                 errors,
+                DiagnosticCode::MissingIdentifier,
                 "Expected ".to_string()
                     + get_token_type_display(&TokenType::Identifier("".into(),))
             );
@@ -71,7 +73,7 @@ impl PrefixExp {
 }
 
 impl Parse for PrefixExp {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let var = Var::parse(token.clone(), lexer, errors);
         if let Some(var) = var {
             let prefix_exp = Self::Var(var);
@@ -84,7 +86,11 @@ impl Parse for PrefixExp {
                 token,
                 lexer,
                 errors,
-                ("Expected <expr>", Symbol::ClosingParenthesis),
+                (
+                    DiagnosticCode::MissingExpression,
+                    "Expected <expr>",
+                    Symbol::ClosingParenthesis,
+                ),
             );
 
             if let Some(expression_wrap) = expression_wrap {
@@ -116,9 +122,19 @@ impl Expression {
 
     /// Inner function for [`Expression::parse`]. This function doesn't check for
     /// operators before nor after the expression, which [`Expression::parse`] does.
-    fn parse_inner(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse_inner(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         match token.token_type {
             TokenType::Error(error) => handle_error_token!(errors, error),
+            TokenType::Literal(Literal::String(_)) => {
+                if let Some(interpolated_string) =
+                    InterpolatedString::parse(token.clone(), lexer, errors)
+                {
+                    return Some(Self::InterpolatedString(interpolated_string));
+                }
+
+                check_string_escapes(&token, errors);
+                Some(Self::String(token))
+            }
             TokenType::Literal(_) => Self::parse_from_literal(token),
             TokenType::Identifier(_)
             | TokenType::PartialKeyword(_)
@@ -141,74 +157,148 @@ impl Expression {
                     .map(Self::Closure)
             }
             TokenType::Keyword(Keyword::Nil) => Some(Self::Nil(token)),
+            TokenType::Symbol(Symbol::Ellipses) => Some(Self::VariadicValues(token)),
             TokenType::Keyword(Keyword::If) => {
                 IfExpression::parse(token, lexer, errors).map(Self::IfExpression)
             }
             _ => None,
         }
     }
-}
 
-impl Parse for Expression {
-    fn parse(mut token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
-        let maybe_unary_operator = match token.token_type {
-            TokenType::Operator(Operator::Minus | Operator::Not | Operator::Length) => {
-                let temp = token;
-                token = lexer.next_token();
+    /// Parses the expression that can be used as an operand for operators, this
+    /// is either a [`typecast`](Expression::TypeCast) or whatever
+    /// [`Expression::parse_inner`] returns.
+    fn parse_operand(
+        token: Token,
+        lexer: &mut Lexer,
+        errors: &mut Vec<Diagnostic>,
+    ) -> Option<Self> {
+        let expression = Self::parse_inner(token, lexer, errors)?;
 
-                Some(temp)
-            }
-            _ => None,
+        maybe_next_token!(lexer, operator, TokenType::Symbol(Symbol::Typecast));
+        let Some(operator) = operator else {
+            return Some(expression);
         };
 
-        let left = Self::parse_inner(token, lexer, errors)?;
-        let left = if let Some(operator) = maybe_unary_operator {
+        Some(Self::TypeCast {
+            expression: Pointer::new(expression),
+            operator,
+            cast_to: safe_unwrap!(
+                lexer,
+                errors,
+                DiagnosticCode::MissingType,
+                "<type>",
+                TypeValue::try_parse(lexer, errors).map(Pointer::new)
+            ),
+        })
+    }
+
+    /// Parses an [`Expression`] while only consuming binary operators with a left
+    /// precedence higher than `limit`. This is the core of the precedence
+    /// climbing algorithm used by [`Expression::parse`], calling it with a limit
+    /// of `0` parses a full expression.
+    fn parse_with_precedence(
+        token: Token,
+        lexer: &mut Lexer,
+        errors: &mut Vec<Diagnostic>,
+        limit: u8,
+    ) -> Option<Self> {
+        let mut left = if let Some(operator) = UnaryOperator::parse(token.clone(), lexer, errors) {
+            let expression = Self::parse_with_precedence(
+                next_token(lexer),
+                lexer,
+                errors,
+                operator.precedence(),
+            )?;
+
             Self::UnaryExpression {
                 operator,
-                expression: Pointer::new(left),
+                expression: Pointer::new(expression),
             }
         } else {
-            left
+            Self::parse_operand(token, lexer, errors)?
         };
 
-        let state = lexer.save_state();
-        let next_token = lexer.next_token();
-
-        match next_token.token_type {
-            TokenType::Operator(_)
-            | TokenType::CompoundOperator(_)
-            | TokenType::Symbol(Symbol::OpeningAngleBrackets)
-            | TokenType::Symbol(Symbol::ClosingAngleBrackets) => Some(Self::BinaryExpression {
-                left: Pointer::new(left),
-                operator: next_token,
-                right: safe_unwrap!(
-                    lexer,
-                    errors,
-                    "Expected <expr>",
-                    Self::try_parse(lexer, errors).map(Pointer::new)
-                ),
-            }),
-            TokenType::Symbol(Symbol::Typecast) => Some(Self::TypeCast {
-                expression: Pointer::new(left),
-                operator: next_token,
-                cast_to: safe_unwrap!(
-                    lexer,
-                    errors,
-                    "Expected <type>",
-                    TypeValue::try_parse(lexer, errors).map(Pointer::new)
-                ),
-            }),
-            _ => {
+        loop {
+            let state = lexer.save_state();
+            let token = next_token(lexer);
+
+            let operator =
+                if let Some(operator) = BinaryOperator::parse(token.clone(), lexer, errors) {
+                    operator
+                } else if matches!(token.token_type, TokenType::CompoundOperator(_)) {
+                    // Compound assignments are statements, the operator and its right
+                    // side are still kept so that nothing is lost, and so that the
+                    // rest of the statement isn't reported as unexpected too.
+                    BinaryOperator::CompoundAssignment(token)
+                } else {
+                    lexer.set_state(state);
+
+                    break;
+                };
+            if operator.precedence() <= limit {
                 lexer.set_state(state);
-                Some(left)
+
+                break;
+            }
+            if let BinaryOperator::CompoundAssignment(token) = &operator
+                && let Ok(range) = token.get_range()
+            {
+                errors.push(Diagnostic::new(
+                    DiagnosticCode::CompoundAssignmentInExpression,
+                    range.start,
+                    range.end,
+                    "Compound assignment operators can't be used in expressions.",
+                ));
             }
+
+            let right = safe_unwrap!(
+                lexer,
+                errors,
+                DiagnosticCode::MissingExpression,
+                "<expr>",
+                Self::try_parse_with_precedence(lexer, errors, operator.right_precedence())
+                    .map(Pointer::new)
+            );
+
+            left = Self::BinaryExpression {
+                left: Pointer::new(left),
+                operator,
+                right,
+            };
         }
+
+        Some(left)
+    }
+
+    /// Like [`Expression::parse_with_precedence`] but resets the lexer's state
+    /// upon failure.
+    fn try_parse_with_precedence(
+        lexer: &mut Lexer,
+        errors: &mut Vec<Diagnostic>,
+        limit: u8,
+    ) -> Option<Self> {
+        let state = lexer.save_state();
+        let expression = Self::parse_with_precedence(next_token(lexer), lexer, errors, limit);
+
+        if expression.is_none() {
+            lexer.set_state(state);
+        }
+
+        expression
+    }
+}
+
+impl Parse for Expression {
+    #[inline]
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
+        Self::parse_with_precedence(token, lexer, errors, 0)
     }
 }
 impl TryParse for Expression {}
 
 impl Parse for IfExpression {
-    fn parse(if_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(if_keyword: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if if_keyword != TokenType::Keyword(Keyword::If) {
             return None;
         }
@@ -216,7 +306,8 @@ impl Parse for IfExpression {
         let condition = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
@@ -226,13 +317,16 @@ impl Parse for IfExpression {
             TokenType::Keyword(Keyword::Then),
             TokenType::Keyword(Keyword::Then),
             errors,
-            "Expected `then`"
+            DiagnosticCode::MissingThen,
+            "Expected `then`",
+            related: RelatedSpan::from_token(&if_keyword, "`if` expression starts here."),
         );
 
         let if_expression = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
@@ -245,12 +339,15 @@ impl Parse for IfExpression {
             TokenType::Keyword(Keyword::Else),
             TokenType::Keyword(Keyword::Else),
             errors,
-            "Expected `else`"
+            DiagnosticCode::MissingElse,
+            "Expected `else`",
+            related: RelatedSpan::from_token(&if_keyword, "`if` expression starts here."),
         );
         let else_expression = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Expression::try_parse(lexer, errors).map(Pointer::new)
         );
 
@@ -271,7 +368,7 @@ impl Parse for ElseIfExpression {
     fn parse(
         else_if_keyword: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<Self> {
         if else_if_keyword != TokenType::Keyword(Keyword::Elseif) {
             return None;
@@ -280,7 +377,8 @@ impl Parse for ElseIfExpression {
         let condition = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
@@ -290,13 +388,16 @@ impl Parse for ElseIfExpression {
             TokenType::Keyword(Keyword::Then),
             TokenType::Keyword(Keyword::Then),
             errors,
-            "Expected `then`"
+            DiagnosticCode::MissingThen,
+            "Expected `then`",
+            related: RelatedSpan::from_token(&else_if_keyword, "`elseif` starts here."),
         );
 
         let expression = safe_unwrap!(
             lexer,
             errors,
-            "Expected <expr>",
+            DiagnosticCode::MissingExpression,
+            "<expr>",
             Pointer::<Expression>::try_parse(lexer, errors)
         );
 
@@ -0,0 +1,183 @@
+//! All `impl` blocks for:
+//!
+//! * [`BinaryOperator`]
+//! * [`UnaryOperator`]
+
+use luau_lexer::prelude::{CompoundOperator, Lexer, Operator, Symbol, Token, TokenType};
+
+use crate::types::{
+    Associativity, BinaryOperator, Diagnostic, OperatorCategory, Parse, UnaryOperator,
+};
+
+/// The precedence of all [`UnaryOperator`]s, between the ones of
+/// [`BinaryOperator::Multiplication`] and [`BinaryOperator::Exponentiation`].
+const UNARY_PRECEDENCE: u8 = 8;
+
+impl BinaryOperator {
+    /// The source token of this operator.
+    pub fn token(&self) -> &Token {
+        match self {
+            Self::Plus(token)
+            | Self::Minus(token)
+            | Self::Multiplication(token)
+            | Self::Division(token)
+            | Self::FloorDivision(token)
+            | Self::Modulo(token)
+            | Self::Exponentiation(token)
+            | Self::Concatenation(token)
+            | Self::Equal(token)
+            | Self::NotEqual(token)
+            | Self::LessThan(token)
+            | Self::LessThanOrEqualTo(token)
+            | Self::GreaterThan(token)
+            | Self::GreaterThanOrEqualTo(token)
+            | Self::And(token)
+            | Self::Or(token)
+            | Self::CompoundAssignment(token) => token,
+        }
+    }
+
+    /// The precedence of this operator, higher values bind tighter. The values
+    /// match the ones used by Luau itself, and [`UnaryOperator::precedence`] is
+    /// only lower than [`BinaryOperator::Exponentiation`]'s.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Or(_) | Self::CompoundAssignment(_) => 1,
+            Self::And(_) => 2,
+            Self::Equal(_)
+            | Self::NotEqual(_)
+            | Self::LessThan(_)
+            | Self::LessThanOrEqualTo(_)
+            | Self::GreaterThan(_)
+            | Self::GreaterThanOrEqualTo(_) => 3,
+            Self::Concatenation(_) => 5,
+            Self::Plus(_) | Self::Minus(_) => 6,
+            Self::Multiplication(_)
+            | Self::Division(_)
+            | Self::FloorDivision(_)
+            | Self::Modulo(_) => 7,
+            Self::Exponentiation(_) => 10,
+        }
+    }
+
+    /// The precedence used when parsing the right side of this operator. It's
+    /// lower than [`BinaryOperator::precedence`] for right associative operators
+    /// so that chains of them group to the right.
+    pub(crate) fn right_precedence(&self) -> u8 {
+        match self.associativity() {
+            Associativity::Left => self.precedence(),
+            Associativity::Right => self.precedence() - 1,
+        }
+    }
+
+    /// The associativity of this operator. Only `..` and `^` are right associative,
+    /// as well as [`BinaryOperator::CompoundAssignment`] which takes the rest of
+    /// the expression.
+    pub fn associativity(&self) -> Associativity {
+        match self {
+            Self::Concatenation(_) | Self::Exponentiation(_) | Self::CompoundAssignment(_) => {
+                Associativity::Right
+            }
+            _ => Associativity::Left,
+        }
+    }
+
+    /// The category this operator belongs to.
+    pub fn category(&self) -> OperatorCategory {
+        match self {
+            Self::Plus(_)
+            | Self::Minus(_)
+            | Self::Multiplication(_)
+            | Self::Division(_)
+            | Self::FloorDivision(_)
+            | Self::Modulo(_)
+            | Self::Exponentiation(_) => OperatorCategory::Arithmetic,
+            Self::Concatenation(_) => OperatorCategory::Concatenation,
+            Self::Equal(_)
+            | Self::NotEqual(_)
+            | Self::LessThan(_)
+            | Self::LessThanOrEqualTo(_)
+            | Self::GreaterThan(_)
+            | Self::GreaterThanOrEqualTo(_) => OperatorCategory::Comparison,
+            Self::And(_) | Self::Or(_) => OperatorCategory::Logical,
+            Self::CompoundAssignment(token) => match token.token_type {
+                TokenType::CompoundOperator(CompoundOperator::ConcatenationEqual) => {
+                    OperatorCategory::Concatenation
+                }
+                _ => OperatorCategory::Arithmetic,
+            },
+        }
+    }
+}
+
+impl Parse for BinaryOperator {
+    fn parse(token: Token, _: &mut Lexer, _: &mut Vec<Diagnostic>) -> Option<Self> {
+        let operator = match token.token_type {
+            TokenType::Operator(Operator::Plus) => Self::Plus,
+            TokenType::Operator(Operator::Minus) => Self::Minus,
+            TokenType::Operator(Operator::Multiplication) => Self::Multiplication,
+            TokenType::Operator(Operator::Division) => Self::Division,
+            TokenType::Operator(Operator::FloorDivision) => Self::FloorDivision,
+            TokenType::Operator(Operator::Modulo) => Self::Modulo,
+            TokenType::Operator(Operator::Exponentiation) => Self::Exponentiation,
+            TokenType::Operator(Operator::Concatenation) => Self::Concatenation,
+            TokenType::CompoundOperator(CompoundOperator::EqualEqual) => Self::Equal,
+            TokenType::Operator(Operator::NotEqual) => Self::NotEqual,
+            TokenType::Symbol(Symbol::OpeningAngleBrackets) => Self::LessThan,
+            TokenType::CompoundOperator(CompoundOperator::LessThanOrEqualTo) => {
+                Self::LessThanOrEqualTo
+            }
+            TokenType::Symbol(Symbol::ClosingAngleBrackets) => Self::GreaterThan,
+            TokenType::CompoundOperator(CompoundOperator::GreaterThanOrEqualTo) => {
+                Self::GreaterThanOrEqualTo
+            }
+            TokenType::Operator(Operator::And) => Self::And,
+            TokenType::Operator(Operator::Or) => Self::Or,
+            _ => return None,
+        };
+
+        Some(operator(token))
+    }
+}
+
+impl UnaryOperator {
+    /// The source token of this operator.
+    pub fn token(&self) -> &Token {
+        match self {
+            Self::Minus(token) | Self::Not(token) | Self::Length(token) => token,
+        }
+    }
+
+    /// The precedence of unary operators, they bind tighter than all binary
+    /// operators except for [`BinaryOperator::Exponentiation`].
+    #[inline]
+    pub fn precedence(&self) -> u8 {
+        UNARY_PRECEDENCE
+    }
+
+    /// The associativity of this operator. Unary operators are always right
+    /// associative, `- -a` is `-(-a)`.
+    pub fn associativity(&self) -> Associativity {
+        Associativity::Right
+    }
+
+    /// The category this operator belongs to.
+    pub fn category(&self) -> OperatorCategory {
+        match self {
+            Self::Minus(_) => OperatorCategory::Arithmetic,
+            Self::Not(_) => OperatorCategory::Logical,
+            Self::Length(_) => OperatorCategory::Length,
+        }
+    }
+}
+
+impl Parse for UnaryOperator {
+    fn parse(token: Token, _: &mut Lexer, _: &mut Vec<Diagnostic>) -> Option<Self> {
+        match token.token_type {
+            TokenType::Operator(Operator::Minus) => Some(Self::Minus(token)),
+            TokenType::Operator(Operator::Not) => Some(Self::Not(token)),
+            TokenType::Operator(Operator::Length) => Some(Self::Length(token)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! All `impl` blocks for [`InterpolatedString`].
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Lexer, Literal, LuauString, Symbol, Token, TokenType, Trivia};
+use smol_str::SmolStr;
+
+use crate::{
+    r#impl::{literals::check_string_escapes, visitor::PositionShifter},
+    types::{
+        Diagnostic, DiagnosticCode, Expression, InterpolatedString, InterpolatedStringSegment,
+        Parse, Pointer, VisitMut,
+    },
+    utils::{advance_position, get_token_type_display_extended, next_token},
+};
+
+/// Get the character offset of `target` in `text`, where `text` starts at
+/// `(0, 0)`.
+fn get_offset(text: &[char], target: Position) -> usize {
+    let mut position = Position::default();
+
+    text.iter()
+        .position(|character| {
+            let is_target = position >= target;
+            advance_position(&mut position, *character);
+
+            is_target
+        })
+        .unwrap_or(text.len())
+}
+
+/// Create a token for a string segment of an [`InterpolatedString`].
+fn string_segment(
+    start: Position,
+    leading_trivia: Vec<Trivia>,
+    characters: &[char],
+    trailing_trivia: Vec<Trivia>,
+    end: Position,
+) -> InterpolatedStringSegment {
+    InterpolatedStringSegment::String(Token {
+        start,
+        leading_trivia,
+        token_type: TokenType::Literal(Literal::String(LuauString::Backticks(
+            characters.iter().copied().collect(),
+        ))),
+        trailing_trivia,
+        end,
+    })
+}
+
+impl InterpolatedString {
+    /// Parse the interpolation at `characters[start..]`, which starts right
+    /// after a `{` at `position`. Returns the expression, the start of the
+    /// closing `}` along with its index in `characters`, and the trivia before
+    /// and after the expression.
+    #[allow(clippy::type_complexity)]
+    fn parse_interpolation(
+        characters: &[char],
+        start: usize,
+        position: Position,
+        errors: &mut Vec<Diagnostic>,
+    ) -> Option<(Expression, (Position, usize), (Vec<Trivia>, Vec<Trivia>))> {
+        let text = characters[start..].iter().copied().collect::<SmolStr>();
+        let mut lexer = Lexer::new(&text);
+        let mut shifter = PositionShifter {
+            old_end: Position::default(),
+            new_end: position,
+        };
+
+        let mut expression_errors = Vec::new();
+        let token = next_token(&mut lexer);
+        let leading_trivia = token.leading_trivia.clone();
+
+        let expression = if token == TokenType::Symbol(Symbol::ClosingCurlyBrackets) {
+            expression_errors.push(
+                Diagnostic::new(
+                    DiagnosticCode::EmptyInterpolation,
+                    token.start,
+                    token.start,
+                    "Expected an expression between `{` and `}`.",
+                )
+                .with_expected("<expr>"),
+            );
+
+            None
+        } else {
+            Expression::parse(token, &mut lexer, &mut expression_errors)
+        };
+        let closing = if expression.is_some() {
+            next_token(&mut lexer)
+        } else {
+            Token::empty(TokenType::EndOfFile)
+        };
+
+        if expression.is_some() && closing != TokenType::Symbol(Symbol::ClosingCurlyBrackets) {
+            expression_errors.push(
+                Diagnostic::new(
+                    DiagnosticCode::UnclosedInterpolation,
+                    closing.start,
+                    closing.start,
+                    "Expected `}` to close the interpolation.",
+                )
+                .with_expected(get_token_type_display_extended(&TokenType::Symbol(
+                    Symbol::ClosingCurlyBrackets,
+                )))
+                .with_found_token(&closing.token_type),
+            );
+        }
+        errors.extend(expression_errors.into_iter().map(|mut error| {
+            error.start = shifter.shift(error.start);
+            error.end = shifter.shift(error.end);
+            for related in &mut error.related {
+                related.start = shifter.shift(related.start);
+                related.end = shifter.shift(related.end);
+            }
+
+            error
+        }));
+
+        let mut expression = expression?;
+        if closing != TokenType::Symbol(Symbol::ClosingCurlyBrackets) {
+            return None;
+        }
+        expression.visit_mut(&mut shifter);
+
+        Some((
+            expression,
+            (
+                shifter.shift(closing.start),
+                start + get_offset(&characters[start..], closing.start),
+            ),
+            (leading_trivia, closing.leading_trivia),
+        ))
+    }
+}
+
+impl Parse for InterpolatedString {
+    /// Parses the interpolations in a backtick string, [`None`] is returned if
+    /// the token isn't a backtick string, if it doesn't have any
+    /// interpolations, or if one of its interpolations is malformed, the errors
+    /// are still added in that last case.
+    fn parse(token: Token, _: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
+        let TokenType::Literal(Literal::String(LuauString::Backticks(string))) = &token.token_type
+        else {
+            return None;
+        };
+        let characters = string.chars().collect::<Vec<_>>();
+
+        let mut segments = Vec::new();
+        let mut segment_start = 0;
+        let mut segment_position = token.start;
+        let mut segment_trivia = token.leading_trivia.clone();
+
+        let mut position = token.start;
+        let mut index = 1;
+        advance_position(&mut position, '`');
+
+        while let Some(&character) = characters.get(index) {
+            if character == '\\' {
+                // Escapes are decoded later, they're only skipped here so that
+                // `\{` and `\u{...}` aren't treated as interpolations.
+                let is_unicode = characters.get(index + 1) == Some(&'u');
+                for character in characters.iter().skip(index).take(2) {
+                    advance_position(&mut position, *character);
+                }
+                index += 2;
+
+                if is_unicode && characters.get(index) == Some(&'{') {
+                    while let Some(&character) = characters.get(index) {
+                        advance_position(&mut position, character);
+                        index += 1;
+
+                        if character == '}' {
+                            break;
+                        }
+                    }
+                }
+
+                continue;
+            }
+
+            advance_position(&mut position, character);
+            index += 1;
+
+            if character != '{' {
+                continue;
+            }
+
+            let (expression, (closing_position, closing_index), (leading, trailing)) =
+                Self::parse_interpolation(&characters, index, position, errors)?;
+
+            segments.push(string_segment(
+                segment_position,
+                segment_trivia,
+                &characters[segment_start..index],
+                leading,
+                position,
+            ));
+            segments.push(InterpolatedStringSegment::Expression(Pointer::new(
+                expression,
+            )));
+
+            segment_start = closing_index;
+            segment_position = closing_position;
+            segment_trivia = trailing;
+            index = closing_index;
+            position = closing_position;
+        }
+
+        if segments.is_empty() {
+            return None;
+        }
+        segments.push(string_segment(
+            segment_position,
+            segment_trivia,
+            &characters[segment_start..],
+            token.trailing_trivia.clone(),
+            token.end,
+        ));
+
+        for segment in &segments {
+            if let InterpolatedStringSegment::String(token) = segment {
+                check_string_escapes(token, errors);
+            }
+        }
+
+        Some(Self { segments })
+    }
+}
@@ -4,18 +4,15 @@
 //! * [`TableAccessKey`]
 //! * [`TableAccess`]
 
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 
-use crate::{
-    handle_error_token,
-    types::{
-        ExpressionWrap, FunctionCall, Parse, ParseWithArgs, Pointer, TableAccess, TableAccessKey,
-        TableAccessPrefix, TableKey, TryParse,
-    },
+use crate::types::{
+    Diagnostic, DiagnosticCode, ExpressionWrap, FunctionCall, Parse, ParseWithArgs, Pointer,
+    TableAccess, TableAccessKey, TableAccessPrefix, TableKey, TryParse,
 };
 
 impl Parse for TableAccessPrefix {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         match token.token_type {
             TokenType::Error(error) => handle_error_token!(errors, error),
             TokenType::Identifier(_) | TokenType::PartialKeyword(_) => {
@@ -25,7 +22,11 @@ impl Parse for TableAccessPrefix {
                 token,
                 lexer,
                 errors,
-                ("Expected <expr>", Symbol::ClosingParenthesis),
+                (
+                    DiagnosticCode::MissingExpression,
+                    "Expected <expr>",
+                    Symbol::ClosingParenthesis,
+                ),
             )
             .map(Pointer::new)
             .map(Self::ExpressionWrap),
@@ -36,7 +37,7 @@ impl Parse for TableAccessPrefix {
 impl TryParse for TableAccessPrefix {}
 
 impl Parse for TableAccessKey {
-    fn parse(maybe_dot: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(maybe_dot: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         Self::parse_with(maybe_dot, lexer, errors, true)
     }
 }
@@ -46,7 +47,7 @@ impl ParseWithArgs<bool> for TableAccessKey {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         accept_expression: bool,
     ) -> Option<Self> {
         match token.token_type {
@@ -58,6 +59,7 @@ impl ParseWithArgs<bool> for TableAccessKey {
                     TokenType::Identifier(_) | TokenType::PartialKeyword(_),
                     TokenType::Identifier("*error*".into()),
                     errors,
+                    DiagnosticCode::MissingIdentifier,
                     "Expected <ident>"
                 );
 
@@ -77,7 +79,7 @@ impl ParseWithArgs<bool> for TableAccessKey {
 }
 
 impl Parse for TableAccess {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         Some(Self {
             prefix: TableAccessPrefix::parse(token, lexer, errors)?,
             accessed_keys: Vec::<TableAccessKey>::try_parse(lexer, errors)?,
@@ -85,7 +87,7 @@ impl Parse for TableAccess {
     }
 }
 impl TryParse for TableAccess {
-    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         Some(Self {
             prefix: TableAccessPrefix::try_parse(lexer, errors)?,
             accessed_keys: Vec::<TableAccessKey>::try_parse(lexer, errors)?,
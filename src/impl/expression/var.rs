@@ -1,11 +1,13 @@
 //! All `impl` blocks for [`Var`].
 
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 
-use crate::types::{Parse, TableAccess, TableAccessKey, TableAccessPrefix, TryParse, Var};
+use crate::types::{
+    Diagnostic, Parse, TableAccess, TableAccessKey, TableAccessPrefix, TryParse, Var,
+};
 
 impl Parse for Var {
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if !matches!(
             token.token_type,
             TokenType::PartialKeyword(_) | TokenType::Identifier(_)
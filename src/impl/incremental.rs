@@ -0,0 +1,549 @@
+//! Incremental reparsing for [`Cst`].
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Keyword, Lexer, Token, TokenType, Trivia};
+use std::mem;
+
+use crate::{
+    r#impl::{
+        line_index::convert_positions, options::check_features, print::LastToken,
+        visitor::PositionShifter,
+    },
+    types::{
+        AstStatus, Block, Cst, Diagnostic, GetRange, LanguageFeatures, LineIndex, ParseWithArgs,
+        PositionEncoding, Print, Statement, Visit, VisitMut, Visitor, VisitorMut,
+    },
+    utils::{advance_position, next_token},
+};
+
+/// All tokens that can end a nested [`Block`], whichever node it's in. Nested
+/// blocks are reparsed until any of them, and the result is only kept if
+/// parsing stopped at the token that ended the block before.
+const BLOCK_ENDS: [TokenType; 4] = [
+    TokenType::Keyword(Keyword::End),
+    TokenType::Keyword(Keyword::Elseif),
+    TokenType::Keyword(Keyword::Else),
+    TokenType::Keyword(Keyword::Until),
+];
+
+/// A [`Block`] along with the tokens around it.
+struct EnclosingBlock<'a> {
+    /// The block itself.
+    block: &'a Block,
+
+    /// The token right before the block, [`None`] for the root one.
+    opener: Option<&'a Token>,
+
+    /// The token right after the block, [`None`] for the root one.
+    terminator: Option<&'a Token>,
+}
+
+/// A [`Visitor`] finding the innermost nested [`Block`] containing a change,
+/// where the change doesn't touch the tokens right before and after the block.
+struct BlockFinder<'a> {
+    /// The start of the change.
+    start: Position,
+
+    /// The end of the change in the old source code.
+    old_end: Position,
+
+    /// The last token visited.
+    previous: Option<&'a Token>,
+
+    /// The blocks being visited.
+    open: Vec<EnclosingBlock<'a>>,
+
+    /// The blocks that were left, waiting for the token after them.
+    closed: Vec<EnclosingBlock<'a>>,
+
+    /// The innermost block found.
+    found: Option<EnclosingBlock<'a>>,
+}
+
+impl<'a> Visitor<'a> for BlockFinder<'a> {
+    #[inline]
+    fn visit_block(&mut self, block: &'a Block) {
+        self.open.push(EnclosingBlock {
+            block,
+            opener: self.previous,
+            terminator: None,
+        });
+    }
+
+    #[inline]
+    fn leave_block(&mut self, _: &'a Block) {
+        self.closed.extend(self.open.pop());
+    }
+
+    fn visit_token(&mut self, token: &'a Token) {
+        // Inner blocks are left first, so the first match is the innermost one.
+        for mut block in self.closed.drain(..) {
+            if self.found.is_none()
+                && block.opener.is_some_and(|opener| opener.end < self.start)
+                && self.old_end < token.start
+            {
+                block.terminator = Some(token);
+                self.found = Some(block);
+            }
+        }
+
+        self.previous = Some(token);
+    }
+
+    #[inline]
+    fn skip_children(&mut self) -> bool {
+        self.found.is_some()
+    }
+}
+
+/// A [`VisitorMut`] putting the reparsed statements in their [`Block`], and
+/// moving all tokens after the change to their new positions.
+struct Splicer {
+    /// The start of the change.
+    start: Position,
+
+    /// The shifter used for tokens after the change.
+    shifter: PositionShifter,
+
+    /// The start of the token before the block to splice into, [`None`] if
+    /// it's the root block.
+    target: Option<Position>,
+
+    /// The start of the last token visited.
+    previous: Option<Position>,
+
+    /// Whether or not each of the blocks being visited is the target.
+    blocks: Vec<bool>,
+
+    /// Whether or not to skip the children of the node just visited.
+    skip: bool,
+
+    /// The start of the token before the block and its new trailing trivia,
+    /// if it was lexed again.
+    opener: Option<(Position, Vec<Trivia>)>,
+
+    /// The old start of the token after the block and the new token, if it was
+    /// lexed again.
+    terminator: Option<(Position, Token)>,
+
+    /// The old start of the first reused token and its new leading trivia,
+    /// which changes if the tokens before it did.
+    leading_trivia: Option<(Position, Vec<Trivia>)>,
+
+    /// The reparsed statements.
+    reparsed: Block,
+
+    /// The number of statements kept before the reparsed ones.
+    kept: usize,
+
+    /// The index of the first statement reused after the reparsed ones.
+    reused: usize,
+
+    /// Whether or not the old [`last statement`](Block::last_statement) is
+    /// reused.
+    reuses_last_statement: bool,
+}
+
+impl Splicer {
+    /// Replace the changed statements of `block` with the reparsed ones.
+    fn splice(&mut self, block: &mut Block) {
+        let reparsed = mem::take(&mut self.reparsed);
+        let reused = block.statements.split_off(self.reused);
+
+        block.statements.truncate(self.kept);
+        block.statements.extend(reparsed.statements);
+        block.statements.extend(reused);
+
+        if !self.reuses_last_statement {
+            block.last_statement = reparsed.last_statement;
+        }
+    }
+}
+
+impl VisitorMut for Splicer {
+    fn visit_token(&mut self, token: &mut Token) {
+        self.previous = Some(token.start);
+
+        if let Some((_, trivia)) = self.opener.take_if(|(start, _)| *start == token.start) {
+            token.trailing_trivia = trivia;
+        } else if let Some((_, new_token)) =
+            self.terminator.take_if(|(start, _)| *start == token.start)
+        {
+            *token = new_token;
+        } else if token.start >= self.shifter.old_end {
+            if let Some((_, trivia)) = self
+                .leading_trivia
+                .take_if(|(start, _)| *start == token.start)
+            {
+                token.leading_trivia = trivia;
+            }
+
+            self.shifter.visit_token(token);
+        }
+    }
+
+    fn visit_block(&mut self, block: &mut Block) {
+        self.blocks.push(self.previous == self.target);
+
+        // Statements before the change have nothing to update, skipping them
+        // avoids cloning them if they're shared.
+        for (statement, semicolon) in &mut block.statements {
+            let end = match semicolon {
+                Some(semicolon) => Ok(semicolon.end),
+                None => statement.get_range().map(|range| range.end),
+            };
+            if end.is_ok_and(|end| end < self.start) {
+                continue;
+            }
+
+            statement.visit_mut(self);
+            semicolon.visit_mut(self);
+        }
+        block.last_statement.visit_mut(self);
+
+        self.skip = true;
+    }
+
+    #[inline]
+    fn leave_block(&mut self, block: &mut Block) {
+        if self.blocks.pop() == Some(true) {
+            self.splice(block);
+        }
+    }
+
+    #[inline]
+    fn skip_children(&mut self) -> bool {
+        mem::take(&mut self.skip)
+    }
+}
+
+/// Get the part of the text that changed between `old_text` and `new_text`,
+/// as the start of the change, its end in `old_text`, and its end in
+/// `new_text`. Returns [`None`] if both texts are the same.
+fn get_changed_range(old_text: &str, new_text: &str) -> Option<(Position, Position, Position)> {
+    let old_chars = old_text.chars().collect::<Vec<_>>();
+    let new_chars = new_text.chars().collect::<Vec<_>>();
+
+    let prefix = old_chars
+        .iter()
+        .zip(&new_chars)
+        .take_while(|(old, new)| old == new)
+        .count();
+    if prefix == old_chars.len() && prefix == new_chars.len() {
+        return None;
+    }
+
+    let suffix = old_chars[prefix..]
+        .iter()
+        .rev()
+        .zip(new_chars[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let mut start = Position::default();
+    for character in &old_chars[..prefix] {
+        advance_position(&mut start, *character);
+    }
+
+    let mut old_end = start;
+    for character in &old_chars[prefix..old_chars.len() - suffix] {
+        advance_position(&mut old_end, *character);
+    }
+
+    let mut new_end = start;
+    for character in &new_chars[prefix..new_chars.len() - suffix] {
+        advance_position(&mut new_end, *character);
+    }
+
+    Some((start, old_end, new_end))
+}
+
+impl Cst {
+    /// Reparse this [`Cst`] after its source code changed from `old_text` to
+    /// `new_text`. Only the statements touched by the change in the innermost
+    /// block containing it (and the one before them, as its end may depend on
+    /// what follows it) are reparsed, statements before them are reused as is,
+    /// and all tokens after them are moved to their new positions. Usages of
+    /// features missing from `features` are reported like a full parse does.
+    ///
+    /// Returns [`None`] if the [`Cst`] can't be reparsed incrementally, which is
+    /// the case when it has errors or when the reparsed statements don't end
+    /// where the reused ones start, callers should do a full parse in that
+    /// case.
+    pub(crate) fn reparse(
+        mut self,
+        old_text: &str,
+        new_text: &str,
+        features: LanguageFeatures,
+    ) -> Option<Self> {
+        if self.has_errors() {
+            return None;
+        }
+        let Some((start, old_end, new_end)) = get_changed_range(old_text, new_text) else {
+            return Some(self);
+        };
+
+        // Converting the whole tree would clone all nodes it shares with older
+        // trees, so only the changed range and the reparsed nodes are.
+        let encoding = self.position_encoding;
+        let index = LineIndex::new(new_text);
+        let (start, old_end, new_end) = if encoding == PositionEncoding::Utf32 {
+            (start, old_end, new_end)
+        } else {
+            let old_index = LineIndex::new(old_text);
+
+            (
+                old_index.convert(start, PositionEncoding::Utf32, encoding),
+                old_index.convert(old_end, PositionEncoding::Utf32, encoding),
+                index.convert(new_end, PositionEncoding::Utf32, encoding),
+            )
+        };
+
+        self.errors = self.reparse_block(start, old_end, new_end, new_text, &index)?;
+        check_features(&self.block, features, &mut self.errors);
+        self.status = if self.errors.is_empty() {
+            AstStatus::Complete
+        } else {
+            AstStatus::HasErrors
+        };
+
+        Some(self)
+    }
+
+    /// Reparse the changed statements of the innermost block containing the
+    /// change, and put them in place of the old ones. Returns the errors met
+    /// while reparsing, or [`None`] if the result would differ from a full
+    /// parse, in which case the [`Cst`] is left untouched. The passed positions
+    /// use the encoding of the [`Cst`], and `index` is the one of `new_text`.
+    fn reparse_block(
+        &mut self,
+        start: Position,
+        old_end: Position,
+        new_end: Position,
+        new_text: &str,
+        index: &LineIndex,
+    ) -> Option<Vec<Diagnostic>> {
+        let encoding = self.position_encoding;
+        let mut finder = BlockFinder {
+            start,
+            old_end,
+            previous: None,
+            open: Vec::new(),
+            closed: Vec::new(),
+            found: None,
+        };
+        self.block.visit(&mut finder);
+
+        let EnclosingBlock {
+            block,
+            opener,
+            terminator,
+        } = finder.found.unwrap_or(EnclosingBlock {
+            block: &self.block,
+            opener: None,
+            terminator: None,
+        });
+
+        // The `EndOfFile` statement is only in the root block, after the
+        // last statement, and is never reused since its leading trivia depends
+        // on how the statements before it were parsed.
+        let has_end_of_file = block
+            .statements
+            .last()
+            .is_some_and(|(statement, _)| matches!(&**statement, Statement::EndOfFile(_)));
+        let statements_end = block.statements.len() - usize::from(has_end_of_file);
+        let has_last_statement = block.last_statement.is_some();
+
+        // The starts of all statements in source order.
+        let starts = block.statements[..statements_end]
+            .iter()
+            .map(|(statement, _)| statement.get_range())
+            .chain(
+                block
+                    .last_statement
+                    .iter()
+                    .map(|(statement, _)| statement.get_range()),
+            )
+            .chain(
+                block.statements[statements_end..]
+                    .iter()
+                    .map(|(statement, _)| statement.get_range()),
+            )
+            .map(|range| range.ok().map(|range| range.start))
+            .collect::<Option<Vec<_>>>()?;
+        let statement_index =
+            |index: usize| index - usize::from(has_last_statement && index > statements_end);
+
+        // Statements whose trailing trivia ends before the change are untouched,
+        // but the last of them is still reparsed.
+        let first_reparsed = starts
+            .get(1..)
+            .map(|rest| rest.partition_point(|statement_start| *statement_start < start))
+            .unwrap_or_default()
+            .saturating_sub(1);
+        let last_reusable = starts.len() - usize::from(has_end_of_file);
+
+        // Statements are only reused if they and their leading trivia are after
+        // the change.
+        let first_reusable = (starts.partition_point(|statement_start| *statement_start < old_end)
+            + 1)
+        .max(first_reparsed + 1)
+        .min(last_reusable);
+
+        let shifter = PositionShifter { old_end, new_end };
+        let candidates = starts[first_reusable..last_reusable]
+            .iter()
+            .chain(terminator.map(|token| &token.start))
+            .map(|statement_start| {
+                index.convert(
+                    shifter.shift(*statement_start),
+                    encoding,
+                    PositionEncoding::Utf32,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // The lexer doesn't allow setting the trivia before the next token,
+        // so it's moved to the last token before the reparsed statements and
+        // lexes it again.
+        let relexed = if first_reparsed > 0 {
+            let mut last_token = LastToken::default();
+            block.statements[first_reparsed - 1].visit(&mut last_token);
+
+            Some(last_token.token?)
+        } else {
+            opener
+        };
+
+        let mut lexer = Lexer::new(new_text);
+        let mut opener_trivia = None;
+        if let Some(relexed) = relexed {
+            let relexed_start = index.convert(relexed.start, encoding, PositionEncoding::Utf32);
+            let mut position = Position::default();
+            let mut chars = new_text.chars();
+
+            while position < relexed_start {
+                let character = chars.next()?;
+                if position >= lexer.lexer_position() {
+                    lexer.increment_position_by_char(character);
+                }
+
+                advance_position(&mut position, character);
+            }
+
+            let token = next_token(&mut lexer);
+            if first_reparsed == 0 {
+                opener_trivia = Some((relexed.start, token.trailing_trivia));
+            }
+        }
+
+        let mut errors = Vec::new();
+        let token = next_token(&mut lexer);
+        let (mut reparsed, mut next_token) = if candidates.binary_search(&token.start).is_ok()
+            || (terminator.is_some() && BLOCK_ENDS.contains(&token.token_type))
+        {
+            (Block::default(), token)
+        } else {
+            let block = Block::parse_with(
+                token,
+                &mut lexer,
+                &mut errors,
+                (
+                    candidates.as_slice(),
+                    terminator.is_some().then_some(BLOCK_ENDS),
+                ),
+            )?;
+
+            (block, next_token(&mut lexer))
+        };
+
+        let reached_end = reparsed
+            .statements
+            .last()
+            .is_some_and(|(statement, _)| matches!(&**statement, Statement::EndOfFile(_)));
+        let reused = if reached_end {
+            starts.len()
+        } else {
+            first_reusable + candidates.binary_search(&next_token.start).ok()?
+        };
+
+        // A token that failed to parse right before the reused statements is
+        // dropped without an error, so make sure nothing was skipped. Nothing
+        // being parsed at all (`None` above) is the same case.
+        let mut last_token = LastToken::default();
+        reparsed.visit(&mut last_token);
+        if let Some(token) = last_token.token
+            && !reached_end
+        {
+            let mut end = token.end;
+            for character in token.print_final_trivia().chars() {
+                advance_position(&mut end, character);
+            }
+
+            if end != next_token.start {
+                return None;
+            }
+        }
+
+        // When reparsing from the start of a nested block, its first tokens may
+        // belong to the node before it instead (like a return type), which
+        // would show up as errors here.
+        if first_reparsed == 0 && opener.is_some() && !errors.is_empty() {
+            return None;
+        }
+
+        // The lexer always uses UTF-32.
+        convert_positions(
+            Some(&mut reparsed),
+            &mut errors,
+            index,
+            PositionEncoding::Utf32,
+            encoding,
+        );
+        convert_positions(
+            Some(&mut next_token),
+            &mut [],
+            index,
+            PositionEncoding::Utf32,
+            encoding,
+        );
+
+        let reuses_last_statement = has_last_statement && reused <= statements_end;
+        if reparsed.last_statement.is_some() && (reused < last_reusable || reuses_last_statement) {
+            return None;
+        }
+
+        let leading_trivia = (!reached_end && reused < starts.len())
+            .then(|| (starts[reused], next_token.leading_trivia.clone()));
+        let terminator = match terminator {
+            Some(terminator) if reused == starts.len() => {
+                if next_token.token_type != terminator.token_type {
+                    return None;
+                }
+
+                Some((terminator.start, next_token))
+            }
+            _ => None,
+        };
+
+        let mut splicer = Splicer {
+            start,
+            shifter,
+            target: opener.map(|token| token.start),
+            previous: None,
+            blocks: Vec::new(),
+            skip: false,
+            opener: opener_trivia,
+            terminator,
+            leading_trivia,
+            reparsed,
+            kept: first_reparsed,
+            reused: statement_index(reused),
+            reuses_last_statement,
+        };
+
+        self.block.visit_mut(&mut splicer);
+
+        Some(errors)
+    }
+}
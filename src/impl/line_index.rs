@@ -0,0 +1,410 @@
+//! All `impl` blocks for [`LineIndex`] and [`PositionEncoding`].
+
+use lsp_types::{Position, PositionEncodingKind};
+use luau_lexer::prelude::Token;
+
+use crate::types::{
+    Cst, Diagnostic, LineIndex, PositionEncoding, Print, Visit, VisitMut, Visitor, VisitorMut,
+    WideCharacter,
+};
+
+impl PositionEncoding {
+    /// Get the encoding matching the passed LSP
+    /// [`PositionEncodingKind`], if it's one of the 3 known ones.
+    pub fn from_kind(kind: &PositionEncodingKind) -> Option<Self> {
+        if *kind == PositionEncodingKind::UTF8 {
+            Some(Self::Utf8)
+        } else if *kind == PositionEncodingKind::UTF16 {
+            Some(Self::Utf16)
+        } else if *kind == PositionEncodingKind::UTF32 {
+            Some(Self::Utf32)
+        } else {
+            None
+        }
+    }
+
+    /// The number of columns the passed character takes in this encoding.
+    #[inline]
+    pub const fn len(self, character: char) -> u32 {
+        match self {
+            Self::Utf8 => character.len_utf8() as u32,
+            Self::Utf16 => character.len_utf16() as u32,
+            Self::Utf32 => 1,
+        }
+    }
+
+    /// Same as [`PositionEncoding::len`] but from the number of bytes the
+    /// character takes.
+    #[inline]
+    const fn len_from_utf8(self, len_utf8: u8) -> u32 {
+        match self {
+            Self::Utf8 => len_utf8 as u32,
+            Self::Utf16 if len_utf8 == 4 => 2,
+            Self::Utf16 | Self::Utf32 => 1,
+        }
+    }
+}
+
+impl From<PositionEncoding> for PositionEncodingKind {
+    #[inline]
+    fn from(encoding: PositionEncoding) -> Self {
+        match encoding {
+            PositionEncoding::Utf8 => Self::UTF8,
+            PositionEncoding::Utf16 => Self::UTF16,
+            PositionEncoding::Utf32 => Self::UTF32,
+        }
+    }
+}
+
+impl LineIndex {
+    /// Build the index of the passed text.
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_characters = Vec::new();
+        let mut line = 0;
+        let mut character = 0;
+
+        for (offset, char) in text.char_indices() {
+            if char == '\n' {
+                line_starts.push(offset + 1);
+                line += 1;
+                character = 0;
+
+                continue;
+            }
+
+            if !char.is_ascii() {
+                wide_characters.push(WideCharacter {
+                    line,
+                    character,
+                    len_utf8: char.len_utf8() as u8,
+                });
+            }
+            character += 1;
+        }
+
+        Self {
+            line_starts,
+            wide_characters,
+            len: text.len(),
+        }
+    }
+
+    /// Build the index of the code the passed [`Cst`] was parsed from, without
+    /// needing the code itself.
+    ///
+    /// # Note
+    ///
+    /// Tokens dropped while recovering from syntax errors aren't in the [`Cst`],
+    /// they're assumed to be made of ASCII characters. Use [`LineIndex::new`]
+    /// with the actual code if it's available and the [`Cst`] has errors.
+    pub fn from_cst(cst: &Cst) -> Self {
+        let mut builder = SourceBuilder {
+            text: String::new(),
+            position: Position::default(),
+            encoding: cst.position_encoding,
+        };
+        cst.visit(&mut builder);
+
+        Self::new(&builder.text)
+    }
+
+    /// The length of the indexed text, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether or not the indexed text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of lines in the indexed text. This is always at least 1.
+    #[inline]
+    pub fn line_count(&self) -> u32 {
+        self.line_starts.len() as u32
+    }
+
+    /// The byte range of the passed line, without the `\n` ending it.
+    fn line_range(&self, line: u32) -> Option<(usize, usize)> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .map_or(self.len, |next_start| next_start - 1);
+
+        Some((start, end))
+    }
+
+    /// The non-ASCII characters in the passed line.
+    fn wide_characters(&self, line: u32) -> &[WideCharacter] {
+        let start = self
+            .wide_characters
+            .partition_point(|character| character.line < line);
+        let end = self
+            .wide_characters
+            .partition_point(|character| character.line <= line);
+
+        &self.wide_characters[start..end]
+    }
+
+    /// Convert a column in the passed line from `encoding` to
+    /// [`PositionEncoding::Utf32`]. Columns in the middle of a character are
+    /// moved to its start.
+    fn utf32_column(&self, line: u32, column: u32, encoding: PositionEncoding) -> u32 {
+        if encoding == PositionEncoding::Utf32 {
+            return column;
+        }
+
+        let mut extra = 0;
+
+        for character in self.wide_characters(line) {
+            let start = character.character + extra;
+            if start >= column {
+                break;
+            }
+
+            let len = encoding.len_from_utf8(character.len_utf8);
+            if column < start + len {
+                return character.character;
+            }
+
+            extra += len - 1;
+        }
+
+        column - extra
+    }
+
+    /// Convert a column in the passed line from [`PositionEncoding::Utf32`] to
+    /// `encoding`.
+    fn encoded_column(&self, line: u32, column: u32, encoding: PositionEncoding) -> u32 {
+        if encoding == PositionEncoding::Utf32 {
+            return column;
+        }
+
+        column
+            + self
+                .wide_characters(line)
+                .iter()
+                .take_while(|character| character.character < column)
+                .map(|character| encoding.len_from_utf8(character.len_utf8) - 1)
+                .sum::<u32>()
+    }
+
+    /// The length of the passed line, without the `\n` ending it, in the passed
+    /// encoding. Lines after the end of the text have a length of 0.
+    pub fn line_length(&self, line: u32, encoding: PositionEncoding) -> u32 {
+        let Some((start, end)) = self.line_range(line) else {
+            return 0;
+        };
+        let bytes = (end - start) as u32;
+
+        match encoding {
+            PositionEncoding::Utf8 => bytes,
+            _ => {
+                let characters = self.utf32_column(line, bytes, PositionEncoding::Utf8);
+
+                self.encoded_column(line, characters, encoding)
+            }
+        }
+    }
+
+    /// Get the byte offset of the passed [`Position`], which uses `encoding`.
+    /// Positions after the end of a line are clamped to it and positions after
+    /// the end of the text are clamped to its length.
+    pub fn offset(&self, position: Position, encoding: PositionEncoding) -> usize {
+        let Some((start, end)) = self.line_range(position.line) else {
+            return self.len;
+        };
+
+        let character = self.utf32_column(position.line, position.character, encoding);
+        let column = self.encoded_column(position.line, character, PositionEncoding::Utf8);
+
+        (start + column as usize).min(end)
+    }
+
+    /// Get the [`Position`], using `encoding`, of the passed byte offset.
+    /// Offsets after the end of the text are clamped to its length, and ones in
+    /// the middle of a character are moved to its start.
+    pub fn position(&self, offset: usize, encoding: PositionEncoding) -> Position {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let column = (offset - self.line_starts[line]) as u32;
+        let line = line as u32;
+
+        let character = self.utf32_column(line, column, PositionEncoding::Utf8);
+
+        Position::new(line, self.encoded_column(line, character, encoding))
+    }
+
+    /// Convert the passed [`Position`] from one encoding to another.
+    pub fn convert(
+        &self,
+        position: Position,
+        from: PositionEncoding,
+        to: PositionEncoding,
+    ) -> Position {
+        if from == to {
+            return position;
+        }
+
+        let character = self.utf32_column(position.line, position.character, from);
+
+        Position::new(
+            position.line,
+            self.encoded_column(position.line, character, to),
+        )
+    }
+}
+
+/// A [`Visitor`] rebuilding the source code of a [`Cst`] from its tokens, for
+/// [`LineIndex::from_cst`]. Unlike printing, it keeps the tokens at their
+/// positions even if ones before them were dropped while parsing.
+struct SourceBuilder {
+    /// The code built so far.
+    text: String,
+
+    /// The position at the end of [`SourceBuilder::text`].
+    position: Position,
+
+    /// The encoding positions in the [`Cst`] use.
+    encoding: PositionEncoding,
+}
+
+impl SourceBuilder {
+    /// Add the passed text.
+    fn push(&mut self, text: &str) {
+        for character in text.chars() {
+            if character == '\n' {
+                self.position = Position::new(self.position.line + 1, 0);
+            } else {
+                self.position.character += self.encoding.len(character);
+            }
+        }
+
+        self.text.push_str(text);
+    }
+
+    /// Add spaces and new lines until the passed position is reached, nothing
+    /// is added if it's already reached.
+    fn pad_to(&mut self, position: Position) {
+        while self.position.line < position.line {
+            self.push("\n");
+        }
+        if self.position.line == position.line {
+            while self.position.character < position.character {
+                self.push(" ");
+            }
+        }
+    }
+}
+
+impl Visitor<'_> for SourceBuilder {
+    fn visit_token(&mut self, token: &Token) {
+        let trivia = token
+            .leading_trivia
+            .iter()
+            .map(Print::print)
+            .collect::<String>();
+
+        // The leading trivia ends where the token starts, so its start is
+        // found by going backwards from there.
+        let lines = trivia.matches('\n').count() as u32;
+        let trivia_start = if lines == 0 {
+            let len = trivia
+                .chars()
+                .map(|char| self.encoding.len(char))
+                .sum::<u32>();
+
+            Position::new(token.start.line, token.start.character.saturating_sub(len))
+        } else {
+            Position::new(token.start.line.saturating_sub(lines), 0)
+        };
+
+        self.pad_to(trivia_start);
+        self.push(&trivia);
+        self.pad_to(token.start);
+
+        if let Some(text) = token.token_type.try_as_string() {
+            self.push(&text);
+        }
+    }
+}
+
+/// A [`VisitorMut`] converting all positions in a node from one encoding to
+/// another.
+struct PositionConverter<'a> {
+    /// The index of the code the node was parsed from.
+    index: &'a LineIndex,
+
+    /// The encoding to convert from.
+    from: PositionEncoding,
+
+    /// The encoding to convert to.
+    to: PositionEncoding,
+}
+
+impl PositionConverter<'_> {
+    /// Convert the passed position.
+    #[inline]
+    fn convert(&self, position: Position) -> Position {
+        self.index.convert(position, self.from, self.to)
+    }
+}
+
+impl VisitorMut for PositionConverter<'_> {
+    #[inline]
+    fn visit_token(&mut self, token: &mut Token) {
+        token.start = self.convert(token.start);
+        token.end = self.convert(token.end);
+    }
+}
+
+/// Convert all positions in the passed node and the diagnostics reported while
+/// parsing it from one encoding to another. `index` must be the index of the
+/// code the node was parsed from.
+pub(crate) fn convert_positions<T: VisitMut + ?Sized>(
+    node: Option<&mut T>,
+    errors: &mut [Diagnostic],
+    index: &LineIndex,
+    from: PositionEncoding,
+    to: PositionEncoding,
+) {
+    if from == to {
+        return;
+    }
+
+    let mut converter = PositionConverter { index, from, to };
+    if let Some(node) = node {
+        node.visit_mut(&mut converter);
+    }
+
+    for error in errors {
+        error.start = converter.convert(error.start);
+        error.end = converter.convert(error.end);
+
+        for related in &mut error.related {
+            related.start = converter.convert(related.start);
+            related.end = converter.convert(related.end);
+        }
+    }
+}
+
+impl Cst {
+    /// Convert all positions in this [`Cst`], including the ones in its
+    /// [`errors`](Cst::errors), to the passed encoding. `index` must be the
+    /// index of the code this [`Cst`] was parsed from.
+    pub fn set_position_encoding(&mut self, index: &LineIndex, encoding: PositionEncoding) {
+        convert_positions(
+            Some(&mut self.block),
+            &mut self.errors,
+            index,
+            self.position_encoding,
+            encoding,
+        );
+
+        self.position_encoding = encoding;
+    }
+}
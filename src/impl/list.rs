@@ -1,11 +1,12 @@
 //! All `impl` blocks for [`List`].
 
 use lsp_types::Range;
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 use std::ops::{Deref, DerefMut};
 
-use crate::types::{
-    GetRange, GetRangeError, List, ListItem, Parse, ParseWithArgs, Print, TryParse,
+use crate::{
+    types::{Diagnostic, GetRange, GetRangeError, List, ListItem, Parse, ParseWithArgs, TryParse},
+    utils::next_token,
 };
 
 impl<T> List<T> {
@@ -39,7 +40,7 @@ impl<T> List<T> {
                 break;
             }
 
-            token = lexer.next_token();
+            token = next_token(lexer);
         }
 
         lexer.set_state(state);
@@ -57,7 +58,7 @@ impl<T> Default for List<T> {
 
 impl<T: Parse> Parse for List<T> {
     #[inline]
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         Self::parse(token, lexer, |token, lexer| T::parse(token, lexer, errors))
     }
 }
@@ -68,7 +69,7 @@ impl<A: Clone, T: ParseWithArgs<A>> ParseWithArgs<A> for List<T> {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         args: A,
     ) -> Option<Self> {
         Self::parse(token, lexer, |token, lexer| {
@@ -126,50 +127,3 @@ impl<T: GetRange> GetRange for ListItem<T> {
         }
     }
 }
-
-impl<T: Print> Print for List<T> {
-    #[inline]
-    fn print(&self) -> String {
-        self.items.print()
-    }
-
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        self.items.print_final_trivia()
-    }
-
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        self.items.print_without_final_trivia()
-    }
-}
-
-impl<T: Print> Print for ListItem<T> {
-    #[inline]
-    fn print(&self) -> String {
-        match self {
-            Self::Trailing { item, separator } => {
-                item.print_without_final_trivia() + &separator.print()
-            }
-            Self::NonTrailing(item) => item.print(),
-        }
-    }
-
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        match self {
-            ListItem::Trailing { separator, .. } => separator.print_final_trivia(),
-            ListItem::NonTrailing(item) => item.print_final_trivia(),
-        }
-    }
-
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        match self {
-            ListItem::Trailing { item, separator } => {
-                item.print_without_final_trivia() + &separator.print_without_final_trivia()
-            }
-            ListItem::NonTrailing(item) => item.print_without_final_trivia(),
-        }
-    }
-}
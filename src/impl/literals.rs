@@ -0,0 +1,507 @@
+//! All `impl` blocks for [`ParsedNumber`], [`ParseNumberError`], and
+//! [`ParsedString`].
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Literal, LuauNumber, LuauString, Token, TokenType};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    num::IntErrorKind,
+    str::FromStr,
+};
+
+use crate::{
+    types::{
+        AstStatus, Cst, Diagnostic, DiagnosticCode, Expression, GetRange, ParseNumberError,
+        ParsedNumber, ParsedString, Visit, Visitor,
+    },
+    utils::advance_position,
+};
+
+/// Whether or not the passed number token, if it's an integer written in
+/// decimal, can be stored in an [`f64`] without being rounded. Other numbers
+/// always return `true`.
+fn is_exact_integer(token: &Token) -> bool {
+    let TokenType::Literal(Literal::Number(LuauNumber::Plain(number))) = &token.token_type else {
+        return true;
+    };
+    if number.contains(['.', 'e', 'E']) {
+        return true;
+    }
+
+    number
+        .replace('_', "")
+        .parse::<u128>()
+        .is_ok_and(|integer| integer as f64 as u128 == integer)
+}
+
+/// Parse the digits of a hex or binary number.
+fn parse_integer(digits: &str, radix: u32) -> Result<ParsedNumber, ParseNumberError> {
+    // `from_str_radix` accepts a leading sign, which Luau doesn't, parsing the
+    // sign alone is the only way to get the right error for it.
+    let digits = if digits.starts_with(['+', '-']) {
+        &digits[..1]
+    } else {
+        digits
+    };
+
+    match u128::from_str_radix(digits, radix) {
+        Ok(value) if value <= u64::MAX as u128 => Ok(ParsedNumber::HexOrByte(value as i128)),
+        Ok(_) => Err(ParseNumberError::OutOfRange),
+        Err(error) if *error.kind() == IntErrorKind::PosOverflow => {
+            Err(ParseNumberError::OutOfRange)
+        }
+        Err(error) => Err(ParseNumberError::HexOrByte(error)),
+    }
+}
+
+impl ParsedNumber {
+    /// Parse the passed Luau number. Hexadecimal (`0x`) and binary (`0b`)
+    /// numbers, `_` separators, exponents (`1e5`), and numbers starting with a
+    /// decimal point (`.5`) are all supported.
+    pub fn parse(number: &str) -> Result<Self, ParseNumberError> {
+        let number = number.replace('_', "");
+
+        if let Some(digits) = number
+            .strip_prefix("0x")
+            .or_else(|| number.strip_prefix("0X"))
+        {
+            return parse_integer(digits, 16);
+        }
+        if let Some(digits) = number
+            .strip_prefix("0b")
+            .or_else(|| number.strip_prefix("0B"))
+        {
+            return parse_integer(digits, 2);
+        }
+
+        // `f64::from_str` accepts `inf` and `nan`, which aren't numbers in Luau,
+        // they're turned into an empty string to get an error for them.
+        let is_valid = number
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || matches!(byte, b'.' | b'e' | b'E' | b'+' | b'-'));
+        let value = if is_valid { number.as_str() } else { "" }
+            .parse::<f64>()
+            .map_err(ParseNumberError::Other)?;
+
+        Ok(Self::Other(value))
+    }
+
+    /// Parse the number in the passed token, [`None`] is returned if it isn't
+    /// a number.
+    #[inline]
+    pub fn from_token(token: &Token) -> Option<Result<Self, ParseNumberError>> {
+        match &token.token_type {
+            TokenType::Literal(Literal::Number(number)) => Some(Self::try_from(number)),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ParsedNumber {
+    type Err = ParseNumberError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&LuauNumber> for ParsedNumber {
+    type Error = ParseNumberError;
+
+    #[inline]
+    fn try_from(value: &LuauNumber) -> Result<Self, Self::Error> {
+        match value {
+            LuauNumber::Plain(number) | LuauNumber::Binary(number) | LuauNumber::Hex(number) => {
+                Self::parse(number)
+            }
+        }
+    }
+}
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HexOrByte(error) => write!(f, "Malformed number: {error}."),
+            Self::Other(error) => write!(f, "Malformed number: {error}."),
+            Self::OutOfRange => write!(f, "Number is out of range."),
+        }
+    }
+}
+
+impl Error for ParseNumberError {}
+
+/// A helper to decode the escapes in strings.
+struct StringDecoder<'a> {
+    /// The characters of the string, excluding the quotes.
+    characters: &'a [char],
+
+    /// The index of the current character.
+    index: usize,
+
+    /// The position of the current character.
+    position: Position,
+
+    /// Whether or not `` \` `` and `\{` are valid escapes.
+    is_interpolated: bool,
+
+    /// The decoded bytes.
+    bytes: Vec<u8>,
+
+    /// The errors met while decoding.
+    errors: Vec<Diagnostic>,
+}
+
+impl StringDecoder<'_> {
+    /// Get the current character.
+    #[inline]
+    fn current(&self) -> Option<char> {
+        self.characters.get(self.index).copied()
+    }
+
+    /// Move to the next character.
+    #[inline]
+    fn advance(&mut self) {
+        if let Some(character) = self.current() {
+            advance_position(&mut self.position, character);
+            self.index += 1;
+        }
+    }
+
+    /// Add the passed character to the decoded bytes.
+    #[inline]
+    fn push_char(&mut self, character: char) {
+        self.bytes
+            .extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    /// Add the passed codepoint, encoded as UTF-8, to the decoded bytes. Unlike
+    /// [`char`]s, surrogates are allowed.
+    fn push_codepoint(&mut self, codepoint: u32) {
+        match codepoint {
+            0..0x80 => self.bytes.push(codepoint as u8),
+            0x80..0x800 => self.bytes.extend([
+                0xC0 | (codepoint >> 6) as u8,
+                0x80 | (codepoint & 0x3F) as u8,
+            ]),
+            0x800..0x10000 => self.bytes.extend([
+                0xE0 | (codepoint >> 12) as u8,
+                0x80 | ((codepoint >> 6) & 0x3F) as u8,
+                0x80 | (codepoint & 0x3F) as u8,
+            ]),
+            _ => self.bytes.extend([
+                0xF0 | (codepoint >> 18) as u8,
+                0x80 | ((codepoint >> 12) & 0x3F) as u8,
+                0x80 | ((codepoint >> 6) & 0x3F) as u8,
+                0x80 | (codepoint & 0x3F) as u8,
+            ]),
+        }
+    }
+
+    /// Add an error starting at `start` and ending at the current character.
+    #[inline]
+    fn error(&mut self, start: Position, message: &str) {
+        self.errors.push(Diagnostic::new(
+            DiagnosticCode::InvalidEscape,
+            start,
+            self.position,
+            message,
+        ));
+    }
+
+    /// Take up to `max` digits in the passed radix.
+    fn take_digits(&mut self, radix: u32, max: usize) -> Option<u32> {
+        let mut value: Option<u32> = None;
+
+        for _ in 0..max {
+            let Some(digit) = self
+                .current()
+                .and_then(|character| character.to_digit(radix))
+            else {
+                break;
+            };
+
+            value = Some(
+                value
+                    .unwrap_or_default()
+                    .saturating_mul(radix)
+                    .saturating_add(digit),
+            );
+            self.advance();
+        }
+
+        value
+    }
+
+    /// Decode the escape sequence starting at the current character, which is
+    /// the one right after the `\`.
+    fn decode_escape(&mut self, start: Position) {
+        let Some(character) = self.current() else {
+            return self.error(start, "Unfinished escape sequence.");
+        };
+
+        let byte = match character {
+            'a' => b'\x07',
+            'b' => b'\x08',
+            'f' => b'\x0C',
+            'n' | '\n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => b'\x0B',
+            '\\' | '"' | '\'' => character as u8,
+            '`' | '{' if self.is_interpolated => character as u8,
+            '\r' => {
+                self.advance();
+                if self.current() == Some('\n') {
+                    self.advance();
+                }
+
+                return self.bytes.push(b'\n');
+            }
+            'z' => {
+                self.advance();
+                while self.current().is_some_and(char::is_whitespace) {
+                    self.advance();
+                }
+
+                return;
+            }
+            'x' => {
+                self.advance();
+
+                let is_valid = self.characters[self.index..]
+                    .iter()
+                    .take(2)
+                    .filter(|character| character.is_ascii_hexdigit())
+                    .count()
+                    == 2;
+                if !is_valid {
+                    return self
+                        .error(start, "Hexadecimal escapes must have 2 hexadecimal digits.");
+                }
+
+                let value = self.take_digits(16, 2).unwrap_or_default();
+                return self.bytes.push(value as u8);
+            }
+            'u' => {
+                self.advance();
+
+                if self.current() != Some('{') {
+                    return self.error(start, "Expected `{` after `\\u`.");
+                }
+                self.advance();
+
+                let value = self.take_digits(16, usize::MAX);
+                if self.current() != Some('}') {
+                    return self.error(start, "Expected `}` to close unicode escape.");
+                }
+                self.advance();
+
+                match value {
+                    None => self.error(start, "Unicode escapes must have at least 1 digit."),
+                    Some(value) if value > 0x10FFFF => {
+                        self.error(start, "Unicode escapes can't be larger than `10FFFF`.")
+                    }
+                    Some(value) => self.push_codepoint(value),
+                }
+
+                return;
+            }
+            '0'..='9' => {
+                let value = self.take_digits(10, 3).unwrap_or_default();
+                if value > 255 {
+                    return self.error(start, "Decimal escapes can't be larger than 255.");
+                }
+
+                return self.bytes.push(value as u8);
+            }
+            _ => {
+                self.advance();
+                return self.error(start, "Invalid escape sequence.");
+            }
+        };
+
+        self.advance();
+        self.bytes.push(byte);
+    }
+
+    /// Decode the whole string.
+    fn decode(mut self) -> (ParsedString, Vec<Diagnostic>) {
+        while let Some(character) = self.current() {
+            if character == '\\' {
+                let start = self.position;
+                self.advance();
+                self.decode_escape(start);
+            } else {
+                self.push_char(character);
+                self.advance();
+            }
+        }
+
+        (ParsedString(self.bytes), self.errors)
+    }
+}
+
+/// Get the number of `\` right before `index`.
+#[inline]
+fn count_back_slashes(characters: &[char], index: usize) -> usize {
+    characters[..index]
+        .iter()
+        .rev()
+        .take_while(|character| **character == '\\')
+        .count()
+}
+
+impl ParsedString {
+    /// Decode the passed string literal into its runtime value, `start` is the
+    /// position of the string in the source code and is only used for the
+    /// positions of the returned errors.
+    ///
+    /// Invalid escapes are skipped and reported as errors, the returned string
+    /// is still usable in that case. Parts of
+    /// [`interpolated strings`](crate::types::InterpolatedString) are decoded
+    /// without the `}` and `{` around them.
+    pub fn parse(string: &LuauString, start: Position) -> (Self, Vec<Diagnostic>) {
+        let (string, is_interpolated) = match string {
+            LuauString::SingleQuotes(string) | LuauString::DoubleQuotes(string) => (string, false),
+            LuauString::Backticks(string) => (string, true),
+            LuauString::MultiLine(string) => {
+                let level = string.chars().skip(1).take_while(|c| *c == '=').count();
+                let content = string
+                    .get(level + 2..)
+                    .unwrap_or_default()
+                    .strip_suffix(&format!("]{}]", "=".repeat(level)))
+                    .unwrap_or_else(|| string.get(level + 2..).unwrap_or_default());
+
+                // A new line right after the opening brackets is skipped.
+                let content = content
+                    .strip_prefix("\r\n")
+                    .or_else(|| content.strip_prefix('\n'))
+                    .unwrap_or(content);
+
+                return (Self(content.as_bytes().to_vec()), Vec::new());
+            }
+        };
+
+        let characters = string.chars().collect::<Vec<_>>();
+        let Some(opening) = characters.first() else {
+            return (Self::default(), Vec::new());
+        };
+        let closing = match opening {
+            '`' | '}' => ['`', '{'],
+            quote => [*quote, *quote],
+        };
+        let end = if characters.len() > 1
+            && closing.contains(characters.last().unwrap())
+            && count_back_slashes(&characters, characters.len() - 1).is_multiple_of(2)
+        {
+            characters.len() - 1
+        } else {
+            characters.len()
+        };
+
+        let mut position = start;
+        advance_position(&mut position, *opening);
+
+        StringDecoder {
+            characters: &characters[1..end],
+            index: 0,
+            position,
+            is_interpolated,
+            bytes: Vec::new(),
+            errors: Vec::new(),
+        }
+        .decode()
+    }
+
+    /// Decode the string in the passed token, [`None`] is returned if it isn't
+    /// a string. See [`ParsedString::parse`].
+    #[inline]
+    pub fn from_token(token: &Token) -> Option<(Self, Vec<Diagnostic>)> {
+        match &token.token_type {
+            TokenType::Literal(Literal::String(string)) => Some(Self::parse(string, token.start)),
+            _ => None,
+        }
+    }
+
+    /// Get this string as a [`str`], if it's valid UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        str::from_utf8(&self.0).ok()
+    }
+}
+
+/// Check the escapes in the passed string token, adding errors for invalid
+/// ones. Does nothing for tokens that aren't strings.
+#[inline]
+pub(crate) fn check_string_escapes(token: &Token, errors: &mut Vec<Diagnostic>) {
+    if let Some((_, string_errors)) = ParsedString::from_token(token) {
+        errors.extend(string_errors);
+    }
+}
+
+/// A [`Visitor`] checking the ranges of all numbers in a [`Cst`].
+#[derive(Default)]
+struct NumberChecker {
+    /// The diagnostics found so far.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl NumberChecker {
+    /// Add a diagnostic at the passed token.
+    fn report(&mut self, token: &Token, code: DiagnosticCode, message: String) {
+        if let Ok(range) = token.get_range() {
+            self.diagnostics
+                .push(Diagnostic::new(code, range.start, range.end, message));
+        }
+    }
+}
+
+impl Visitor<'_> for NumberChecker {
+    fn visit_expression(&mut self, expression: &Expression) {
+        let Expression::Number(token) = expression else {
+            return;
+        };
+        let Some(result) = ParsedNumber::from_token(token) else {
+            return;
+        };
+
+        match result {
+            Ok(ParsedNumber::Other(_)) if !is_exact_integer(token) => self.report(
+                token,
+                DiagnosticCode::ImpreciseNumber,
+                "Number can't be represented exactly and will be rounded.".to_string(),
+            ),
+            Ok(_) => (),
+            Err(error) => {
+                let code = match error {
+                    ParseNumberError::OutOfRange => DiagnosticCode::NumberOutOfRange,
+                    ParseNumberError::HexOrByte(_) | ParseNumberError::Other(_) => {
+                        DiagnosticCode::MalformedNumber
+                    }
+                };
+
+                self.report(token, code, error.to_string())
+            }
+        }
+    }
+}
+
+impl Cst {
+    /// Check all numbers in this [`Cst`], adding a diagnostic to
+    /// [`Cst::errors`] for every number that's malformed, that's out of range,
+    /// or that can't be represented exactly. This pass is optional and isn't
+    /// done by the [`Parser`](crate::prelude::Parser). Only malformed numbers
+    /// are errors, the [`status`](Cst::status) is set to
+    /// [`HasErrors`](AstStatus::HasErrors) if there are any.
+    pub fn check_numbers(&mut self) {
+        let mut checker = NumberChecker::default();
+        self.visit(&mut checker);
+
+        if checker.diagnostics.iter().any(Diagnostic::is_error) {
+            self.status = AstStatus::HasErrors;
+        }
+        self.errors.extend(checker.diagnostics);
+    }
+}
@@ -0,0 +1,147 @@
+//! Conversions from this crate's types to the ones used by the language server
+//! protocol.
+
+use lsp_types::{
+    DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Position, Range,
+    Uri,
+};
+use std::{collections::HashMap, str::FromStr};
+
+use crate::types::{Cst, Diagnostic, LineIndex, PositionEncoding, RelatedSpan, Severity};
+
+/// The `source` of all diagnostics reported by this crate.
+const SOURCE: &str = "luau-parser";
+
+/// Convert the passed position, which uses `encoding`, to use UTF-32 columns,
+/// clamping it to the end of its line.
+fn to_utf32_position(
+    index: &LineIndex,
+    encoding: PositionEncoding,
+    position: Position,
+) -> Position {
+    let position = index.convert(position, encoding, PositionEncoding::Utf32);
+    let line_length = index.line_length(position.line, PositionEncoding::Utf32);
+
+    Position::new(position.line, position.character.min(line_length))
+}
+
+/// Convert the passed range, which uses `encoding`, to use UTF-16 columns.
+/// Empty ranges, like the ones of errors without an end, are widened to cover
+/// the character after them, or the one before them at the end of a line, so
+/// that editors still show them.
+fn to_utf16_range(
+    index: &LineIndex,
+    encoding: PositionEncoding,
+    start: Position,
+    end: Position,
+) -> Range {
+    let start = to_utf32_position(index, encoding, start);
+    let end = to_utf32_position(index, encoding, end);
+
+    let (start, end) = if end > start {
+        (start, end)
+    } else if start.character < index.line_length(start.line, PositionEncoding::Utf32) {
+        (start, Position::new(start.line, start.character + 1))
+    } else if start.character > 0 {
+        (Position::new(start.line, start.character - 1), start)
+    } else {
+        (start, start)
+    };
+
+    Range::new(
+        index.convert(start, PositionEncoding::Utf32, PositionEncoding::Utf16),
+        index.convert(end, PositionEncoding::Utf32, PositionEncoding::Utf16),
+    )
+}
+
+impl From<Severity> for DiagnosticSeverity {
+    #[inline]
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => Self::ERROR,
+            Severity::Warning => Self::WARNING,
+            Severity::Information => Self::INFORMATION,
+            Severity::Hint => Self::HINT,
+        }
+    }
+}
+
+/// Merge diagnostics with the same range and code into one. The first one's
+/// message is kept, the others' related spans are added to it, and its
+/// severity is raised to the highest of them.
+fn merge_duplicates(diagnostics: &[Diagnostic]) -> Vec<Diagnostic> {
+    let mut merged: Vec<Diagnostic> = Vec::with_capacity(diagnostics.len());
+    let mut indices = HashMap::with_capacity(diagnostics.len());
+
+    for diagnostic in diagnostics {
+        let key = (diagnostic.start, diagnostic.end, diagnostic.code);
+        let Some(&index) = indices.get(&key) else {
+            indices.insert(key, merged.len());
+            merged.push(diagnostic.clone());
+            continue;
+        };
+        let existing = &mut merged[index];
+
+        for related in &diagnostic.related {
+            if !existing.related.contains(related) {
+                existing.related.push(related.clone());
+            }
+        }
+
+        existing.severity = existing.severity.min(diagnostic.severity);
+    }
+
+    merged
+}
+
+impl Cst {
+    /// Get the [`errors`](Cst::errors) of this [`Cst`] as LSP diagnostics, ready
+    /// to be published.
+    ///
+    /// * Columns are converted to UTF-16, which is what LSP uses by default.
+    /// * Errors without an end are widened to cover a single character.
+    /// * Errors with the same range and code are merged into one.
+    /// * Related spans are only included if [`Cst::uri`] is an absolute [`Uri`].
+    ///
+    /// `source` must be the code this [`Cst`] was parsed from. It's needed as
+    /// tokens dropped while recovering from errors aren't in the [`Cst`].
+    pub fn lsp_diagnostics(&self, source: &str) -> Vec<lsp_types::Diagnostic> {
+        if self.errors.is_empty() {
+            return Vec::new();
+        }
+
+        let index = LineIndex::new(source);
+        let range = |start, end| to_utf16_range(&index, self.position_encoding, start, end);
+
+        let uri = Uri::from_str(&self.uri)
+            .ok()
+            .filter(|uri| uri.scheme().is_some());
+        let related_information = |related: &[RelatedSpan]| {
+            let uri = uri.as_ref()?;
+
+            Some(
+                related
+                    .iter()
+                    .map(|span| DiagnosticRelatedInformation {
+                        location: Location::new(uri.clone(), range(span.start, span.end)),
+                        message: span.message.to_string(),
+                    })
+                    .collect(),
+            )
+        };
+
+        merge_duplicates(&self.errors)
+            .into_iter()
+            .map(|diagnostic| lsp_types::Diagnostic {
+                range: range(diagnostic.start, diagnostic.end),
+                severity: Some(diagnostic.severity.into()),
+                code: Some(NumberOrString::String(diagnostic.code.code().to_string())),
+                source: Some(SOURCE.to_string()),
+                related_information: related_information(&diagnostic.related)
+                    .filter(|related: &Vec<_>| !related.is_empty()),
+                message: diagnostic.message.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
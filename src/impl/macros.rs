@@ -11,7 +11,9 @@
 ///     pattern, // Must be `TokenType`
 ///     replacement, // Must be `TokenType`
 ///     errors,
+///     DiagnosticCode::MissingEnd,
 ///     "error_message",
+///     related: RelatedSpan::from_token(..), // optional
 /// )
 /// ```
 #[doc(hidden)]
@@ -22,7 +24,10 @@ macro_rules! next_token_recoverable {
         $pattern: pat,
         $replacement: expr,
         $errors: ident,
+        $code: expr,
         $error_message: expr
+        $(, related: $related: expr)?
+        $(,)?
     ) => {
         next_token_recoverable_with_condition!(
             $lexer,
@@ -30,7 +35,9 @@ macro_rules! next_token_recoverable {
             matches!(&$name.token_type, $pattern),
             $replacement,
             $errors,
+            $code,
             $error_message
+            $(, related: $related)?
         )
     };
 }
@@ -44,22 +51,28 @@ macro_rules! next_token_recoverable_with_condition {
         $condition: expr,
         $replacement: expr,
         $errors: ident,
+        $code: expr,
         $error_message: expr
+        $(, related: $related: expr)?
+        $(,)?
     ) => {
         let state = $lexer.save_state();
-        let mut $name = $lexer.next_token();
+        let mut $name = $crate::utils::next_token($lexer);
         if !$condition {
             $errors.push(
-                luau_lexer::prelude::ParseError::new(
+                $crate::types::Diagnostic::new(
+                    $code,
+                    state.lexer_position(),
                     state.lexer_position(),
                     format!(
                         "{} found {}",
                         $error_message,
                         $crate::utils::get_token_type_display_extended(&$name.token_type)
                     ),
-                    Some(state.lexer_position()),
                 )
-                .into(),
+                .with_expected($crate::utils::get_token_type_display_extended(&$replacement))
+                .with_found_token(&$name.token_type)
+                $(.with_related($related))?,
             );
 
             $name = luau_lexer::prelude::Token {
@@ -97,7 +110,7 @@ macro_rules! maybe_next_token {
 macro_rules! maybe_next_token_with_condition {
     ($lexer: ident, $name: ident, $condition: expr) => {
         let state = $lexer.save_state();
-        let $name = $lexer.next_token();
+        let $name = $crate::utils::next_token($lexer);
 
         let $name = if $condition {
             Some($name)
@@ -136,7 +149,7 @@ macro_rules! maybe_next_token_with_condition {
 /// ```ignore
 /// parse_function!(
 ///     let attributes = <expr>; // Type must be `Vec<Attribute>`.
-///     lexer.next_token(),
+///     next_token(lexer),
 ///     lexer,
 ///     errors,
 ///     { attributes } // extra field that matches that in the struct
@@ -169,6 +182,7 @@ macro_rules! parse_function {
         let generics = parse_bracketed!(
             $lexer,
             $errors,
+            $crate::types::DiagnosticCode::MissingGenericDeclaration,
             "Expected <generic declaration>",
             TokenType::Symbol(Symbol::OpeningAngleBrackets),
             Symbol::ClosingAngleBrackets,
@@ -178,6 +192,7 @@ macro_rules! parse_function {
         let parameters = force_parse_bracketed!(
             $lexer,
             $errors,
+            $crate::types::DiagnosticCode::MissingParameters,
             "Expected <parameter>",
             (
                 TokenType::Symbol(Symbol::OpeningParenthesis),
@@ -202,8 +217,13 @@ macro_rules! parse_function {
             TokenType::Keyword(Keyword::End),
             TokenType::Keyword(Keyword::End),
             $errors,
+            $crate::types::DiagnosticCode::MissingEnd,
             "Expected ".to_string()
-                + get_token_type_display_extended(&TokenType::Keyword(Keyword::End))
+                + get_token_type_display_extended(&TokenType::Keyword(Keyword::End)),
+            related: $crate::types::RelatedSpan::from_token(
+                &function_keyword,
+                "Unclosed function starts here.",
+            ),
         );
 
         Some(Self {
@@ -218,3 +238,23 @@ macro_rules! parse_function {
         })
     }};
 }
+
+/// Get the range of a function which starts at its attributes if it has any,
+/// or at `$keyword` otherwise.
+///
+/// # Usage
+///
+/// ```ignore
+/// function_range!(self, <keyword field>)
+/// ```
+#[doc(hidden)]
+macro_rules! function_range {
+    ($self: ident, $keyword: ident) => {{
+        let start = match $self.attributes.first() {
+            Some(attribute) => attribute.get_range()?.start,
+            None => $self.$keyword.get_range()?.start,
+        };
+
+        Ok(Range::new(start, $self.end_keyword.get_range()?.end))
+    }};
+}
@@ -6,44 +6,57 @@ mod macros;
 mod block;
 mod bracketed;
 mod cst;
+mod diagnostic;
 mod expression;
+mod incremental;
+pub(crate) mod line_index;
 mod list;
+pub(crate) mod literals;
+mod lsp;
 mod name;
+pub(crate) mod options;
+pub(crate) mod print;
 mod value;
+pub(crate) mod visitor;
 
 use lsp_types::Range;
 use luau_lexer::{
-    prelude::{Comment, Lexer, ParseError, Token, Trivia},
+    prelude::{Comment, Lexer, Literal, LuauNumber, LuauString, Token, Trivia},
     token::TokenType,
 };
-
-use crate::types::{
-    GetRange, GetRangeError, Parse, ParseWithArgs, Pointer, Print, TryParse, TryParseWithArgs,
+use std::fmt;
+
+use crate::{
+    types::{
+        Diagnostic, GetRange, GetRangeError, Parse, ParseWithArgs, Pointer, Print, TryParse,
+        TryParseWithArgs,
+    },
+    utils::next_token,
 };
 
 impl<T: Parse> Parse for Pointer<T> {
     #[inline]
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         T::parse(token, lexer, errors).map(Self::new)
     }
 }
 impl<T: TryParse + Parse> TryParse for Pointer<T> {
     #[inline]
-    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         T::try_parse(lexer, errors).map(Self::new)
     }
 }
 
 impl<T: Parse> Parse for Vec<T> {
     #[inline]
-    fn parse(mut token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(mut token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         let mut values = Vec::new();
         let mut state = lexer.save_state();
 
         while let Some(value) = T::parse(token, lexer, errors) {
             values.push(value);
             state = lexer.save_state();
-            token = lexer.next_token();
+            token = next_token(lexer);
         }
 
         lexer.set_state(state);
@@ -58,7 +71,7 @@ impl<T: ParseWithArgs<A>, A: Clone> ParseWithArgs<A> for Vec<T> {
     fn parse_with(
         mut token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         args: A,
     ) -> Option<Self> {
         let mut values = Vec::new();
@@ -67,7 +80,7 @@ impl<T: ParseWithArgs<A>, A: Clone> ParseWithArgs<A> for Vec<T> {
         while let Some(value) = T::parse_with(token, lexer, errors, args.clone()) {
             values.push(value);
             state = lexer.save_state();
-            token = lexer.next_token();
+            token = next_token(lexer);
         }
 
         lexer.set_state(state);
@@ -78,6 +91,8 @@ impl<T: ParseWithArgs<A>, A: Clone> ParseWithArgs<A> for Vec<T> {
 impl<T: ParseWithArgs<A>, A: Clone> TryParseWithArgs<A> for T {}
 
 impl GetRange for Token {
+    /// Get the range of the token itself, without its trivia. Tokens lexed by
+    /// the parser have their [`end`](Token::end) moved to the end of their text.
     #[inline]
     fn get_range(&self) -> Result<Range, GetRangeError> {
         Ok(Range::new(self.start, self.end))
@@ -86,72 +101,103 @@ impl GetRange for Token {
 
 impl Print for Comment {
     #[inline]
-    fn print(&self) -> String {
-        match self {
-            Comment::SingleLine(smol_str) | Comment::MultiLine(smol_str) => smol_str.to_string(),
-        }
+    fn print_final_trivia_to(&self, _: &mut impl fmt::Write) -> fmt::Result {
+        Ok(())
     }
 
-    fn print_final_trivia(&self) -> String {
-        unreachable!()
-    }
-    fn print_without_final_trivia(&self) -> String {
-        unreachable!()
+    #[inline]
+    fn print_without_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Comment::SingleLine(smol_str) | Comment::MultiLine(smol_str) => out.write_str(smol_str),
+        }
     }
 }
 impl Print for Trivia {
     #[inline]
-    fn print(&self) -> String {
-        match self {
-            Trivia::Spaces(smol_str) => smol_str.to_string(),
-            Trivia::Comment(comment) => comment.print(),
-        }
+    fn print_final_trivia_to(&self, _: &mut impl fmt::Write) -> fmt::Result {
+        Ok(())
     }
 
-    fn print_final_trivia(&self) -> String {
-        unreachable!()
-    }
-    fn print_without_final_trivia(&self) -> String {
-        unreachable!()
+    #[inline]
+    fn print_without_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Trivia::Spaces(smol_str) => out.write_str(smol_str),
+            Trivia::Comment(comment) => comment.print_without_final_trivia_to(out),
+        }
     }
 }
 
 /// [`Print`] implementation for [`Vec<Trivia>`] as the default one won't work.
 /// It takes `&[Trivia]` so we don't need to `.clone()`.
-fn print_trivia(trivia: &[Trivia]) -> String {
+fn print_trivia(trivia: &[Trivia], out: &mut impl fmt::Write) -> fmt::Result {
     trivia
         .iter()
-        .fold("".to_string(), |str, item| str + &item.print())
+        .try_for_each(|item| item.print_without_final_trivia_to(out))
+}
+
+/// Writes the text of the passed [`token type`](TokenType), which is the same as
+/// [`TokenType::try_as_string`] but without allocating.
+fn print_token_type(token_type: &TokenType, out: &mut impl fmt::Write) -> fmt::Result {
+    match token_type {
+        TokenType::Literal(Literal::Number(
+            LuauNumber::Plain(smol_str) | LuauNumber::Binary(smol_str) | LuauNumber::Hex(smol_str),
+        ))
+        | TokenType::Literal(Literal::String(
+            LuauString::SingleQuotes(smol_str)
+            | LuauString::DoubleQuotes(smol_str)
+            | LuauString::Backticks(smol_str)
+            | LuauString::MultiLine(smol_str),
+        ))
+        | TokenType::Identifier(smol_str)
+        | TokenType::Comment(Comment::SingleLine(smol_str) | Comment::MultiLine(smol_str)) => {
+            out.write_str(smol_str)
+        }
+        TokenType::Literal(Literal::Boolean(true)) => out.write_str("true"),
+        TokenType::Literal(Literal::Boolean(false)) => out.write_str("false"),
+        TokenType::Keyword(keyword) => write!(out, "{keyword}"),
+        TokenType::PartialKeyword(partial_keyword) => write!(out, "{partial_keyword}"),
+        TokenType::Symbol(symbol) => write!(out, "{symbol}"),
+        TokenType::Operator(operator) => write!(out, "{operator}"),
+        TokenType::CompoundOperator(compound_operator) => write!(out, "{compound_operator}"),
+        TokenType::EndOfFile | TokenType::Error(_) => Ok(()),
+    }
+}
+
+/// Whether or not the passed [`token type`](TokenType) has text to print.
+#[inline]
+const fn has_text(token_type: &TokenType) -> bool {
+    !matches!(token_type, TokenType::EndOfFile | TokenType::Error(_))
 }
 
 impl Print for Token {
     #[inline]
-    fn print_final_trivia(&self) -> String {
+    fn print_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
         if self.token_type == TokenType::EndOfFile {
-            print_trivia(&self.leading_trivia)
+            print_trivia(&self.leading_trivia, out)
         } else {
-            print_trivia(&self.trailing_trivia)
+            print_trivia(&self.trailing_trivia, out)
         }
     }
 
     #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        self.token_type
-            .try_as_string()
-            .map(|token_type| print_trivia(&self.leading_trivia) + &token_type)
-            .unwrap_or_default()
+    fn print_without_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        if has_text(&self.token_type) {
+            print_trivia(&self.leading_trivia, out)?;
+            print_token_type(&self.token_type, out)?;
+        }
+
+        Ok(())
     }
 
     #[inline]
-    fn print(&self) -> String {
-        self.token_type
-            .try_as_string()
-            .map(|token_type| {
-                print_trivia(&self.leading_trivia)
-                    + &token_type
-                    + &print_trivia(&self.trailing_trivia)
-            })
-            .unwrap_or_default()
+    fn print_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        if has_text(&self.token_type) {
+            print_trivia(&self.leading_trivia, out)?;
+            print_token_type(&self.token_type, out)?;
+            print_trivia(&self.trailing_trivia, out)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -161,47 +207,6 @@ impl<T: GetRange> GetRange for Pointer<T> {
         (**self).get_range()
     }
 }
-impl<T: Print> Print for Pointer<T> {
-    #[inline]
-    fn print(&self) -> String {
-        (**self).print()
-    }
-
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        (**self).print_final_trivia()
-    }
-
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        (**self).print_without_final_trivia()
-    }
-}
-
-impl<T: Print> Print for Option<T> {
-    #[inline]
-    fn print(&self) -> String {
-        match self {
-            Some(item) => item.print(),
-            None => "".to_string(),
-        }
-    }
-
-    fn print_final_trivia(&self) -> String {
-        match self {
-            Some(item) => item.print_final_trivia(),
-            None => "".to_string(),
-        }
-    }
-
-    fn print_without_final_trivia(&self) -> String {
-        match self {
-            Some(item) => item.print_without_final_trivia(),
-            None => "".to_string(),
-        }
-    }
-}
-
 impl<T: GetRange> GetRange for Vec<T> {
     #[inline]
     fn get_range(&self) -> Result<Range, GetRangeError> {
@@ -217,46 +222,3 @@ impl<T: GetRange> GetRange for Vec<T> {
         }
     }
 }
-impl<T: Print> Print for Vec<T> {
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        self.last()
-            .map(|item| item.print_final_trivia())
-            .unwrap_or_default()
-    }
-
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        self.iter().fold("".to_string(), |str, item| {
-            str + &item.print_without_final_trivia()
-        })
-    }
-}
-
-impl<T: Print, U: Print> Print for (T, U) {
-    #[inline]
-    fn print(&self) -> String {
-        let end = self.1.print();
-
-        if end.is_empty() {
-            self.0.print()
-        } else {
-            self.0.print_without_final_trivia() + &end
-        }
-    }
-
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        let maybe_final_trivia = self.1.print_final_trivia();
-        if maybe_final_trivia.is_empty() {
-            self.0.print_final_trivia()
-        } else {
-            maybe_final_trivia
-        }
-    }
-
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        self.0.print_without_final_trivia() + &self.1.print_without_final_trivia()
-    }
-}
@@ -1,9 +1,9 @@
 //! All `impl` blocks for [`Name`].
 
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 use smol_str::SmolStr;
 
-use crate::types::{Name, Parse, Pointer, TryParse, TypeValue};
+use crate::types::{Diagnostic, Name, Parse, Pointer, TryParse, TypeValue};
 
 impl Name {
     /// An error name that should be used when a name failed to parse but must exist.
@@ -15,7 +15,7 @@ impl Name {
 }
 
 impl Parse for Name {
-    fn parse(name: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<Self> {
+    fn parse(name: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<Self> {
         if !matches!(
             name.token_type,
             TokenType::Identifier(_) | TokenType::PartialKeyword(_)
@@ -0,0 +1,323 @@
+//! All `impl` blocks for [`ParserOptions`] and related types, as well as the
+//! check reporting usages of disabled [`LanguageFeatures`].
+
+use lsp_types::Range;
+use luau_lexer::{
+    prelude::{CompoundOperator, Literal, LuauNumber, LuauString, Token},
+    token::TokenType,
+};
+
+use crate::types::{
+    Attribute, BinaryOperator, CompoundSetExpression, Diagnostic, DiagnosticCode,
+    GenericDeclarationParameter, GetRange, IfExpression, InterpolatedString, LanguageFeatures,
+    LanguageLevel, ParserOptions, PositionEncoding, TerminationStatement, TypeDefinition,
+    TypeFunction, TypeValue, Visit, Visitor,
+};
+
+impl LanguageFeatures {
+    /// All features enabled.
+    pub const ALL: Self = Self {
+        continue_statement: true,
+        compound_assignment: true,
+        floor_division: true,
+        attributes: true,
+        types: true,
+        type_functions: true,
+        generic_defaults: true,
+        string_interpolation: true,
+        if_expressions: true,
+        binary_numbers: true,
+        number_separators: true,
+    };
+
+    /// All features disabled.
+    pub const NONE: Self = Self {
+        continue_statement: false,
+        compound_assignment: false,
+        floor_division: false,
+        attributes: false,
+        types: false,
+        type_functions: false,
+        generic_defaults: false,
+        string_interpolation: false,
+        if_expressions: false,
+        binary_numbers: false,
+        number_separators: false,
+    };
+
+    /// The features supported by the passed [`LanguageLevel`].
+    pub const fn new(level: LanguageLevel) -> Self {
+        match level {
+            LanguageLevel::Lua51 => Self::NONE,
+            LanguageLevel::Luau => Self {
+                type_functions: false,
+                ..Self::ALL
+            },
+            LanguageLevel::LuauNewSolver => Self::ALL,
+        }
+    }
+
+    /// Whether or not all features are enabled.
+    #[inline]
+    pub fn is_all(&self) -> bool {
+        *self == Self::ALL
+    }
+}
+
+impl Default for LanguageFeatures {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl From<LanguageLevel> for LanguageFeatures {
+    #[inline]
+    fn from(level: LanguageLevel) -> Self {
+        Self::new(level)
+    }
+}
+
+impl ParserOptions {
+    /// Create options targeting the passed [`LanguageLevel`].
+    #[inline]
+    pub const fn new(level: LanguageLevel) -> Self {
+        Self {
+            features: LanguageFeatures::new(level),
+            position_encoding: PositionEncoding::Utf32,
+        }
+    }
+
+    /// Set the enabled language features. Meant to be chained.
+    #[inline]
+    pub const fn with_features(mut self, features: LanguageFeatures) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Set how the columns of positions in parsed code are counted. Meant to
+    /// be chained.
+    #[inline]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+}
+
+impl From<LanguageLevel> for ParserOptions {
+    #[inline]
+    fn from(level: LanguageLevel) -> Self {
+        Self::new(level)
+    }
+}
+
+/// A [`Visitor`] reporting usages of disabled [`LanguageFeatures`].
+struct FeatureChecker<'a> {
+    /// The enabled features.
+    features: LanguageFeatures,
+
+    /// Where errors are reported.
+    errors: &'a mut Vec<Diagnostic>,
+
+    /// How many type nodes the visitor is currently in. Only the outermost one
+    /// is reported when types are disabled.
+    type_depth: usize,
+
+    /// How many interpolated strings the visitor is currently in. Their
+    /// segments are backtick strings, but only the whole string is reported.
+    interpolated_string_depth: usize,
+}
+
+impl FeatureChecker<'_> {
+    /// Report the usage of the passed feature at the passed range.
+    fn report(&mut self, feature: &str, range: Range) {
+        self.errors.push(Diagnostic::new(
+            DiagnosticCode::DisabledFeature,
+            range.start,
+            range.end,
+            format!("The `{feature}` feature is disabled."),
+        ));
+    }
+
+    /// Report the usage of the passed feature at the passed node, nodes without
+    /// a range are skipped as they already have a syntax error.
+    #[inline]
+    fn report_node(&mut self, feature: &str, node: &impl GetRange) {
+        if let Ok(range) = node.get_range() {
+            self.report(feature, range);
+        }
+    }
+
+    /// Enter a type node, reporting it if it's the outermost one and types are
+    /// disabled.
+    fn enter_type(&mut self, node: &impl GetRange) {
+        if self.type_depth == 0 && !self.features.types {
+            self.report_node("types", node);
+        }
+        self.type_depth += 1;
+    }
+}
+
+impl Visitor<'_> for FeatureChecker<'_> {
+    fn visit_termination_statement(&mut self, node: &TerminationStatement) {
+        if let TerminationStatement::Continue(token) = node
+            && !self.features.continue_statement
+        {
+            self.report_node("continue_statement", token);
+        }
+    }
+
+    fn visit_compound_set_expression(&mut self, node: &CompoundSetExpression) {
+        if !self.features.compound_assignment {
+            self.report_node("compound_assignment", &node.operation);
+        } else if !self.features.floor_division
+            && node.operation == TokenType::CompoundOperator(CompoundOperator::FloorDivisionEqual)
+        {
+            self.report_node("floor_division", &node.operation);
+        }
+    }
+
+    fn visit_binary_operator(&mut self, node: &BinaryOperator) {
+        if let BinaryOperator::FloorDivision(token) = node
+            && !self.features.floor_division
+        {
+            self.report_node("floor_division", token);
+        }
+    }
+
+    fn visit_attribute(&mut self, node: &Attribute) {
+        if !self.features.attributes {
+            self.report_node("attributes", node);
+        }
+    }
+
+    fn visit_interpolated_string(&mut self, node: &InterpolatedString) {
+        if self.interpolated_string_depth == 0 && !self.features.string_interpolation {
+            self.report_node("string_interpolation", node);
+        }
+        self.interpolated_string_depth += 1;
+    }
+
+    fn leave_interpolated_string(&mut self, _: &InterpolatedString) {
+        self.interpolated_string_depth -= 1;
+    }
+
+    fn visit_token(&mut self, node: &Token) {
+        match &node.token_type {
+            TokenType::Literal(Literal::String(LuauString::Backticks(_)))
+                if self.interpolated_string_depth == 0 && !self.features.string_interpolation =>
+            {
+                self.report_node("string_interpolation", node);
+            }
+            TokenType::Literal(Literal::Number(LuauNumber::Binary(_)))
+                if !self.features.binary_numbers =>
+            {
+                self.report_node("binary_numbers", node);
+            }
+            TokenType::Literal(Literal::Number(
+                LuauNumber::Plain(text) | LuauNumber::Binary(text) | LuauNumber::Hex(text),
+            )) if text.contains('_') && !self.features.number_separators => {
+                self.report_node("number_separators", node);
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_if_expression(&mut self, node: &IfExpression) {
+        if !self.features.if_expressions {
+            self.report_node("if_expressions", node);
+        }
+    }
+
+    fn visit_type_function(&mut self, node: &TypeFunction) {
+        if self.type_depth == 0 {
+            if !self.features.type_functions {
+                self.report_node("type_functions", node);
+            } else if !self.features.types {
+                self.report_node("types", node);
+            }
+        }
+        self.type_depth += 1;
+    }
+
+    fn leave_type_function(&mut self, _: &TypeFunction) {
+        self.type_depth -= 1;
+    }
+
+    fn visit_type_definition(&mut self, node: &TypeDefinition) {
+        self.enter_type(node);
+    }
+
+    fn leave_type_definition(&mut self, _: &TypeDefinition) {
+        self.type_depth -= 1;
+    }
+
+    fn visit_type_value(&mut self, node: &TypeValue) {
+        self.enter_type(node);
+    }
+
+    fn leave_type_value(&mut self, _: &TypeValue) {
+        self.type_depth -= 1;
+    }
+
+    fn visit_generic_declaration_parameter(&mut self, node: &GenericDeclarationParameter) {
+        if self.features.types
+            && !self.features.generic_defaults
+            && let Some(equal) = &node.equal
+        {
+            let end = node
+                .default
+                .as_ref()
+                .and_then(|default| default.get_range().ok())
+                .map_or(equal.end, |range| range.end);
+
+            self.report("generic_defaults", Range::new(equal.start, end));
+        }
+
+        self.enter_type(node);
+    }
+
+    fn leave_generic_declaration_parameter(&mut self, _: &GenericDeclarationParameter) {
+        self.type_depth -= 1;
+    }
+}
+
+/// Report all usages of features that aren't enabled in `features` in the
+/// passed node. Nothing is done if all features are enabled. The reported
+/// errors are merged into `errors` in position order, assuming `errors` is
+/// already sorted. Returns whether or not anything was reported.
+pub(crate) fn check_features<T: Visit + ?Sized>(
+    node: &T,
+    features: LanguageFeatures,
+    errors: &mut Vec<Diagnostic>,
+) -> bool {
+    if features.is_all() {
+        return false;
+    }
+
+    let mut found = Vec::new();
+    node.visit(&mut FeatureChecker {
+        features,
+        errors: &mut found,
+        type_depth: 0,
+        interpolated_string_depth: 0,
+    });
+    if found.is_empty() {
+        return false;
+    }
+
+    let mut merged = Vec::with_capacity(errors.len() + found.len());
+    let mut found = found.into_iter().peekable();
+    for error in errors.drain(..) {
+        while let Some(feature_error) =
+            found.next_if(|feature_error| feature_error.start < error.start)
+        {
+            merged.push(feature_error);
+        }
+        merged.push(error);
+    }
+    merged.extend(found);
+    *errors = merged;
+
+    true
+}
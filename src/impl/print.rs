@@ -0,0 +1,228 @@
+//! [`Print`] implementations for all nodes that implement [`Visit`].
+
+use luau_lexer::prelude::{Token, Trivia};
+use std::{fmt, mem};
+
+use crate::types::{
+    Attribute, BinaryOperator, Block, Bracketed, Closure, CompoundSetExpression, ConversionError,
+    DoBlock, ElseIfExpression, ElseIfStatement, ElseStatement, EndOfFile, Expression,
+    FunctionArgument, FunctionArguments, FunctionCall, FunctionCallInvoked,
+    GenericDeclarationParameter, GenericFor, GenericParameterInfo, GenericParameterInfoDefault,
+    GlobalFunction, GlobalFunctionName, IfExpression, IfStatement, InterpolatedString,
+    InterpolatedStringSegment, List, ListItem, LocalAssignment, LocalFunction, Name, NumericalFor,
+    Parameter, ParameterTypeName, Pointer, PrefixExp, Print, RepeatBlock, SetExpression, Statement,
+    Table, TableAccess, TableAccessKey, TableAccessPrefix, TableField, TableFieldValue, TableKey,
+    TerminationStatement, TypeDefinition, TypeFunction, TypeValue, UnaryOperator, Var, Visit,
+    Visitor, WhileLoop,
+};
+
+/// A [`Visitor`] that streams every token it visits into `out`, stopping at
+/// the first error.
+struct Printer<'a, 'b, W: fmt::Write> {
+    /// The sink to write to.
+    out: &'b mut W,
+
+    /// The last token visited.
+    last_token: Option<&'a Token>,
+
+    /// Whether or not to skip the children of the last visited node, which is
+    /// the case for tokens as their trivia is already printed with them.
+    skip: bool,
+
+    /// The result of writing so far.
+    result: fmt::Result,
+}
+
+impl<'a, W: fmt::Write> Visitor<'a> for Printer<'a, '_, W> {
+    #[inline]
+    fn visit_token(&mut self, token: &'a Token) {
+        self.last_token = Some(token);
+        self.skip = true;
+
+        if self.result.is_ok() {
+            self.result = token.print_without_final_trivia_to(self.out);
+        }
+    }
+
+    #[inline]
+    fn visit_trivia(&mut self, trivia: &'a Trivia) {
+        if self.result.is_ok() {
+            self.result = trivia.print_without_final_trivia_to(self.out);
+        }
+    }
+
+    #[inline]
+    fn skip_children(&mut self) -> bool {
+        mem::take(&mut self.skip)
+    }
+}
+
+/// A [`Visitor`] getting the last token in a node.
+#[derive(Default)]
+pub(crate) struct LastToken<'a> {
+    /// The last token visited.
+    pub(crate) token: Option<&'a Token>,
+
+    /// Whether or not to skip the children of the last visited node, which is
+    /// the case for tokens.
+    skip: bool,
+}
+
+impl<'a> Visitor<'a> for LastToken<'a> {
+    #[inline]
+    fn visit_token(&mut self, token: &'a Token) {
+        self.token = Some(token);
+        self.skip = true;
+    }
+
+    #[inline]
+    fn skip_children(&mut self) -> bool {
+        mem::take(&mut self.skip)
+    }
+}
+
+/// Print `node` into `out` using a [`Printer`], returning the last token
+/// visited.
+#[inline]
+fn print_node<'a, N: Visit>(
+    node: &'a N,
+    out: &mut impl fmt::Write,
+) -> Result<Option<&'a Token>, fmt::Error> {
+    let mut printer = Printer {
+        out,
+        last_token: None,
+        skip: false,
+        result: Ok(()),
+    };
+    node.visit(&mut printer);
+
+    printer.result.map(|()| printer.last_token)
+}
+
+/// Helper macro to implement [`Print`] for nodes using their [`Visit`]
+/// implementation, which already walks every token in source order. Wrappers
+/// use it too, instead of printing their items one by one, so that the whole
+/// node is walked once and the last token is only looked for once.
+macro_rules! impl_print {
+    (@impl [$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> Print for $ty {
+            #[inline]
+            fn print_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+                let mut last_token = LastToken::default();
+                self.visit(&mut last_token);
+
+                last_token.token.map_or(Ok(()), |token| token.print_final_trivia_to(out))
+            }
+
+            #[inline]
+            fn print_without_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+                print_node(self, out).map(|_| ())
+            }
+
+            #[inline]
+            fn print_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+                print_node(self, out)?.map_or(Ok(()), |token| token.print_final_trivia_to(out))
+            }
+        }
+    };
+    (@generic <$($generic:ident),+> $ty:ty) => {
+        impl_print!(@impl [$($generic: Visit),+] $ty);
+    };
+    ($($ty:ty),* $(,)?) => {
+        $( impl_print!(@impl [] $ty); )*
+    };
+}
+
+impl_print!(@generic <T> Pointer<T>);
+impl_print!(@generic <T> Option<T>);
+impl_print!(@generic <T> Vec<T>);
+impl_print!(@generic <T, U> (T, U));
+impl_print!(@generic <T> Bracketed<T>);
+impl_print!(@generic <T> List<T>);
+impl_print!(@generic <T> ListItem<T>);
+
+impl_print!(
+    Block,
+    Statement,
+    TerminationStatement,
+    LocalFunction,
+    GlobalFunction,
+    GlobalFunctionName,
+    TypeFunction,
+    Parameter,
+    Attribute,
+    LocalAssignment,
+    SetExpression,
+    CompoundSetExpression,
+    DoBlock,
+    GenericFor,
+    NumericalFor,
+    IfStatement,
+    ElseIfStatement,
+    ElseStatement,
+    RepeatBlock,
+    WhileLoop,
+    EndOfFile,
+    TypeDefinition,
+    TypeValue,
+    ParameterTypeName,
+    GenericParameterInfo,
+    GenericDeclarationParameter,
+    GenericParameterInfoDefault,
+    Expression,
+    PrefixExp,
+    IfExpression,
+    ElseIfExpression,
+    BinaryOperator,
+    UnaryOperator,
+    Var,
+    TableAccess,
+    TableAccessPrefix,
+    TableAccessKey,
+    FunctionCall,
+    FunctionCallInvoked,
+    FunctionArguments,
+    FunctionArgument,
+    Closure,
+    InterpolatedString,
+    InterpolatedStringSegment,
+    Name,
+    Table,
+    TableField,
+    TableKey,
+    TableFieldValue,
+);
+
+impl Print for ConversionError {
+    fn print_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Self::FunctionCall(function_call) => function_call.print_final_trivia_to(out),
+            Self::Var(var) => var.print_final_trivia_to(out),
+            Self::UnaryExpression { expression, .. } => expression.print_final_trivia_to(out),
+            Self::BinaryExpression { right, .. } => right.print_final_trivia_to(out),
+        }
+    }
+
+    fn print_without_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Self::FunctionCall(function_call) => function_call.print_without_final_trivia_to(out),
+            Self::Var(var) => var.print_without_final_trivia_to(out),
+            Self::UnaryExpression {
+                operator,
+                expression,
+            } => {
+                operator.print_without_final_trivia_to(out)?;
+                expression.print_without_final_trivia_to(out)
+            }
+            Self::BinaryExpression {
+                left,
+                operator,
+                right,
+            } => {
+                left.print_without_final_trivia_to(out)?;
+                operator.print_without_final_trivia_to(out)?;
+                right.print_without_final_trivia_to(out)
+            }
+        }
+    }
+}
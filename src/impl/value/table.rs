@@ -6,16 +6,13 @@
 //! * [`Table`]
 
 use lsp_types::Range;
-use luau_lexer::prelude::{Lexer, ParseError, Symbol, Token, TokenType};
+use luau_lexer::prelude::{Lexer, Symbol, Token, TokenType};
 use std::cell::Cell;
 
-use crate::{
-    safe_unwrap,
-    types::{
-        Bracketed, BracketedList, Expression, FunctionArguments, GetRange, GetRangeError, Parse,
-        ParseWithArgs, Pointer, Print, Table, TableAccessKey, TableField, TableFieldValue,
-        TableKey, TryParse, TryParseWithArgs, TypeValue,
-    },
+use crate::types::{
+    Bracketed, BracketedList, Diagnostic, DiagnosticCode, Expression, FunctionArguments, GetRange,
+    GetRangeError, Parse, ParseWithArgs, Pointer, Table, TableAccessKey, TableField,
+    TableFieldValue, TableKey, TryParse, TryParseWithArgs, TypeValue,
 };
 
 /// A simple struct holding arguments needed for parsing tables.
@@ -60,7 +57,7 @@ impl ParseWithArgs<bool> for TableKey {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         is_type: bool,
     ) -> Option<Self> {
         match token.token_type {
@@ -71,7 +68,11 @@ impl ParseWithArgs<bool> for TableKey {
                         token,
                         lexer,
                         errors,
-                        ("Expected <type>", Symbol::ClosingBrackets),
+                        (
+                            DiagnosticCode::MissingType,
+                            "Expected <type>",
+                            Symbol::ClosingBrackets,
+                        ),
                     )
                     .map(Self::Type)
                 } else {
@@ -79,7 +80,11 @@ impl ParseWithArgs<bool> for TableKey {
                         token,
                         lexer,
                         errors,
-                        ("Expected <expr>", Symbol::ClosingBrackets),
+                        (
+                            DiagnosticCode::MissingExpression,
+                            "Expected <expr>",
+                            Symbol::ClosingBrackets,
+                        ),
                     )
                     .map(Self::Expression)
                 }
@@ -93,7 +98,7 @@ impl ParseWithArgs<&ParseArgs> for TableField {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         parse_args: &ParseArgs,
     ) -> Option<Self> {
         if token == TokenType::Symbol(Symbol::ClosingCurlyBrackets) {
@@ -135,7 +140,8 @@ impl ParseWithArgs<&ParseArgs> for TableField {
                 value: safe_unwrap!(
                     lexer,
                     errors,
-                    "Expected <type>",
+                    DiagnosticCode::MissingType,
+                    "<type>",
                     TableFieldValue::parse_with(token.clone(), lexer, errors, parse_args.is_type)
                         .map(Pointer::new)
                 ),
@@ -163,15 +169,17 @@ impl ParseWithArgs<bool> for TableFieldValue {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         is_type: bool,
     ) -> Option<Self> {
         if is_type {
-            TypeValue::parse(token, lexer, errors).map(Self::Type)
-        } else if token == TokenType::Symbol(Symbol::Ellipses) {
-            Some(Self::VariadicValues(token))
-        } else {
-            Expression::parse(token, lexer, errors).map(Self::Expression)
+            return TypeValue::parse(token, lexer, errors).map(Self::Type);
+        }
+
+        // Only a lone `...` is `VariadicValues`, `{ ... + 1 }` is an expression.
+        match Expression::parse(token, lexer, errors)? {
+            Expression::VariadicValues(token) => Some(Self::VariadicValues(token)),
+            expression => Some(Self::Expression(expression)),
         }
     }
 }
@@ -180,7 +188,7 @@ impl ParseWithArgs<bool> for Table {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         is_type: bool,
     ) -> Option<Self> {
         if !matches!(
@@ -195,6 +203,7 @@ impl ParseWithArgs<bool> for Table {
             lexer,
             errors,
             (
+                DiagnosticCode::MissingTableField,
                 "Expected <table-field>",
                 Symbol::ClosingCurlyBrackets,
                 &ParseArgs::new(is_type, 1),
@@ -209,7 +218,7 @@ impl Parse<FunctionArguments> for Table {
     fn parse(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<FunctionArguments> {
         Self::parse_with(token, lexer, errors, false).map(FunctionArguments::Table)
     }
@@ -221,7 +230,7 @@ impl Parse<TableAccessKey> for TableKey {
     fn parse(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
     ) -> Option<TableAccessKey> {
         Self::parse_with(token, lexer, errors, false)
             .map(Pointer::new)
@@ -257,49 +266,3 @@ impl GetRange for TableField {
         }
     }
 }
-
-impl Print for TableKey {
-    #[inline]
-    fn print(&self) -> String {
-        match self {
-            TableKey::Simple(token) => token.print(),
-            TableKey::Expression(bracketed) => bracketed.print(),
-            TableKey::Type(bracketed) => bracketed.print(),
-            _ => "".to_string(),
-        }
-    }
-
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        match self {
-            TableKey::Simple(token) => token.print_final_trivia(),
-            TableKey::Expression(bracketed) => bracketed.print_final_trivia(),
-            TableKey::Type(bracketed) => bracketed.print_final_trivia(),
-            _ => "".to_string(),
-        }
-    }
-
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        match self {
-            TableKey::Simple(token) => token.print_without_final_trivia(),
-            TableKey::Expression(bracketed) => bracketed.print_without_final_trivia(),
-            TableKey::Type(bracketed) => bracketed.print_without_final_trivia(),
-            _ => "".to_string(),
-        }
-    }
-}
-
-impl Print for TableField {
-    #[inline]
-    fn print_without_final_trivia(&self) -> String {
-        self.key.print_without_final_trivia()
-            + &self.equal_or_colon.print_without_final_trivia()
-            + &self.value.print_without_final_trivia()
-    }
-
-    #[inline]
-    fn print_final_trivia(&self) -> String {
-        self.value.print_final_trivia()
-    }
-}
@@ -0,0 +1,633 @@
+//! All `impl` blocks for [`Visit`] and [`VisitMut`].
+
+use lsp_types::Position;
+use luau_lexer::prelude::{PositionExt, Token, Trivia};
+
+use crate::types::{
+    Attribute, BinaryOperator, Block, Bracketed, Closure, CompoundSetExpression, Cst, DoBlock,
+    ElseIfExpression, ElseIfStatement, ElseStatement, EndOfFile, Expression, FunctionArgument,
+    FunctionArguments, FunctionCall, FunctionCallInvoked, GenericDeclarationParameter, GenericFor,
+    GenericParameterInfo, GenericParameterInfoDefault, GlobalFunction, GlobalFunctionName,
+    IfExpression, IfStatement, InterpolatedString, InterpolatedStringSegment, List, ListItem,
+    LocalAssignment, LocalFunction, Name, NumericalFor, Parameter, ParameterTypeName, Pointer,
+    PrefixExp, RepeatBlock, SetExpression, Table, TableAccess, TableAccessKey, TableAccessPrefix,
+    TableField, TableFieldValue, TableKey, TerminationStatement, TypeDefinition, TypeFunction,
+    TypeValue, UnaryOperator, Var, Visit, VisitMut, Visitor, VisitorMut, WhileLoop,
+};
+
+/// Helper macro to implement [`Visit`] and [`VisitMut`] for a node. Fields (or
+/// variants' fields) are passed in the order they appear in the source code.
+/// All fields must be listed, fields that aren't nodes are listed after
+/// `ignore`, so that adding a field to a node without visiting it is a compile
+/// error.
+///
+/// # Usage
+///
+/// ```ignore
+/// impl_visit!(Struct, visit_struct, leave_struct, struct { field1, field2 });
+/// impl_visit!(Struct, visit_struct, leave_struct, struct { field1 } ignore { field2 });
+/// impl_visit!(Enum, visit_enum, leave_enum, enum {
+///     Unit,
+///     Tuple(item),
+///     Struct { field1, field2 },
+/// });
+/// ```
+macro_rules! impl_visit {
+    (
+        $ty:ty,
+        $visit:ident,
+        $leave:ident,
+        enum {
+            $( $variant:ident $( ( $($tuple:ident),* ) )? $( { $($field:ident),* } )? ),*
+            $(,)?
+        }
+    ) => {
+        impl Visit for $ty {
+            fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+                visitor.$visit(self);
+
+                if !visitor.skip_children() {
+                    match self {
+                        $( Self::$variant $( ( $($tuple),* ) )? $( { $($field),* } )? => {
+                            $( $( $tuple.visit(visitor); )* )?
+                            $( $( $field.visit(visitor); )* )?
+                        } )*
+                    }
+                }
+
+                visitor.$leave(self);
+            }
+        }
+
+        impl VisitMut for $ty {
+            fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                visitor.$visit(self);
+
+                if !visitor.skip_children() {
+                    match self {
+                        $( Self::$variant $( ( $($tuple),* ) )? $( { $($field),* } )? => {
+                            $( $( $tuple.visit_mut(visitor); )* )?
+                            $( $( $field.visit_mut(visitor); )* )?
+                        } )*
+                    }
+                }
+
+                visitor.$leave(self);
+            }
+        }
+    };
+    (
+        $ty:ty,
+        $visit:ident,
+        $leave:ident,
+        struct { $($field:tt),* $(,)? }
+        $( ignore { $($ignored:ident),* $(,)? } )?
+    ) => {
+        impl Visit for $ty {
+            fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+                // Fails to compile if a field is missing from the lists.
+                let Self { $($field: _,)* $($($ignored: _,)*)? } = self;
+
+                visitor.$visit(self);
+                if !visitor.skip_children() {
+                    $( self.$field.visit(visitor); )*
+                }
+                visitor.$leave(self);
+            }
+        }
+
+        impl VisitMut for $ty {
+            fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                // Fails to compile if a field is missing from the lists.
+                let Self { $($field: _,)* $($($ignored: _,)*)? } = self;
+
+                visitor.$visit(self);
+                if !visitor.skip_children() {
+                    $( self.$field.visit_mut(visitor); )*
+                }
+                visitor.$leave(self);
+            }
+        }
+    };
+}
+
+impl Visit for Token {
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_token(self);
+        if !visitor.skip_children() {
+            self.leading_trivia.visit(visitor);
+        }
+        visitor.leave_token(self);
+    }
+}
+impl VisitMut for Token {
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_token(self);
+        if !visitor.skip_children() {
+            self.leading_trivia.visit_mut(visitor);
+        }
+        visitor.leave_token(self);
+    }
+}
+
+impl Visit for Trivia {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_trivia(self);
+        visitor.leave_trivia(self);
+    }
+}
+impl VisitMut for Trivia {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_trivia(self);
+        visitor.leave_trivia(self);
+    }
+}
+
+impl<T: Visit> Visit for Pointer<T> {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        (**self).visit(visitor)
+    }
+}
+impl<T: VisitMut + Clone> VisitMut for Pointer<T> {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        Pointer::make_mut(self).visit_mut(visitor)
+    }
+}
+
+impl<T: Visit> Visit for Option<T> {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        if let Some(item) = self {
+            item.visit(visitor)
+        }
+    }
+}
+impl<T: VisitMut> VisitMut for Option<T> {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(item) = self {
+            item.visit_mut(visitor)
+        }
+    }
+}
+
+impl<T: Visit> Visit for Vec<T> {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        for item in self {
+            item.visit(visitor)
+        }
+    }
+}
+impl<T: VisitMut> VisitMut for Vec<T> {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for item in self {
+            item.visit_mut(visitor)
+        }
+    }
+}
+
+impl<T: Visit, U: Visit> Visit for (T, U) {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        self.0.visit(visitor);
+        self.1.visit(visitor);
+    }
+}
+impl<T: VisitMut, U: VisitMut> VisitMut for (T, U) {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.0.visit_mut(visitor);
+        self.1.visit_mut(visitor);
+    }
+}
+
+impl<T: Visit> Visit for Bracketed<T> {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        self.opening_bracket.visit(visitor);
+        self.item.visit(visitor);
+        self.closing_bracket.visit(visitor);
+    }
+}
+impl<T: VisitMut> VisitMut for Bracketed<T> {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.opening_bracket.visit_mut(visitor);
+        self.item.visit_mut(visitor);
+        self.closing_bracket.visit_mut(visitor);
+    }
+}
+
+impl<T: Visit> Visit for List<T> {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        self.items.visit(visitor)
+    }
+}
+impl<T: VisitMut> VisitMut for List<T> {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.items.visit_mut(visitor)
+    }
+}
+
+impl<T: Visit> Visit for ListItem<T> {
+    #[inline]
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        match self {
+            Self::Trailing { item, separator } => {
+                item.visit(visitor);
+                separator.visit(visitor);
+            }
+            Self::NonTrailing(item) => item.visit(visitor),
+        }
+    }
+}
+impl<T: VisitMut> VisitMut for ListItem<T> {
+    #[inline]
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            Self::Trailing { item, separator } => {
+                item.visit_mut(visitor);
+                separator.visit_mut(visitor);
+            }
+            Self::NonTrailing(item) => item.visit_mut(visitor),
+        }
+    }
+}
+
+impl Visit for TableKey {
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_table_key(self);
+
+        if !visitor.skip_children() {
+            match self {
+                Self::ERROR | Self::UndefinedNumber(_) | Self::UndefinedString(_) => (),
+                Self::Simple(token) => token.visit(visitor),
+                Self::Expression(bracketed) => bracketed.visit(visitor),
+                Self::Type(bracketed) => bracketed.visit(visitor),
+            }
+        }
+
+        visitor.leave_table_key(self);
+    }
+}
+impl VisitMut for TableKey {
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_table_key(self);
+
+        if !visitor.skip_children() {
+            match self {
+                Self::ERROR | Self::UndefinedNumber(_) | Self::UndefinedString(_) => (),
+                Self::Simple(token) => token.visit_mut(visitor),
+                Self::Expression(bracketed) => bracketed.visit_mut(visitor),
+                Self::Type(bracketed) => bracketed.visit_mut(visitor),
+            }
+        }
+
+        visitor.leave_table_key(self);
+    }
+}
+
+impl_visit!(Cst, visit_cst, leave_cst, struct { block } ignore {
+    uri,
+    errors,
+    status,
+    position_encoding,
+});
+impl_visit!(Block, visit_block, leave_block, struct { statements, last_statement });
+impl_visit!(TerminationStatement, visit_termination_statement, leave_termination_statement, enum {
+    Break(token),
+    Continue(token),
+    Return { return_keyword, expressions },
+});
+
+impl_visit!(LocalFunction, visit_local_function, leave_local_function, struct {
+    attributes,
+    local_keyword,
+    function_keyword,
+    function_name,
+    generics,
+    parameters,
+    colon,
+    return_type,
+    body,
+    end_keyword,
+});
+impl_visit!(GlobalFunction, visit_global_function, leave_global_function, struct {
+    attributes,
+    function_keyword,
+    function_name,
+    generics,
+    parameters,
+    colon,
+    return_type,
+    body,
+    end_keyword,
+});
+impl_visit!(GlobalFunctionName, visit_global_function_name, leave_global_function_name, enum {
+    SimpleName(name),
+    Table { table, keys, method },
+});
+impl_visit!(TypeFunction, visit_type_function, leave_type_function, struct {
+    export_keyword,
+    type_keyword,
+    function_keyword,
+    function_name,
+    generics,
+    parameters,
+    colon,
+    return_type,
+    body,
+    end_keyword,
+});
+impl_visit!(Parameter, visit_parameter, leave_parameter, struct { name, colon, r#type });
+impl_visit!(Attribute, visit_attribute, leave_attribute, struct { at, attribute });
+impl_visit!(LocalAssignment, visit_local_assignment, leave_local_assignment, struct {
+    local_token,
+    name_list,
+    equal_token,
+    expressions,
+});
+impl_visit!(SetExpression, visit_set_expression, leave_set_expression, struct {
+    variables,
+    equal,
+    values,
+});
+impl_visit!(CompoundSetExpression, visit_compound_set_expression, leave_compound_set_expression, struct {
+    variable,
+    operation,
+    value,
+});
+impl_visit!(DoBlock, visit_do_block, leave_do_block, struct { do_keyword, body, end_keyword });
+impl_visit!(GenericFor, visit_generic_for, leave_generic_for, struct {
+    for_keyword,
+    names,
+    in_keyword,
+    expressions,
+    do_block,
+});
+impl_visit!(NumericalFor, visit_numerical_for, leave_numerical_for, struct {
+    for_keyword,
+    variable,
+    equal_keyword,
+    start,
+    start_comma,
+    end,
+    end_comma,
+    step,
+    do_block,
+});
+impl_visit!(IfStatement, visit_if_statement, leave_if_statement, struct {
+    if_keyword,
+    condition,
+    then_keyword,
+    body,
+    else_if_statements,
+    else_statement,
+    end_keyword,
+});
+impl_visit!(ElseIfStatement, visit_else_if_statement, leave_else_if_statement, struct {
+    elseif_keyword,
+    condition,
+    then_keyword,
+    body,
+});
+impl_visit!(ElseStatement, visit_else_statement, leave_else_statement, struct { else_keyword, body });
+impl_visit!(RepeatBlock, visit_repeat_block, leave_repeat_block, struct {
+    repeat_keyword,
+    body,
+    until_keyword,
+    condition,
+});
+impl_visit!(WhileLoop, visit_while_loop, leave_while_loop, struct {
+    while_keyword,
+    condition,
+    do_block,
+});
+impl_visit!(EndOfFile, visit_end_of_file, leave_end_of_file, struct { 0 });
+
+impl_visit!(TypeDefinition, visit_type_definition, leave_type_definition, struct {
+    export_keyword,
+    type_keyword,
+    type_name,
+    generics,
+    equal_sign,
+    type_value,
+});
+impl_visit!(TypeValue, visit_type_value, leave_type_value, enum {
+    ERROR,
+    String(token),
+    Boolean(token),
+    Nil(token),
+    Wrap(bracketed),
+    Function { generics, parameters, arrow, return_type },
+    Basic { base, generics },
+    GenericPack { name, ellipsis },
+    Intersection { left, ampersand, right },
+    Union { left, pipe, right },
+    Module { module, dot, name, generics },
+    Optional { base, question_mark },
+    Table(table),
+    Typeof { typeof_token, inner },
+    Tuple(tuple),
+    Variadic { ellipsis, type_value },
+    VariadicPack { ellipsis, name },
+});
+impl_visit!(ParameterTypeName, visit_parameter_type_name, leave_parameter_type_name, enum {
+    Normal(name),
+    Type(type_value),
+});
+impl_visit!(GenericParameterInfo, visit_generic_parameter_info, leave_generic_parameter_info, enum {
+    ERROR,
+    Name(name),
+    Pack { name, ellipsis },
+});
+impl_visit!(
+    GenericDeclarationParameter,
+    visit_generic_declaration_parameter,
+    leave_generic_declaration_parameter,
+    struct { parameter, equal, default }
+);
+impl_visit!(
+    GenericParameterInfoDefault,
+    visit_generic_parameter_info_default,
+    leave_generic_parameter_info_default,
+    enum {
+        ERROR,
+        Name(name),
+        Pack(pack),
+    }
+);
+
+impl_visit!(Expression, visit_expression, leave_expression, enum {
+    ERROR,
+    Nil(token),
+    Boolean(token),
+    Number(token),
+    String(token),
+    VariadicValues(token),
+    InterpolatedString(interpolated_string),
+    Closure(closure),
+    FunctionCall(function_call),
+    ExpressionWrap(wrap),
+    Var(var),
+    Table(table),
+    UnaryExpression { operator, expression },
+    BinaryExpression { left, operator, right },
+    TypeCast { expression, operator, cast_to },
+    IfExpression(if_expression),
+});
+impl_visit!(PrefixExp, visit_prefix_exp, leave_prefix_exp, enum {
+    Var(var),
+    FunctionCall(function_call),
+    ExpressionWrap(wrap),
+});
+impl_visit!(IfExpression, visit_if_expression, leave_if_expression, struct {
+    if_keyword,
+    condition,
+    then_keyword,
+    if_expression,
+    else_if_expressions,
+    else_keyword,
+    else_expression,
+});
+impl_visit!(ElseIfExpression, visit_else_if_expression, leave_else_if_expression, struct {
+    else_if_keyword,
+    condition,
+    then_keyword,
+    expression,
+});
+impl_visit!(BinaryOperator, visit_binary_operator, leave_binary_operator, enum {
+    Plus(token),
+    Minus(token),
+    Multiplication(token),
+    Division(token),
+    FloorDivision(token),
+    Modulo(token),
+    Exponentiation(token),
+    Concatenation(token),
+    Equal(token),
+    NotEqual(token),
+    LessThan(token),
+    LessThanOrEqualTo(token),
+    GreaterThan(token),
+    GreaterThanOrEqualTo(token),
+    And(token),
+    Or(token),
+    CompoundAssignment(token),
+});
+impl_visit!(UnaryOperator, visit_unary_operator, leave_unary_operator, enum {
+    Minus(token),
+    Not(token),
+    Length(token),
+});
+impl_visit!(Var, visit_var, leave_var, enum {
+    ERROR,
+    Name(name),
+    TableAccess(table_access),
+});
+impl_visit!(TableAccess, visit_table_access, leave_table_access, struct { prefix, accessed_keys });
+impl_visit!(TableAccessPrefix, visit_table_access_prefix, leave_table_access_prefix, enum {
+    Name(name),
+    FunctionCall(function_call),
+    ExpressionWrap(wrap),
+});
+impl_visit!(TableAccessKey, visit_table_access_key, leave_table_access_key, enum {
+    Expression(key),
+    Name { dot, name },
+});
+impl_visit!(FunctionCall, visit_function_call, leave_function_call, struct { invoked, arguments });
+impl_visit!(FunctionCallInvoked, visit_function_call_invoked, leave_function_call_invoked, enum {
+    Function(prefix_exp),
+    TableMethod { table, colon, method },
+});
+impl_visit!(FunctionArguments, visit_function_arguments, leave_function_arguments, enum {
+    String(token),
+    Table(table),
+    List(list),
+});
+impl_visit!(FunctionArgument, visit_function_argument, leave_function_argument, enum {
+    Expression(expression),
+    VariadicValues(token),
+});
+impl_visit!(Closure, visit_closure, leave_closure, struct {
+    attributes,
+    function_keyword,
+    generics,
+    parameters,
+    colon,
+    return_type,
+    body,
+    end_keyword,
+});
+impl_visit!(InterpolatedString, visit_interpolated_string, leave_interpolated_string, struct {
+    segments,
+});
+impl_visit!(InterpolatedStringSegment, visit_interpolated_string_segment, leave_interpolated_string_segment, enum {
+    String(token),
+    Expression(expression),
+});
+impl_visit!(Name, visit_name, leave_name, struct { name, colon, r#type });
+
+impl_visit!(Table, visit_table, leave_table, struct { 0 });
+impl_visit!(TableField, visit_table_field, leave_table_field, struct {
+    key,
+    equal_or_colon,
+    value,
+});
+impl_visit!(TableFieldValue, visit_table_field_value, leave_table_field_value, enum {
+    ERROR,
+    Expression(expression),
+    Type(type_value),
+    VariadicValues(token),
+});
+
+/// A [`VisitorMut`] moving tokens that were after a change in the source code
+/// to their new positions, also used to move tokens lexed from a part of the
+/// source code, with `old_end` set to the start of the file.
+pub(crate) struct PositionShifter {
+    /// The end of the changed text in the old source code.
+    pub(crate) old_end: Position,
+
+    /// The end of the changed text in the new source code.
+    pub(crate) new_end: Position,
+}
+
+impl PositionShifter {
+    /// Get the new position of the passed one, which must be after
+    /// [`PositionShifter::old_end`].
+    #[inline]
+    pub(crate) fn shift(&self, position: Position) -> Position {
+        if position.line == self.old_end.line {
+            Position::new(
+                self.new_end.line,
+                self.new_end.character + position.character - self.old_end.character,
+            )
+        } else {
+            Position::new(
+                position.line + self.new_end.line - self.old_end.line,
+                position.character,
+            )
+        }
+    }
+}
+
+impl VisitorMut for PositionShifter {
+    #[inline]
+    fn visit_token(&mut self, token: &mut Token) {
+        // Tokens without positions, see `Token::empty`.
+        if token.start == Position::MAX {
+            return;
+        }
+
+        token.start = self.shift(token.start);
+        token.end = self.shift(token.end);
+    }
+}
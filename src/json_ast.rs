@@ -0,0 +1,1252 @@
+//! # JSON AST
+//!
+//! A stable JSON representation of a [`Cst`], modelled on the JSON emitted by
+//! Luau's own `luau-ast` tool. Unlike the derived `serde` implementations, this
+//! output doesn't depend on the layout of this crate's types: it only contains
+//! AST nodes, and it only changes when [`JSON_AST_VERSION`] is bumped.
+//!
+//! ## Shape
+//!
+//! ```json
+//! {
+//!     "version": 1,
+//!     "root": { "kind": "AstStatBlock", "location": "0,0 - 0,11", "hasEnd": false, "body": [...] },
+//!     "commentLocations": [{ "kind": "Comment", "location": "1,0 - 1,8" }],
+//!     "errors": [{ "code": "E0010", "message": "...", "location": "2,4 - 2,5" }],
+//! }
+//! ```
+//!
+//! * Every node has a `kind`, which matches the `type` field of `luau-ast`'s
+//!   output (`AstStatLocal`, `AstExprCall`, `AstTypeReference`, ...), and a
+//!   `location` of the form `"startLine,startColumn - endLine,endColumn"`, all
+//!   zero-based. `location` is `null` for nodes that failed to parse.
+//! * The remaining fields use `luau-ast`'s names (`vars`, `values`, `func`,
+//!   `args`, `thenbody`, ...).
+//! * Names are resolved, so variables are either `AstExprLocal`, with a `local`
+//!   pointing at their declaration, or `AstExprGlobal`.
+//! * Nodes that had syntax errors are `AstStatError`, `AstExprError` or
+//!   `AstTypeError`.
+//! * `elseif`s are nested `AstStatIf`s (or `AstExprIfElse`s) in `elsebody`
+//!   (or `falseExpr`), and `T?` is a union with an `AstTypeOptional`, just
+//!   like in Luau.
+//! * A `trivia` array, with the `kind`, `location` and `text` of every
+//!   whitespace and comment, is added when
+//!   [`include_trivia`](JsonAstOptions::include_trivia) is set.
+//!
+//! ## Note
+//!
+//! Tokens skipped while recovering from syntax errors aren't part of the
+//! [`Cst`], so the locations of trivia (and comments) after them may be off if
+//! the [`Cst`] [has errors](crate::types::AstStatus::HasErrors).
+
+use std::{collections::HashMap, ptr};
+
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::{Comment, Literal, Symbol, Token, TokenType, Trivia};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    semantic::{Resolution, SemanticModel},
+    types::{
+        Attribute, BinaryOperator, Block, BracketedList, Cst, Diagnostic, Expression,
+        FunctionArgument, FunctionArguments, FunctionCall, FunctionCallInvoked, GenericDeclaration,
+        GenericParameterInfo, GenericParameterInfoDefault, GetRange, GlobalFunctionName,
+        IfStatement, InterpolatedStringSegment, List, Name, Parameter, ParameterTypeName,
+        ParsedNumber, ParsedString, Pointer, PositionEncoding, PrefixExp, Print, Statement, Table,
+        TableAccess, TableAccessKey, TableAccessPrefix, TableFieldValue, TableKey,
+        TerminationStatement, TypeValue, UnaryOperator, Var, Visit, Visitor,
+    },
+};
+
+/// The version of the JSON format produced by [`Cst::to_json_ast`]. It's
+/// bumped whenever the output changes in a way that isn't backwards compatible,
+/// adding new fields isn't considered breaking.
+pub const JSON_AST_VERSION: u32 = 1;
+
+/// Options for [`Cst::to_json_ast_with`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct JsonAstOptions {
+    /// Whether or not to add a `trivia` array with all whitespace and comments.
+    pub include_trivia: bool,
+}
+
+/// Format a [`Range`] the way `luau-ast` does.
+#[inline]
+fn format_range(range: Range) -> String {
+    format!(
+        "{},{} - {},{}",
+        range.start.line, range.start.character, range.end.line, range.end.character
+    )
+}
+
+/// Get the location of the passed node, [`Value::Null`] if it doesn't have one.
+#[inline]
+fn location(node: &impl GetRange) -> Value {
+    node.get_range()
+        .map_or(Value::Null, |range| format_range(range).into())
+}
+
+/// Get the location spanning from the start of `start` to the end of `end`.
+#[inline]
+fn location_between(start: &impl GetRange, end: &impl GetRange) -> Value {
+    match (start.get_range(), end.get_range()) {
+        (Ok(start), Ok(end)) => format_range(Range::new(start.start, end.end)).into(),
+        _ => Value::Null,
+    }
+}
+
+/// Create a node with the passed `kind`, `location`, and extra fields.
+fn node(kind: &str, location: Value, fields: Value) -> Value {
+    let mut object = Map::new();
+    object.insert("kind".to_string(), kind.into());
+    object.insert("location".to_string(), location);
+
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+
+    Value::Object(object)
+}
+
+/// Get the name in the passed token, an empty string if it isn't a name.
+#[inline]
+fn name(token: &Token) -> String {
+    token.token_type.try_as_string().unwrap_or_default()
+}
+
+/// Decode the string in the passed token, invalid UTF-8 is replaced.
+#[inline]
+fn string_value(token: &Token) -> String {
+    ParsedString::from_token(token)
+        .map(|(string, _)| String::from_utf8_lossy(&string.0).into_owned())
+        .unwrap_or_default()
+}
+
+/// Get the name `luau-ast` uses for the passed operator.
+fn binary_operator_name(operator: &BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Plus(_) => "Add",
+        BinaryOperator::Minus(_) => "Sub",
+        BinaryOperator::Multiplication(_) => "Mul",
+        BinaryOperator::Division(_) => "Div",
+        BinaryOperator::FloorDivision(_) => "FloorDiv",
+        BinaryOperator::Modulo(_) => "Mod",
+        BinaryOperator::Exponentiation(_) => "Pow",
+        BinaryOperator::Concatenation(_) => "Concat",
+        BinaryOperator::Equal(_) => "CompareEq",
+        BinaryOperator::NotEqual(_) => "CompareNe",
+        BinaryOperator::LessThan(_) => "CompareLt",
+        BinaryOperator::LessThanOrEqualTo(_) => "CompareLe",
+        BinaryOperator::GreaterThan(_) => "CompareGt",
+        BinaryOperator::GreaterThanOrEqualTo(_) => "CompareGe",
+        BinaryOperator::And(_) => "And",
+        BinaryOperator::Or(_) => "Or",
+        BinaryOperator::CompoundAssignment(token) => compound_operator_name(token),
+    }
+}
+
+/// Get the name `luau-ast` uses for the operator of a compound assignment.
+fn compound_operator_name(operator: &Token) -> &'static str {
+    match operator.print().trim() {
+        "+=" => "Add",
+        "-=" => "Sub",
+        "*=" => "Mul",
+        "/=" => "Div",
+        "//=" => "FloorDiv",
+        "%=" => "Mod",
+        "^=" => "Pow",
+        "..=" => "Concat",
+        _ => "",
+    }
+}
+
+/// A [`Visitor`] collecting all trivia along with their locations.
+#[derive(Default)]
+struct TriviaCollector<'a> {
+    /// The end of the last visited token.
+    last_end: Position,
+
+    /// The trivia found so far, along with their ranges.
+    trivia: Vec<(&'a Trivia, Range)>,
+
+    /// How columns are counted in the [`Cst`].
+    encoding: PositionEncoding,
+}
+
+impl<'a> Visitor<'a> for TriviaCollector<'a> {
+    fn visit_token(&mut self, token: &'a Token) {
+        let mut position = self.last_end;
+
+        for trivia in &token.leading_trivia {
+            let start = position;
+            for character in trivia.print().chars() {
+                if character == '\n' {
+                    position = Position::new(position.line + 1, 0);
+                } else {
+                    position.character += self.encoding.len(character);
+                }
+            }
+
+            self.trivia.push((trivia, Range::new(start, position)));
+        }
+
+        if let Ok(range) = token.get_range() {
+            self.last_end = range.end;
+        }
+    }
+}
+
+/// Get the `kind` of the passed trivia.
+#[inline]
+fn trivia_kind(trivia: &Trivia) -> &'static str {
+    match trivia {
+        Trivia::Spaces(_) => "Whitespace",
+        Trivia::Comment(Comment::SingleLine(_)) => "Comment",
+        Trivia::Comment(Comment::MultiLine(_)) => "BlockComment",
+    }
+}
+
+/// The struct converting a [`Cst`] to JSON.
+struct Encoder<'a> {
+    /// The declaration of each name that references a local, keyed by the
+    /// address of the referencing token.
+    locals: HashMap<*const Token, &'a Token>,
+}
+
+impl<'a> Encoder<'a> {
+    /// Create a new [`Encoder`] for the passed [`Cst`].
+    fn new(cst: &'a Cst) -> Self {
+        let model = SemanticModel::new(cst);
+        let locals = model
+            .references
+            .iter()
+            .filter_map(|reference| match reference.resolution {
+                Resolution::Binding(id) => {
+                    Some((ptr::from_ref(reference.token), model.binding(id).token))
+                }
+                Resolution::Global => None,
+            })
+            .collect();
+
+        Self { locals }
+    }
+
+    /// Encode an optional item.
+    #[inline]
+    fn optional<T>(&self, item: Option<&T>, encode: impl Fn(&Self, &T) -> Value) -> Value {
+        item.map_or(Value::Null, |item| encode(self, item))
+    }
+
+    /// Encode all items in a list.
+    #[inline]
+    fn list<T>(&self, list: &List<T>, encode: impl Fn(&Self, &T) -> Value) -> Value {
+        list.iter().map(|item| encode(self, item)).collect()
+    }
+
+    /// Encode a [`Block`] as an `AstStatBlock`.
+    fn block(&self, block: &Block, has_end: bool) -> Value {
+        let mut body = block
+            .statements
+            .iter()
+            .filter(|(statement, _)| !matches!(**statement, Statement::EndOfFile(_)))
+            .map(|(statement, _)| self.statement(statement))
+            .collect::<Vec<_>>();
+
+        if let Some((last_statement, _)) = &block.last_statement {
+            body.push(self.termination_statement(last_statement));
+        }
+
+        node(
+            "AstStatBlock",
+            location(block),
+            json!({ "hasEnd": has_end, "body": body }),
+        )
+    }
+
+    /// Encode a declared name as an `AstLocal`.
+    fn local(&self, name_token: &Token, r#type: Option<&Pointer<TypeValue>>) -> Value {
+        node(
+            "AstLocal",
+            location(name_token),
+            json!({
+                "name": name(name_token),
+                "luauType": self.optional(r#type, |this, r#type| this.type_value(r#type)),
+            }),
+        )
+    }
+
+    /// Encode a [`Name`] as an `AstLocal`.
+    #[inline]
+    fn name(&self, name: &Name) -> Value {
+        self.local(&name.name, name.r#type.as_ref())
+    }
+
+    /// Encode a name referencing a variable.
+    fn variable(&self, token: &Token) -> Value {
+        match self.locals.get(&ptr::from_ref(token)) {
+            Some(declaration) => node(
+                "AstExprLocal",
+                location(token),
+                json!({
+                    "local": node(
+                        "AstLocal",
+                        location(*declaration),
+                        json!({ "name": name(declaration) }),
+                    ),
+                }),
+            ),
+            None => node(
+                "AstExprGlobal",
+                location(token),
+                json!({ "global": name(token) }),
+            ),
+        }
+    }
+
+    /// Encode all attributes of a function.
+    fn attributes(&self, attributes: &[Attribute]) -> Value {
+        attributes
+            .iter()
+            .map(|attribute| {
+                node(
+                    "AstAttr",
+                    location(attribute),
+                    json!({ "name": name(&attribute.attribute) }),
+                )
+            })
+            .collect()
+    }
+
+    /// Encode generic declarations as `(generics, genericPacks)`.
+    fn generics(&self, generics: Option<&Pointer<GenericDeclaration>>) -> (Value, Value) {
+        let mut types = Vec::new();
+        let mut packs = Vec::new();
+
+        for generic in generics.iter().flat_map(|generics| generics.item.iter()) {
+            let default = generic.default.as_ref().map(|default| match default {
+                GenericParameterInfoDefault::ERROR => node("AstTypeError", Value::Null, json!({})),
+                GenericParameterInfoDefault::Name(token) => self.type_reference(token, None, None),
+                GenericParameterInfoDefault::Pack(r#type) => self.type_value(r#type),
+            });
+
+            match &generic.parameter {
+                GenericParameterInfo::ERROR => (),
+                GenericParameterInfo::Name(token) => types.push(node(
+                    "AstGenericType",
+                    location(generic),
+                    json!({ "name": name(token), "defaultValue": default }),
+                )),
+                GenericParameterInfo::Pack { name: token, .. } => packs.push(node(
+                    "AstGenericTypePack",
+                    location(generic),
+                    json!({ "name": name(token), "defaultValue": default }),
+                )),
+            }
+        }
+
+        (types.into(), packs.into())
+    }
+
+    /// Encode a function as an `AstExprFunction`.
+    #[allow(clippy::too_many_arguments)]
+    fn function(
+        &self,
+        location: Value,
+        attributes: &[Attribute],
+        generics: Option<&Pointer<GenericDeclaration>>,
+        parameters: &BracketedList<Parameter>,
+        return_type: Option<&Pointer<TypeValue>>,
+        body: &Block,
+        is_method: bool,
+    ) -> Value {
+        let (generics, generic_packs) = self.generics(generics);
+        let (arguments, vararg) = parameters
+            .item
+            .iter()
+            .map(|parameter| &**parameter)
+            .partition::<Vec<&Parameter>, _>(|parameter| {
+                parameter.name != TokenType::Symbol(Symbol::Ellipses)
+            });
+        let vararg = vararg.first();
+
+        node(
+            "AstExprFunction",
+            location,
+            json!({
+                "attributes": self.attributes(attributes),
+                "generics": generics,
+                "genericPacks": generic_packs,
+                "self": is_method,
+                "args": arguments
+                    .iter()
+                    .map(|parameter| self.local(&parameter.name, parameter.r#type.as_ref()))
+                    .collect::<Vec<_>>(),
+                "vararg": vararg.is_some(),
+                "varargLocation": vararg.map_or(Value::Null, |parameter| self::location(&parameter.name)),
+                "varargAnnotation": self.optional(
+                    vararg.and_then(|parameter| parameter.r#type.as_ref()),
+                    |this, r#type| this.type_value(r#type),
+                ),
+                "returnAnnotation": self.optional(return_type, |this, r#type| this.type_value(r#type)),
+                "body": self.block(body, true),
+            }),
+        )
+    }
+
+    /// Encode a [`Statement`].
+    fn statement(&self, statement: &Statement) -> Value {
+        match statement {
+            Statement::ERROR | Statement::EndOfFile(_) => {
+                node("AstStatError", Value::Null, json!({}))
+            }
+            Statement::LocalFunction(local_function) => node(
+                "AstStatLocalFunction",
+                location(&**local_function),
+                json!({
+                    "name": self.local(&local_function.function_name, None),
+                    "func": self.function(
+                        location_between(&local_function.function_keyword, &local_function.end_keyword),
+                        &local_function.attributes,
+                        local_function.generics.as_ref(),
+                        &local_function.parameters,
+                        local_function.return_type.as_ref(),
+                        &local_function.body,
+                        false,
+                    ),
+                }),
+            ),
+            Statement::LocalAssignment(local_assignment) => node(
+                "AstStatLocal",
+                location(&**local_assignment),
+                json!({
+                    "vars": self.list(&local_assignment.name_list, Self::name),
+                    "values": self.list(&local_assignment.expressions, |this, expression| {
+                        this.expression(expression)
+                    }),
+                }),
+            ),
+            Statement::TypeDefinition(type_definition) => {
+                let (generics, generic_packs) = self.generics(type_definition.generics.as_ref());
+
+                node(
+                    "AstStatTypeAlias",
+                    location(&**type_definition),
+                    json!({
+                        "name": name(&type_definition.type_name),
+                        "generics": generics,
+                        "genericPacks": generic_packs,
+                        "value": self.type_value(&type_definition.type_value),
+                        "exported": type_definition.export_keyword.is_some(),
+                    }),
+                )
+            }
+            Statement::IfStatement(if_statement) => self.if_statement(if_statement),
+            Statement::DoBlock(do_block) => self.block(&do_block.body, true),
+            Statement::GenericFor(generic_for) => node(
+                "AstStatForIn",
+                location(&**generic_for),
+                json!({
+                    "vars": self.list(&generic_for.names, Self::name),
+                    "values": self.list(&generic_for.expressions, |this, expression| {
+                        this.expression(expression)
+                    }),
+                    "body": self.block(&generic_for.do_block.body, true),
+                    "hasIn": true,
+                    "hasDo": true,
+                }),
+            ),
+            Statement::NumericalFor(numerical_for) => node(
+                "AstStatFor",
+                location(&**numerical_for),
+                json!({
+                    "var": self.name(&numerical_for.variable),
+                    "from": self.expression(&numerical_for.start),
+                    "to": self.expression(&numerical_for.end),
+                    "step": self.optional(numerical_for.step.as_ref(), |this, step| {
+                        this.expression(step)
+                    }),
+                    "body": self.block(&numerical_for.do_block.body, true),
+                    "hasDo": true,
+                }),
+            ),
+            Statement::RepeatBlock(repeat_block) => node(
+                "AstStatRepeat",
+                location(&**repeat_block),
+                json!({
+                    "condition": self.expression(&repeat_block.condition),
+                    "body": self.block(&repeat_block.body, true),
+                }),
+            ),
+            Statement::WhileLoop(while_loop) => node(
+                "AstStatWhile",
+                location(&**while_loop),
+                json!({
+                    "condition": self.expression(&while_loop.condition),
+                    "body": self.block(&while_loop.do_block.body, true),
+                    "hasDo": true,
+                }),
+            ),
+            Statement::SetExpression(set_expression) => node(
+                "AstStatAssign",
+                location(&**set_expression),
+                json!({
+                    "vars": self.list(&set_expression.variables, Self::var),
+                    "values": self.list(&set_expression.values, |this, expression| {
+                        this.expression(expression)
+                    }),
+                }),
+            ),
+            Statement::CompoundSetExpression(compound_set_expression) => node(
+                "AstStatCompoundAssign",
+                location(&**compound_set_expression),
+                json!({
+                    "op": compound_operator_name(&compound_set_expression.operation),
+                    "var": self.var(&compound_set_expression.variable),
+                    "value": self.expression(&compound_set_expression.value),
+                }),
+            ),
+            Statement::FunctionCall(function_call) => node(
+                "AstStatExpr",
+                location(&**function_call),
+                json!({ "expr": self.function_call(function_call) }),
+            ),
+            Statement::GlobalFunction(global_function) => {
+                let is_method = matches!(
+                    &global_function.function_name,
+                    GlobalFunctionName::Table {
+                        method: Some(_),
+                        ..
+                    }
+                );
+
+                node(
+                    "AstStatFunction",
+                    location(&**global_function),
+                    json!({
+                        "name": self.global_function_name(&global_function.function_name),
+                        "func": self.function(
+                            location_between(&global_function.function_keyword, &global_function.end_keyword),
+                            &global_function.attributes,
+                            global_function.generics.as_ref(),
+                            &global_function.parameters,
+                            global_function.return_type.as_ref(),
+                            &global_function.body,
+                            is_method,
+                        ),
+                    }),
+                )
+            }
+            Statement::TypeFunction(type_function) => node(
+                "AstStatTypeFunction",
+                location(&**type_function),
+                json!({
+                    "name": name(&type_function.function_name),
+                    "body": self.function(
+                        location_between(&type_function.function_keyword, &type_function.end_keyword),
+                        &[],
+                        type_function.generics.as_ref(),
+                        &type_function.parameters,
+                        type_function.return_type.as_ref(),
+                        &type_function.body,
+                        false,
+                    ),
+                    "exported": type_function.export_keyword.is_some(),
+                }),
+            ),
+        }
+    }
+
+    /// Encode a [`TerminationStatement`].
+    fn termination_statement(&self, statement: &TerminationStatement) -> Value {
+        match statement {
+            TerminationStatement::Break(token) => node("AstStatBreak", location(token), json!({})),
+            TerminationStatement::Continue(token) => {
+                node("AstStatContinue", location(token), json!({}))
+            }
+            TerminationStatement::Return { expressions, .. } => node(
+                "AstStatReturn",
+                location(statement),
+                json!({
+                    "list": expressions.as_ref().map_or_else(Vec::new, |expressions| {
+                        expressions
+                            .iter()
+                            .map(|expression| self.expression(expression))
+                            .collect()
+                    }),
+                }),
+            ),
+        }
+    }
+
+    /// Encode an [`IfStatement`] as an `AstStatIf`, `elseif`s are nested
+    /// `AstStatIf`s.
+    fn if_statement(&self, if_statement: &IfStatement) -> Value {
+        let mut else_body = if_statement
+            .else_statement
+            .as_ref()
+            .map_or(Value::Null, |else_statement| {
+                self.block(&else_statement.body, true)
+            });
+
+        for else_if in if_statement.else_if_statements.iter().rev() {
+            else_body = node(
+                "AstStatIf",
+                location_between(&else_if.elseif_keyword, &if_statement.end_keyword),
+                json!({
+                    "condition": self.expression(&else_if.condition),
+                    "thenbody": self.block(&else_if.body, true),
+                    "elsebody": else_body,
+                    "hasThen": true,
+                }),
+            );
+        }
+
+        node(
+            "AstStatIf",
+            location(if_statement),
+            json!({
+                "condition": self.expression(&if_statement.condition),
+                "thenbody": self.block(&if_statement.body, true),
+                "elsebody": else_body,
+                "hasThen": true,
+            }),
+        )
+    }
+
+    /// Encode an index into `expression` at `key`.
+    fn index(&self, expression: Value, start: &impl GetRange, key: &TableAccessKey) -> Value {
+        match key {
+            TableAccessKey::Name { dot, name: key } => node(
+                "AstExprIndexName",
+                location_between(start, key),
+                json!({
+                    "expr": expression,
+                    "index": name(key),
+                    "indexLocation": location(&**key),
+                    "op": dot.print().trim(),
+                }),
+            ),
+            TableAccessKey::Expression(table_key) => node(
+                "AstExprIndexExpr",
+                location_between(start, key),
+                json!({
+                    "expr": expression,
+                    "index": match &**table_key {
+                        TableKey::Expression(bracketed) => self.expression(&bracketed.item),
+                        _ => node("AstExprError", location(&**table_key), json!({})),
+                    },
+                }),
+            ),
+        }
+    }
+
+    /// Encode the name of a global function.
+    fn global_function_name(&self, function_name: &GlobalFunctionName) -> Value {
+        match function_name {
+            GlobalFunctionName::SimpleName(token) => self.variable(token),
+            GlobalFunctionName::Table {
+                table,
+                keys,
+                method,
+            } => {
+                let expression = keys.iter().fold(self.variable(table), |expression, key| {
+                    self.index(expression, table, key)
+                });
+
+                match method {
+                    Some(method) => node(
+                        "AstExprIndexName",
+                        location_between(table, &method.1),
+                        json!({
+                            "expr": expression,
+                            "index": name(&method.1),
+                            "indexLocation": location(&method.1),
+                            "op": ":",
+                        }),
+                    ),
+                    None => expression,
+                }
+            }
+        }
+    }
+
+    /// Encode a [`TableAccess`] as nested `AstExprIndexName`s and
+    /// `AstExprIndexExpr`s.
+    fn table_access(&self, table_access: &TableAccess) -> Value {
+        let prefix = match &table_access.prefix {
+            TableAccessPrefix::Name(token) => self.variable(token),
+            TableAccessPrefix::FunctionCall(function_call) => self.function_call(function_call),
+            TableAccessPrefix::ExpressionWrap(wrap) => node(
+                "AstExprGroup",
+                location(&**wrap),
+                json!({ "expr": self.expression(&wrap.item) }),
+            ),
+        };
+
+        table_access
+            .accessed_keys
+            .iter()
+            .fold(prefix, |expression, key| {
+                self.index(expression, &table_access.prefix, key)
+            })
+    }
+
+    /// Encode a [`Var`].
+    fn var(&self, var: &Var) -> Value {
+        match var {
+            Var::ERROR => node("AstExprError", Value::Null, json!({})),
+            Var::Name(token) => self.variable(token),
+            Var::TableAccess(table_access) => self.table_access(table_access),
+        }
+    }
+
+    /// Encode a [`PrefixExp`].
+    fn prefix_exp(&self, prefix_exp: &PrefixExp) -> Value {
+        match prefix_exp {
+            PrefixExp::Var(var) => self.var(var),
+            PrefixExp::FunctionCall(function_call) => self.function_call(function_call),
+            PrefixExp::ExpressionWrap(wrap) => node(
+                "AstExprGroup",
+                location(wrap),
+                json!({ "expr": self.expression(&wrap.item) }),
+            ),
+        }
+    }
+
+    /// Encode a [`FunctionCall`] as an `AstExprCall`.
+    fn function_call(&self, function_call: &FunctionCall) -> Value {
+        let (function, is_method) = match &function_call.invoked {
+            FunctionCallInvoked::Function(prefix_exp) => (self.prefix_exp(prefix_exp), false),
+            FunctionCallInvoked::TableMethod { table, method, .. } => (
+                node(
+                    "AstExprIndexName",
+                    location_between(&**table, &**method),
+                    json!({
+                        "expr": self.prefix_exp(table),
+                        "index": name(method),
+                        "indexLocation": location(&**method),
+                        "op": ":",
+                    }),
+                ),
+                true,
+            ),
+        };
+        let arguments = match &function_call.arguments {
+            FunctionArguments::String(token) => vec![self.string(token)],
+            FunctionArguments::Table(table) => vec![self.table(table)],
+            FunctionArguments::List(list) => list
+                .item
+                .iter()
+                .map(|argument| match &***argument {
+                    FunctionArgument::Expression(expression) => self.expression(expression),
+                    FunctionArgument::VariadicValues(token) => {
+                        node("AstExprVarargs", location(token), json!({}))
+                    }
+                })
+                .collect(),
+        };
+
+        node(
+            "AstExprCall",
+            location(function_call),
+            json!({
+                "func": function,
+                "args": arguments,
+                "self": is_method,
+                "argLocation": location(&function_call.arguments),
+            }),
+        )
+    }
+
+    /// Encode a string token as an `AstExprConstantString`.
+    #[inline]
+    fn string(&self, token: &Token) -> Value {
+        node(
+            "AstExprConstantString",
+            location(token),
+            json!({ "value": string_value(token) }),
+        )
+    }
+
+    /// Encode a [`Table`] as an `AstExprTable`.
+    fn table(&self, table: &Table) -> Value {
+        let items = table
+            .0
+            .item
+            .iter()
+            .map(|field| {
+                let value = match &*field.value {
+                    TableFieldValue::Expression(expression) => self.expression(expression),
+                    TableFieldValue::VariadicValues(token) => {
+                        node("AstExprVarargs", location(token), json!({}))
+                    }
+                    TableFieldValue::ERROR | TableFieldValue::Type(_) => {
+                        node("AstExprError", location(&*field.value), json!({}))
+                    }
+                };
+
+                match &*field.key {
+                    TableKey::Simple(key) => json!({
+                        "kind": "record",
+                        "key": node(
+                            "AstExprConstantString",
+                            location(key),
+                            json!({ "value": name(key) }),
+                        ),
+                        "value": value,
+                    }),
+                    TableKey::Expression(bracketed) => json!({
+                        "kind": "general",
+                        "key": self.expression(&bracketed.item),
+                        "value": value,
+                    }),
+                    _ => json!({ "kind": "item", "value": value }),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        node("AstExprTable", location(table), json!({ "items": items }))
+    }
+
+    /// Encode an [`Expression`].
+    fn expression(&self, expression: &Expression) -> Value {
+        match expression {
+            Expression::ERROR => node("AstExprError", Value::Null, json!({})),
+            Expression::Nil(token) => node("AstExprConstantNil", location(token), json!({})),
+            Expression::VariadicValues(token) => node("AstExprVarargs", location(token), json!({})),
+            Expression::Boolean(token) => node(
+                "AstExprConstantBool",
+                location(token),
+                json!({
+                    "value": token.token_type == TokenType::Literal(Literal::Boolean(true)),
+                }),
+            ),
+            Expression::Number(token) => {
+                let value = match ParsedNumber::from_token(token) {
+                    Some(Ok(ParsedNumber::HexOrByte(value))) => json!(value as f64),
+                    Some(Ok(ParsedNumber::Other(value))) => json!(value),
+                    _ => Value::Null,
+                };
+
+                node(
+                    "AstExprConstantNumber",
+                    location(token),
+                    json!({ "value": value }),
+                )
+            }
+            Expression::String(token) => self.string(token),
+            Expression::InterpolatedString(interpolated_string) => {
+                let mut strings = Vec::new();
+                let mut expressions = Vec::new();
+
+                for segment in &interpolated_string.segments {
+                    match segment {
+                        InterpolatedStringSegment::String(token) => {
+                            strings.push(string_value(token));
+                        }
+                        InterpolatedStringSegment::Expression(expression) => {
+                            expressions.push(self.expression(expression));
+                        }
+                    }
+                }
+
+                node(
+                    "AstExprInterpString",
+                    location(interpolated_string),
+                    json!({ "strings": strings, "expressions": expressions }),
+                )
+            }
+            Expression::Closure(closure) => self.function(
+                location(&**closure),
+                &closure.attributes,
+                closure.generics.as_ref(),
+                &closure.parameters,
+                closure.return_type.as_ref(),
+                &closure.body,
+                false,
+            ),
+            Expression::FunctionCall(function_call) => self.function_call(function_call),
+            Expression::ExpressionWrap(wrap) => node(
+                "AstExprGroup",
+                location(wrap),
+                json!({ "expr": self.expression(&wrap.item) }),
+            ),
+            Expression::Var(var) => self.var(var),
+            Expression::Table(table) => self.table(table),
+            Expression::UnaryExpression {
+                operator,
+                expression: inner,
+            } => node(
+                "AstExprUnary",
+                location(expression),
+                json!({
+                    "op": match operator {
+                        UnaryOperator::Minus(_) => "Minus",
+                        UnaryOperator::Not(_) => "Not",
+                        UnaryOperator::Length(_) => "Len",
+                    },
+                    "expr": self.expression(inner),
+                }),
+            ),
+            Expression::BinaryExpression {
+                left,
+                operator,
+                right,
+            } => node(
+                "AstExprBinary",
+                location(expression),
+                json!({
+                    "op": binary_operator_name(operator),
+                    "left": self.expression(left),
+                    "right": self.expression(right),
+                }),
+            ),
+            Expression::TypeCast {
+                expression: inner,
+                cast_to,
+                ..
+            } => node(
+                "AstExprTypeAssertion",
+                location(expression),
+                json!({
+                    "expr": self.expression(inner),
+                    "annotation": self.type_value(cast_to),
+                }),
+            ),
+            Expression::IfExpression(if_expression) => {
+                let mut false_expression = self.expression(&if_expression.else_expression);
+
+                for else_if in if_expression.else_if_expressions.iter().rev() {
+                    false_expression = node(
+                        "AstExprIfElse",
+                        location_between(&else_if.else_if_keyword, &if_expression.else_expression),
+                        json!({
+                            "condition": self.expression(&else_if.condition),
+                            "hasThen": true,
+                            "trueExpr": self.expression(&else_if.expression),
+                            "hasElse": true,
+                            "falseExpr": false_expression,
+                        }),
+                    );
+                }
+
+                node(
+                    "AstExprIfElse",
+                    location(if_expression),
+                    json!({
+                        "condition": self.expression(&if_expression.condition),
+                        "hasThen": true,
+                        "trueExpr": self.expression(&if_expression.if_expression),
+                        "hasElse": true,
+                        "falseExpr": false_expression,
+                    }),
+                )
+            }
+        }
+    }
+
+    /// Encode a reference to a named type as an `AstTypeReference`.
+    fn type_reference(
+        &self,
+        name_token: &Token,
+        prefix: Option<&Token>,
+        generics: Option<&Pointer<BracketedList<Pointer<TypeValue>>>>,
+    ) -> Value {
+        let location = match prefix {
+            Some(prefix) => location_between(prefix, name_token),
+            None => location(name_token),
+        };
+
+        node(
+            "AstTypeReference",
+            location,
+            json!({
+                "name": name(name_token),
+                "nameLocation": self::location(name_token),
+                "prefix": prefix.map(name),
+                "parameters": generics.map_or_else(Vec::new, |generics| {
+                    generics.item.iter().map(|r#type| self.type_value(r#type)).collect()
+                }),
+            }),
+        )
+    }
+
+    /// Encode a list of types as a `typeList`, the last type is the `tailType`
+    /// if it's a pack.
+    fn type_list<'b>(&self, types: impl Iterator<Item = &'b TypeValue>) -> Value {
+        let mut types = types.collect::<Vec<_>>();
+        let tail = types
+            .pop_if(|r#type| {
+                matches!(
+                    r#type,
+                    TypeValue::GenericPack { .. }
+                        | TypeValue::Variadic { .. }
+                        | TypeValue::VariadicPack { .. }
+                )
+            })
+            .map_or(Value::Null, |r#type| self.type_value(r#type));
+
+        json!({
+            "kind": "AstTypeList",
+            "types": types.into_iter().map(|r#type| self.type_value(r#type)).collect::<Vec<_>>(),
+            "tailType": tail,
+        })
+    }
+
+    /// Collect all types in nested unions or intersections.
+    fn flatten<'b>(&self, r#type: &'b TypeValue, is_union: bool, types: &mut Vec<&'b TypeValue>) {
+        match r#type {
+            TypeValue::Union { left, right, .. } if is_union => {
+                self.flatten(left, is_union, types);
+                self.flatten(right, is_union, types);
+            }
+            TypeValue::Intersection { left, right, .. } if !is_union => {
+                self.flatten(left, is_union, types);
+                self.flatten(right, is_union, types);
+            }
+            _ => types.push(r#type),
+        }
+    }
+
+    /// Encode a [`TypeValue`].
+    fn type_value(&self, r#type: &TypeValue) -> Value {
+        match r#type {
+            TypeValue::ERROR => node("AstTypeError", Value::Null, json!({})),
+            TypeValue::String(token) => node(
+                "AstTypeSingletonString",
+                location(token),
+                json!({ "value": string_value(token) }),
+            ),
+            TypeValue::Boolean(token) => node(
+                "AstTypeSingletonBool",
+                location(token),
+                json!({
+                    "value": token.token_type == TokenType::Literal(Literal::Boolean(true)),
+                }),
+            ),
+            TypeValue::Nil(token) => self.type_reference(token, None, None),
+            TypeValue::Wrap(bracketed) => node(
+                "AstTypeGroup",
+                location(bracketed),
+                json!({ "inner": self.type_value(&bracketed.item) }),
+            ),
+            TypeValue::Function {
+                generics,
+                parameters,
+                return_type,
+                ..
+            } => {
+                let (generics, generic_packs) = self.generics(generics.as_ref());
+                let argument_names = parameters
+                    .item
+                    .iter()
+                    .map(|parameter| match &**parameter {
+                        ParameterTypeName::Normal(name) => json!({
+                            "name": self::name(&name.name),
+                            "location": location(&name.name),
+                        }),
+                        ParameterTypeName::Type(_) => Value::Null,
+                    })
+                    .collect::<Vec<_>>();
+                let argument_types = parameters.item.iter().map(|parameter| match &**parameter {
+                    ParameterTypeName::Normal(name) => {
+                        name.r#type.as_deref().unwrap_or(&TypeValue::ERROR)
+                    }
+                    ParameterTypeName::Type(r#type) => r#type,
+                });
+
+                node(
+                    "AstTypeFunction",
+                    location(r#type),
+                    json!({
+                        "generics": generics,
+                        "genericPacks": generic_packs,
+                        "argTypes": self.type_list(argument_types),
+                        "argNames": argument_names,
+                        "returnTypes": self.type_value(return_type),
+                    }),
+                )
+            }
+            TypeValue::Basic { base, generics } => {
+                self.type_reference(base, None, generics.as_ref())
+            }
+            TypeValue::Module {
+                module,
+                name,
+                generics,
+                ..
+            } => self.type_reference(name, Some(module), generics.as_ref()),
+            TypeValue::GenericPack { name: token, .. }
+            | TypeValue::VariadicPack { name: token, .. } => node(
+                "AstTypePackGeneric",
+                location(r#type),
+                json!({ "genericName": name(token) }),
+            ),
+            TypeValue::Intersection { .. } | TypeValue::Union { .. } => {
+                let is_union = matches!(r#type, TypeValue::Union { .. });
+                let mut types = Vec::new();
+                self.flatten(r#type, is_union, &mut types);
+
+                node(
+                    if is_union {
+                        "AstTypeUnion"
+                    } else {
+                        "AstTypeIntersection"
+                    },
+                    location(r#type),
+                    json!({
+                        "types": types
+                            .into_iter()
+                            .map(|r#type| self.type_value(r#type))
+                            .collect::<Vec<_>>(),
+                    }),
+                )
+            }
+            TypeValue::Optional {
+                base,
+                question_mark,
+            } => node(
+                "AstTypeUnion",
+                location(r#type),
+                json!({
+                    "types": [
+                        self.type_value(base),
+                        node("AstTypeOptional", location(question_mark), json!({})),
+                    ],
+                }),
+            ),
+            TypeValue::Table(table) => self.table_type(table),
+            TypeValue::Typeof { inner, .. } => node(
+                "AstTypeTypeof",
+                location(r#type),
+                json!({ "expr": self.expression(&inner.item) }),
+            ),
+            TypeValue::Tuple(types) => node(
+                "AstTypePackExplicit",
+                location(types),
+                json!({ "typeList": self.type_list(types.item.iter().map(|r#type| &***r#type)) }),
+            ),
+            TypeValue::Variadic { type_value, .. } => node(
+                "AstTypePackVariadic",
+                location(r#type),
+                json!({ "variadicType": self.type_value(type_value) }),
+            ),
+        }
+    }
+
+    /// Encode a [`Table`] type as an `AstTypeTable`.
+    fn table_type(&self, table: &Table) -> Value {
+        let mut properties = Vec::new();
+        let mut indexer = Value::Null;
+
+        for field in table.0.item.iter() {
+            let value = match &*field.value {
+                TableFieldValue::Type(r#type) => self.type_value(r#type),
+                _ => node("AstTypeError", location(&*field.value), json!({})),
+            };
+
+            match &*field.key {
+                TableKey::Simple(key) => properties.push(node(
+                    "AstTableProp",
+                    location(&**field),
+                    json!({ "name": name(key), "propType": value }),
+                )),
+                TableKey::Type(bracketed) => {
+                    indexer = node(
+                        "AstTableIndexer",
+                        location(&**field),
+                        json!({
+                            "indexType": self.type_value(&bracketed.item),
+                            "resultType": value,
+                        }),
+                    );
+                }
+                _ => {
+                    indexer = node(
+                        "AstTableIndexer",
+                        location(&**field),
+                        json!({
+                            "indexType": node(
+                                "AstTypeReference",
+                                Value::Null,
+                                json!({
+                                    "name": "number",
+                                    "nameLocation": Value::Null,
+                                    "prefix": Value::Null,
+                                    "parameters": [],
+                                }),
+                            ),
+                            "resultType": value,
+                        }),
+                    );
+                }
+            }
+        }
+
+        node(
+            "AstTypeTable",
+            location(table),
+            json!({ "props": properties, "indexer": indexer }),
+        )
+    }
+}
+
+/// Encode a [`Diagnostic`].
+fn error(error: &Diagnostic) -> Value {
+    json!({
+        "code": error.code.code(),
+        "message": error.message,
+        "location": format_range(error.range()),
+    })
+}
+
+impl Cst {
+    /// Convert this [`Cst`] to the stable JSON format described in the
+    /// [`json_ast`](crate::json_ast) module, without trivia.
+    #[inline]
+    pub fn to_json_ast(&self) -> Value {
+        self.to_json_ast_with(JsonAstOptions::default())
+    }
+
+    /// Convert this [`Cst`] to the stable JSON format described in the
+    /// [`json_ast`](crate::json_ast) module.
+    pub fn to_json_ast_with(&self, options: JsonAstOptions) -> Value {
+        let mut trivia_collector = TriviaCollector {
+            encoding: self.position_encoding,
+            ..Default::default()
+        };
+        self.visit(&mut trivia_collector);
+
+        let comments = trivia_collector
+            .trivia
+            .iter()
+            .filter(|(trivia, _)| matches!(trivia, Trivia::Comment(_)))
+            .map(|(trivia, range)| {
+                json!({ "kind": trivia_kind(trivia), "location": format_range(*range) })
+            })
+            .collect::<Vec<_>>();
+
+        let mut output = json!({
+            "version": JSON_AST_VERSION,
+            "root": Encoder::new(self).block(&self.block, false),
+            "commentLocations": comments,
+            "errors": self.errors.iter().map(error).collect::<Vec<_>>(),
+        });
+
+        if options.include_trivia {
+            output["trivia"] = trivia_collector
+                .trivia
+                .iter()
+                .map(|(trivia, range)| {
+                    json!({
+                        "kind": trivia_kind(trivia),
+                        "location": format_range(*range),
+                        "text": trivia.print(),
+                    })
+                })
+                .collect();
+        }
+
+        output
+    }
+}
@@ -7,8 +7,20 @@
 #[macro_use]
 mod macros;
 
+pub mod build;
+pub mod format;
 mod r#impl;
+#[cfg(feature = "serde")]
+pub mod json_ast;
+pub mod minify;
 pub mod parser;
+pub mod ranges;
+pub mod rename;
+pub mod rewrite;
+pub mod semantic;
+pub mod semantic_tokens;
+pub mod symbols;
+pub mod transpile;
 pub mod types;
 mod utils;
 pub use luau_lexer;
@@ -17,6 +29,8 @@ pub use luau_lexer;
 pub mod prelude {
     pub use crate::parser::*;
     pub use crate::types::*;
+    // `lsp_types` has its own `Diagnostic`, this one takes priority.
+    pub use crate::types::Diagnostic;
     pub use lsp_types::*;
     pub use luau_lexer::prelude::*;
 }
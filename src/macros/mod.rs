@@ -13,14 +13,15 @@ mod parse;
 #[doc(hidden)]
 macro_rules! handle_error_token {
     ($errors: ident, $error: expr) => {{
-        $errors.push($error);
+        $errors.push($error.into());
 
         None
     }};
 }
 
-/// Safely `unwrap` an [`Option`] by using the default value and sending the
-/// passed error if it's [`None`].
+/// Safely `unwrap` an [`Option`] by using the default value and sending a
+/// diagnostic with the passed code if it's [`None`]. The message is `Expected`
+/// followed by the passed expected item.
 ///
 /// # Usage
 ///
@@ -28,21 +29,31 @@ macro_rules! handle_error_token {
 /// safe_unwrap!(
 ///     lexer,
 ///     errors,
-///     "<error message>",
+///     DiagnosticCode::MissingExpression,
+///     "<expr>",
 ///     <expr> // this must be an `Option`, or have an `unwrap_or_else` function.
 /// )
 /// ```
 #[macro_export]
 #[doc(hidden)]
 macro_rules! safe_unwrap {
-    ($lexer: ident, $errors: ident, $error_message: expr, $expr: expr) => {{
+    ($lexer: ident, $errors: ident, $code: expr, $expected: literal, $expr: expr) => {{
         $expr.unwrap_or_else(|| {
             let state = $lexer.save_state();
-            $errors.push(ParseError::new(
-                state.lexer_position(),
-                $error_message,
-                Some(state.lexer_position()),
-            ));
+            let position = state.lexer_position();
+            let found = $lexer.next_token();
+            $lexer.set_state(state);
+
+            $errors.push(
+                $crate::types::Diagnostic::new(
+                    $code,
+                    position,
+                    position,
+                    concat!("Expected ", $expected),
+                )
+                .with_expected($expected)
+                .with_found_token(&found.token_type),
+            );
 
             Default::default()
         })
@@ -8,6 +8,7 @@
 ///  parse_bracketed!(
 ///     lexer,
 ///     errors,
+///     DiagnosticCode::MissingExpression, // code used if the item is missing
 ///     "<error message>",
 ///     TokenType::Symbol(Symbol::OpeningParenthesis), // opening token
 ///     Symbol::ClosingParenthesis, // closing token
@@ -19,6 +20,7 @@ macro_rules! parse_bracketed {
     (
         $lexer: ident,
         $errors: ident,
+        $code: expr,
         $error_message: literal,
         $opening: pat,
         $closing: expr,
@@ -30,7 +32,7 @@ macro_rules! parse_bracketed {
                 __opening_bracket,
                 $lexer,
                 $errors,
-                ($error_message, $closing),
+                ($code, $error_message, $closing),
             )
         } else {
             None
@@ -47,6 +49,7 @@ macro_rules! parse_bracketed {
 ///  parse_bracketed!(
 ///     lexer,
 ///     errors,
+///     DiagnosticCode::MissingExpression, // code used if the item is missing
 ///     "<error message>",
 ///     (
 ///         TokenType::Symbol(Symbol::OpeningParenthesis), // opening token
@@ -60,6 +63,7 @@ macro_rules! force_parse_bracketed {
     (
         $lexer: ident,
         $errors: ident,
+        $code: expr,
         $error_message: literal,
         ($opening: pat, $opening_replacement: expr),
         $closing: expr,
@@ -70,6 +74,7 @@ macro_rules! force_parse_bracketed {
             $opening,
             $opening_replacement,
             $errors,
+            $code,
             $error_message
         );
 
@@ -77,7 +82,7 @@ macro_rules! force_parse_bracketed {
             __opening_bracket,
             $lexer,
             $errors,
-            ($error_message, $closing),
+            ($code, $error_message, $closing),
         )
         .unwrap_or_else(|| $crate::types::Bracketed {
             opening_bracket: Token::empty($opening_replacement),
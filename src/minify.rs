@@ -0,0 +1,309 @@
+//! # Minifier
+//!
+//! Prints a [`Cst`] using as few characters as possible, see [`Cst::minify`].
+//!
+//! * Comments and whitespace are removed. Spaces are only kept where the
+//!   tokens around them would otherwise be read as one, like in `a - -b` or
+//!   `1 ..x`.
+//! * Semicolons are removed, except before statements starting with `(`, as
+//!   `f()(g)()` is a single statement.
+//! * Locals, parameters, and loop variables can be renamed to short names, see
+//!   [`MinifyOptions::rename_locals`]. Scopes are resolved using a
+//!   [`SemanticModel`], and globals are never renamed or shadowed.
+//!
+//! Parsing the minified code gives the same tree, without trivia.
+//!
+//! ```rust
+//! # use luau_parser::{minify::MinifyOptions, prelude::{Parser, ParserOptions}};
+//! let code = "local value = 1 -- The value.\nprint(value - -1)";
+//! let cst = Parser::new(code, ParserOptions::default()).parse("");
+//!
+//! assert_eq!(
+//!     cst.minify(MinifyOptions::default()).unwrap(),
+//!     "local value=1 print(value- -1)"
+//! );
+//! assert_eq!(
+//!     cst.minify(MinifyOptions { rename_locals: true }).unwrap(),
+//!     "local a=1 print(a- -1)"
+//! );
+//! ```
+//!
+//! ## Note
+//!
+//! Renaming locals changes behavior of code accessing them by name, like code
+//! using `debug.getlocal` or loaded with `loadstring`.
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Literal, Symbol, Token, TokenType};
+use smol_str::SmolStr;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use crate::{
+    semantic::{BindingKind, SemanticModel},
+    types::{Block, Cst, GetRange, Visit, Visitor},
+};
+
+/// Names that can't be used for locals.
+const RESERVED: &[&str] = &[
+    "and", "break", "continue", "do", "else", "elseif", "end", "export", "false", "for",
+    "function", "if", "in", "local", "nil", "not", "or", "repeat", "return", "self", "then",
+    "true", "type", "typeof", "until", "while",
+];
+
+/// The characters names can start with.
+const FIRST_CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_";
+
+/// The characters names can continue with.
+const CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_0123456789";
+
+/// Options changing what [`Cst::minify`] does.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MinifyOptions {
+    /// Whether or not to rename locals, parameters, and loop variables to
+    /// short names. Names are reused for variables that aren't used at the
+    /// same time.
+    pub rename_locals: bool,
+}
+
+/// Errors that may occur in [`Cst::minify`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MinifyError {
+    /// The [`Cst`] [has errors](Cst::has_errors) and is missing parts of the
+    /// code.
+    HasErrors,
+}
+
+impl fmt::Display for MinifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HasErrors => f.write_str("can't minify code with syntax errors"),
+        }
+    }
+}
+
+impl Error for MinifyError {}
+
+/// Whether or not a space is needed between the passed tokens so they aren't
+/// read as one. `previous_is_number` is whether or not the previous token is a
+/// number, which can't be followed by a `.`.
+fn needs_space(previous: &str, next: &str, previous_is_number: bool) -> bool {
+    let (Some(previous), Some(next)) = (previous.chars().last(), next.chars().next()) else {
+        return false;
+    };
+    let is_word = |character: char| character.is_alphanumeric() || character == '_';
+
+    match (previous, next) {
+        (previous, next) if is_word(previous) && is_word(next) => true,
+        (_, '.') if previous_is_number => true,
+        ('-', '-') | ('[', '[' | '=') | ('.', '.') => true,
+        ('=' | '<' | '>' | '~', '=') | ('<', '<') | ('>', '>') | (':', ':') | ('/', '/') => true,
+        _ => false,
+    }
+}
+
+/// Get the `index`th short name, not skipping reserved ones.
+fn short_name(mut index: usize) -> String {
+    let mut name = String::new();
+    name.push(FIRST_CHARACTERS[index % FIRST_CHARACTERS.len()] as char);
+    index /= FIRST_CHARACTERS.len();
+
+    while index > 0 {
+        index -= 1;
+        name.push(CHARACTERS[index % CHARACTERS.len()] as char);
+        index /= CHARACTERS.len();
+    }
+
+    name
+}
+
+/// Pick short names for all bindings in the passed model, keyed by the start of
+/// the tokens to rename.
+///
+/// Each binding is live from where it's visible to its last reference, and
+/// bindings that are never live at the same time can share a name: a reference
+/// can only be captured by a binding with the same name that becomes visible
+/// between the reference and the binding it refers to, while the latter is
+/// live. Names used by globals are skipped so they aren't shadowed.
+///
+/// Names are picked in the order bindings become visible, each binding getting
+/// the first name that isn't used by a live one.
+fn short_names(model: &SemanticModel) -> HashMap<Position, SmolStr> {
+    let reserved = model
+        .globals()
+        .map(|reference| {
+            reference
+                .token
+                .token_type
+                .try_as_string()
+                .unwrap_or_default()
+        })
+        .chain(RESERVED.iter().map(ToString::to_string))
+        .collect::<HashSet<_>>();
+    let mut names = (0..)
+        .map(short_name)
+        .filter(|name| !reserved.contains(name))
+        .map(SmolStr::from);
+    let mut available_names = Vec::new();
+
+    let mut bindings = model
+        .bindings
+        .iter()
+        // The implicit `self` has no name to rename, and neither do `...`
+        // parameters.
+        .filter(|binding| {
+            binding.kind != BindingKind::ImplicitSelf
+                && matches!(binding.token.token_type, TokenType::Identifier(_))
+        })
+        .map(|binding| {
+            // Parameters and loop variables are live until the last one of
+            // their function or loop, so they all get different names.
+            let declared_with = match binding.kind {
+                BindingKind::Parameter | BindingKind::ForVariable => model
+                    .scope(binding.scope)
+                    .bindings
+                    .iter()
+                    .map(|id| model.binding(*id))
+                    .filter(|other| other.kind == binding.kind)
+                    .map(|other| other.visible_from)
+                    .max(),
+                _ => None,
+            };
+            let end = model
+                .references_of(binding.id)
+                .map(|reference| reference.token.start)
+                .chain(declared_with)
+                .max()
+                .unwrap_or(binding.visible_from);
+
+            (binding, binding.visible_from, end.max(binding.visible_from))
+        })
+        .collect::<Vec<_>>();
+    bindings.sort_by_key(|(_, start, _)| *start);
+
+    let mut renames = HashMap::new();
+    // The end of each live binding with the index of its name.
+    let mut live: Vec<(Position, usize)> = Vec::new();
+
+    for (binding, start, end) in bindings {
+        live.retain(|(live_end, _)| *live_end >= start);
+
+        let index = (0..)
+            .find(|index| live.iter().all(|(_, used)| used != index))
+            .unwrap_or_default();
+        while available_names.len() <= index {
+            available_names.extend(names.next());
+        }
+        live.push((end, index));
+
+        let name = available_names[index].clone();
+        for reference in model.references_of(binding.id) {
+            renames.insert(reference.token.start, name.clone());
+        }
+        renames.insert(binding.token.start, name);
+    }
+
+    renames
+}
+
+/// A [`Visitor`] writing the minified version of the visited code.
+#[derive(Default)]
+struct Minifier {
+    /// The code written so far.
+    output: String,
+
+    /// The new names of the tokens starting at a position.
+    renames: HashMap<Position, SmolStr>,
+
+    /// Semicolons separating statements, which are removed.
+    semicolons: HashSet<Position>,
+
+    /// Starts of statements that aren't the first ones in their blocks, a
+    /// semicolon is added before the ones starting with `(`.
+    statement_starts: HashSet<Position>,
+
+    /// Whether or not the last written token is a number.
+    last_is_number: bool,
+}
+
+impl Minifier {
+    /// Write the passed token text, adding a space before it if needed.
+    fn write(&mut self, text: &str, is_number: bool) {
+        if text.is_empty() {
+            return;
+        }
+        if needs_space(&self.output, text, self.last_is_number) {
+            self.output.push(' ');
+        }
+
+        self.output.push_str(text);
+        self.last_is_number = is_number;
+    }
+}
+
+impl Visitor<'_> for Minifier {
+    fn visit_token(&mut self, token: &Token) {
+        if self.semicolons.remove(&token.start) {
+            return;
+        }
+        if token.token_type == TokenType::Symbol(Symbol::OpeningParenthesis)
+            && self.statement_starts.remove(&token.start)
+        {
+            self.write(";", false);
+        }
+
+        let text = match self.renames.get(&token.start) {
+            Some(name) => name.to_string(),
+            None => token.token_type.try_as_string().unwrap_or_default(),
+        };
+        let is_number = matches!(token.token_type, TokenType::Literal(Literal::Number(_)));
+
+        self.write(&text, is_number);
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        let statements = node
+            .statements
+            .iter()
+            .map(|(statement, semicolon)| (statement.get_range(), semicolon))
+            .chain(
+                node.last_statement
+                    .iter()
+                    .map(|(statement, semicolon)| (statement.get_range(), semicolon)),
+            );
+
+        for (index, (range, semicolon)) in statements.enumerate() {
+            if index != 0
+                && let Ok(range) = range
+            {
+                self.statement_starts.insert(range.start);
+            }
+            if let Some(semicolon) = semicolon {
+                self.semicolons.insert(semicolon.start);
+            }
+        }
+    }
+}
+
+impl Cst {
+    /// Minify this [`Cst`], see the [module docs](crate::minify) for what's
+    /// changed. [`Cst`]s with syntax errors are refused as parts of their code
+    /// are missing.
+    pub fn minify(&self, options: MinifyOptions) -> Result<String, MinifyError> {
+        if self.has_errors() {
+            return Err(MinifyError::HasErrors);
+        }
+
+        let mut minifier = Minifier::default();
+        if options.rename_locals {
+            minifier.renames = short_names(&SemanticModel::new(self));
+        }
+        self.visit(&mut minifier);
+
+        Ok(minifier.output)
+    }
+}
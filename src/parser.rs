@@ -1,15 +1,54 @@
 //! The main item of this crate, the actual [`parser`](Parser).
 
-use luau_lexer::lexer::Lexer;
 #[cfg(feature = "cache")]
-use std::collections::HashMap;
+use lsp_types::TextDocumentContentChangeEvent;
+use luau_lexer::{
+    lexer::Lexer,
+    prelude::{Symbol, Token},
+    token::TokenType,
+};
+#[cfg(feature = "cache")]
+use std::{collections::HashMap, mem};
 
-use crate::types::{Cst, Pointer};
+#[cfg(feature = "cache")]
+use crate::types::Print;
+use crate::{
+    r#impl::{line_index::convert_positions, options::check_features},
+    types::{
+        AstStatus, Block, Cst, Diagnostic, DiagnosticCode, Expression, LineIndex, Parse,
+        ParserOptions, Pointer, PositionEncoding, Statement, TerminationStatement, TypeValue,
+        Visit, VisitMut,
+    },
+    utils::{get_token_type_display_extended, next_token},
+};
 
 /// The cache used in [`Parser`] when `cache` feature is enabled.
 #[cfg(feature = "cache")]
 pub type ParserCache = HashMap<String, Pointer<Cst>>;
 
+/// A node parsed on its own, outside of a [`CST`](Cst), using one of
+/// [`Parser::parse_expression`], [`Parser::parse_type`],
+/// [`Parser::parse_statement`], [`Parser::parse_last_statement`], or
+/// [`Parser::parse_block`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Parsed<T> {
+    /// The parsed node, [`None`] if it couldn't be parsed at all.
+    pub node: Option<T>,
+
+    /// All errors met while parsing, including ones for any input left after
+    /// the node.
+    pub errors: Vec<Diagnostic>,
+}
+
+impl<T> Parsed<T> {
+    /// Whether or not parsing produced errors.
+    #[inline]
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
 /// A Luau parser.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(not(feature = "cache"), derive(Copy, Hash, PartialOrd, Ord))]
@@ -21,35 +60,138 @@ pub struct Parser {
     #[cfg(feature = "cache")]
     cache: ParserCache,
 
+    /// The source code of cached [`CST`](Cst)s, used by
+    /// [`Parser::apply_changes`]. The current input is only moved here when
+    /// it's replaced, so parsing doesn't copy it.
+    #[cfg(feature = "cache")]
+    sources: HashMap<String, String>,
+
+    /// The URI of the last [`CST`](Cst) parsed from [`Parser::input`].
+    #[cfg(feature = "cache")]
+    input_uri: Option<String>,
+
+    /// The current input, used to convert positions to
+    /// [`Parser::position_encoding`], and by [`Parser::apply_changes`].
+    input: String,
+
+    /// The options used when parsing.
+    options: ParserOptions,
+
     /// The lexer.
     lexer: Lexer,
 }
 
 impl Parser {
-    /// Create a new [`parser`](Parser).
+    /// Create a new [`parser`](Parser) using the passed options. Use
+    /// [`ParserOptions::default`] to parse Luau with all features enabled.
+    ///
+    /// ```rust
+    /// # use luau_parser::prelude::{DiagnosticCode, LanguageLevel, Parser};
+    /// let cst = Parser::new("x += 1", LanguageLevel::Lua51.into()).parse("");
+    /// assert_eq!(cst.errors[0].code, DiagnosticCode::DisabledFeature);
+    /// ```
     #[inline]
-    pub fn new(input: &str) -> Self {
+    pub fn new(input: &str, options: ParserOptions) -> Self {
         Self {
             #[cfg(feature = "cache")]
             cache: HashMap::new(),
+            #[cfg(feature = "cache")]
+            sources: HashMap::new(),
+            #[cfg(feature = "cache")]
+            input_uri: None,
+            input: input.to_string(),
+            options,
             lexer: Lexer::new(input),
         }
     }
 
     /// Set the parser's input. Meant to be chained.
     pub fn with_input(mut self, input: &str) -> Self {
-        self.lexer = self.lexer.with_input(input);
+        self.set_input(input);
         self
     }
 
     /// Set the parser's input.
     pub fn set_input(&mut self, input: &str) {
-        self.lexer.set_input(input);
+        #[cfg(feature = "cache")]
+        if let Some(uri) = self.input_uri.take() {
+            self.sources.insert(uri, mem::take(&mut self.input));
+        }
+
+        self.input = input.to_string();
+        self.lexer = Lexer::new(input);
+    }
+
+    /// Set how the columns of positions in parsed [`CST`](Cst)s, and the ones
+    /// passed to [`Parser::apply_changes`], are counted. This affects the
+    /// results of [`GetRange`](crate::types::GetRange) on all nodes. Meant to
+    /// be chained.
+    #[inline]
+    pub fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.set_position_encoding(encoding);
+        self
+    }
+
+    /// Set how the columns of positions in parsed [`CST`](Cst)s, and the ones
+    /// passed to [`Parser::apply_changes`], are counted. This affects the
+    /// results of [`GetRange`](crate::types::GetRange) on all nodes.
+    ///
+    /// Defaults to [`PositionEncoding::Utf32`], which is what the lexer
+    /// produces, using any other encoding makes parsing a bit slower as all
+    /// positions are converted after parsing.
+    #[inline]
+    pub fn set_position_encoding(&mut self, encoding: PositionEncoding) {
+        self.options.position_encoding = encoding;
+    }
+
+    /// How the columns of positions in parsed [`CST`](Cst)s are counted.
+    #[inline]
+    pub fn position_encoding(&self) -> PositionEncoding {
+        self.options.position_encoding
+    }
+
+    /// Set the options used when parsing. Meant to be chained.
+    #[inline]
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    /// Set the options used when parsing.
+    #[inline]
+    pub fn set_options(&mut self, options: ParserOptions) {
+        self.options = options;
+    }
+
+    /// The options used when parsing.
+    #[inline]
+    pub fn options(&self) -> ParserOptions {
+        self.options
     }
 
     /// Parse Luau code into an [`CST`](Cst).
     pub fn parse(&mut self, uri: &str) -> Pointer<Cst> {
-        let cst = Pointer::new(Cst::parse(self.lexer.next_token(), &mut self.lexer, uri));
+        let mut cst = Cst::parse(next_token(&mut self.lexer), &mut self.lexer, uri);
+        if check_features(&cst.block, self.options.features, &mut cst.errors) {
+            cst.status = AstStatus::HasErrors;
+        }
+        if self.options.position_encoding != cst.position_encoding {
+            cst.set_position_encoding(&LineIndex::new(&self.input), self.options.position_encoding);
+        }
+
+        #[cfg(feature = "cache")]
+        {
+            // The same input may be parsed under different URIs.
+            if let Some(input_uri) = self.input_uri.take()
+                && input_uri != uri
+            {
+                self.sources.insert(input_uri, self.input.clone());
+            }
+            self.sources.remove(uri);
+            self.input_uri = Some(uri.to_string());
+        }
+
+        let cst = Pointer::new(cst);
 
         #[cfg(feature = "cache")]
         {
@@ -62,6 +204,162 @@ impl Parser {
         cst
     }
 
+    /// Parse `code`, which must contain nothing but a node parsed by `parse`.
+    /// If the node isn't there at all, an error with the passed code, expecting
+    /// `expected`, is reported.
+    fn parse_fragment<T: Visit + VisitMut>(
+        &self,
+        code: &str,
+        parse: impl FnOnce(Token, &mut Lexer, &mut Vec<Diagnostic>) -> Option<T>,
+        missing_code: DiagnosticCode,
+        expected: &str,
+    ) -> Parsed<T> {
+        let mut lexer = Lexer::new(code);
+        let mut errors = Vec::new();
+
+        let token = next_token(&mut lexer);
+        let mut node = if token == TokenType::EndOfFile {
+            None
+        } else {
+            parse(token.clone(), &mut lexer, &mut errors)
+        };
+
+        let next_token = if node.is_some() {
+            next_token(&mut lexer)
+        } else {
+            token
+        };
+
+        let (error_code, expected) = if node.is_none() {
+            (missing_code, expected)
+        } else {
+            (DiagnosticCode::TrailingInput, "<eof>")
+        };
+        if node.is_none() || next_token != TokenType::EndOfFile {
+            let found = get_token_type_display_extended(&next_token.token_type);
+
+            errors.push(
+                Diagnostic::new(
+                    error_code,
+                    next_token.start,
+                    next_token.end,
+                    format!("Expected {expected} found {found}"),
+                )
+                .with_expected(expected)
+                .with_found(found),
+            );
+        }
+
+        if let Some(node) = &node {
+            check_features(node, self.options.features, &mut errors);
+        }
+        if self.options.position_encoding != PositionEncoding::Utf32 {
+            convert_positions(
+                node.as_mut(),
+                &mut errors,
+                &LineIndex::new(code),
+                PositionEncoding::Utf32,
+                self.options.position_encoding,
+            );
+        }
+
+        Parsed { node, errors }
+    }
+
+    /// Parse a single [`Expression`]. Input after the expression is reported
+    /// as an error.
+    ///
+    /// ```rust
+    /// # use luau_parser::prelude::{DiagnosticCode, Parser};
+    /// let parsed = Parser::default().parse_expression("1 + 2");
+    /// assert!(parsed.node.is_some() && !parsed.has_errors());
+    ///
+    /// let parsed = Parser::default().parse_expression("1 + 2 3");
+    /// assert_eq!(parsed.errors[0].code, DiagnosticCode::TrailingInput);
+    /// ```
+    #[inline]
+    pub fn parse_expression(&self, code: &str) -> Parsed<Expression> {
+        self.parse_fragment(
+            code,
+            Expression::parse,
+            DiagnosticCode::MissingExpression,
+            "<expr>",
+        )
+    }
+
+    /// Parse a single [`TypeValue`]. Input after the type is reported as an
+    /// error.
+    #[inline]
+    pub fn parse_type(&self, code: &str) -> Parsed<TypeValue> {
+        self.parse_fragment(
+            code,
+            TypeValue::parse,
+            DiagnosticCode::MissingType,
+            "<type>",
+        )
+    }
+
+    /// Parse a single [`Statement`] and the optional `;` after it, like in
+    /// [`Block::statements`]. Input after them is reported as an error.
+    ///
+    /// # Note
+    ///
+    /// `return`, `break`, and `continue` are [`TerminationStatement`]s and
+    /// can't be parsed with this function, use [`Parser::parse_last_statement`]
+    /// instead.
+    ///
+    /// ```rust
+    /// # use luau_parser::prelude::{DiagnosticCode, Parser};
+    /// let parsed = Parser::default().parse_statement("local x = 1;");
+    /// assert!(!parsed.has_errors() && parsed.node.unwrap().1.is_some());
+    ///
+    /// let parsed = Parser::default().parse_statement("return 1");
+    /// assert_eq!(parsed.errors[0].code, DiagnosticCode::MissingStatement);
+    /// ```
+    #[inline]
+    pub fn parse_statement(&self, code: &str) -> Parsed<(Statement, Option<Token>)> {
+        self.parse_fragment(
+            code,
+            parse_with_semicolon,
+            DiagnosticCode::MissingStatement,
+            "<statement>",
+        )
+    }
+
+    /// Parse a single [`TerminationStatement`] (`return`, `break`, or
+    /// `continue`) and the optional `;` after it, like in
+    /// [`Block::last_statement`]. Input after them is reported as an error.
+    #[inline]
+    pub fn parse_last_statement(
+        &self,
+        code: &str,
+    ) -> Parsed<(TerminationStatement, Option<Token>)> {
+        self.parse_fragment(
+            code,
+            parse_with_semicolon,
+            DiagnosticCode::MissingStatement,
+            "<termination statement>",
+        )
+    }
+
+    /// Parse a [`Block`], this is the same as [`Parser::parse`] but without
+    /// creating a [`CST`](Cst) or caching it.
+    pub fn parse_block(&self, code: &str) -> Parsed<Block> {
+        let mut lexer = Lexer::new(code);
+        let mut cst = Cst::parse(next_token(&mut lexer), &mut lexer, "");
+        if check_features(&cst.block, self.options.features, &mut cst.errors) {
+            cst.status = AstStatus::HasErrors;
+        }
+        if self.options.position_encoding != cst.position_encoding {
+            cst.set_position_encoding(&LineIndex::new(code), self.options.position_encoding);
+        }
+
+        Parsed {
+            node: Some(cst.block),
+            errors: cst.errors,
+        }
+    }
+
     /// Get a specific [`CST`](Cst) from the cache, this function assumes the
     /// cst does exist. If it may or may not exist, use
     /// [`maybe_get_ast`](Self::maybe_get_ast).
@@ -105,5 +403,98 @@ impl Parser {
     #[inline]
     pub fn clear_cache(&mut self) {
         self.cache.clear();
+        self.sources.clear();
+        self.input_uri = None;
+    }
+
+    /// Apply the passed changes to the cached [`CST`](Cst) of `uri` and return
+    /// the updated one. The changes are applied in order, the same way the
+    /// `textDocument/didChange` notification specifies.
+    ///
+    /// Only the statements affected by the changes, in the innermost block
+    /// containing them, are reparsed. Unchanged statements before them are
+    /// reused and all tokens after them are moved to their new positions, the
+    /// result is the same as parsing the whole file again. A full reparse is
+    /// done instead if the [`CST`](Cst) isn't cached, has errors, or if the
+    /// reparsed statements don't line up with the reused ones.
+    ///
+    /// # Note
+    ///
+    /// Moved statements are only edited in place if no other [`Pointer`]s to
+    /// them exist, holding onto the old [`CST`](Cst) makes them get cloned.
+    #[cfg(feature = "cache")]
+    pub fn apply_changes(
+        &mut self,
+        uri: &str,
+        changes: &[TextDocumentContentChangeEvent],
+    ) -> Pointer<Cst> {
+        let old_text = match self.sources.remove(uri) {
+            Some(text) => text,
+            None if self.input_uri.as_deref() == Some(uri) => {
+                self.input_uri = None;
+                self.input.clone()
+            }
+            None => self
+                .cache
+                .get(uri)
+                .map(|cst| cst.block.print())
+                .unwrap_or_default(),
+        };
+        let mut new_text = old_text.clone();
+
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let index = LineIndex::new(&new_text);
+                    let start = index.offset(range.start, self.options.position_encoding);
+                    let end = index
+                        .offset(range.end, self.options.position_encoding)
+                        .max(start);
+
+                    new_text.replace_range(start..end, &change.text);
+                }
+                None => new_text = change.text.clone(),
+            }
+        }
+
+        let cst = self.cache.remove(uri).and_then(|cst| {
+            Pointer::try_unwrap(cst)
+                .unwrap_or_else(|cst| (*cst).clone())
+                .reparse(&old_text, &new_text, self.options.features)
+        });
+
+        match cst {
+            Some(cst) => {
+                let cst = Pointer::new(cst);
+
+                self.sources.insert(uri.to_string(), new_text);
+                self.cache.insert(uri.to_string(), cst.clone());
+
+                cst
+            }
+            None => {
+                self.set_input(&new_text);
+                self.parse(uri)
+            }
+        }
+    }
+}
+
+/// Parse a `T` and the optional `;` after it.
+fn parse_with_semicolon<T: Parse>(
+    token: Token,
+    lexer: &mut Lexer,
+    errors: &mut Vec<Diagnostic>,
+) -> Option<(T, Option<Token>)> {
+    let node = T::parse(token, lexer, errors)?;
+
+    let state = lexer.save_state();
+    let semicolon = next_token(lexer);
+    if semicolon == TokenType::Symbol(Symbol::Semicolon) {
+        Some((node, Some(semicolon)))
+    } else {
+        lexer.set_state(state);
+
+        Some((node, None))
     }
 }
@@ -0,0 +1,257 @@
+//! # Folding and selection ranges
+//!
+//! Code structure used by editors, see [`Cst::folding_ranges`] and
+//! [`Cst::selection_ranges`].
+//!
+//! ```rust
+//! # use luau_parser::prelude::{FoldingRangeKind, Parser, ParserOptions, Position};
+//! let code = "--#region Helpers\nlocal function f()\n    return 1\nend\n--#endregion";
+//! let cst = Parser::new(code, ParserOptions::default()).parse("");
+//!
+//! let ranges = cst.folding_ranges();
+//! assert_eq!((ranges[0].start_line, ranges[0].end_line), (0, 4));
+//! assert_eq!(ranges[0].kind, Some(FoldingRangeKind::Region));
+//! assert_eq!((ranges[1].start_line, ranges[1].end_line), (1, 2));
+//!
+//! // `1`, `return 1`, the function's body, the function, ...
+//! let selection = &cst.selection_ranges(&[Position::new(2, 11)])[0];
+//! assert_eq!(selection.range.start, Position::new(2, 11));
+//! assert_eq!(selection.parent.as_ref().unwrap().range.start, Position::new(2, 4));
+//! ```
+
+use lsp_types::{FoldingRange, FoldingRangeKind, Position, Range, SelectionRange};
+use luau_lexer::prelude::{Comment, Token, Trivia};
+
+use crate::types::{
+    Closure, Cst, GenericFor, GetRange, GlobalFunction, IfStatement, LocalFunction, Node,
+    NumericalFor, Print, RepeatBlock, Statement, Table, TypeFunction, Visit, Visitor, WhileLoop,
+};
+
+/// A [`Visitor`] collecting the [`FoldingRange`]s of a [`Cst`].
+#[derive(Debug, Default)]
+struct FoldingCollector {
+    /// The ranges found so far.
+    ranges: Vec<FoldingRange>,
+
+    /// The lines of the `--#region` comments that weren't closed yet.
+    regions: Vec<u32>,
+}
+
+impl FoldingCollector {
+    /// Add a folding range from `start_line` to `end_line`, if it spans more
+    /// than one line.
+    fn add(&mut self, start_line: u32, end_line: u32, kind: Option<FoldingRangeKind>) {
+        if end_line > start_line {
+            self.ranges.push(FoldingRange {
+                start_line,
+                end_line,
+                kind,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Add a folding range for a block starting on the passed line and closed
+    /// by `closing`, which stays visible.
+    #[inline]
+    fn add_block(&mut self, start_line: u32, closing: &Token) {
+        self.add(start_line, closing.start.line.saturating_sub(1), None);
+    }
+
+    /// Add a folding range for the node, which is closed by `closing`.
+    #[inline]
+    fn add_node(&mut self, node: &impl GetRange, closing: &Token) {
+        if let Ok(range) = node.get_range() {
+            self.add_block(range.start.line, closing);
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for FoldingCollector {
+    fn visit_local_function(&mut self, node: &'a LocalFunction) {
+        self.add_node(node, &node.end_keyword);
+    }
+
+    fn visit_global_function(&mut self, node: &'a GlobalFunction) {
+        self.add_node(node, &node.end_keyword);
+    }
+
+    fn visit_type_function(&mut self, node: &'a TypeFunction) {
+        self.add_node(node, &node.end_keyword);
+    }
+
+    fn visit_closure(&mut self, node: &'a Closure) {
+        self.add_node(node, &node.end_keyword);
+    }
+
+    fn visit_statement(&mut self, node: &'a Statement) {
+        // Loops have `do` blocks too, they're folded with the loops.
+        if let Statement::DoBlock(do_block) = node {
+            self.add_block(do_block.do_keyword.start.line, &do_block.end_keyword);
+        }
+    }
+
+    fn visit_generic_for(&mut self, node: &'a GenericFor) {
+        self.add_block(node.for_keyword.start.line, &node.do_block.end_keyword);
+    }
+
+    fn visit_numerical_for(&mut self, node: &'a NumericalFor) {
+        self.add_block(node.for_keyword.start.line, &node.do_block.end_keyword);
+    }
+
+    fn visit_while_loop(&mut self, node: &'a WhileLoop) {
+        self.add_block(node.while_keyword.start.line, &node.do_block.end_keyword);
+    }
+
+    fn visit_repeat_block(&mut self, node: &'a RepeatBlock) {
+        self.add_block(node.repeat_keyword.start.line, &node.until_keyword);
+    }
+
+    fn visit_if_statement(&mut self, node: &'a IfStatement) {
+        let mut start = &node.if_keyword;
+        for else_if in &node.else_if_statements {
+            self.add_block(start.start.line, &else_if.elseif_keyword);
+            start = &else_if.elseif_keyword;
+        }
+        if let Some(else_statement) = &node.else_statement {
+            self.add_block(start.start.line, &else_statement.else_keyword);
+            start = &else_statement.else_keyword;
+        }
+
+        self.add_block(start.start.line, &node.end_keyword);
+    }
+
+    fn visit_table(&mut self, node: &'a Table) {
+        self.add_block(node.0.opening_bracket.start.line, &node.0.closing_bracket);
+    }
+
+    fn visit_token(&mut self, node: &'a Token) {
+        // Trivia is right before the token, so its lines are counted from the
+        // token's.
+        let texts = node
+            .leading_trivia
+            .iter()
+            .map(|trivia| trivia.print())
+            .collect::<Vec<_>>();
+        let newlines = |text: &str| text.matches('\n').count() as u32;
+        let mut line = node
+            .start
+            .line
+            .saturating_sub(texts.iter().map(|text| newlines(text)).sum());
+
+        for (trivia, text) in node.leading_trivia.iter().zip(&texts) {
+            let start_line = line;
+            line += newlines(text);
+
+            let Trivia::Comment(comment) = trivia else {
+                continue;
+            };
+            match comment {
+                Comment::MultiLine(_) => {
+                    self.add(start_line, line, Some(FoldingRangeKind::Comment));
+                }
+                Comment::SingleLine(text) => {
+                    let text = text.trim_start_matches('-').trim_start();
+
+                    if text.starts_with("#region") {
+                        self.regions.push(start_line);
+                    } else if text.starts_with("#endregion")
+                        && let Some(start) = self.regions.pop()
+                    {
+                        self.add(start, start_line, Some(FoldingRangeKind::Region));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A [`Visitor`] collecting the ranges of all nodes containing a position.
+#[derive(Debug)]
+struct SelectionCollector {
+    /// The position to find the nodes of.
+    position: Position,
+
+    /// The ranges of the nodes containing the position, from the outermost
+    /// one to the innermost one.
+    ranges: Vec<Range>,
+}
+
+/// Whether or not `outer` contains `inner`.
+#[inline]
+fn contains(outer: Range, inner: Range) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+impl<'a> Visitor<'a> for SelectionCollector {
+    fn visit_node(&mut self, node: Node<'a>) {
+        let Ok(range) = node.get_range() else {
+            return;
+        };
+        if !contains(range, Range::new(self.position, self.position)) {
+            return;
+        }
+
+        // Siblings may touch at the position, the later one is kept.
+        while self
+            .ranges
+            .last()
+            .is_some_and(|last| !contains(*last, range))
+        {
+            self.ranges.pop();
+        }
+        if self.ranges.last() != Some(&range) {
+            self.ranges.push(range);
+        }
+    }
+}
+
+impl Cst {
+    /// Get the ranges editors can fold in this [`Cst`], sorted by their start
+    /// line:
+    ///
+    /// * All nodes with a [`Block`](crate::types::Block), each branch of `if`
+    ///   statements on its own. The line of the keyword closing the block,
+    ///   like `end`, isn't folded.
+    /// * Tables spanning multiple lines, without their closing `}`.
+    /// * Multi-line comments, like `--[[ ]]`.
+    /// * Regions between `--#region` and `--#endregion` comments.
+    pub fn folding_ranges(&self) -> Vec<FoldingRange> {
+        let mut collector = FoldingCollector::default();
+        self.visit(&mut collector);
+
+        let mut ranges = collector.ranges;
+        ranges.sort_by_key(|range| (range.start_line, range.end_line));
+
+        ranges
+    }
+
+    /// Get the chain of ranges of the nodes containing each of the passed
+    /// positions, from the innermost one, for expand-selection. Positions not
+    /// in any node get an empty range.
+    pub fn selection_ranges(&self, positions: &[Position]) -> Vec<SelectionRange> {
+        positions
+            .iter()
+            .map(|&position| {
+                let mut collector = SelectionCollector {
+                    position,
+                    ranges: Vec::new(),
+                };
+                self.visit(&mut collector);
+
+                let mut ranges = collector.ranges.into_iter();
+                let outermost = SelectionRange {
+                    range: ranges
+                        .next()
+                        .unwrap_or_else(|| Range::new(position, position)),
+                    parent: None,
+                };
+
+                ranges.fold(outermost, |parent, range| SelectionRange {
+                    range,
+                    parent: Some(Box::new(parent)),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,262 @@
+//! # Rename
+//!
+//! Renames a local variable, a type, or a generic parameter with all its
+//! references, see [`rename`]. Values and types live in separate namespaces,
+//! so renaming one never touches the other.
+//!
+//! ```rust
+//! # use luau_parser::{prelude::{Parser, ParserOptions, Position}, rename::rename};
+//! let code = "type Id<T> = T\nlocal id: Id<number> = 1\nprint(id)";
+//! let cst = Parser::new(code, ParserOptions::default()).parse("file:///main.luau");
+//!
+//! let edit = rename(&cst, Position::new(1, 6), "value").unwrap();
+//! let edits = edit.changes.unwrap().into_values().next().unwrap();
+//! assert_eq!(edits.len(), 2);
+//! ```
+
+use lsp_types::{Position, TextEdit, Uri, WorkspaceEdit};
+use luau_lexer::prelude::{Lexer, Token, TokenType};
+use std::{cmp::Reverse, collections::HashMap, error::Error, fmt, iter, str::FromStr};
+
+use crate::{
+    semantic::{BUILT_IN_TYPES, BindingId, BindingKind, Resolution, SemanticModel, TypeBindingId},
+    types::{Cst, GetRange},
+};
+
+/// Errors that may occur in [`rename`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RenameError {
+    /// There's no local variable, type, or generic parameter at the position.
+    /// Globals, built-in types, and the implicit `self` can't be renamed.
+    NothingToRename,
+
+    /// The new name isn't a valid name.
+    InvalidName,
+
+    /// The new name is a keyword.
+    Keyword,
+
+    /// The new name is already used by a variable or type that's visible
+    /// where the renamed one is, or the other way around. Renaming would
+    /// shadow one of them or change what a reference refers to.
+    Shadowing,
+
+    /// [`Cst::uri`] isn't a valid [`Uri`].
+    InvalidUri,
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NothingToRename => f.write_str("there's nothing to rename here"),
+            Self::InvalidName => f.write_str("the new name isn't a valid name"),
+            Self::Keyword => f.write_str("the new name is a keyword"),
+            Self::Shadowing => f.write_str("the new name is already used in this scope"),
+            Self::InvalidUri => f.write_str("the uri of the cst is invalid"),
+        }
+    }
+}
+
+impl Error for RenameError {}
+
+/// Check that `name` can be used as a new name.
+fn check_name(name: &str) -> Result<(), RenameError> {
+    match Lexer::new(name).next_token().token_type {
+        TokenType::Identifier(identifier) if identifier == name => Ok(()),
+        TokenType::PartialKeyword(keyword)
+            if TokenType::PartialKeyword(keyword)
+                .try_as_string()
+                .as_deref()
+                == Some(name) =>
+        {
+            Ok(())
+        }
+        TokenType::Keyword(_) | TokenType::Literal(_) | TokenType::Operator(_)
+            if name
+                .chars()
+                .all(|character| character.is_ascii_alphabetic()) =>
+        {
+            Err(RenameError::Keyword)
+        }
+        _ => Err(RenameError::InvalidName),
+    }
+}
+
+/// Which of the two value bindings `name` would refer to at the passed
+/// position if both were called `name`, if any of them is visible there.
+fn innermost(
+    model: &SemanticModel,
+    first: BindingId,
+    second: BindingId,
+    position: Position,
+) -> Option<BindingId> {
+    let mut scope = Some(model.scope_at(position));
+
+    while let Some(id) = scope {
+        let scope_data = model.scope(id);
+        let binding = scope_data.bindings.iter().rev().find(|&&binding| {
+            (binding == first || binding == second)
+                && model.binding(binding).visible_from <= position
+        });
+
+        if binding.is_some() {
+            return binding.copied();
+        }
+        scope = scope_data.parent;
+    }
+
+    None
+}
+
+/// The tokens to rename for the value binding, if renaming it to `new_name`
+/// doesn't change what any name refers to.
+fn value_tokens<'a>(
+    model: &SemanticModel<'a>,
+    binding: BindingId,
+    new_name: &str,
+) -> Result<Vec<&'a Token>, RenameError> {
+    let data = model.binding(binding);
+    if data.kind == BindingKind::ImplicitSelf
+        || !matches!(data.token.token_type, TokenType::Identifier(_))
+    {
+        return Err(RenameError::NothingToRename);
+    }
+
+    // References to the renamed binding must not end up referring to another
+    // one, and references to the new name must not end up referring to it.
+    let shadowed = model.references_of(binding).any(|reference| {
+        let position = reference.token.start;
+
+        match model.lookup(new_name, position) {
+            Resolution::Binding(other) => {
+                other != binding && innermost(model, binding, other, position) == Some(other)
+            }
+            Resolution::Global => false,
+        }
+    });
+    let shadows = model
+        .references
+        .iter()
+        .filter(|reference| reference.token.token_type.try_as_string().as_deref() == Some(new_name))
+        .any(|reference| {
+            let position = reference.token.start;
+
+            match reference.resolution {
+                Resolution::Binding(other) => {
+                    other != binding && innermost(model, binding, other, position) == Some(binding)
+                }
+                Resolution::Global => innermost(model, binding, binding, position).is_some(),
+            }
+        });
+    // Even when no reference changes, a binding called `new_name` can't be
+    // visible anywhere the renamed one is, or the other way around.
+    let conflicts = model
+        .bindings
+        .iter()
+        .filter(|other| other.id != binding && other.name == new_name)
+        .any(|other| {
+            innermost(model, binding, binding, other.token.start).is_some()
+                || iter::once(data.token)
+                    .chain(
+                        model
+                            .references_of(binding)
+                            .map(|reference| reference.token),
+                    )
+                    .any(|token| {
+                        model.lookup(new_name, token.start) == Resolution::Binding(other.id)
+                    })
+        });
+    if shadowed || shadows || conflicts {
+        return Err(RenameError::Shadowing);
+    }
+
+    Ok(iter::once(data.token)
+        .chain(
+            model
+                .references_of(binding)
+                .map(|reference| reference.token),
+        )
+        .collect())
+}
+
+/// Whether or not the first type binding is nested in the second one, so it
+/// takes precedence where both are visible.
+fn is_inner_type(model: &SemanticModel, first: TypeBindingId, second: TypeBindingId) -> bool {
+    // Same as `SemanticModel::lookup_type`.
+    let key = |id: TypeBindingId| {
+        let range = model.type_binding(id).range;
+        (range.start, Reverse(range.end), id)
+    };
+
+    key(first) > key(second)
+}
+
+/// The tokens to rename for the type binding, if renaming it to `new_name`
+/// doesn't change what any type refers to.
+fn type_tokens<'a>(
+    model: &SemanticModel<'a>,
+    binding: TypeBindingId,
+    new_name: &str,
+) -> Result<Vec<&'a Token>, RenameError> {
+    let data = model.type_binding(binding);
+
+    let shadowed = model.type_references_of(binding).any(|reference| {
+        model
+            .lookup_type(new_name, reference.token.start)
+            .is_some_and(|other| other != binding && is_inner_type(model, other, binding))
+    });
+    let shadows = model
+        .type_references
+        .iter()
+        .filter(|reference| {
+            reference.token.token_type.try_as_string().as_deref() == Some(new_name)
+                && data.range.start <= reference.token.start
+                && reference.token.start <= data.range.end
+        })
+        .any(|reference| match reference.resolution {
+            Some(other) => other != binding && is_inner_type(model, binding, other),
+            None => true,
+        });
+    if shadowed || shadows || BUILT_IN_TYPES.contains(&new_name) {
+        return Err(RenameError::Shadowing);
+    }
+
+    Ok(iter::once(data.token)
+        .chain(
+            model
+                .type_references_of(binding)
+                .map(|reference| reference.token),
+        )
+        .collect())
+}
+
+/// Rename the local variable, type, or generic parameter at the passed
+/// position to `new_name`, with all its references. Nothing is renamed if it
+/// would make any name refer to something else, or if it would shadow
+/// another variable or type.
+pub fn rename(cst: &Cst, position: Position, new_name: &str) -> Result<WorkspaceEdit, RenameError> {
+    check_name(new_name)?;
+
+    let model = SemanticModel::new(cst);
+    let tokens = if let Some(binding) = model.binding_at(position) {
+        value_tokens(&model, binding, new_name)?
+    } else if model
+        .reference_at(position)
+        .is_some_and(|reference| reference.resolution == Resolution::Global)
+    {
+        return Err(RenameError::NothingToRename);
+    } else if let Some(binding) = model.type_binding_at(position) {
+        type_tokens(&model, binding, new_name)?
+    } else {
+        return Err(RenameError::NothingToRename);
+    };
+
+    let uri = Uri::from_str(&cst.uri).map_err(|_| RenameError::InvalidUri)?;
+    let edits = tokens
+        .into_iter()
+        .filter_map(|token| token.get_range().ok())
+        .map(|range| TextEdit::new(range, new_name.to_string()))
+        .collect();
+
+    Ok(WorkspaceEdit::new(HashMap::from([(uri, edits)])))
+}
@@ -0,0 +1,484 @@
+//! # Rewriter
+//!
+//! Records changes to the nodes of a [`Cst`] and turns them into
+//! [`TextEdit`]s against the code it was parsed from, see [`Rewriter`]. Nodes
+//! are never changed in place, so nothing needs to be cloned out of its
+//! [`Pointer`](crate::types::Pointer)s.
+//!
+//! ```rust
+//! # use luau_parser::{prelude::{Parser, ParserOptions, Statement}, rewrite::Rewriter};
+//! let code = "local function foo()\nend\n\n-- Unused.\nlocal x = 1 -- One.\nfoo()\n";
+//! let cst = Parser::new(code, ParserOptions::default()).parse("");
+//! let mut rewriter = Rewriter::new(&cst);
+//!
+//! let (statement, _) = &cst.block.statements[0];
+//! let Statement::LocalFunction(function) = &**statement else { unreachable!() };
+//! rewriter.replace_text(&function.function_name, "bar").unwrap();
+//!
+//! let (statement, _) = &cst.block.statements[1];
+//! rewriter.remove(&**statement).unwrap();
+//!
+//! assert_eq!(rewriter.apply().unwrap(), "local function bar()\nend\n\nfoo()\n");
+//! ```
+//!
+//! ## Note
+//!
+//! The code is printed back from the [`Cst`]. Tokens dropped while recovering
+//! from syntax errors aren't in it, so edits may be off if the [`Cst`]
+//! [has errors](Cst::has_errors).
+
+use lsp_types::{Range, TextEdit};
+use luau_lexer::prelude::{Symbol, Token, TokenType, Trivia};
+use std::{error::Error, fmt, ops};
+
+use crate::{
+    format::comments::sorted_tokens,
+    types::{Cst, LineIndex, PositionEncoding, Print, Visit, Visitor},
+};
+
+/// Errors that may occur while using a [`Rewriter`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RewriteError {
+    /// The node isn't in the rewritten [`Cst`], or it has no tokens.
+    UnknownNode,
+
+    /// Two of the recorded changes touch the same code.
+    Overlapping,
+}
+
+impl fmt::Display for RewriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownNode => f.write_str("the node isn't in the rewritten cst"),
+            Self::Overlapping => f.write_str("two changes touch the same code"),
+        }
+    }
+}
+
+impl Error for RewriteError {}
+
+/// A [`Visitor`] writing the code of the visited nodes, without the leading
+/// trivia of the first token.
+#[derive(Default)]
+struct CodeWriter {
+    /// The code written so far.
+    code: String,
+
+    /// Whether or not a token was written.
+    started: bool,
+}
+
+impl Visitor<'_> for CodeWriter {
+    fn visit_token(&mut self, token: &Token) {
+        if self.started {
+            for trivia in &token.leading_trivia {
+                self.code.push_str(&trivia.print());
+            }
+        }
+
+        self.code
+            .push_str(&token.token_type.try_as_string().unwrap_or_default());
+        self.started = true;
+    }
+}
+
+/// A recorded change, replacing a byte range of the code.
+#[derive(Clone, Debug)]
+struct Edit {
+    /// The replaced code.
+    range: ops::Range<usize>,
+
+    /// The new code.
+    text: String,
+}
+
+/// Where a node is in the code.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    /// The index of the first token of the node.
+    first: usize,
+
+    /// The index of the last token of the node.
+    last: usize,
+}
+
+/// Records replacements, insertions, and deletions of the nodes of a [`Cst`],
+/// and turns them into minimal [`TextEdit`]s with [`Rewriter::edits`].
+///
+/// Nodes on their own lines are inserted and removed as whole lines, with the
+/// comments on the lines right above them and the ones after them on the same
+/// line. Other nodes only take the spaces around them with them.
+#[derive(Clone, Debug)]
+pub struct Rewriter<'a> {
+    /// The code of the [`Cst`].
+    code: String,
+
+    /// The index of [`code`](Self::code).
+    index: LineIndex,
+
+    /// How the columns of positions are counted.
+    encoding: PositionEncoding,
+
+    /// All tokens of the [`Cst`], in order.
+    tokens: Vec<&'a Token>,
+
+    /// The changes recorded so far.
+    edits: Vec<Edit>,
+}
+
+impl<'a> Rewriter<'a> {
+    /// Create a new [`Rewriter`] for the passed [`Cst`].
+    pub fn new(cst: &'a Cst) -> Self {
+        let code = cst.block.print();
+
+        Self {
+            index: LineIndex::new(&code),
+            code,
+            encoding: cst.position_encoding,
+            tokens: sorted_tokens(&cst.block),
+            edits: Vec::new(),
+        }
+    }
+
+    /// Find where the passed node is.
+    fn span(&self, node: &(impl Visit + ?Sized)) -> Result<Span, RewriteError> {
+        let tokens = sorted_tokens(node);
+        let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+            return Err(RewriteError::UnknownNode);
+        };
+
+        let find = |token: &Token| {
+            self.tokens
+                .binary_search_by_key(&token.start, |token| token.start)
+                .ok()
+                .filter(|index| self.tokens[*index] == token)
+                .ok_or(RewriteError::UnknownNode)
+        };
+
+        Ok(Span {
+            first: find(first)?,
+            last: find(last)?,
+        })
+    }
+
+    /// The byte offset of the start of the passed token.
+    #[inline]
+    fn start(&self, index: usize) -> usize {
+        self.index.offset(self.tokens[index].start, self.encoding)
+    }
+
+    /// The byte offset of the end of the passed token.
+    #[inline]
+    fn end(&self, index: usize) -> usize {
+        self.index.offset(self.tokens[index].end, self.encoding)
+    }
+
+    /// Whether or not the token at the passed index is `symbol`.
+    #[inline]
+    fn is_symbol(&self, index: usize, symbol: Symbol) -> bool {
+        self.tokens
+            .get(index)
+            .is_some_and(|token| token.token_type == TokenType::Symbol(symbol))
+    }
+
+    /// The byte offset of the start of the line `offset` is on.
+    #[inline]
+    fn line_start(&self, offset: usize) -> usize {
+        self.code[..offset].rfind('\n').map_or(0, |index| index + 1)
+    }
+
+    /// The indentation of the line `offset` is on.
+    fn indentation(&self, offset: usize) -> &str {
+        let start = self.line_start(offset);
+        let line = &self.code[start..];
+
+        &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+    }
+
+    /// Where the code of the node starts, with the comments on the lines right
+    /// above it, if it's the first thing on its line.
+    fn own_line_start(&self, span: Span) -> Option<usize> {
+        let start = self.start(span.first);
+        let mut line_start = self.line_start(start);
+        if !self.code[line_start..start].trim().is_empty() {
+            return None;
+        }
+
+        let token = self.tokens[span.first];
+        let mut offset = start
+            - token
+                .leading_trivia
+                .iter()
+                .map(|trivia| trivia.print().len())
+                .sum::<usize>();
+        let mut comments = Vec::new();
+        for trivia in &token.leading_trivia {
+            let length = trivia.print().len();
+            if let Trivia::Comment(_) = trivia {
+                comments.push(offset..offset + length);
+            }
+            offset += length;
+        }
+
+        for comment in comments.into_iter().rev() {
+            let between = &self.code[comment.end..line_start];
+            let comment_line_start = self.line_start(comment.start);
+
+            if !between.trim().is_empty()
+                || between.matches('\n').count() != 1
+                || !self.code[comment_line_start..comment.start]
+                    .trim()
+                    .is_empty()
+            {
+                break;
+            }
+            line_start = comment_line_start;
+        }
+
+        Some(line_start)
+    }
+
+    /// Where the line the node ends on ends, before the `\n`, if there's only
+    /// trivia after the node on it.
+    fn own_line_end(&self, span: Span) -> Option<usize> {
+        let Some(next) = self.tokens.get(span.last + 1) else {
+            return Some(self.code.len());
+        };
+
+        let mut offset = self.end(span.last);
+        for trivia in &next.leading_trivia {
+            let text = trivia.print();
+            if let Trivia::Spaces(spaces) = trivia
+                && let Some(index) = spaces.find('\n')
+            {
+                return Some(offset + index);
+            }
+            offset += text.len();
+        }
+
+        (next.token_type == TokenType::EndOfFile).then_some(self.code.len())
+    }
+
+    /// Indent all lines of `text` but the first one like the line `offset` is
+    /// on.
+    fn indent(&self, text: &str, offset: usize) -> String {
+        text.replace('\n', &format!("\n{}", self.indentation(offset)))
+    }
+
+    /// Record replacing the passed byte range with `text`.
+    #[inline]
+    fn record(&mut self, range: ops::Range<usize>, text: String) {
+        self.edits.push(Edit { range, text });
+    }
+
+    /// Replace `node` with `new`. The trivia around `node` is kept, and lines
+    /// of `new` after the first one are indented like `node`.
+    pub fn replace(
+        &mut self,
+        node: &(impl Visit + ?Sized),
+        new: &(impl Visit + ?Sized),
+    ) -> Result<(), RewriteError> {
+        let mut writer = CodeWriter::default();
+        new.visit(&mut writer);
+
+        self.replace_text(node, &writer.code)
+    }
+
+    /// Replace `node` with the passed code. The trivia around `node` is kept,
+    /// and lines of `text` after the first one are indented like `node`.
+    pub fn replace_text(
+        &mut self,
+        node: &(impl Visit + ?Sized),
+        text: &str,
+    ) -> Result<(), RewriteError> {
+        let span = self.span(node)?;
+        let (start, end) = (self.start(span.first), self.end(span.last));
+
+        let text = self.indent(text, start);
+        self.record(start..end, text);
+
+        Ok(())
+    }
+
+    /// Insert `new` before `node`. It's put on its own line, indented like
+    /// `node` and above its comments, if `node` is the first thing on its line,
+    /// and separated from it by a space otherwise.
+    pub fn insert_before(
+        &mut self,
+        node: &(impl Visit + ?Sized),
+        new: &(impl Visit + ?Sized),
+    ) -> Result<(), RewriteError> {
+        let span = self.span(node)?;
+        let start = self.start(span.first);
+
+        let mut writer = CodeWriter::default();
+        new.visit(&mut writer);
+        let text = self.indent(&writer.code, start);
+
+        match self.own_line_start(span) {
+            Some(line_start) => {
+                let text = format!("{}{text}\n", self.indentation(start));
+                self.record(line_start..line_start, text);
+            }
+            None => self.record(start..start, format!("{text} ")),
+        }
+
+        Ok(())
+    }
+
+    /// Insert `new` after `node` and the `;` after it, if any. It's put on its
+    /// own line, indented like `node` and below the comments after it, if
+    /// `node` is the last thing on its line, and separated from it by a space
+    /// otherwise.
+    pub fn insert_after(
+        &mut self,
+        node: &(impl Visit + ?Sized),
+        new: &(impl Visit + ?Sized),
+    ) -> Result<(), RewriteError> {
+        let mut span = self.span(node)?;
+        if self.is_symbol(span.last + 1, Symbol::Semicolon) {
+            span.last += 1;
+        }
+        let start = self.start(span.first);
+
+        let mut writer = CodeWriter::default();
+        new.visit(&mut writer);
+        let text = self.indent(&writer.code, start);
+
+        match self.own_line_end(span) {
+            Some(line_end) => {
+                let text = format!("\n{}{text}", self.indentation(start));
+                self.record(line_end..line_end, text);
+            }
+            None => {
+                let end = self.end(span.last);
+                self.record(end..end, format!(" {text}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove `node`, with the `;` or `,` after it, or the `,` before it if it's
+    /// the last item of a list.
+    ///
+    /// If `node` is alone on its lines, they're removed with the comments on
+    /// the lines right above them, the ones after `node`, and the `\n` ending
+    /// them. Otherwise, the spaces after `node`, or before it if there's
+    /// something other than spaces after it, are removed with it.
+    pub fn remove(&mut self, node: &(impl Visit + ?Sized)) -> Result<(), RewriteError> {
+        let mut span = self.span(node)?;
+        if self.is_symbol(span.last + 1, Symbol::Semicolon)
+            || self.is_symbol(span.last + 1, Symbol::Comma)
+        {
+            span.last += 1;
+        } else if span.first > 0 && self.is_symbol(span.first - 1, Symbol::Comma) {
+            span.first -= 1;
+        }
+
+        if let (Some(start), Some(end)) = (self.own_line_start(span), self.own_line_end(span)) {
+            let range = if end < self.code.len() {
+                start..end + 1
+            } else {
+                // Remove the `\n` before the node instead of the missing one
+                // after it.
+                start.saturating_sub(1)..end
+            };
+            self.record(range, String::new());
+
+            return Ok(());
+        }
+
+        let (start, end) = (self.start(span.first), self.end(span.last));
+        let after = self
+            .tokens
+            .get(span.last + 1)
+            .map_or(self.code.len(), |_| self.start(span.last + 1));
+        let before = match span.first {
+            0 => 0,
+            first => self.end(first - 1),
+        };
+
+        let range =
+            if self.code[end..after].trim().is_empty() && !self.code[end..after].contains('\n') {
+                start..after
+            } else if self.code[before..start].trim().is_empty()
+                && !self.code[before..start].contains('\n')
+            {
+                before..end
+            } else {
+                start..end
+            };
+        self.record(range, String::new());
+
+        Ok(())
+    }
+
+    /// Get the recorded changes, with the common start and end of the replaced
+    /// and new code trimmed, and changes that do nothing dropped.
+    fn trimmed_edits(&self) -> Result<Vec<Edit>, RewriteError> {
+        let mut edits = self
+            .edits
+            .iter()
+            .filter_map(|edit| {
+                let old = &self.code[edit.range.clone()];
+                let prefix = old
+                    .char_indices()
+                    .zip(edit.text.chars())
+                    .find(|((_, old), new)| old != new)
+                    .map_or(old.len().min(edit.text.len()), |((index, _), _)| index);
+                let (old_rest, new_rest) = (&old[prefix..], &edit.text[prefix..]);
+                let suffix = old_rest
+                    .chars()
+                    .rev()
+                    .zip(new_rest.chars().rev())
+                    .take_while(|(old, new)| old == new)
+                    .map(|(old, _)| old.len_utf8())
+                    .sum::<usize>();
+
+                let range = edit.range.start + prefix..edit.range.end - suffix;
+                let text = &new_rest[..new_rest.len() - suffix];
+
+                (!range.is_empty() || !text.is_empty()).then(|| Edit {
+                    range,
+                    text: text.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+        if edits
+            .windows(2)
+            .any(|pair| pair[0].range.end > pair[1].range.start)
+        {
+            return Err(RewriteError::Overlapping);
+        }
+
+        Ok(edits)
+    }
+
+    /// Get the [`TextEdit`]s making the recorded changes, in order. Only the
+    /// parts of the code that actually change are edited.
+    pub fn edits(&self) -> Result<Vec<TextEdit>, RewriteError> {
+        Ok(self
+            .trimmed_edits()?
+            .into_iter()
+            .map(|edit| {
+                let start = self.index.position(edit.range.start, self.encoding);
+                let end = self.index.position(edit.range.end, self.encoding);
+
+                TextEdit::new(Range::new(start, end), edit.text)
+            })
+            .collect())
+    }
+
+    /// Make the recorded changes, returning the new code.
+    pub fn apply(&self) -> Result<String, RewriteError> {
+        let mut code = self.code.clone();
+        for edit in self.trimmed_edits()?.into_iter().rev() {
+            code.replace_range(edit.range, &edit.text);
+        }
+
+        Ok(code)
+    }
+}
@@ -0,0 +1,872 @@
+//! # Semantic model
+//!
+//! Scope and binding resolution on top of a [`Cst`]. A [`SemanticModel`]
+//! contains a tree of [`scopes`](Scope), every local [`binding`](Binding)
+//! declared in them, and every [`reference`](Reference) to a name in the code,
+//! each resolved to the binding it refers to or marked as a global.
+//!
+//! ```rust
+//! # use luau_parser::{prelude::{Parser, ParserOptions}, semantic::{Resolution, SemanticModel}};
+//! let cst = Parser::new("local x = 1\nprint(x)", ParserOptions::default()).parse("");
+//! let model = SemanticModel::new(&cst);
+//!
+//! // `print` is a global while `x` refers to the local declared above it.
+//! assert_eq!(model.references[0].resolution, Resolution::Global);
+//! assert_eq!(model.references[1].resolution, Resolution::Binding(model.bindings[0].id));
+//! ```
+//!
+//! Types live in a namespace of their own, see [`TypeBinding`] and
+//! [`TypeReference`]. Type definitions and type functions are visible in the
+//! whole block declaring them, even before their declaration, and generics in
+//! the whole node declaring them.
+
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::Token;
+use smol_str::SmolStr;
+use std::{cmp::Reverse, ptr};
+
+use crate::types::{
+    Block, Closure, CompoundSetExpression, Cst, GenericDeclaration, GenericFor,
+    GenericParameterInfo, GenericParameterInfoDefault, GetRange, GlobalFunction,
+    GlobalFunctionName, LocalAssignment, LocalFunction, NumericalFor, Parameter, Pointer,
+    RepeatBlock, SetExpression, TableAccessPrefix, TypeDefinition, TypeFunction, TypeValue, Var,
+    Visit, Visitor,
+};
+
+/// The names of built-in types, which are never [`TypeBinding`]s and can't be
+/// redefined.
+pub const BUILT_IN_TYPES: &[&str] = &[
+    "any", "boolean", "buffer", "never", "nil", "number", "string", "thread", "unknown",
+    "userdata", "vector",
+];
+
+/// The index of a [`Scope`] in [`SemanticModel::scopes`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScopeId(pub usize);
+
+/// The index of a [`Binding`] in [`SemanticModel::bindings`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BindingId(pub usize);
+
+/// The index of a [`Reference`] in [`SemanticModel::references`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReferenceId(pub usize);
+
+/// The index of a [`TypeBinding`] in [`SemanticModel::type_bindings`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TypeBindingId(pub usize);
+
+/// The index of a [`TypeReference`] in [`SemanticModel::type_references`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TypeReferenceId(pub usize);
+
+/// The kind of a [`Scope`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScopeKind {
+    /// The top-level scope of the file.
+    File,
+
+    /// The scope of a function, which holds its parameters. The body of the
+    /// function has a [`block scope`](ScopeKind::Block) of its own.
+    Function,
+
+    /// The scope of a [`Block`], like the body of a `do` block or an `if`
+    /// statement.
+    Block,
+}
+
+/// A scope in the code.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Scope {
+    /// The id of this scope.
+    pub id: ScopeId,
+
+    /// The kind of this scope.
+    pub kind: ScopeKind,
+
+    /// The range this scope covers.
+    pub range: Range,
+
+    /// The scope containing this one, [`None`] only for the
+    /// [`file scope`](ScopeKind::File).
+    pub parent: Option<ScopeId>,
+
+    /// The scopes directly inside this one, in source order.
+    pub children: Vec<ScopeId>,
+
+    /// The bindings declared in this scope, in declaration order.
+    pub bindings: Vec<BindingId>,
+}
+
+/// The kind of a [`Binding`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BindingKind {
+    /// A name in a [`LocalAssignment`].
+    Local,
+
+    /// The name of a [`LocalFunction`].
+    LocalFunction,
+
+    /// A [`Parameter`] of a function.
+    Parameter,
+
+    /// A variable declared by a [`GenericFor`] or a [`NumericalFor`].
+    ForVariable,
+
+    /// The implicit `self` parameter of methods (`function foo:bar() end`),
+    /// its [`token`](Binding::token) is the name of the method.
+    ImplicitSelf,
+}
+
+/// A local variable.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Binding<'a> {
+    /// The id of this binding.
+    pub id: BindingId,
+
+    /// The name of this binding.
+    pub name: SmolStr,
+
+    /// The token declaring this binding.
+    pub token: &'a Token,
+
+    /// The kind of this binding.
+    pub kind: BindingKind,
+
+    /// The scope this binding is declared in.
+    pub scope: ScopeId,
+
+    /// The position from which this binding is visible, this is the end of
+    /// the statement for [`locals`](BindingKind::Local), as `local x = x`
+    /// refers to the outer `x`, and the start of [`Binding::token`] otherwise.
+    pub visible_from: Position,
+
+    /// All references to this binding, in source order.
+    pub references: Vec<ReferenceId>,
+}
+
+/// What a [`Reference`] refers to.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Resolution {
+    /// A local [`Binding`].
+    Binding(BindingId),
+
+    /// A global variable.
+    Global,
+}
+
+/// How a [`Reference`] uses the variable.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReferenceKind {
+    /// The value is read, ex. `print(x)`.
+    Read,
+
+    /// The value is overwritten, ex. `x = 1`.
+    Write,
+
+    /// The value is read then overwritten, ex. `x += 1`.
+    ReadWrite,
+}
+
+/// A use of a name in an expression.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Reference<'a> {
+    /// The id of this reference.
+    pub id: ReferenceId,
+
+    /// The token of the name.
+    pub token: &'a Token,
+
+    /// The scope this reference is in.
+    pub scope: ScopeId,
+
+    /// How the variable is used.
+    pub kind: ReferenceKind,
+
+    /// What this reference refers to.
+    pub resolution: Resolution,
+}
+
+/// The kind of a [`TypeBinding`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TypeBindingKind {
+    /// The name of a [`TypeDefinition`].
+    TypeDefinition,
+
+    /// The name of a [`TypeFunction`].
+    TypeFunction,
+
+    /// A generic parameter, like `T` in `<T>`.
+    Generic,
+
+    /// A generic pack, like `T` in `<T...>`.
+    GenericPack,
+}
+
+/// A type name.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TypeBinding<'a> {
+    /// The id of this binding.
+    pub id: TypeBindingId,
+
+    /// The name of this binding.
+    pub name: SmolStr,
+
+    /// The token declaring this binding.
+    pub token: &'a Token,
+
+    /// The kind of this binding.
+    pub kind: TypeBindingKind,
+
+    /// Where this binding is visible. It's the whole block declaring it for
+    /// [`type definitions`](TypeBindingKind::TypeDefinition) and
+    /// [`type functions`](TypeBindingKind::TypeFunction), and the node
+    /// declaring them for generics.
+    pub range: Range,
+
+    /// All references to this binding, in source order.
+    pub references: Vec<TypeReferenceId>,
+}
+
+/// A use of a type name.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TypeReference<'a> {
+    /// The id of this reference.
+    pub id: TypeReferenceId,
+
+    /// The token of the name.
+    pub token: &'a Token,
+
+    /// The binding this reference refers to, [`None`] for built-in types like
+    /// `number` and types that don't exist.
+    pub resolution: Option<TypeBindingId>,
+}
+
+/// The scopes, bindings and references of a [`Cst`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SemanticModel<'a> {
+    /// All scopes, the first one is always the [`file scope`](ScopeKind::File).
+    pub scopes: Vec<Scope>,
+
+    /// All bindings, in declaration order.
+    pub bindings: Vec<Binding<'a>>,
+
+    /// All references, in source order.
+    pub references: Vec<Reference<'a>>,
+
+    /// All type bindings, in declaration order.
+    pub type_bindings: Vec<TypeBinding<'a>>,
+
+    /// All type references, in source order.
+    pub type_references: Vec<TypeReference<'a>>,
+}
+
+/// Whether or not `range` contains `position`, both ends are inclusive.
+#[inline]
+fn contains(range: Range, position: Position) -> bool {
+    range.start <= position && position <= range.end
+}
+
+/// Get the name in the passed token.
+#[inline]
+fn get_name(token: &Token) -> SmolStr {
+    token.token_type.try_as_string().unwrap_or_default().into()
+}
+
+/// A [`Visitor`] building a [`SemanticModel`].
+struct ModelBuilder<'a> {
+    /// The model being built.
+    model: SemanticModel<'a>,
+
+    /// The scopes currently entered, innermost last.
+    stack: Vec<ScopeId>,
+
+    /// Loop variables to declare once the body of their loop is entered.
+    loop_variables: Vec<(&'a Block, Vec<&'a Token>)>,
+
+    /// Bodies of `repeat` blocks currently entered, their scopes only end
+    /// after the `until` condition.
+    repeat_bodies: Vec<&'a Block>,
+
+    /// Variables that are assigned to in the current set expression.
+    written: Vec<(&'a Var, ReferenceKind)>,
+}
+
+impl<'a> ModelBuilder<'a> {
+    /// Get the innermost scope.
+    #[inline]
+    fn current_scope(&self) -> ScopeId {
+        *self.stack.last().unwrap()
+    }
+
+    /// Enter a new scope.
+    fn push_scope(&mut self, kind: ScopeKind, range: Range) {
+        let id = ScopeId(self.model.scopes.len());
+        let parent = self.stack.last().copied();
+
+        if let Some(parent) = parent {
+            self.model.scopes[parent.0].children.push(id);
+        }
+        self.model.scopes.push(Scope {
+            id,
+            kind,
+            range,
+            parent,
+            children: Vec::new(),
+            bindings: Vec::new(),
+        });
+        self.stack.push(id);
+    }
+
+    /// Leave the innermost scope.
+    #[inline]
+    fn pop_scope(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Declare a new binding in the innermost scope.
+    #[inline]
+    fn declare(&mut self, token: &'a Token, kind: BindingKind, visible_from: Position) {
+        self.declare_with_name(get_name(token), token, kind, visible_from);
+    }
+
+    /// Declare a new binding in the innermost scope, with a name that's
+    /// different from the one in its token.
+    fn declare_with_name(
+        &mut self,
+        name: SmolStr,
+        token: &'a Token,
+        kind: BindingKind,
+        visible_from: Position,
+    ) {
+        let id = BindingId(self.model.bindings.len());
+        let scope = self.current_scope();
+
+        self.model.scopes[scope.0].bindings.push(id);
+        self.model.bindings.push(Binding {
+            id,
+            name,
+            token,
+            kind,
+            scope,
+            visible_from,
+            references: Vec::new(),
+        });
+    }
+
+    /// Add a reference to the name in `token`, resolving it to the innermost
+    /// binding with the same name in the entered scopes.
+    fn reference(&mut self, token: &'a Token, kind: ReferenceKind) {
+        let id = ReferenceId(self.model.references.len());
+        let name = get_name(token);
+
+        // Bindings are only declared once they're visible, so the last one
+        // with that name in the innermost scope having one is the right one.
+        let binding = self.stack.iter().rev().find_map(|scope| {
+            self.model.scopes[scope.0]
+                .bindings
+                .iter()
+                .rev()
+                .find(|binding| self.model.bindings[binding.0].name == name)
+        });
+        let resolution = match binding {
+            Some(&binding) => {
+                self.model.bindings[binding.0].references.push(id);
+                Resolution::Binding(binding)
+            }
+            None => Resolution::Global,
+        };
+
+        self.model.references.push(Reference {
+            id,
+            token,
+            scope: self.current_scope(),
+            kind,
+            resolution,
+        });
+    }
+
+    /// Enter the scope of a function.
+    #[inline]
+    fn enter_function(&mut self, range: Range) {
+        self.push_scope(ScopeKind::Function, range);
+    }
+
+    /// Declare a new type binding visible in `range`.
+    fn declare_type(&mut self, token: &'a Token, kind: TypeBindingKind, range: Range) {
+        self.model.type_bindings.push(TypeBinding {
+            id: TypeBindingId(self.model.type_bindings.len()),
+            name: get_name(token),
+            token,
+            kind,
+            range,
+            references: Vec::new(),
+        });
+    }
+
+    /// Declare the passed generics, visible in the node at `range`.
+    fn declare_generics(
+        &mut self,
+        generics: &'a Option<Pointer<GenericDeclaration>>,
+        range: Range,
+    ) {
+        for generic in generics.iter().flat_map(|generics| generics.item.iter()) {
+            match &generic.parameter {
+                GenericParameterInfo::ERROR => (),
+                GenericParameterInfo::Name(name) => {
+                    self.declare_type(name, TypeBindingKind::Generic, range)
+                }
+                GenericParameterInfo::Pack { name, .. } => {
+                    self.declare_type(name, TypeBindingKind::GenericPack, range)
+                }
+            }
+        }
+    }
+
+    /// Add a reference to the type name in `token`. It's resolved once all
+    /// types are declared, as they can be used before their declaration.
+    fn reference_type(&mut self, token: &'a Token) {
+        self.model.type_references.push(TypeReference {
+            id: TypeReferenceId(self.model.type_references.len()),
+            token,
+            resolution: None,
+        });
+    }
+
+    /// Resolve all type references, see [`SemanticModel::lookup_type`].
+    fn resolve_types(&mut self) {
+        for index in 0..self.model.type_references.len() {
+            let reference = &self.model.type_references[index];
+            let id = reference.id;
+            let resolution = self
+                .model
+                .lookup_type(&get_name(reference.token), reference.token.start);
+
+            if let Some(binding) = resolution {
+                self.model.type_bindings[binding.0].references.push(id);
+            }
+            self.model.type_references[index].resolution = resolution;
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ModelBuilder<'a> {
+    fn visit_block(&mut self, block: &'a Block) {
+        self.push_scope(ScopeKind::Block, block.get_range().unwrap_or_default());
+
+        if self
+            .loop_variables
+            .last()
+            .is_some_and(|(body, _)| ptr::eq(*body, block))
+        {
+            let (_, variables) = self.loop_variables.pop().unwrap();
+            for variable in variables {
+                self.declare(variable, BindingKind::ForVariable, variable.start);
+            }
+        }
+    }
+
+    fn leave_block(&mut self, block: &'a Block) {
+        if !self
+            .repeat_bodies
+            .last()
+            .is_some_and(|body| ptr::eq(*body, block))
+        {
+            self.pop_scope();
+        }
+    }
+
+    fn visit_repeat_block(&mut self, repeat_block: &'a RepeatBlock) {
+        self.repeat_bodies.push(&repeat_block.body);
+    }
+
+    fn leave_repeat_block(&mut self, repeat_block: &'a RepeatBlock) {
+        self.repeat_bodies.pop();
+
+        // The condition is still in the scope of the body.
+        if let Ok(range) = repeat_block.get_range() {
+            let scope = self.current_scope();
+            self.model.scopes[scope.0].range.end = range.end;
+        }
+        self.pop_scope();
+    }
+
+    fn visit_generic_for(&mut self, generic_for: &'a GenericFor) {
+        self.loop_variables.push((
+            &generic_for.do_block.body,
+            generic_for.names.iter().map(|name| &name.name).collect(),
+        ));
+    }
+
+    fn visit_numerical_for(&mut self, numerical_for: &'a NumericalFor) {
+        self.loop_variables.push((
+            &numerical_for.do_block.body,
+            vec![&numerical_for.variable.name],
+        ));
+    }
+
+    fn leave_local_assignment(&mut self, local_assignment: &'a LocalAssignment) {
+        let visible_from = local_assignment
+            .get_range()
+            .map(|range| range.end)
+            .unwrap_or_default();
+
+        for name in local_assignment.name_list.iter() {
+            self.declare(&name.name, BindingKind::Local, visible_from);
+        }
+    }
+
+    fn visit_local_function(&mut self, local_function: &'a LocalFunction) {
+        let range = local_function.get_range().unwrap_or_default();
+
+        // Declared before the body so that the function can call itself.
+        self.declare(
+            &local_function.function_name,
+            BindingKind::LocalFunction,
+            local_function.function_name.start,
+        );
+        self.declare_generics(&local_function.generics, range);
+        self.enter_function(range);
+    }
+
+    #[inline]
+    fn leave_local_function(&mut self, _: &'a LocalFunction) {
+        self.pop_scope();
+    }
+
+    fn visit_global_function(&mut self, global_function: &'a GlobalFunction) {
+        let range = global_function.get_range().unwrap_or_default();
+
+        self.declare_generics(&global_function.generics, range);
+        self.enter_function(range);
+    }
+
+    #[inline]
+    fn leave_global_function(&mut self, _: &'a GlobalFunction) {
+        self.pop_scope();
+    }
+
+    fn visit_global_function_name(&mut self, name: &'a GlobalFunctionName) {
+        match name {
+            GlobalFunctionName::SimpleName(token) => self.reference(token, ReferenceKind::Write),
+            GlobalFunctionName::Table { table, method, .. } => {
+                self.reference(table, ReferenceKind::Read);
+
+                if let Some(method) = method {
+                    self.declare_with_name(
+                        "self".into(),
+                        &method.1,
+                        BindingKind::ImplicitSelf,
+                        method.1.start,
+                    );
+                }
+            }
+        }
+    }
+
+    fn visit_closure(&mut self, closure: &'a Closure) {
+        let range = closure.get_range().unwrap_or_default();
+
+        self.declare_generics(&closure.generics, range);
+        self.enter_function(range);
+    }
+
+    #[inline]
+    fn leave_closure(&mut self, _: &'a Closure) {
+        self.pop_scope();
+    }
+
+    fn visit_type_function(&mut self, type_function: &'a TypeFunction) {
+        let range = type_function.get_range().unwrap_or_default();
+        let block_range = self.model.scopes[self.current_scope().0].range;
+
+        self.declare_type(
+            &type_function.function_name,
+            TypeBindingKind::TypeFunction,
+            block_range,
+        );
+        self.declare_generics(&type_function.generics, range);
+        self.enter_function(range);
+    }
+
+    #[inline]
+    fn leave_type_function(&mut self, _: &'a TypeFunction) {
+        self.pop_scope();
+    }
+
+    fn visit_type_definition(&mut self, type_definition: &'a TypeDefinition) {
+        let block_range = self.model.scopes[self.current_scope().0].range;
+
+        self.declare_type(
+            &type_definition.type_name,
+            TypeBindingKind::TypeDefinition,
+            block_range,
+        );
+        self.declare_generics(
+            &type_definition.generics,
+            type_definition.get_range().unwrap_or_default(),
+        );
+    }
+
+    fn visit_type_value(&mut self, type_value: &'a TypeValue) {
+        match type_value {
+            TypeValue::Function { generics, .. } => {
+                self.declare_generics(generics, type_value.get_range().unwrap_or_default());
+            }
+            TypeValue::Basic { base: name, .. }
+            | TypeValue::GenericPack { name, .. }
+            | TypeValue::VariadicPack { name, .. } => self.reference_type(name),
+            TypeValue::Module { module, .. } => self.reference(module, ReferenceKind::Read),
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn visit_generic_parameter_info_default(&mut self, default: &'a GenericParameterInfoDefault) {
+        if let GenericParameterInfoDefault::Name(name) = default {
+            self.reference_type(name);
+        }
+    }
+
+    #[inline]
+    fn visit_parameter(&mut self, parameter: &'a Parameter) {
+        self.declare(
+            &parameter.name,
+            BindingKind::Parameter,
+            parameter.name.start,
+        );
+    }
+
+    fn visit_set_expression(&mut self, set_expression: &'a SetExpression) {
+        self.written.extend(
+            set_expression
+                .variables
+                .iter()
+                .map(|variable| (&**variable, ReferenceKind::Write)),
+        );
+    }
+
+    #[inline]
+    fn leave_set_expression(&mut self, _: &'a SetExpression) {
+        self.written.clear();
+    }
+
+    #[inline]
+    fn visit_compound_set_expression(&mut self, compound_set: &'a CompoundSetExpression) {
+        self.written
+            .push((&compound_set.variable, ReferenceKind::ReadWrite));
+    }
+
+    #[inline]
+    fn leave_compound_set_expression(&mut self, _: &'a CompoundSetExpression) {
+        self.written.clear();
+    }
+
+    fn visit_var(&mut self, var: &'a Var) {
+        if let Var::Name(token) = var {
+            let kind = self
+                .written
+                .iter()
+                .find(|(variable, _)| ptr::eq(*variable, var))
+                .map(|(_, kind)| *kind)
+                .unwrap_or(ReferenceKind::Read);
+
+            self.reference(token, kind);
+        }
+    }
+
+    #[inline]
+    fn visit_table_access_prefix(&mut self, prefix: &'a TableAccessPrefix) {
+        if let TableAccessPrefix::Name(token) = prefix {
+            self.reference(token, ReferenceKind::Read);
+        }
+    }
+}
+
+impl<'a> SemanticModel<'a> {
+    /// Build the semantic model of the passed [`Cst`].
+    pub fn new(cst: &'a Cst) -> Self {
+        let mut builder = ModelBuilder {
+            model: Self::default(),
+            stack: Vec::new(),
+            loop_variables: Vec::new(),
+            repeat_bodies: Vec::new(),
+            written: Vec::new(),
+        };
+        builder.push_scope(ScopeKind::File, cst.get_range().unwrap_or_default());
+
+        // The file scope and the scope of the top-level block are the same.
+        for (statement, _) in &cst.block.statements {
+            statement.visit(&mut builder);
+        }
+        if let Some((statement, _)) = &cst.block.last_statement {
+            statement.visit(&mut builder);
+        }
+        builder.resolve_types();
+
+        builder.model
+    }
+
+    /// Get the [`Scope`] with the passed id.
+    #[inline]
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    /// Get the [`Binding`] with the passed id.
+    #[inline]
+    pub fn binding(&self, id: BindingId) -> &Binding<'a> {
+        &self.bindings[id.0]
+    }
+
+    /// Get the [`Reference`] with the passed id.
+    #[inline]
+    pub fn reference(&self, id: ReferenceId) -> &Reference<'a> {
+        &self.references[id.0]
+    }
+
+    /// Get all references to the passed binding, in source order.
+    #[inline]
+    pub fn references_of(&self, id: BindingId) -> impl Iterator<Item = &Reference<'a>> {
+        self.bindings[id.0]
+            .references
+            .iter()
+            .map(|id| &self.references[id.0])
+    }
+
+    /// Get all references to global variables, in source order.
+    #[inline]
+    pub fn globals(&self) -> impl Iterator<Item = &Reference<'a>> {
+        self.references
+            .iter()
+            .filter(|reference| reference.resolution == Resolution::Global)
+    }
+
+    /// Get the innermost [`Scope`] containing the passed position.
+    pub fn scope_at(&self, position: Position) -> ScopeId {
+        let mut scope = ScopeId(0);
+
+        while let Some(&child) = self.scopes[scope.0]
+            .children
+            .iter()
+            .find(|child| contains(self.scopes[child.0].range, position))
+        {
+            scope = child;
+        }
+
+        scope
+    }
+
+    /// Get the reference whose token contains the passed position.
+    pub fn reference_at(&self, position: Position) -> Option<&Reference<'a>> {
+        let index = self
+            .references
+            .partition_point(|reference| reference.token.start <= position);
+
+        self.references[..index].last().filter(|reference| {
+            reference
+                .token
+                .get_range()
+                .is_ok_and(|range| contains(range, position))
+        })
+    }
+
+    /// Get the binding declared or referenced at the passed position.
+    pub fn binding_at(&self, position: Position) -> Option<BindingId> {
+        if let Some(reference) = self.reference_at(position) {
+            return match reference.resolution {
+                Resolution::Binding(binding) => Some(binding),
+                Resolution::Global => None,
+            };
+        }
+
+        self.bindings
+            .iter()
+            .find(|binding| {
+                binding
+                    .token
+                    .get_range()
+                    .is_ok_and(|range| contains(range, position))
+            })
+            .map(|binding| binding.id)
+    }
+
+    /// Get the [`TypeBinding`] with the passed id.
+    #[inline]
+    pub fn type_binding(&self, id: TypeBindingId) -> &TypeBinding<'a> {
+        &self.type_bindings[id.0]
+    }
+
+    /// Get the [`TypeReference`] with the passed id.
+    #[inline]
+    pub fn type_reference(&self, id: TypeReferenceId) -> &TypeReference<'a> {
+        &self.type_references[id.0]
+    }
+
+    /// Get all references to the passed type binding, in source order.
+    #[inline]
+    pub fn type_references_of(
+        &self,
+        id: TypeBindingId,
+    ) -> impl Iterator<Item = &TypeReference<'a>> {
+        self.type_bindings[id.0]
+            .references
+            .iter()
+            .map(|id| &self.type_references[id.0])
+    }
+
+    /// Get the type binding declared or referenced at the passed position.
+    pub fn type_binding_at(&self, position: Position) -> Option<TypeBindingId> {
+        let at_position = |token: &Token| {
+            token
+                .get_range()
+                .is_ok_and(|range| contains(range, position))
+        };
+
+        if let Some(reference) = self
+            .type_references
+            .iter()
+            .find(|reference| at_position(reference.token))
+        {
+            return reference.resolution;
+        }
+
+        self.type_bindings
+            .iter()
+            .find(|binding| at_position(binding.token))
+            .map(|binding| binding.id)
+    }
+
+    /// Resolve the type `name` as if it was referenced at the passed
+    /// position, to the innermost binding with that name visible there.
+    pub fn lookup_type(&self, name: &str, position: Position) -> Option<TypeBindingId> {
+        self.type_bindings
+            .iter()
+            .filter(|binding| binding.name == name && contains(binding.range, position))
+            // Ranges containing the same position are nested, so the
+            // innermost one starts last. Later bindings win if two have the
+            // same range, like types redefined in the same block.
+            .max_by_key(|binding| (binding.range.start, Reverse(binding.range.end)))
+            .map(|binding| binding.id)
+    }
+
+    /// Resolve `name` as if it was referenced at the passed position.
+    pub fn lookup(&self, name: &str, position: Position) -> Resolution {
+        let mut scope = Some(self.scope_at(position));
+
+        while let Some(id) = scope {
+            let scope_data = &self.scopes[id.0];
+            let binding = scope_data.bindings.iter().rev().find(|binding| {
+                let binding = &self.bindings[binding.0];
+                binding.name == name && binding.visible_from <= position
+            });
+
+            if let Some(&binding) = binding {
+                return Resolution::Binding(binding);
+            }
+            scope = scope_data.parent;
+        }
+
+        Resolution::Global
+    }
+}
@@ -0,0 +1,355 @@
+//! # Semantic tokens
+//!
+//! Highlighting based on what names refer to, see [`semantic_tokens`] and
+//! [`semantic_tokens_range`]. Scopes are resolved using a [`SemanticModel`],
+//! so locals, parameters, and globals are told apart, and so are types and
+//! values with the same name.
+//!
+//! Token types and modifiers are indices in [`TOKEN_TYPES`] and
+//! [`TOKEN_MODIFIERS`], see [`legend`]. Keywords, literals, operators, and
+//! comments aren't included, they're left to the editor's grammar.
+//!
+//! ```rust
+//! # use luau_parser::{prelude::{Parser, ParserOptions, SemanticTokenType}, semantic_tokens::{semantic_tokens, TOKEN_TYPES}};
+//! let cst = Parser::new("type Point = { x: number }\nlocal p: Point", ParserOptions::default()).parse("");
+//! let tokens = semantic_tokens(&cst).data;
+//!
+//! let types = tokens.iter().map(|token| &TOKEN_TYPES[token.token_type as usize]);
+//! assert_eq!(
+//!     types.collect::<Vec<_>>(),
+//!     [
+//!         &SemanticTokenType::TYPE,     // Point
+//!         &SemanticTokenType::PROPERTY, // x
+//!         &SemanticTokenType::TYPE,     // number
+//!         &SemanticTokenType::VARIABLE, // p
+//!         &SemanticTokenType::TYPE,     // Point
+//!     ]
+//! );
+//! ```
+
+use lsp_types::{
+    Position, Range, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
+    SemanticTokensLegend,
+};
+use luau_lexer::prelude::Token;
+use std::collections::BTreeMap;
+
+use crate::{
+    semantic::{
+        BUILT_IN_TYPES, BindingKind, ReferenceKind, Resolution, SemanticModel, TypeBindingKind,
+    },
+    types::{
+        Attribute, Cst, FunctionCallInvoked, GlobalFunctionName, ParameterTypeName, TableAccessKey,
+        TableKey, TypeValue, Visit, Visitor,
+    },
+};
+
+/// The token types used in [`SemanticToken::token_type`], in order.
+pub const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::METHOD,
+    SemanticTokenType::DECORATOR,
+];
+
+/// The token modifiers used in [`SemanticToken::token_modifiers_bitset`], in
+/// order. `global` marks global variables, `defaultLibrary` built-in types.
+pub const TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::MODIFICATION,
+    SemanticTokenModifier::DEFAULT_LIBRARY,
+    SemanticTokenModifier::new("global"),
+];
+
+/// The [`SemanticTokensLegend`] to register with the client.
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+/// An index in [`TOKEN_TYPES`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    /// [`SemanticTokenType::NAMESPACE`].
+    Namespace,
+
+    /// [`SemanticTokenType::TYPE`].
+    Type,
+
+    /// [`SemanticTokenType::TYPE_PARAMETER`].
+    TypeParameter,
+
+    /// [`SemanticTokenType::PARAMETER`].
+    Parameter,
+
+    /// [`SemanticTokenType::VARIABLE`].
+    Variable,
+
+    /// [`SemanticTokenType::PROPERTY`].
+    Property,
+
+    /// [`SemanticTokenType::FUNCTION`].
+    Function,
+
+    /// [`SemanticTokenType::METHOD`].
+    Method,
+
+    /// [`SemanticTokenType::DECORATOR`].
+    Decorator,
+}
+
+/// [`SemanticTokenModifier::DECLARATION`].
+const DECLARATION: u32 = 1;
+
+/// [`SemanticTokenModifier::MODIFICATION`].
+const MODIFICATION: u32 = 1 << 1;
+
+/// [`SemanticTokenModifier::DEFAULT_LIBRARY`].
+const DEFAULT_LIBRARY: u32 = 1 << 2;
+
+/// The `global` modifier.
+const GLOBAL: u32 = 1 << 3;
+
+/// A classified token.
+#[derive(Clone, Copy, Debug)]
+struct Classified {
+    /// The length of the token.
+    length: u32,
+
+    /// The token type.
+    kind: Kind,
+
+    /// The token modifiers.
+    modifiers: u32,
+}
+
+/// Classifies tokens, the last classification of a token wins.
+#[derive(Debug, Default)]
+struct Classifier {
+    /// All classified tokens, by their start.
+    tokens: BTreeMap<Position, Classified>,
+}
+
+impl Classifier {
+    /// Classify the passed token. Tokens spanning multiple lines or with no
+    /// text are ignored.
+    fn classify(&mut self, token: &Token, kind: Kind, modifiers: u32) {
+        if token.start.line != token.end.line || token.end.character <= token.start.character {
+            return;
+        }
+
+        self.tokens.insert(
+            token.start,
+            Classified {
+                length: token.end.character - token.start.character,
+                kind,
+                modifiers,
+            },
+        );
+    }
+
+    /// Change the type of the passed token, keeping its modifiers.
+    fn reclassify(&mut self, token: &Token, kind: Kind, modifiers: u32) {
+        let old = self
+            .tokens
+            .get(&token.start)
+            .map_or(0, |classified| classified.modifiers);
+
+        self.classify(token, kind, old | modifiers);
+    }
+
+    /// Classify the names of the [`SemanticModel`].
+    fn classify_model(&mut self, model: &SemanticModel) {
+        let binding_kind = |kind| match kind {
+            BindingKind::Parameter | BindingKind::ImplicitSelf => Kind::Parameter,
+            BindingKind::LocalFunction => Kind::Function,
+            BindingKind::Local | BindingKind::ForVariable => Kind::Variable,
+        };
+        let type_kind = |kind| match kind {
+            TypeBindingKind::TypeDefinition | TypeBindingKind::TypeFunction => Kind::Type,
+            TypeBindingKind::Generic | TypeBindingKind::GenericPack => Kind::TypeParameter,
+        };
+
+        for binding in &model.bindings {
+            // Its token is the method's name.
+            if binding.kind != BindingKind::ImplicitSelf {
+                self.classify(binding.token, binding_kind(binding.kind), DECLARATION);
+            }
+        }
+        for reference in &model.references {
+            let modifiers = match reference.kind {
+                ReferenceKind::Read => 0,
+                ReferenceKind::Write | ReferenceKind::ReadWrite => MODIFICATION,
+            };
+
+            match reference.resolution {
+                Resolution::Binding(id) => self.classify(
+                    reference.token,
+                    binding_kind(model.binding(id).kind),
+                    modifiers,
+                ),
+                Resolution::Global => {
+                    self.classify(reference.token, Kind::Variable, modifiers | GLOBAL);
+                }
+            }
+        }
+
+        for binding in &model.type_bindings {
+            self.classify(binding.token, type_kind(binding.kind), DECLARATION);
+        }
+        for reference in &model.type_references {
+            match reference.resolution {
+                Some(id) => {
+                    self.classify(reference.token, type_kind(model.type_binding(id).kind), 0);
+                }
+                None => {
+                    let is_built_in = reference
+                        .token
+                        .token_type
+                        .try_as_string()
+                        .is_some_and(|name| BUILT_IN_TYPES.contains(&name.as_str()));
+
+                    self.classify(
+                        reference.token,
+                        Kind::Type,
+                        if is_built_in { DEFAULT_LIBRARY } else { 0 },
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for Classifier {
+    fn visit_attribute(&mut self, node: &'a Attribute) {
+        self.classify(&node.at, Kind::Decorator, 0);
+        self.classify(&node.attribute, Kind::Decorator, 0);
+    }
+
+    // After its keys are classified as properties.
+    fn leave_global_function_name(&mut self, node: &'a GlobalFunctionName) {
+        match node {
+            GlobalFunctionName::SimpleName(name) => {
+                self.reclassify(name, Kind::Function, DECLARATION);
+            }
+            GlobalFunctionName::Table { keys, method, .. } => {
+                let names = keys.iter().filter_map(|key| match key {
+                    TableAccessKey::Name { name, .. } => Some(&**name),
+                    TableAccessKey::Expression(_) => None,
+                });
+
+                match method {
+                    Some(method) => {
+                        for name in names {
+                            self.classify(name, Kind::Property, 0);
+                        }
+                        self.classify(&method.1, Kind::Method, DECLARATION);
+                    }
+                    None => {
+                        let mut names = names.peekable();
+                        while let Some(name) = names.next() {
+                            if names.peek().is_some() {
+                                self.classify(name, Kind::Property, 0);
+                            } else {
+                                self.classify(name, Kind::Function, DECLARATION);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_table_access_key(&mut self, node: &'a TableAccessKey) {
+        if let TableAccessKey::Name { name, .. } = node {
+            self.classify(name, Kind::Property, 0);
+        }
+    }
+
+    fn visit_function_call_invoked(&mut self, node: &'a FunctionCallInvoked) {
+        if let FunctionCallInvoked::TableMethod { method, .. } = node {
+            self.classify(method, Kind::Method, 0);
+        }
+    }
+
+    fn visit_table_key(&mut self, node: &'a TableKey) {
+        if let TableKey::Simple(name) = node {
+            self.classify(name, Kind::Property, 0);
+        }
+    }
+
+    fn visit_parameter_type_name(&mut self, node: &'a ParameterTypeName) {
+        if let ParameterTypeName::Normal(name) = node {
+            self.classify(&name.name, Kind::Parameter, DECLARATION);
+        }
+    }
+
+    fn visit_type_value(&mut self, node: &'a TypeValue) {
+        if let TypeValue::Module { module, name, .. } = node {
+            self.classify(module, Kind::Namespace, 0);
+            self.classify(name, Kind::Type, 0);
+        }
+    }
+}
+
+/// Classify the tokens of the [`Cst`] and delta-encode the ones `filter`
+/// keeps.
+fn encode(cst: &Cst, filter: impl Fn(Position, Position) -> bool) -> SemanticTokens {
+    let mut classifier = Classifier::default();
+    classifier.classify_model(&SemanticModel::new(cst));
+    cst.visit(&mut classifier);
+
+    let mut previous = Position::new(0, 0);
+    let data = classifier
+        .tokens
+        .into_iter()
+        .filter(|(start, classified)| {
+            filter(
+                *start,
+                Position::new(start.line, start.character + classified.length),
+            )
+        })
+        .map(|(start, classified)| {
+            let delta_line = start.line - previous.line;
+            let delta_start = if delta_line == 0 {
+                start.character - previous.character
+            } else {
+                start.character
+            };
+            previous = start;
+
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: classified.length,
+                token_type: classified.kind as u32,
+                token_modifiers_bitset: classified.modifiers,
+            }
+        })
+        .collect();
+
+    SemanticTokens {
+        result_id: None,
+        data,
+    }
+}
+
+/// Get the semantic tokens of the whole [`Cst`].
+#[inline]
+pub fn semantic_tokens(cst: &Cst) -> SemanticTokens {
+    encode(cst, |_, _| true)
+}
+
+/// Get the semantic tokens of the [`Cst`] that overlap the passed range.
+/// Names are still resolved using the whole [`Cst`].
+#[inline]
+pub fn semantic_tokens_range(cst: &Cst, range: Range) -> SemanticTokens {
+    encode(cst, |start, end| start < range.end && range.start < end)
+}
@@ -0,0 +1,434 @@
+//! # Document symbols
+//!
+//! The outline of a [`Cst`], see [`Cst::document_symbols`]. It has:
+//!
+//! * Local, global, and type functions, and type definitions, wherever they
+//!   are.
+//! * Variables declared by top-level local assignments.
+//! * Fields of tables assigned to these variables, like the functions of a
+//!   module table, and fields of tables assigned to these fields.
+//!
+//! Symbols declared in a function, or in a table, are its children. Other
+//! blocks, like `do` blocks and loops, don't have symbols of their own.
+//! Top-level functions in a table, like `function M.new()`, are children of
+//! the table's symbol, or of its field they're in, and are named after the
+//! rest of their name.
+//!
+//! ```rust
+//! # use luau_parser::prelude::{Parser, ParserOptions, SymbolKind};
+//! let code = "local M = {}\n\nfunction M.new()\n    local function helper() end\nend\n\nreturn M";
+//! let cst = Parser::new(code, ParserOptions::default()).parse("");
+//! let symbols = cst.document_symbols();
+//!
+//! assert_eq!(symbols[0].name, "M");
+//!
+//! let new = &symbols[0].children.as_ref().unwrap()[0];
+//! assert_eq!(new.name, "new");
+//! assert_eq!(new.kind, SymbolKind::FUNCTION);
+//! assert_eq!(new.children.as_ref().unwrap()[0].name, "helper");
+//! ```
+
+use lsp_types::{DocumentSymbol, Range, SymbolKind};
+use luau_lexer::prelude::{Token, TokenType};
+use std::ptr;
+
+use crate::types::{
+    Block, Cst, Expression, GetRange, GlobalFunction, GlobalFunctionName, LocalAssignment,
+    LocalFunction, Table, TableAccessKey, TableField, TableFieldValue, TableKey, TypeDefinition,
+    TypeFunction, Visit, Visitor,
+};
+
+/// Create a [`DocumentSymbol`] without children.
+#[allow(deprecated)]
+fn symbol(name: String, kind: SymbolKind, range: Range, selection_range: Range) -> DocumentSymbol {
+    DocumentSymbol {
+        name,
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: None,
+    }
+}
+
+/// Create a [`DocumentSymbol`] for the passed node, named after `name`.
+fn token_symbol(node: &impl GetRange, name: &Token, kind: SymbolKind) -> Option<DocumentSymbol> {
+    let TokenType::Identifier(text) = &name.token_type else {
+        return None;
+    };
+
+    Some(symbol(
+        text.to_string(),
+        kind,
+        node.get_range().ok()?,
+        name.get_range().ok()?,
+    ))
+}
+
+/// The kind of a symbol holding the passed value.
+fn value_kind(value: &Expression, otherwise: SymbolKind) -> SymbolKind {
+    match value {
+        Expression::Closure(_) => SymbolKind::FUNCTION,
+        Expression::Table(_) => SymbolKind::OBJECT,
+        _ => otherwise,
+    }
+}
+
+/// The path of a global function, like `["Foo", "bar"]` and `Some("baz")` for
+/// `Foo.bar:baz`.
+type FunctionPath = (Vec<String>, Option<String>);
+
+/// Split the name of a global function into its [`FunctionPath`].
+fn global_function_path(name: &GlobalFunctionName) -> Option<FunctionPath> {
+    let text = |token: &Token| match &token.token_type {
+        TokenType::Identifier(text) => Some(text.to_string()),
+        _ => None,
+    };
+
+    match name {
+        GlobalFunctionName::SimpleName(token) => Some((vec![text(token)?], None)),
+        GlobalFunctionName::Table {
+            table,
+            keys,
+            method,
+        } => {
+            let mut path = vec![text(table)?];
+            for key in keys {
+                let TableAccessKey::Name { name: key, .. } = key else {
+                    return None;
+                };
+                path.push(text(key)?);
+            }
+            let method = match method {
+                Some(method) => Some(text(&method.1)?),
+                None => None,
+            };
+
+            Some((path, method))
+        }
+    }
+}
+
+/// Join a [`FunctionPath`] back into a name, like `Foo.bar:baz`.
+fn join_path(path: &[String], method: Option<&str>) -> String {
+    let mut name = path.join(".");
+    if let Some(method) = method {
+        if !name.is_empty() {
+            name.push(':');
+        }
+        name.push_str(method);
+    }
+
+    name
+}
+
+/// Add the symbol of a global function to `parent`, or to the field of
+/// `parent` the `rest` of its path goes through. It's renamed to what's left
+/// of its path. The ranges of the symbols it's nested in are extended to
+/// contain it, as children must be inside their parent.
+fn nest(
+    parent: &mut DocumentSymbol,
+    rest: &[String],
+    method: Option<&str>,
+    mut symbol: DocumentSymbol,
+) {
+    parent.range.end = parent.range.end.max(symbol.range.end);
+
+    // The last key is the function itself, unless it's a method.
+    if rest.len() > usize::from(method.is_none())
+        && let Some(child) = parent
+            .children
+            .iter_mut()
+            .flatten()
+            .find(|child| child.name == rest[0])
+    {
+        return nest(child, &rest[1..], method, symbol);
+    }
+
+    symbol.name = join_path(rest, method);
+
+    let children = parent.children.get_or_insert_default();
+    children.push(symbol);
+    children.sort_by_key(|child| child.selection_range.start);
+}
+
+/// A symbol whose children are being collected.
+#[derive(Debug, Default)]
+struct Frame {
+    /// The symbol, if the node has one. Children of nodes without one belong
+    /// to the parent frame.
+    symbol: Option<DocumentSymbol>,
+
+    /// The children found so far.
+    children: Vec<DocumentSymbol>,
+}
+
+/// A [`Visitor`] collecting the [`DocumentSymbol`]s of a [`Cst`].
+#[derive(Debug)]
+struct SymbolCollector<'a> {
+    /// The symbols being collected, the first one is the root of the file.
+    frames: Vec<Frame>,
+
+    /// How many blocks deep the visitor is.
+    depth: usize,
+
+    /// The symbols of top-level variables, waiting for their value to be
+    /// visited.
+    pending: Vec<(&'a Expression, DocumentSymbol)>,
+
+    /// The values of variables whose frame is open.
+    values: Vec<&'a Expression>,
+
+    /// The tables whose fields are symbols.
+    tables: Vec<&'a Table>,
+
+    /// Whether or not the fields of each table being visited are symbols.
+    table_stack: Vec<bool>,
+
+    /// The paths of the global functions being visited, if they're top-level
+    /// functions in a table.
+    function_paths: Vec<Option<FunctionPath>>,
+
+    /// The symbols of top-level functions in tables, to nest in the symbol of
+    /// their table.
+    members: Vec<(FunctionPath, DocumentSymbol)>,
+}
+
+impl Default for SymbolCollector<'_> {
+    fn default() -> Self {
+        Self {
+            frames: vec![Frame::default()],
+            depth: 0,
+            pending: Vec::new(),
+            values: Vec::new(),
+            tables: Vec::new(),
+            table_stack: Vec::new(),
+            function_paths: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+}
+
+impl SymbolCollector<'_> {
+    /// Open a frame for the passed symbol.
+    #[inline]
+    fn open(&mut self, symbol: Option<DocumentSymbol>) {
+        self.frames.push(Frame {
+            symbol,
+            children: Vec::new(),
+        });
+    }
+
+    /// Close the last frame, adding its symbol to its parent.
+    fn close(&mut self) {
+        let Some(mut frame) = self.frames.pop() else {
+            return;
+        };
+        let parent = &mut self
+            .frames
+            .last_mut()
+            .expect("the root is never closed")
+            .children;
+
+        match frame.symbol {
+            Some(mut symbol) => {
+                frame
+                    .children
+                    .sort_by_key(|child| child.selection_range.start);
+                symbol.children = (!frame.children.is_empty()).then_some(frame.children);
+                parent.push(symbol);
+            }
+            None => parent.append(&mut frame.children),
+        }
+    }
+
+    /// Mark the table as one whose fields are symbols, if `value` is one.
+    #[inline]
+    fn track_table<'a>(tables: &mut Vec<&'a Table>, value: &'a Expression) {
+        if let Expression::Table(table) = value {
+            tables.push(table);
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for SymbolCollector<'a> {
+    fn visit_block(&mut self, _: &'a Block) {
+        self.depth += 1;
+    }
+
+    fn leave_block(&mut self, _: &'a Block) {
+        self.depth -= 1;
+    }
+
+    fn visit_local_function(&mut self, node: &'a LocalFunction) {
+        self.open(token_symbol(
+            node,
+            &node.function_name,
+            SymbolKind::FUNCTION,
+        ));
+    }
+
+    fn leave_local_function(&mut self, _: &'a LocalFunction) {
+        self.close();
+    }
+
+    fn visit_global_function(&mut self, node: &'a GlobalFunction) {
+        let kind = match &node.function_name {
+            GlobalFunctionName::Table {
+                method: Some(_), ..
+            } => SymbolKind::METHOD,
+            _ => SymbolKind::FUNCTION,
+        };
+        let path = global_function_path(&node.function_name);
+        let symbol = path.as_ref().and_then(|(path, method)| {
+            Some(symbol(
+                join_path(path, method.as_deref()),
+                kind,
+                node.get_range().ok()?,
+                node.function_name.get_range().ok()?,
+            ))
+        });
+
+        let is_member = self.depth == 1
+            && symbol.is_some()
+            && matches!(node.function_name, GlobalFunctionName::Table { .. });
+        self.function_paths.push(path.filter(|_| is_member));
+        self.open(symbol);
+    }
+
+    fn leave_global_function(&mut self, _: &'a GlobalFunction) {
+        self.close();
+
+        if let Some(Some(path)) = self.function_paths.pop()
+            && let Some(symbol) = self.frames.last_mut().unwrap().children.pop()
+        {
+            self.members.push((path, symbol));
+        }
+    }
+
+    fn visit_type_function(&mut self, node: &'a TypeFunction) {
+        self.open(token_symbol(
+            node,
+            &node.function_name,
+            SymbolKind::FUNCTION,
+        ));
+    }
+
+    fn leave_type_function(&mut self, _: &'a TypeFunction) {
+        self.close();
+    }
+
+    fn visit_type_definition(&mut self, node: &'a TypeDefinition) {
+        self.open(token_symbol(node, &node.type_name, SymbolKind::STRUCT));
+    }
+
+    fn leave_type_definition(&mut self, _: &'a TypeDefinition) {
+        self.close();
+    }
+
+    fn visit_local_assignment(&mut self, node: &'a LocalAssignment) {
+        if self.depth != 1 {
+            return;
+        }
+
+        for (index, name) in node.name_list.iter().enumerate() {
+            let value = node.expressions.get(index).map(|value| &***value);
+            let kind = value.map_or(SymbolKind::VARIABLE, |value| {
+                value_kind(value, SymbolKind::VARIABLE)
+            });
+            let Some(symbol) = token_symbol(node, &name.name, kind) else {
+                continue;
+            };
+
+            match value {
+                Some(value) => self.pending.push((value, symbol)),
+                None => self.frames.last_mut().unwrap().children.push(symbol),
+            }
+        }
+    }
+
+    fn visit_expression(&mut self, node: &'a Expression) {
+        let Some(index) = self
+            .pending
+            .iter()
+            .position(|(value, _)| ptr::eq(*value, node))
+        else {
+            return;
+        };
+
+        let (value, symbol) = self.pending.remove(index);
+        Self::track_table(&mut self.tables, value);
+        self.values.push(value);
+        self.open(Some(symbol));
+    }
+
+    fn leave_expression(&mut self, node: &'a Expression) {
+        if self
+            .values
+            .last()
+            .is_some_and(|value| ptr::eq(*value, node))
+        {
+            self.values.pop();
+            self.close();
+        }
+    }
+
+    fn visit_table(&mut self, node: &'a Table) {
+        let is_symbol = self.tables.iter().any(|table| ptr::eq(*table, node));
+        self.table_stack.push(is_symbol);
+    }
+
+    fn leave_table(&mut self, _: &'a Table) {
+        self.table_stack.pop();
+    }
+
+    fn visit_table_field(&mut self, node: &'a TableField) {
+        let symbol = match (&*node.key, &*node.value) {
+            (TableKey::Simple(name), TableFieldValue::Expression(value))
+                if self.table_stack.last() == Some(&true) =>
+            {
+                Self::track_table(&mut self.tables, value);
+                token_symbol(node, name, value_kind(value, SymbolKind::FIELD))
+            }
+            _ => None,
+        };
+
+        self.open(symbol);
+    }
+
+    fn leave_table_field(&mut self, _: &'a TableField) {
+        self.close();
+    }
+}
+
+impl Cst {
+    /// Get the outline of this [`Cst`], see the [module docs](crate::symbols)
+    /// for what's in it. Symbols are in source order.
+    pub fn document_symbols(&self) -> Vec<DocumentSymbol> {
+        let mut collector = SymbolCollector::default();
+        self.visit(&mut collector);
+
+        // Variables whose value wasn't visited, which only happens if the
+        // `Cst` has errors.
+        let mut symbols = collector.frames.pop().unwrap_or_default().children;
+        symbols.extend(collector.pending.into_iter().map(|(_, symbol)| symbol));
+
+        // Functions in a table are nested in the last symbol named after the
+        // table declared before them, if there's one.
+        for ((path, method), symbol) in collector.members {
+            let table = symbols.iter_mut().rev().find(|table| {
+                table.name == path[0] && table.selection_range.start < symbol.range.start
+            });
+
+            match table {
+                Some(table) => nest(table, &path[1..], method.as_deref(), symbol),
+                None => symbols.push(symbol),
+            }
+        }
+        symbols.sort_by_key(|child| child.selection_range.start);
+
+        symbols
+    }
+}
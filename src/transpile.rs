@@ -0,0 +1,706 @@
+//! # Lua 5.1 transpiler
+//!
+//! Turns a [`Cst`] into plain Lua 5.1 code, see [`Cst::to_lua51`].
+//!
+//! * All type syntax is removed: type definitions, type functions, type
+//!   annotations, return types, generics, and type casts. Attributes are
+//!   removed too.
+//! * `continue` is lowered by wrapping the body of the loop in a
+//!   `repeat ... until true`, `break`s in that body set a flag that's checked
+//!   after it.
+//! * Compound assignments (`x += 1`) become normal assignments, the table and
+//!   key of indexed targets (`t[k] += 1`) are stored in locals first so they're
+//!   only evaluated once.
+//! * If expressions become `((cond) and {a} or {b})[1]`, which only evaluates the
+//!   chosen branch.
+//! * Interpolated strings become concatenations of `tostring` calls, `//`
+//!   becomes `math.floor(a / b)`.
+//! * Binary numbers, `_` separators in numbers, backtick strings, and the `\x`,
+//!   `\u{}`, and `\z` escapes are converted to their Lua 5.1 equivalents.
+//!
+//! Every token stays on the line it was on, and removed code is replaced by the
+//! new lines in it, so line numbers in stack traces match the original code.
+//!
+//! ```rust
+//! # use luau_parser::prelude::{Parser, ParserOptions};
+//! let cst = Parser::new("local x: number = 1\nx //= 2", ParserOptions::default()).parse("");
+//!
+//! assert_eq!(cst.to_lua51().unwrap(), "local x = 1\nx = math.floor(x / (2))");
+//! ```
+//!
+//! ## Note
+//!
+//! * The generated code uses the `math` and `tostring` globals and declares
+//!   locals starting with `__luau_`, it won't work if those globals are
+//!   shadowed or if the code uses locals with the same names.
+//! * `repeat` loops using `continue` whose `until` condition uses a local
+//!   declared in their body are refused, as wrapping the body in another loop
+//!   would turn that local into a global, see
+//!   [`TranspileError::ContinueWithUntilLocal`].
+//! * Strings with a `\z` escape followed by new lines are put on one line.
+
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::{Literal, LuauNumber, LuauString, Token, TokenType, Trivia};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use crate::{
+    semantic::{Resolution, SemanticModel},
+    types::{
+        Attribute, BinaryOperator, Block, Closure, CompoundSetExpression, Cst, Expression,
+        GenericFor, GetRange, GlobalFunction, IfExpression, InterpolatedString,
+        InterpolatedStringSegment, LocalFunction, Name, NumericalFor, Parameter, ParsedNumber,
+        ParsedString, Print, RepeatBlock, Statement, TableAccessKey, TableKey,
+        TerminationStatement, TypeFunction, Var, Visit, Visitor, WhileLoop,
+    },
+};
+
+/// The local holding the table of an indexed compound assignment.
+const TABLE_LOCAL: &str = "__luau_table";
+
+/// The local holding the key of an indexed compound assignment.
+const KEY_LOCAL: &str = "__luau_key";
+
+/// The local set when a loop using `continue` is exited with `break`.
+const BREAK_LOCAL: &str = "__luau_break";
+
+/// Errors that may occur in [`Cst::to_lua51`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TranspileError {
+    /// The [`Cst`] [has errors](Cst::has_errors) and is missing parts of the
+    /// code.
+    HasErrors,
+
+    /// A `repeat` loop using `continue` has an `until` condition using a local
+    /// declared in its body, at the passed position. The body is wrapped in
+    /// another loop to lower `continue`, so the local wouldn't be visible in
+    /// the condition anymore.
+    ContinueWithUntilLocal(Position),
+}
+
+impl fmt::Display for TranspileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HasErrors => f.write_str("can't transpile code with syntax errors"),
+            Self::ContinueWithUntilLocal(position) => write!(
+                f,
+                "can't lower `continue` in a `repeat` loop whose `until` condition uses a local \
+                declared in its body, at {}:{}",
+                position.line + 1,
+                position.character + 1
+            ),
+        }
+    }
+}
+
+impl Error for TranspileError {}
+
+/// Write the passed bytes as a double quoted Lua 5.1 string. New lines are
+/// escaped with a `\` followed by an actual new line to keep the line count.
+fn quote(bytes: &[u8]) -> String {
+    let mut string = String::with_capacity(bytes.len() + 2);
+    string.push('"');
+
+    for chunk in bytes.utf8_chunks() {
+        for character in chunk.valid().chars() {
+            match character {
+                '"' => string.push_str("\\\""),
+                '\\' => string.push_str("\\\\"),
+                '\n' => string.push_str("\\\n"),
+                '\r' => string.push_str("\\r"),
+                '\t' => string.push_str("\\t"),
+                character if character.is_ascii_control() => {
+                    string.push_str(&format!("\\{:03}", character as u8));
+                }
+                character => string.push(character),
+            }
+        }
+        for byte in chunk.invalid() {
+            string.push_str(&format!("\\{byte:03}"));
+        }
+    }
+
+    string.push('"');
+    string
+}
+
+/// Get the Lua 5.1 text of the passed token.
+fn token_text(token: &Token) -> String {
+    match &token.token_type {
+        TokenType::Literal(Literal::Number(number @ LuauNumber::Binary(text))) => {
+            match ParsedNumber::try_from(number) {
+                Ok(ParsedNumber::HexOrByte(value)) => value.to_string(),
+                _ => text.replace('_', ""),
+            }
+        }
+        TokenType::Literal(Literal::Number(LuauNumber::Plain(text) | LuauNumber::Hex(text))) => {
+            text.replace('_', "")
+        }
+        TokenType::Literal(Literal::String(string @ LuauString::Backticks(_))) => {
+            quote(&ParsedString::parse(string, token.start).0.0)
+        }
+        TokenType::Literal(Literal::String(
+            string @ (LuauString::SingleQuotes(text) | LuauString::DoubleQuotes(text)),
+        )) if ["\\x", "\\u", "\\z"]
+            .iter()
+            .any(|escape| text.contains(escape)) =>
+        {
+            quote(&ParsedString::parse(string, token.start).0.0)
+        }
+        token_type => token_type.try_as_string().unwrap_or_default(),
+    }
+}
+
+/// Whether or not a space is needed between the passed characters so they
+/// aren't read as one token.
+#[inline]
+fn needs_space(previous: char, next: char) -> bool {
+    let is_word = |character: char| character.is_alphanumeric() || character == '_';
+    let is_joining = |character: char| matches!(character, '-' | '.' | '=' | '<' | '>' | '~' | '[');
+
+    (is_word(previous) && (is_word(next) || next == '.'))
+        || (is_joining(previous) && (is_joining(next) || next.is_ascii_digit()))
+}
+
+/// A [`Visitor`] finding the `continue` and `break` statements of a loop,
+/// excluding the ones in nested loops and functions.
+#[derive(Default)]
+struct LoopScanner {
+    /// How many nested loops and functions the visitor is in.
+    depth: usize,
+
+    /// The `continue` tokens found.
+    continues: Vec<Position>,
+
+    /// The `break` tokens found.
+    breaks: Vec<Position>,
+}
+
+impl LoopScanner {
+    /// Enter a nested loop or function.
+    #[inline]
+    fn enter(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Leave a nested loop or function.
+    #[inline]
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+impl Visitor<'_> for LoopScanner {
+    fn visit_termination_statement(&mut self, node: &TerminationStatement) {
+        if self.depth != 0 {
+            return;
+        }
+
+        match node {
+            TerminationStatement::Continue(token) => self.continues.push(token.start),
+            TerminationStatement::Break(token) => self.breaks.push(token.start),
+            TerminationStatement::Return { .. } => (),
+        }
+    }
+
+    fn visit_while_loop(&mut self, _: &WhileLoop) {
+        self.enter();
+    }
+    fn leave_while_loop(&mut self, _: &WhileLoop) {
+        self.leave();
+    }
+    fn visit_numerical_for(&mut self, _: &NumericalFor) {
+        self.enter();
+    }
+    fn leave_numerical_for(&mut self, _: &NumericalFor) {
+        self.leave();
+    }
+    fn visit_generic_for(&mut self, _: &GenericFor) {
+        self.enter();
+    }
+    fn leave_generic_for(&mut self, _: &GenericFor) {
+        self.leave();
+    }
+    fn visit_repeat_block(&mut self, _: &RepeatBlock) {
+        self.enter();
+    }
+    fn leave_repeat_block(&mut self, _: &RepeatBlock) {
+        self.leave();
+    }
+    fn visit_local_function(&mut self, _: &LocalFunction) {
+        self.enter();
+    }
+    fn leave_local_function(&mut self, _: &LocalFunction) {
+        self.leave();
+    }
+    fn visit_global_function(&mut self, _: &GlobalFunction) {
+        self.enter();
+    }
+    fn leave_global_function(&mut self, _: &GlobalFunction) {
+        self.leave();
+    }
+    fn visit_type_function(&mut self, _: &TypeFunction) {
+        self.enter();
+    }
+    fn leave_type_function(&mut self, _: &TypeFunction) {
+        self.leave();
+    }
+    fn visit_closure(&mut self, _: &Closure) {
+        self.enter();
+    }
+    fn leave_closure(&mut self, _: &Closure) {
+        self.leave();
+    }
+}
+
+/// A [`Visitor`] writing the Lua 5.1 version of the visited code. Nodes plan
+/// their changes before their tokens are visited, as text to remove, replace,
+/// or insert around specific tokens.
+#[derive(Default)]
+struct Transpiler {
+    /// The code written so far.
+    output: String,
+
+    /// Ranges of code to remove, only the new lines in them are kept.
+    removed: Vec<Range>,
+
+    /// Text replacing the token starting at a position.
+    replacements: HashMap<Position, String>,
+
+    /// Text inserted before the token starting at a position, after its
+    /// leading trivia.
+    before: HashMap<Position, String>,
+
+    /// Text inserted after the token ending at a position.
+    after: HashMap<Position, String>,
+
+    /// Tokens whose leading trivia is dropped if it's only spaces on the same
+    /// line.
+    trimmed: HashSet<Position>,
+
+    /// Whether or not code was removed right before the current token without
+    /// leaving any new lines, in which case a space might be needed.
+    removed_inline: bool,
+
+    /// The ranges of the bodies and conditions of the `repeat` loops whose
+    /// `continue`s were lowered.
+    lowered_repeats: Vec<(Range, Range)>,
+}
+
+impl Transpiler {
+    /// Remove the code of the passed node.
+    #[inline]
+    fn remove(&mut self, node: &impl GetRange) {
+        if let Ok(range) = node.get_range() {
+            self.removed.push(range);
+        }
+    }
+
+    /// Remove the code from the start of the passed token to the end of the
+    /// passed node, or to the end of the token if the node has no range.
+    fn remove_from(&mut self, token: &Token, node: Option<&impl GetRange>) {
+        let end = node
+            .and_then(|node| node.get_range().ok())
+            .map_or(token.end, |range| range.end);
+
+        self.removed.push(Range::new(token.start, end));
+    }
+
+    /// Replace the text of the passed token.
+    #[inline]
+    fn replace(&mut self, token: &Token, text: impl Into<String>) {
+        self.replacements.insert(token.start, text.into());
+    }
+
+    /// Insert text before the passed node. Text inserted by inner nodes goes
+    /// after the one inserted by outer ones.
+    fn insert_before(&mut self, node: &impl GetRange, text: &str) {
+        if let Ok(range) = node.get_range() {
+            self.before.entry(range.start).or_default().push_str(text);
+        }
+    }
+
+    /// Drop the spaces before the passed node, used when the token before it
+    /// is replaced by an opening bracket.
+    fn trim_before(&mut self, node: &impl GetRange) {
+        if let Ok(range) = node.get_range() {
+            self.trimmed.insert(range.start);
+        }
+    }
+
+    /// Insert text after the passed node. Text inserted by inner nodes goes
+    /// before the one inserted by outer ones.
+    fn insert_after(&mut self, node: &impl GetRange, text: &str) {
+        if let Ok(range) = node.get_range() {
+            self.after.entry(range.end).or_default().insert_str(0, text);
+        }
+    }
+
+    /// Whether or not the token starting at the passed position is removed.
+    fn is_removed(&mut self, start: Position) -> bool {
+        self.removed.retain(|range| range.end > start);
+        self.removed
+            .iter()
+            .any(|range| range.start <= start && start < range.end)
+    }
+
+    /// Write the passed text, adding a space before it if it would otherwise
+    /// be merged with the previous token because of removed code.
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        if self.removed_inline
+            && let (Some(previous), Some(next)) = (self.output.chars().last(), text.chars().next())
+            && needs_space(previous, next)
+        {
+            self.output.push(' ');
+        }
+
+        self.removed_inline = false;
+        self.output.push_str(text);
+    }
+
+    /// Remove type annotations, generics, return types, and attributes of a
+    /// function.
+    fn remove_function_types(
+        &mut self,
+        generics: Option<&impl GetRange>,
+        colon: Option<&Token>,
+        return_type: Option<&impl GetRange>,
+    ) {
+        if let Some(generics) = generics {
+            self.remove(generics);
+        }
+        if let Some(colon) = colon {
+            self.remove_from(colon, return_type);
+        }
+    }
+
+    /// Turn `condition then value` into `(condition) and {value}`, for if
+    /// expressions.
+    fn lower_if_branch(&mut self, condition: &Expression, then: &Token, value: &Expression) {
+        self.insert_before(condition, "(");
+        self.insert_after(condition, ")");
+        self.replace(then, "and");
+        self.insert_before(value, "{");
+        self.insert_after(value, "}");
+    }
+
+    /// Lower the `continue`s in the passed loop body, `opening` is the token
+    /// before the body and `closing` is the one after it. Returns whether or
+    /// not the body has any `continue`.
+    fn lower_loop(&mut self, body: &Block, opening: &Token, closing: &Token) -> bool {
+        let mut scanner = LoopScanner::default();
+        body.visit(&mut scanner);
+
+        if scanner.continues.is_empty() {
+            return false;
+        }
+
+        for start in scanner.continues {
+            self.replacements.insert(start, "break".to_string());
+        }
+
+        if scanner.breaks.is_empty() {
+            self.insert_after(opening, " repeat");
+            self.insert_before(closing, "until true ");
+        } else {
+            for start in scanner.breaks {
+                self.replacements
+                    .insert(start, format!("do {BREAK_LOCAL} = true break end"));
+            }
+
+            self.insert_after(opening, &format!(" local {BREAK_LOCAL} = false repeat"));
+            self.insert_before(
+                closing,
+                &format!("until true if {BREAK_LOCAL} then break end "),
+            );
+        }
+
+        true
+    }
+}
+
+impl Visitor<'_> for Transpiler {
+    fn visit_token(&mut self, token: &Token) {
+        let text = self
+            .replacements
+            .remove(&token.start)
+            .unwrap_or_else(|| token_text(token));
+        let before = self.before.remove(&token.start).unwrap_or_default();
+        let after = self.after.remove(&token.end).unwrap_or_default();
+
+        if self.is_removed(token.start) {
+            let removed = token
+                .leading_trivia
+                .iter()
+                .map(Print::print)
+                .chain([text])
+                .collect::<String>();
+            let lines = removed.matches('\n').count();
+
+            if lines == 0 {
+                self.removed_inline = true;
+            } else {
+                self.write(&"\n".repeat(lines));
+            }
+
+            self.write(&before);
+            self.write(&after);
+        } else {
+            let is_trimmed = self.trimmed.remove(&token.start)
+                && token.leading_trivia.iter().all(
+                    |trivia| matches!(trivia, Trivia::Spaces(spaces) if !spaces.contains('\n')),
+                );
+
+            if !is_trimmed {
+                for trivia in &token.leading_trivia {
+                    self.write(&trivia.print());
+                }
+            }
+
+            self.write(&before);
+            self.write(&text);
+            self.write(&after);
+        }
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        for (statement, semicolon) in &node.statements {
+            if matches!(
+                **statement,
+                Statement::TypeDefinition(_) | Statement::TypeFunction(_)
+            ) {
+                self.remove(statement);
+                if let Some(semicolon) = semicolon {
+                    self.remove(semicolon);
+                }
+            }
+        }
+    }
+
+    fn visit_attribute(&mut self, node: &Attribute) {
+        self.remove(node);
+    }
+
+    fn visit_name(&mut self, node: &Name) {
+        if let Some(colon) = &node.colon {
+            self.remove_from(colon, node.r#type.as_ref());
+        }
+    }
+
+    fn visit_parameter(&mut self, node: &Parameter) {
+        if let Some(colon) = &node.colon {
+            self.remove_from(colon, node.r#type.as_ref());
+        }
+    }
+
+    fn visit_local_function(&mut self, node: &LocalFunction) {
+        self.remove_function_types(
+            node.generics.as_ref(),
+            node.colon.as_deref(),
+            node.return_type.as_ref(),
+        );
+    }
+
+    fn visit_global_function(&mut self, node: &GlobalFunction) {
+        self.remove_function_types(
+            node.generics.as_ref(),
+            node.colon.as_deref(),
+            node.return_type.as_ref(),
+        );
+    }
+
+    fn visit_closure(&mut self, node: &Closure) {
+        self.remove_function_types(
+            node.generics.as_ref(),
+            node.colon.as_deref(),
+            node.return_type.as_ref(),
+        );
+    }
+
+    fn visit_expression(&mut self, node: &Expression) {
+        match node {
+            Expression::TypeCast {
+                operator, cast_to, ..
+            } => self.remove_from(operator, Some(cast_to)),
+            Expression::BinaryExpression {
+                left,
+                operator: BinaryOperator::FloorDivision(operator),
+                right,
+            } => {
+                self.insert_before(left, "math.floor(");
+                self.replace(operator, "/");
+                self.insert_after(right, ")");
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_if_expression(&mut self, node: &IfExpression) {
+        self.replace(&node.if_keyword, "(");
+        self.trim_before(&node.condition);
+        self.lower_if_branch(&node.condition, &node.then_keyword, &node.if_expression);
+
+        for else_if in node.else_if_expressions.iter() {
+            self.replace(&else_if.else_if_keyword, "or");
+            self.lower_if_branch(
+                &else_if.condition,
+                &else_if.then_keyword,
+                &else_if.expression,
+            );
+        }
+
+        self.replace(&node.else_keyword, "or");
+        self.insert_before(&node.else_expression, "{");
+        self.insert_after(&node.else_expression, "})[1]");
+    }
+
+    fn visit_interpolated_string(&mut self, node: &InterpolatedString) {
+        let last = node.segments.len().saturating_sub(1);
+
+        for (i, segment) in node.segments.iter().enumerate() {
+            let InterpolatedStringSegment::String(token) = segment else {
+                continue;
+            };
+            let text = ParsedString::from_token(token)
+                .map(|(string, _)| string.0)
+                .filter(|string| !string.is_empty())
+                .map(|string| quote(&string));
+
+            let replacement = match (i, text) {
+                (0, Some(text)) => format!("({text} .. tostring("),
+                (0, None) => "(tostring(".to_string(),
+                (i, Some(text)) if i == last => format!(") .. {text})"),
+                (i, None) if i == last => "))".to_string(),
+                (_, Some(text)) => format!(") .. {text} .. tostring("),
+                (_, None) => ") .. tostring(".to_string(),
+            };
+            self.replace(token, replacement);
+        }
+    }
+
+    fn visit_compound_set_expression(&mut self, node: &CompoundSetExpression) {
+        let operator = node
+            .operation
+            .token_type
+            .try_as_string()
+            .unwrap_or_default();
+        let operator = operator.trim_end_matches('=');
+
+        let (target, assign, closing) = match &node.variable {
+            Var::Name(name) => (
+                name.token_type.try_as_string().unwrap_or_default(),
+                String::new(),
+                "",
+            ),
+            Var::TableAccess(access) => {
+                match access.accessed_keys.last() {
+                    Some(TableAccessKey::Name { dot, name }) => {
+                        let key = name.token_type.try_as_string().unwrap_or_default();
+
+                        self.replace(dot, ", ");
+                        self.replace(name, quote(key.as_bytes()));
+                    }
+                    Some(TableAccessKey::Expression(key)) => {
+                        let TableKey::Expression(key) = &**key else {
+                            return;
+                        };
+
+                        self.replace(&key.opening_bracket, ", ");
+                        self.replace(&key.closing_bracket, "");
+                    }
+                    None => return,
+                }
+
+                let target = format!("{TABLE_LOCAL}[{KEY_LOCAL}]");
+                self.insert_before(
+                    &node.variable,
+                    &format!("do local {TABLE_LOCAL}, {KEY_LOCAL} = "),
+                );
+
+                (target.clone(), format!("{target} "), " end")
+            }
+            Var::ERROR => return,
+        };
+
+        if operator == "//" {
+            self.replace(&node.operation, format!("{assign}= math.floor({target} /"));
+            self.insert_after(&node.value, &format!(")){closing}"));
+        } else {
+            self.replace(&node.operation, format!("{assign}= {target} {operator}"));
+            self.insert_after(&node.value, &format!("){closing}"));
+        }
+        self.insert_before(&node.value, "(");
+    }
+
+    fn visit_while_loop(&mut self, node: &WhileLoop) {
+        let do_block = &node.do_block;
+        self.lower_loop(&do_block.body, &do_block.do_keyword, &do_block.end_keyword);
+    }
+
+    fn visit_numerical_for(&mut self, node: &NumericalFor) {
+        let do_block = &node.do_block;
+        self.lower_loop(&do_block.body, &do_block.do_keyword, &do_block.end_keyword);
+    }
+
+    fn visit_generic_for(&mut self, node: &GenericFor) {
+        let do_block = &node.do_block;
+        self.lower_loop(&do_block.body, &do_block.do_keyword, &do_block.end_keyword);
+    }
+
+    fn visit_repeat_block(&mut self, node: &RepeatBlock) {
+        if self.lower_loop(&node.body, &node.repeat_keyword, &node.until_keyword)
+            && let Ok(body) = node.body.get_range()
+            && let Ok(condition) = node.condition.get_range()
+        {
+            self.lowered_repeats.push((body, condition));
+        }
+    }
+}
+
+/// Whether or not `range` contains `position`.
+#[inline]
+fn contains(range: Range, position: Position) -> bool {
+    range.start <= position && position < range.end
+}
+
+impl Cst {
+    /// Transpile this [`Cst`] to Lua 5.1 code, see the
+    /// [module docs](crate::transpile) for what's changed. [`Cst`]s with
+    /// syntax errors are refused as parts of their code are missing, and so
+    /// are `continue`s that can't be lowered, see
+    /// [`TranspileError::ContinueWithUntilLocal`].
+    pub fn to_lua51(&self) -> Result<String, TranspileError> {
+        if self.has_errors() {
+            return Err(TranspileError::HasErrors);
+        }
+
+        let mut transpiler = Transpiler::default();
+        self.visit(&mut transpiler);
+
+        if !transpiler.lowered_repeats.is_empty() {
+            let model = SemanticModel::new(self);
+
+            for (body, condition) in transpiler.lowered_repeats {
+                for reference in &model.references {
+                    if let Resolution::Binding(id) = reference.resolution
+                        && contains(condition, reference.token.start)
+                        && contains(body, model.binding(id).token.start)
+                    {
+                        return Err(TranspileError::ContinueWithUntilLocal(
+                            reference.token.start,
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(transpiler.output)
+    }
+}
@@ -1,12 +1,12 @@
 //! Module holding do blocks.
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
 use crate::types::Block;
 
 /// A struct representing a do statement
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct DoBlock {
     /// The `do` keyword.
@@ -1,5 +1,5 @@
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 use std::ops::{Deref, DerefMut};
 
 /// The [`EndOfFile`][luau_lexer::prelude::TokenType::EndOfFile] token. This
@@ -7,9 +7,9 @@ use std::ops::{Deref, DerefMut};
 /// a file has no code but only comments - the comments will be stored in
 /// [`token.leading_trivia`](Token::leading_trivia) and there's no other way
 /// to detect such comments.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct EndOfFile(Token);
+pub struct EndOfFile(pub(crate) Token);
 
 impl EndOfFile {
     #[inline]
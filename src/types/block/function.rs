@@ -1,12 +1,12 @@
 //! Local and global functions.
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
 use crate::types::{Block, BracketedList, GenericDeclaration, Pointer, TableAccessKey, TypeValue};
 
 /// A struct representing a local function.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct LocalFunction {
     /// Attributes before the function.
@@ -43,7 +43,7 @@ pub struct LocalFunction {
 /// Parameter that a function can have. The difference between this and
 /// [`Name`](crate::types::Name) is the fact that [`Parameter.name`](Parameter::name)
 /// can match variadic values (`...`) while `Name` can't.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Parameter {
     /// The actual name.
@@ -58,7 +58,7 @@ pub struct Parameter {
 }
 
 /// An attribute that can be placed before a function.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Attribute {
     /// `@` character.
@@ -69,7 +69,7 @@ pub struct Attribute {
 }
 
 /// An enum representing possible ways in which a global function's name can be.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum GlobalFunctionName {
     /// Just a simple name, this is usually in local functions but some people don't do so.
@@ -138,7 +138,7 @@ pub enum GlobalFunctionName {
 }
 
 /// A struct representing a local function.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct GlobalFunction {
     /// Attributes before the function.
@@ -170,7 +170,7 @@ pub struct GlobalFunction {
 }
 
 /// Type functions (LuauSolver V2).
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TypeFunction {
     /// The `export` keyword.
@@ -1,12 +1,12 @@
 //! Numerical for loop struct
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
 use crate::types::{DoBlock, Expression, Name, Pointer};
 
 /// A struct representing a numerical for loop.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct NumericalFor {
     /// The `for` keyword.
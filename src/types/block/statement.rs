@@ -4,7 +4,7 @@
 //! outside it too, like in a formatter or a lsp.
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
 use crate::types::{
     CompoundSetExpression, DoBlock, EndOfFile, Expression, FunctionCall, GenericFor,
@@ -20,7 +20,7 @@ macro_rules! generate_statement {
     ),* $(,)?) => {
         /// All possible statements in a [`CST`](crate::types::Cst), excluding
         /// [ending ones](TerminationStatement).
-        #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+        #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
         #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
         pub enum Statement {
             /// This statement had an error and couldn't parse anything.
@@ -34,7 +34,7 @@ macro_rules! generate_statement {
             pub(crate) fn __parse(
                 token: luau_lexer::prelude::Token,
                 lexer: &mut luau_lexer::prelude::Lexer,
-                errors: &mut Vec<luau_lexer::prelude::ParseError>
+                errors: &mut Vec<$crate::types::Diagnostic>
             ) -> Option<Self> {
                 use $crate::types::Parse as _;
 
@@ -45,6 +45,36 @@ macro_rules! generate_statement {
                 }
             }
         }
+
+        impl $crate::types::Visit for Statement {
+            fn visit<'a, V: $crate::types::Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+                visitor.visit_statement(self);
+
+                if !visitor.skip_children() {
+                    match self {
+                        Self::ERROR => (),
+                        $( Self::$name(node) => node.visit(visitor), )*
+                    }
+                }
+
+                visitor.leave_statement(self);
+            }
+        }
+
+        impl $crate::types::VisitMut for Statement {
+            fn visit_mut<V: $crate::types::VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                visitor.visit_statement(self);
+
+                if !visitor.skip_children() {
+                    match self {
+                        Self::ERROR => (),
+                        $( Self::$name(node) => node.visit_mut(visitor), )*
+                    }
+                }
+
+                visitor.leave_statement(self);
+            }
+        }
     };
 }
 
@@ -3,12 +3,15 @@
 //! Module containing definition for type definitions.
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
-use crate::types::{Bracketed, BracketedList, Expression, FunctionCall, Name, Pointer, Table, Var};
+use crate::types::{
+    BinaryOperator, Bracketed, BracketedList, Expression, FunctionCall, Name, Pointer, Table,
+    UnaryOperator, Var,
+};
 
 /// Possible values for a type.
-#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum TypeValue {
     /// This [`TypeValue`] had a syntax error.
@@ -238,7 +241,7 @@ pub enum TypeValue {
 }
 
 /// Parameters of a [`TypeValue::Function`].
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ParameterTypeName {
     /// A normal `name: type` syntax.
@@ -250,7 +253,7 @@ pub enum ParameterTypeName {
 
 /// A struct for a type definition. Holds needed data to be able to write it back as valid
 /// luau.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TypeDefinition {
     /// The `export` keyword.
@@ -280,7 +283,7 @@ pub type GenericParameters = BracketedList<GenericParameterInfo>;
 
 /// A generic declaration parameter used in [`generics declarations`](GenericDeclaration).
 /// Can either be a name or a variadic pack.
-#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum GenericParameterInfo {
     /// This [`GenericParameterInfo`] had a syntax error.
@@ -301,7 +304,7 @@ pub enum GenericParameterInfo {
 
 /// A generic declaration parameter used in [`generic declarations`](GenericDeclaration).
 /// Consists of a [`parameter info`](GenericParameterInfo) and an optional default type.
-#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct GenericDeclarationParameter {
     /// The parameter passed as a generic type, can be a simple name or a generic pack.
@@ -316,7 +319,7 @@ pub struct GenericDeclarationParameter {
 }
 
 /// Struct holding **default** values for generic arguments.
-#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum GenericParameterInfoDefault {
     /// There was an error parsing.
@@ -344,7 +347,7 @@ pub enum GenericParameterInfoDefault {
 pub type GenericDeclaration = BracketedList<GenericDeclarationParameter>;
 
 /// Possible errors converting from an expression to a type definition.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ConversionError {
     /// Function calls can't be converted to types since the parser won't look for the
@@ -358,7 +361,7 @@ pub enum ConversionError {
     /// expression, pass the inner one and not the unary.
     UnaryExpression {
         /// The operator.
-        operator: Token,
+        operator: UnaryOperator,
 
         /// The actual expression this operator is affecting.
         expression: Pointer<Expression>,
@@ -371,7 +374,7 @@ pub enum ConversionError {
         left: Pointer<Expression>,
 
         /// The operator between the expressions.
-        operator: Token,
+        operator: BinaryOperator,
 
         /// The right expression.
         right: Pointer<Expression>,
@@ -1,7 +1,6 @@
-use luau_lexer::error::ParseError;
 use smol_str::SmolStr;
 
-use crate::types::Block;
+use crate::types::{Block, Diagnostic, PositionEncoding};
 
 /// An enum representing different states of a CST.
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -28,11 +27,14 @@ pub struct Cst {
     /// The [`block`](Block) of code for this scope.
     pub block: Block,
 
-    /// All [`syntactical errors`](ParseError) in this CST.
-    pub errors: Vec<ParseError>,
+    /// All [`syntactical errors`](Diagnostic) in this CST.
+    pub errors: Vec<Diagnostic>,
 
     /// The status of the [`CST`](Cst). If it isn't [`complete`](AstStatus::Complete), it's
     /// better to not use it for operations which affect the source code, like formatting;
     /// the output will have missing parts of the code.
     pub status: AstStatus,
+
+    /// How the columns of all positions in this [`CST`](Cst) are counted.
+    pub position_encoding: PositionEncoding,
 }
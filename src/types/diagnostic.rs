@@ -0,0 +1,181 @@
+//! Diagnostics reported while parsing or checking a [`Cst`](crate::types::Cst).
+
+use lsp_types::Position;
+use smol_str::SmolStr;
+
+/// How severe a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Severity {
+    /// The code is invalid, the [`Cst`](crate::types::Cst) will be missing
+    /// parts of it.
+    #[default]
+    Error,
+
+    /// The code is valid but most likely doesn't do what was intended.
+    Warning,
+
+    /// Extra information about the code.
+    Information,
+
+    /// A suggestion to improve the code.
+    Hint,
+}
+
+/// Helper macro to generate [`DiagnosticCode`] along with the functions
+/// getting its code, name, and default severity.
+macro_rules! generate_diagnostic_codes {
+    ($( $(#[$meta:meta])* $code:literal $name:ident => $severity:ident ),* $(,)?) => {
+        /// A stable code identifying the kind of a [`Diagnostic`]. Codes never
+        /// change meaning between versions, new ones are only ever added, which
+        /// makes them safe to filter, suppress, or localize diagnostics with.
+        #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+        #[non_exhaustive]
+        pub enum DiagnosticCode {
+            $(
+                $(#[$meta])*
+                #[doc = ""]
+                #[doc = concat!("Code: `", $code, "`.")]
+                $name,
+            )*
+        }
+
+        impl DiagnosticCode {
+            /// All codes, in order.
+            pub const ALL: &[Self] = &[$( Self::$name ),*];
+
+            /// The code itself, ex. `E0001`.
+            #[inline]
+            pub const fn code(self) -> &'static str {
+                match self {
+                    $( Self::$name => $code, )*
+                }
+            }
+
+            /// The name of the code, ex. `MissingThen`.
+            #[inline]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $( Self::$name => stringify!($name), )*
+                }
+            }
+
+            /// The severity diagnostics with this code have by default.
+            #[inline]
+            pub const fn severity(self) -> Severity {
+                match self {
+                    $( Self::$name => Severity::$severity, )*
+                }
+            }
+        }
+    };
+}
+
+generate_diagnostic_codes! {
+    /// `then` is missing after the condition of an `if` or `elseif`.
+    "E0001" MissingThen => Error,
+    /// `end` is missing for a block.
+    "E0002" MissingEnd => Error,
+    /// `else` is missing in an if expression.
+    "E0003" MissingElse => Error,
+    /// `until` is missing for a `repeat` block.
+    "E0004" MissingUntil => Error,
+    /// `=` is missing.
+    "E0005" MissingEqual => Error,
+    /// `,` is missing.
+    "E0006" MissingComma => Error,
+    /// `->` is missing in a function type.
+    "E0007" MissingArrow => Error,
+    /// The closing bracket of a pair is missing.
+    "E0008" MissingClosingBracket => Error,
+    /// An identifier is missing.
+    "E0009" MissingIdentifier => Error,
+    /// An expression is missing.
+    "E0010" MissingExpression => Error,
+    /// A type is missing.
+    "E0011" MissingType => Error,
+    /// A variable is missing in an assignment.
+    "E0012" MissingVariable => Error,
+    /// An attribute is missing after `@`.
+    "E0013" MissingAttribute => Error,
+    /// Function parameters are missing.
+    "E0014" MissingParameters => Error,
+    /// A generic declaration is missing in `<>`.
+    "E0015" MissingGenericDeclaration => Error,
+    /// A token that can't start a statement.
+    "E0016" UnexpectedToken => Error,
+    /// A statement after `return`, `break`, or `continue` in the same block.
+    "E0017" StatementAfterTermination => Error,
+    /// A compound assignment operator (ex. `+=`) used in an expression.
+    "E0018" CompoundAssignmentInExpression => Error,
+    /// An interpolation without an expression, ex. `` `{}` ``.
+    "E0019" EmptyInterpolation => Error,
+    /// An interpolation without a closing `}`.
+    "E0020" UnclosedInterpolation => Error,
+    /// A token the lexer couldn't understand, ex. an unfinished string.
+    "E0021" InvalidToken => Error,
+    /// An invalid escape sequence in a string.
+    "E0022" InvalidEscape => Error,
+    /// A table field is missing.
+    "E0023" MissingTableField => Error,
+    /// Input left after a fragment parsed with, ex.
+    /// [`Parser::parse_expression`](crate::prelude::Parser::parse_expression).
+    "E0024" TrailingInput => Error,
+    /// A statement is missing.
+    "E0025" MissingStatement => Error,
+    /// A language feature that's disabled in the
+    /// [`ParserOptions`](crate::types::ParserOptions).
+    "E0026" DisabledFeature => Error,
+    /// A number that can't be parsed, ex. `0x` without digits.
+    "E0027" MalformedNumber => Error,
+    /// A hex or binary number that doesn't fit in 64 bits.
+    "W0001" NumberOutOfRange => Warning,
+    /// A number that can't be represented exactly and will be rounded.
+    "W0002" ImpreciseNumber => Warning,
+}
+
+/// A span related to a [`Diagnostic`], ex. the `if` that a missing `end`
+/// belongs to.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RelatedSpan {
+    /// The start of the span.
+    pub start: Position,
+
+    /// The end of the span.
+    pub end: Position,
+
+    /// A message explaining how this span is related.
+    pub message: SmolStr,
+}
+
+/// A diagnostic reported while parsing or checking a
+/// [`Cst`](crate::types::Cst).
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Diagnostic {
+    /// The code identifying this diagnostic.
+    pub code: DiagnosticCode,
+
+    /// How severe this diagnostic is.
+    pub severity: Severity,
+
+    /// The start of the diagnostic.
+    pub start: Position,
+
+    /// The end of the diagnostic.
+    pub end: Position,
+
+    /// A human readable message.
+    pub message: SmolStr,
+
+    /// What was expected, ex. `<then>` or `<expr>`.
+    pub expected: Option<SmolStr>,
+
+    /// What was found instead, ex. `<eof>`.
+    pub found: Option<SmolStr>,
+
+    /// Other spans related to this diagnostic.
+    pub related: Vec<RelatedSpan>,
+}
@@ -1,12 +1,12 @@
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
 use crate::types::{
     Attribute, Block, BracketedList, Expression, GenericDeclaration, Parameter, Pointer, PrefixExp, Table, TypeValue
 };
 
 /// Different ways a function can be called.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum FunctionCallInvoked {
     /// A standalone function call or one in a table.
@@ -39,7 +39,7 @@ pub enum FunctionCallInvoked {
 /// ```lua
 /// local _ = foo(1, 2, 3)
 /// ```
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FunctionCall {
     /// The function being called.
@@ -50,7 +50,7 @@ pub struct FunctionCall {
 }
 
 /// All possible arguments that can be passed to a function.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum FunctionArguments {
     /// A standalone string.
@@ -76,7 +76,7 @@ pub enum FunctionArguments {
 }
 
 /// Arguments that can be passed in a [`FunctionCall`].
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum FunctionArgument {
     /// A normal [`expression`](Expression).
@@ -91,7 +91,7 @@ pub enum FunctionArgument {
 }
 
 /// All possible arguments that can be passed to a function.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Closure {
     /// Attributes before the function.
@@ -103,7 +103,7 @@ pub struct Closure {
     /// The generics of this function.
     pub generics: Option<Pointer<GenericDeclaration>>,
 
-    /// All [`parameters`](Name) of the function.
+    /// All [`parameters`](Parameter) of the function.
     pub parameters: BracketedList<Parameter>,
 
     /// The `:` character between closing parenthesis and returns.
@@ -1,11 +1,11 @@
 //! Types representing all valid Luau expressions.
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 
 use crate::types::{Bracketed, Pointer, Table, TypeValue};
 
-reexport!(table, var, function);
+reexport!(table, var, function, operator, string);
 
 /// A struct representing an expression wrapped in parenthesis.
 pub type ExpressionWrap = Bracketed<Pointer<Expression>>;
@@ -17,7 +17,7 @@ pub type ExpressionWrap = Bracketed<Pointer<Expression>>;
 /// local _ = foo()
 /// local _ = (foo)
 /// ```
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PrefixExp {
     /// A normal variable reference.
@@ -42,7 +42,7 @@ pub enum PrefixExp {
 }
 
 /// An enum representing all possible values for an expression.
-#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Expression {
     /// This [`Expression`] had a syntax error.
@@ -58,9 +58,24 @@ pub enum Expression {
     /// Any number, be it a float, an unsigned integer, an integer or a hex digit.
     Number(Token),
 
-    /// A string, be it double quotes, single quotes, interpolated string, or multi-line.
+    /// A string, be it double quotes, single quotes, multi-line, or an interpolated
+    /// string without any interpolations.
     String(Token),
 
+    /// The variadic values of the current function.
+    ///
+    /// ```lua
+    /// local _ = ...
+    /// ```
+    VariadicValues(Token),
+
+    /// An interpolated string with at least one interpolation.
+    ///
+    /// ```lua
+    /// local _ = `Hello, {name}!`
+    /// ```
+    InterpolatedString(InterpolatedString),
+
     /// An **anonymous** function.
     ///
     /// ```lua
@@ -107,7 +122,7 @@ pub enum Expression {
     /// ```
     UnaryExpression {
         /// The operator.
-        operator: Token,
+        operator: UnaryOperator,
 
         /// The actual expression this operator is affecting.
         expression: Pointer<Expression>,
@@ -121,12 +136,16 @@ pub enum Expression {
     /// local bar = 1 == 1
     /// local qux = bar // 2
     /// ```
+    ///
+    /// Nested binary expressions follow Luau's precedence and associativity rules,
+    /// `1 + 2 * 3` will have `2 * 3` as its [`right`](Expression::BinaryExpression::right)
+    /// while `1 - 2 - 3` will have `1 - 2` as its [`left`](Expression::BinaryExpression::left).
     BinaryExpression {
         /// The left expression.
         left: Pointer<Expression>,
 
         /// The operator between the expressions.
-        operator: Token,
+        operator: BinaryOperator,
 
         /// The right expression.
         right: Pointer<Expression>,
@@ -155,7 +174,7 @@ pub enum Expression {
 }
 
 /// A struct representing an elseif **expression**, only exists in variable declarations.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct IfExpression {
     /// The `if` keyword.
@@ -168,7 +187,7 @@ pub struct IfExpression {
     pub then_keyword: Token,
 
     /// The [`expression`](Expression) that this statement would resolve to if the
-    /// [`condition`](Expression::IfExpression::condition) evaluated to `true`.
+    /// [`condition`](IfExpression::condition) evaluated to `true`.
     pub if_expression: Pointer<Expression>,
 
     /// All `elseif` expressions.
@@ -182,7 +201,7 @@ pub struct IfExpression {
 }
 
 /// A struct representing an elseif **expression**, only exists in expressions.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ElseIfExpression {
     /// The `elseif` keyword.
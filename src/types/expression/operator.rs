@@ -0,0 +1,111 @@
+//! Binary and unary operators used in expressions.
+
+use luau_lexer::prelude::Token;
+use luau_parser_derive::Range;
+
+/// All binary operators that can be used in an
+/// [`Expression::BinaryExpression`](crate::types::Expression::BinaryExpression).
+/// Each variant holds the source token of the operator.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BinaryOperator {
+    /// `+`
+    Plus(Token),
+
+    /// `-`
+    Minus(Token),
+
+    /// `*`
+    Multiplication(Token),
+
+    /// `/`
+    Division(Token),
+
+    /// `//`
+    FloorDivision(Token),
+
+    /// `%`
+    Modulo(Token),
+
+    /// `^`
+    Exponentiation(Token),
+
+    /// `..`
+    Concatenation(Token),
+
+    /// `==`
+    Equal(Token),
+
+    /// `~=`
+    NotEqual(Token),
+
+    /// `<`
+    LessThan(Token),
+
+    /// `<=`
+    LessThanOrEqualTo(Token),
+
+    /// `>`
+    GreaterThan(Token),
+
+    /// `>=`
+    GreaterThanOrEqualTo(Token),
+
+    /// `and`
+    And(Token),
+
+    /// `or`
+    Or(Token),
+
+    /// A compound assignment operator (like `+=`) used in an expression, which
+    /// is an error. It's only kept so that the CST still holds all the tokens.
+    CompoundAssignment(Token),
+}
+
+/// All unary operators that can be used in an
+/// [`Expression::UnaryExpression`](crate::types::Expression::UnaryExpression).
+/// Each variant holds the source token of the operator.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum UnaryOperator {
+    /// `-`
+    Minus(Token),
+
+    /// `not`
+    Not(Token),
+
+    /// `#`
+    Length(Token),
+}
+
+/// The associativity of an operator, it decides how a chain of operators with
+/// the same precedence is grouped.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`.
+    Right,
+}
+
+/// The category an operator belongs to.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum OperatorCategory {
+    /// `+`, `-`, `*`, `/`, `//`, `%`, `^` and unary `-`.
+    Arithmetic,
+
+    /// `==`, `~=`, `<`, `<=`, `>` and `>=`.
+    Comparison,
+
+    /// `and`, `or` and `not`.
+    Logical,
+
+    /// `..`
+    Concatenation,
+
+    /// `#`
+    Length,
+}
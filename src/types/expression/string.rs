@@ -0,0 +1,39 @@
+//! Types representing interpolated strings.
+
+use luau_lexer::prelude::Token;
+use luau_parser_derive::Range;
+
+use crate::types::{Expression, Pointer};
+
+/// A part of an [`InterpolatedString`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum InterpolatedStringSegment {
+    /// A literal part of the string, including the characters around it, which
+    /// are the opening backtick or `}` at the start and the closing backtick or
+    /// `{` at the end.
+    ///
+    /// ```lua
+    /// `Hello, {
+    /// }!`
+    /// ```
+    String(Token),
+
+    /// An expression between `{` and `}`.
+    Expression(Pointer<Expression>),
+}
+
+/// A string with interpolations. The string is split into
+/// [`segments`](InterpolatedStringSegment), which always start and end with a
+/// [`string`](InterpolatedStringSegment::String) and alternate between strings
+/// and expressions.
+///
+/// ```lua
+/// local _ = `Hello, {name}!`
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct InterpolatedString {
+    /// The segments of this string, in source order.
+    pub segments: Vec<InterpolatedStringSegment>,
+}
@@ -0,0 +1,51 @@
+//! Conversions between byte offsets and the different ways of counting columns.
+
+/// How the [`character`](lsp_types::Position::character) of a
+/// [`Position`](lsp_types::Position) is counted. These match LSP's
+/// [`PositionEncodingKind`](lsp_types::PositionEncodingKind)s.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PositionEncoding {
+    /// Columns count bytes.
+    Utf8,
+
+    /// Columns count UTF-16 code units, which is what LSP uses by default and
+    /// what editors like VS Code expect.
+    Utf16,
+
+    /// Columns count characters (unicode scalar values). This is what the lexer
+    /// produces.
+    #[default]
+    Utf32,
+}
+
+/// A non-ASCII character in the text a [`LineIndex`] was built from. ASCII
+/// characters take one unit in all [`encodings`](PositionEncoding) so they
+/// don't need to be stored.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct WideCharacter {
+    /// The line the character is in.
+    pub(crate) line: u32,
+
+    /// The column of the character, in [`PositionEncoding::Utf32`].
+    pub(crate) character: u32,
+
+    /// The number of bytes the character takes.
+    pub(crate) len_utf8: u8,
+}
+
+/// An index of the lines of some text, used to convert between byte offsets and
+/// [`Position`](lsp_types::Position)s in any [`PositionEncoding`]. It's built
+/// once, in linear time, and all conversions after that only do binary
+/// searches over the line, so it's meant to be kept around and reused.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineIndex {
+    /// The byte offset at which each line starts.
+    pub(crate) line_starts: Vec<usize>,
+
+    /// All non-ASCII characters, sorted by position.
+    pub(crate) wide_characters: Vec<WideCharacter>,
+
+    /// The length of the text, in bytes.
+    pub(crate) len: usize,
+}
@@ -2,7 +2,7 @@
 
 use std::num::{ParseFloatError, ParseIntError};
 
-/// An enum representing the return type of [`Number::parse`]..
+/// An enum representing the return type of [`ParsedNumber::parse`].
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ParsedNumber {
@@ -16,8 +16,8 @@ pub enum ParsedNumber {
     Other(f64),
 }
 
-/// An enum representing errors that can occur during [`Number::parse`] stopping it from
-/// parsing the number, they should only be out-of-range errors and thus should be
+/// An enum representing errors that can occur during [`ParsedNumber::parse`] stopping it
+/// from parsing the number, they should only be out-of-range errors and thus should be
 /// displayed for the user asking them to change the number.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ParseNumberError {
@@ -26,4 +26,16 @@ pub enum ParseNumberError {
 
     /// Other numbers in Roblox are stored as floats.
     Other(ParseFloatError),
+
+    /// The number is valid but can't be represented, which is the case for hex and
+    /// binary numbers larger than [`u64::MAX`]. Other numbers too large to be stored
+    /// in an [`f64`] are infinite, like in Luau.
+    OutOfRange,
 }
+
+/// The runtime value of a string literal, returned by [`ParsedString::parse`]. Strings
+/// in Luau are sequences of bytes and escapes like `\xFF` can make them invalid UTF-8,
+/// which is why bytes are stored instead of a [`String`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ParsedString(pub Vec<u8>);
@@ -25,7 +25,8 @@ macro_rules! reexport {
 }
 
 reexport!(
-    block, bracketed, cst, expression, list, literals, name, traits, value
+    block, bracketed, cst, diagnostic, expression, line_index, list, literals, name, options,
+    traits, value, visitor
 );
 
 /// The main pointer used in the [`Cst`]. It's just [`Rc`](std::rc::Rc)
@@ -0,0 +1,71 @@
+//! Options changing how code is parsed.
+
+use crate::types::PositionEncoding;
+
+/// The language a [`Parser`](crate::prelude::Parser) targets, this decides which
+/// [`LanguageFeatures`] are enabled.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LanguageLevel {
+    /// Plain Lua 5.1, all Luau-only features are disabled.
+    Lua51,
+
+    /// Luau with the old type solver, which doesn't support
+    /// [`type functions`](LanguageFeatures::type_functions).
+    Luau,
+
+    /// Luau with the new type solver, all features are enabled.
+    #[default]
+    LuauNewSolver,
+}
+
+/// Language features that can be disabled. Disabled features are still parsed,
+/// but an error is reported wherever they're used.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LanguageFeatures {
+    /// `continue` statements.
+    pub continue_statement: bool,
+
+    /// Compound assignments, ex. `x += 1`.
+    pub compound_assignment: bool,
+
+    /// The floor division operator, `//`.
+    pub floor_division: bool,
+
+    /// Function attributes, ex. `@native`.
+    pub attributes: bool,
+
+    /// Type annotations, type casts, generics, and type definitions.
+    pub types: bool,
+
+    /// Type functions, ex. `type function foo() end`.
+    pub type_functions: bool,
+
+    /// Default values of generics, ex. `type Foo<T = string> = T`.
+    pub generic_defaults: bool,
+
+    /// Interpolated strings, ex. `` `Hello {name}` ``, including backtick
+    /// strings without any interpolation, ex. `` `Hello` ``.
+    pub string_interpolation: bool,
+
+    /// If expressions, ex. `if a then b else c`.
+    pub if_expressions: bool,
+
+    /// Binary numbers, ex. `0b101`.
+    pub binary_numbers: bool,
+
+    /// `_` separators in numbers, ex. `1_000`.
+    pub number_separators: bool,
+}
+
+/// Options changing how a [`Parser`](crate::prelude::Parser) parses code.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ParserOptions {
+    /// The enabled language features.
+    pub features: LanguageFeatures,
+
+    /// How the columns of positions in parsed code are counted.
+    pub position_encoding: PositionEncoding,
+}
@@ -1,24 +1,72 @@
 //! Module holding all trait definitions in this crate.
 
-use luau_lexer::prelude::{Lexer, ParseError, Token};
+use luau_lexer::prelude::{Lexer, Token};
 use lsp_types::Range;
+use std::{fmt, io};
+
+use crate::{
+    types::Diagnostic,
+    utils::{IoAdapter, next_token},
+};
 
 /// A trait to print the token as-is, while preserving all user spaces, comments
 /// and styling.
+///
+/// Printing is done by streaming into a [`fmt::Write`] sink, the
+/// [`String`]-returning methods are thin wrappers around the `*_to` ones, so
+/// prefer the latter when writing to an existing buffer or a file.
 pub trait Print {
     /// Prints only the very final trivia. Used for the default implementation of
-    /// [`Print::print`], which just joins [`Print::print_without_final_trivia`]
-    /// and this function.
-    fn print_final_trivia(&self) -> String;
+    /// [`Print::print_to`], which just joins
+    /// [`Print::print_without_final_trivia_to`] and this function.
+    fn print_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result;
 
     /// Prints the whole token including all surrounding trivia, excluding the
     /// very last trailing trivia.
-    fn print_without_final_trivia(&self) -> String;
+    fn print_without_final_trivia_to(&self, out: &mut impl fmt::Write) -> fmt::Result;
 
     /// Prints the whole token including all surrounding trivia.
     #[inline]
+    fn print_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.print_without_final_trivia_to(out)?;
+        self.print_final_trivia_to(out)
+    }
+
+    /// Same as [`Print::print_to`] but writes to an [`io::Write`] sink.
+    #[inline]
+    fn print_to_io(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut adapter = IoAdapter::new(out);
+
+        self.print_to(&mut adapter).map_err(|_| adapter.into_error())
+    }
+
+    /// Prints only the very final trivia. See [`Print::print_final_trivia_to`].
+    #[inline]
+    fn print_final_trivia(&self) -> String {
+        let mut string = String::new();
+        let _ = self.print_final_trivia_to(&mut string);
+
+        string
+    }
+
+    /// Prints the whole token including all surrounding trivia, excluding the
+    /// very last trailing trivia. See [`Print::print_without_final_trivia_to`].
+    #[inline]
+    fn print_without_final_trivia(&self) -> String {
+        let mut string = String::new();
+        let _ = self.print_without_final_trivia_to(&mut string);
+
+        string
+    }
+
+    /// Prints the whole token including all surrounding trivia. See
+    /// [`Print::print_to`].
+    #[inline]
     fn print(&self) -> String {
-        self.print_without_final_trivia() + &self.print_final_trivia()
+        let mut string = String::new();
+        let _ = self.print_to(&mut string);
+
+        string
     }
 }
 
@@ -26,7 +74,7 @@ pub trait Print {
 /// a specific [`token`](Token).
 pub trait Parse<O = Self> {
     /// Try parsing the current item, starting from the passed token.
-    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<O>;
+    fn parse(token: Token, lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<O>;
 }
 
 /// A wrapper trait for [`Parse`] where it would reset the lexer's state upon
@@ -36,10 +84,10 @@ where
     O: Parse<O>,
 {
     /// Try parsing and reset the lexer's state upon failure.
-    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<ParseError>) -> Option<O> {
+    fn try_parse(lexer: &mut Lexer, errors: &mut Vec<Diagnostic>) -> Option<O> {
         let state = lexer.save_state();
 
-        match O::parse(lexer.next_token(), lexer, errors) {
+        match O::parse(next_token(lexer), lexer, errors) {
             value @ Some(_) => value,
             None => {
                 lexer.set_state(state);
@@ -58,7 +106,7 @@ pub trait ParseWithArgs<T, O = Self> {
     fn parse_with(
         token: Token,
         lexer: &mut Lexer,
-        errors: &mut Vec<ParseError>,
+        errors: &mut Vec<Diagnostic>,
         args: T,
     ) -> Option<O>;
 }
@@ -71,10 +119,10 @@ where
 {
     /// Try parsing and reset the lexer's state upon failure.
     #[inline]
-    fn try_parse_with(lexer: &mut Lexer, errors: &mut Vec<ParseError>, args: T) -> Option<O> {
+    fn try_parse_with(lexer: &mut Lexer, errors: &mut Vec<Diagnostic>, args: T) -> Option<O> {
         let state = lexer.save_state();
 
-        match O2::parse_with(lexer.next_token(), lexer, errors, args) {
+        match O2::parse_with(next_token(lexer), lexer, errors, args) {
             value @ Some(_) => value,
             None => {
                 lexer.set_state(state);
@@ -109,6 +157,11 @@ pub enum GetRangeError {
 }
 
 /// A trait for getting the range for this specific item.
+///
+/// Columns count characters by default, this can be changed with
+/// [`Parser::set_position_encoding`](crate::prelude::Parser::set_position_encoding)
+/// and is stored in [`Cst::position_encoding`](crate::types::Cst::position_encoding).
+/// Use a [`LineIndex`](crate::types::LineIndex) to get byte offsets.
 pub trait GetRange {
     /// Get the range of the node. This will only fail if
     /// [`Cst.status`](crate::types::Cst::status) is
@@ -1,7 +1,7 @@
 //! Holding all needed information for tables.
 
 use luau_lexer::prelude::Token;
-use luau_parser_derive::{Print, Range};
+use luau_parser_derive::Range;
 use smol_str::SmolStr;
 
 use crate::types::{Bracketed, BracketedList, Expression, Pointer, TypeValue};
@@ -54,7 +54,7 @@ pub enum TableKey {
 }
 
 /// A struct representing one table field. It'll always have a [`key`](TableKey) and a
-/// value that's either a [`type`](TypeDefinition) or an [`expression`](Expression). See
+/// value that's either a [`type`](TypeValue) or an [`expression`](Expression). See
 /// [`table field values`](TableFieldValue).
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -71,7 +71,7 @@ pub struct TableField {
 }
 
 /// A possible value for a [`table field`](TableField).
-#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum TableFieldValue {
     /// This [`TableFieldValue`] had a syntax error.
@@ -93,6 +93,6 @@ pub enum TableFieldValue {
 }
 
 /// Struct representing a luau table.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range, Print)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Range)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Table(pub BracketedList<TableField>);
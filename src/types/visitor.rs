@@ -0,0 +1,230 @@
+//! # Visitors
+//!
+//! Traits used to traverse a [`Cst`] without manually matching over every node.
+//! Implement [`Visitor`] (or [`VisitorMut`]) and override the `visit_*` and
+//! `leave_*` methods for the nodes you care about, then call [`Visit::visit`]
+//! (or [`VisitMut::visit_mut`]) on the node to start from, usually the [`Cst`]
+//! itself.
+//!
+//! `visit_*` methods are called before the children of a node are visited and
+//! `leave_*` methods are called after. For [`Visitor`], they default to calling
+//! [`Visitor::visit_node`] and [`Visitor::leave_node`] with a [`Node`], which
+//! is useful for visitors that treat all nodes the same way. Generic
+//! containers like [`Bracketed`], [`List`] and [`Pointer`] don't have methods
+//! of their own, but all of their items, including brackets and separators,
+//! are still visited. Returning
+//! `true` from [`Visitor::skip_children`] right after a `visit_*` method skips
+//! the children of that node.
+//!
+//! ## Trivia
+//!
+//! Trivia between 2 tokens is stored as both the trailing trivia of the first
+//! and the leading trivia of the second, so only the
+//! [`leading trivia`](Token::leading_trivia) of each token is visited, which
+//! means that every trivia in the source code is visited exactly once, the
+//! trivia at the end of the file belongs to the [`EndOfFile`] token.
+//!
+//! [`Bracketed`]: crate::types::Bracketed
+//! [`List`]: crate::types::List
+//! [`Pointer`]: crate::types::Pointer
+
+use luau_lexer::prelude::{Token, Trivia};
+
+use crate::types::{
+    Attribute, BinaryOperator, Block, Closure, CompoundSetExpression, Cst, DoBlock,
+    ElseIfExpression, ElseIfStatement, ElseStatement, EndOfFile, Expression, FunctionArgument,
+    FunctionArguments, FunctionCall, FunctionCallInvoked, GenericDeclarationParameter, GenericFor,
+    GenericParameterInfo, GenericParameterInfoDefault, GlobalFunction, GlobalFunctionName,
+    IfExpression, IfStatement, InterpolatedString, InterpolatedStringSegment, LocalAssignment,
+    LocalFunction, Name, NumericalFor, Parameter, ParameterTypeName, PrefixExp, RepeatBlock,
+    SetExpression, Statement, Table, TableAccess, TableAccessKey, TableAccessPrefix, TableField,
+    TableFieldValue, TableKey, TerminationStatement, TypeDefinition, TypeFunction, TypeValue,
+    UnaryOperator, Var, WhileLoop,
+};
+
+/// A trait for nodes that can be traversed by a [`Visitor`].
+pub trait Visit {
+    /// Visit this node and all of its children, in source order.
+    fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V);
+}
+
+/// A trait for nodes that can be traversed by a [`VisitorMut`].
+pub trait VisitMut {
+    /// Visit this node and all of its children, in source order, allowing the
+    /// visitor to edit them.
+    ///
+    /// # Note
+    ///
+    /// Nodes behind a [`Pointer`](crate::types::Pointer) that's shared with
+    /// other nodes (or [`CST`](Cst)s) are cloned before being edited, so the
+    /// edits never leak to other trees.
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V);
+}
+
+/// Helper macro to generate the [`Node`] enum and the [`Visitor`] and
+/// [`VisitorMut`] traits. The [`Visit`] implementation of every node calls its
+/// `visit_*` and `leave_*` methods, so a node missing from here doesn't compile.
+macro_rules! generate_visitors {
+    ($( $visit:ident, $leave:ident => $ty:ident ),* $(,)?) => {
+        /// A reference to any node in a [`CST`](Cst).
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Node<'a> {
+            $(
+                #[doc = concat!("A [`", stringify!($ty), "`].")]
+                $ty(&'a $ty),
+            )*
+        }
+
+        impl Visit for Node<'_> {
+            fn visit<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+                match self {
+                    $( Self::$ty(node) => node.visit(visitor), )*
+                }
+            }
+        }
+
+        impl $crate::types::GetRange for Node<'_> {
+            fn get_range(&self) -> Result<lsp_types::Range, $crate::types::GetRangeError> {
+                match self {
+                    $( Self::$ty(node) => node.get_range(), )*
+                }
+            }
+        }
+
+        /// A visitor that traverses [`CST`](Cst) nodes by reference. By default,
+        /// all `visit_*` and `leave_*` methods call [`Visitor::visit_node`] and
+        /// [`Visitor::leave_node`] respectively, which do nothing.
+        #[allow(unused_variables)]
+        pub trait Visitor<'a> {
+            /// Called before visiting the children of any node, unless the
+            /// specific `visit_*` method for it was overridden.
+            #[inline]
+            fn visit_node(&mut self, node: Node<'a>) {}
+
+            /// Called after visiting the children of any node, unless the
+            /// specific `leave_*` method for it was overridden.
+            #[inline]
+            fn leave_node(&mut self, node: Node<'a>) {}
+
+            /// Called right after each `visit_*` method, the children of the
+            /// node are skipped if it returns `true`. The matching `leave_*`
+            /// method is still called.
+            #[inline]
+            fn skip_children(&mut self) -> bool {
+                false
+            }
+
+            $(
+                #[doc = concat!("Called before visiting the children of a [`", stringify!($ty), "`].")]
+                #[inline]
+                fn $visit(&mut self, node: &'a $ty) {
+                    self.visit_node(Node::$ty(node))
+                }
+
+                #[doc = concat!("Called after visiting the children of a [`", stringify!($ty), "`].")]
+                #[inline]
+                fn $leave(&mut self, node: &'a $ty) {
+                    self.leave_node(Node::$ty(node))
+                }
+            )*
+
+            /// Called when visiting a [`Trivia`].
+            #[inline]
+            fn visit_trivia(&mut self, node: &'a Trivia) {}
+
+            /// Called after visiting a [`Trivia`].
+            #[inline]
+            fn leave_trivia(&mut self, node: &'a Trivia) {}
+        }
+
+        /// A visitor that traverses [`CST`](Cst) nodes by mutable reference. All
+        /// methods do nothing by default.
+        #[allow(unused_variables)]
+        pub trait VisitorMut {
+            /// Called right after each `visit_*` method, the children of the
+            /// node are skipped if it returns `true`. The matching `leave_*`
+            /// method is still called.
+            #[inline]
+            fn skip_children(&mut self) -> bool {
+                false
+            }
+
+            $(
+                #[doc = concat!("Called before visiting the children of a [`", stringify!($ty), "`].")]
+                #[inline]
+                fn $visit(&mut self, node: &mut $ty) {}
+
+                #[doc = concat!("Called after visiting the children of a [`", stringify!($ty), "`].")]
+                #[inline]
+                fn $leave(&mut self, node: &mut $ty) {}
+            )*
+
+            /// Called when visiting a [`Trivia`].
+            #[inline]
+            fn visit_trivia(&mut self, node: &mut Trivia) {}
+
+            /// Called after visiting a [`Trivia`].
+            #[inline]
+            fn leave_trivia(&mut self, node: &mut Trivia) {}
+        }
+    };
+}
+
+generate_visitors! {
+    visit_cst, leave_cst => Cst,
+    visit_block, leave_block => Block,
+    visit_statement, leave_statement => Statement,
+    visit_termination_statement, leave_termination_statement => TerminationStatement,
+
+    visit_local_function, leave_local_function => LocalFunction,
+    visit_global_function, leave_global_function => GlobalFunction,
+    visit_global_function_name, leave_global_function_name => GlobalFunctionName,
+    visit_type_function, leave_type_function => TypeFunction,
+    visit_parameter, leave_parameter => Parameter,
+    visit_attribute, leave_attribute => Attribute,
+    visit_local_assignment, leave_local_assignment => LocalAssignment,
+    visit_set_expression, leave_set_expression => SetExpression,
+    visit_compound_set_expression, leave_compound_set_expression => CompoundSetExpression,
+    visit_do_block, leave_do_block => DoBlock,
+    visit_generic_for, leave_generic_for => GenericFor,
+    visit_numerical_for, leave_numerical_for => NumericalFor,
+    visit_if_statement, leave_if_statement => IfStatement,
+    visit_else_if_statement, leave_else_if_statement => ElseIfStatement,
+    visit_else_statement, leave_else_statement => ElseStatement,
+    visit_repeat_block, leave_repeat_block => RepeatBlock,
+    visit_while_loop, leave_while_loop => WhileLoop,
+    visit_end_of_file, leave_end_of_file => EndOfFile,
+
+    visit_type_definition, leave_type_definition => TypeDefinition,
+    visit_type_value, leave_type_value => TypeValue,
+    visit_parameter_type_name, leave_parameter_type_name => ParameterTypeName,
+    visit_generic_parameter_info, leave_generic_parameter_info => GenericParameterInfo,
+    visit_generic_declaration_parameter, leave_generic_declaration_parameter => GenericDeclarationParameter,
+    visit_generic_parameter_info_default, leave_generic_parameter_info_default => GenericParameterInfoDefault,
+
+    visit_expression, leave_expression => Expression,
+    visit_prefix_exp, leave_prefix_exp => PrefixExp,
+    visit_if_expression, leave_if_expression => IfExpression,
+    visit_else_if_expression, leave_else_if_expression => ElseIfExpression,
+    visit_binary_operator, leave_binary_operator => BinaryOperator,
+    visit_unary_operator, leave_unary_operator => UnaryOperator,
+    visit_var, leave_var => Var,
+    visit_table_access, leave_table_access => TableAccess,
+    visit_table_access_prefix, leave_table_access_prefix => TableAccessPrefix,
+    visit_table_access_key, leave_table_access_key => TableAccessKey,
+    visit_function_call, leave_function_call => FunctionCall,
+    visit_function_call_invoked, leave_function_call_invoked => FunctionCallInvoked,
+    visit_function_arguments, leave_function_arguments => FunctionArguments,
+    visit_function_argument, leave_function_argument => FunctionArgument,
+    visit_closure, leave_closure => Closure,
+    visit_interpolated_string, leave_interpolated_string => InterpolatedString,
+    visit_interpolated_string_segment, leave_interpolated_string_segment => InterpolatedStringSegment,
+    visit_name, leave_name => Name,
+
+    visit_table, leave_table => Table,
+    visit_table_field, leave_table_field => TableField,
+    visit_table_key, leave_table_key => TableKey,
+    visit_table_field_value, leave_table_field_value => TableFieldValue,
+
+    visit_token, leave_token => Token,
+}
@@ -1,8 +1,10 @@
 //! Utility functions for this crate.
 
+use lsp_types::Position;
 use luau_lexer::prelude::{
-    CompoundOperator, Keyword, Literal, Operator, PartialKeyword, Symbol, TokenType,
+    CompoundOperator, Keyword, Lexer, Literal, Operator, PartialKeyword, Symbol, Token, TokenType,
 };
+use std::{fmt, io};
 
 // Optimization trick
 // The functions here should all be `O(1)`, making them return `String`
@@ -15,7 +17,7 @@ use luau_lexer::prelude::{
 /// string. Unlike [`get_token_type_display_extended`], this doesn't differentiate
 /// between items of the same enum, ie. all items in [`Symbol`] will have the same
 /// output.
-pub(crate) fn get_token_type_display(token_type: &TokenType) -> &str {
+pub(crate) fn get_token_type_display(token_type: &TokenType) -> &'static str {
     match token_type {
         TokenType::Keyword(_) => "<keyword>",
         TokenType::PartialKeyword(_) => "<partial keyword>",
@@ -33,7 +35,7 @@ pub(crate) fn get_token_type_display(token_type: &TokenType) -> &str {
 /// Display the passed [`token type`](TokenType) as a string. Unlike
 /// [`get_token_type_display`], this differentiates between items of the same enum,
 /// ie. [`Symbol::Colon`] and [`Symbol::Equal`] will have different outputs.
-pub(crate) fn get_token_type_display_extended(token_type: &TokenType) -> &str {
+pub(crate) fn get_token_type_display_extended(token_type: &TokenType) -> &'static str {
     match token_type {
         TokenType::Keyword(keyword) => match keyword {
             Keyword::Local => "<local>",
@@ -120,3 +122,67 @@ pub(crate) fn get_token_type_display_extended(token_type: &TokenType) -> &str {
         TokenType::Comment(_) => "<comment>",
     }
 }
+
+/// Move the passed position after the passed character, the same way the lexer
+/// does it.
+#[inline]
+pub(crate) fn advance_position(position: &mut Position, character: char) {
+    if character == '\n' {
+        position.line += 1;
+        position.character = 0;
+    } else {
+        position.character += 1;
+    }
+}
+
+/// Lex the next token. The lexer puts [`Token::end`] after the token's trailing
+/// trivia, which is also the next token's leading trivia, so it's moved back to
+/// the end of the token's text.
+#[inline]
+pub(crate) fn next_token(lexer: &mut Lexer) -> Token {
+    let mut token = lexer.next_token();
+
+    if let Some(text) = token.token_type.try_as_string() {
+        token.end = token.start;
+        for character in text.chars() {
+            advance_position(&mut token.end, character);
+        }
+    }
+
+    token
+}
+
+/// An adapter that allows writing to an [`io::Write`] sink through
+/// [`fmt::Write`], keeping the last [`io::Error`] since [`fmt::Error`] can't
+/// hold it.
+pub(crate) struct IoAdapter<'a, W: io::Write> {
+    /// The actual sink.
+    inner: &'a mut W,
+
+    /// The last error that occurred while writing.
+    error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write> IoAdapter<'a, W> {
+    /// Create a new adapter for the passed sink.
+    #[inline]
+    pub(crate) const fn new(inner: &'a mut W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Get the error that caused writing to fail.
+    #[inline]
+    pub(crate) fn into_error(self) -> io::Error {
+        self.error
+            .unwrap_or_else(|| io::Error::other("formatter error"))
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
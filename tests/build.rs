@@ -0,0 +1,191 @@
+#![cfg(test)]
+
+//! Tests for the [`build`](luau_parser::build) module.
+
+use luau_parser::{
+    build::{
+        assign, binary, block, block_with_last, boolean, call, closure, cst, field, field_with,
+        function, generic_for, if_statement, index, index_with, item, local, local_function,
+        locals, method_call, name, nil, number, numerical_for, paren, repeat_until,
+        return_statement, string, table, unary, while_loop, with_else, with_else_if,
+    },
+    prelude::{Expression, ParsedString, Parser, ParserOptions, Print},
+};
+
+/// Check that the passed code parses without errors and prints back to itself.
+fn check(code: &str) {
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+
+    assert!(cst.errors.is_empty(), "{code}\n{:#?}", cst.errors);
+    assert_eq!(cst.block.print(), code);
+}
+
+#[test]
+fn expressions() {
+    let code = block([local(
+        "t",
+        table([
+            field("a", number(1)),
+            field("not a name", string("\"quoted\"\n")),
+            field_with(boolean(true), nil()),
+            item(number(-2.5)),
+            item(index(method_call("foo", "bar", [name("x")]), "baz")),
+        ]),
+    )
+    .into()]);
+
+    let printed = code.print();
+    assert_eq!(
+        printed,
+        "local t = { a = 1, [\"not a name\"] = \"\\\"quoted\\\"\\n\", [true] = nil, -2.5, foo:bar(x).baz }"
+    );
+    check(&printed);
+
+    let code = block([
+        call("print", [index_with("t", number(1)), table([])]).into(),
+        call(index("string", "format"), []).into(),
+    ]);
+    assert_eq!(code.print(), "print(t[1], {})\nstring.format()");
+
+    let printed = function("Foo.bar:baz", ["a"], block([])).print();
+    assert_eq!(printed, "function Foo.bar:baz(a) end");
+    check(&printed);
+}
+
+#[test]
+fn strings() {
+    // `\1` followed by `2` must not be read back as `\12`.
+    for value in ["\u{1}2", "a\0b", "\u{7f}9\u{1b}[0m", "tab\t\"\\😀\r\n"] {
+        let parsed = Parser::default()
+            .parse_expression(&string(value).print())
+            .node;
+        let Some(Expression::String(token)) = parsed else {
+            panic!("Expected a string, got {parsed:#?}.");
+        };
+        let (parsed, errors) = ParsedString::from_token(&token).unwrap();
+
+        assert!(errors.is_empty(), "{value:?}: {errors:#?}");
+        assert_eq!(parsed.0, value.as_bytes(), "{value:?}");
+    }
+
+    assert_eq!(string("\u{1}2").print(), "\"\\0012\"");
+}
+
+#[test]
+fn operators() {
+    let sum = binary(name("a"), "+", name("b"));
+
+    assert_eq!(binary(sum.clone(), "*", name("c")).print(), "(a + b) * c");
+    assert_eq!(binary(name("c"), "*", sum.clone()).print(), "c * (a + b)");
+    assert_eq!(binary(sum.clone(), "-", name("c")).print(), "a + b - c");
+    assert_eq!(binary(name("c"), "-", sum.clone()).print(), "c - (a + b)");
+    assert_eq!(
+        binary(name("a"), "..", binary(name("b"), "..", name("c"))).print(),
+        "a .. b .. c"
+    );
+    assert_eq!(
+        binary(binary(name("a"), "^", name("b")), "^", name("c")).print(),
+        "(a ^ b) ^ c"
+    );
+    assert_eq!(
+        binary(unary("-", name("a")), "^", number(2)).print(),
+        "(-a) ^ 2"
+    );
+    assert_eq!(unary("-", unary("-", name("a"))).print(), "- -a");
+    assert_eq!(unary("not", sum).print(), "not (a + b)");
+    assert_eq!(unary("#", paren(name("t"))).print(), "#(t)");
+    assert_eq!(number(f64::INFINITY).print(), "math.huge");
+}
+
+#[test]
+fn statements() {
+    let code = cst(block([
+        local_function(
+            "greet",
+            ["name", "..."],
+            block_with_last(
+                [if_statement(
+                    binary(name("name"), "==", nil()),
+                    block([assign(name("name"), string("world")).into()]),
+                )
+                .into()],
+                Some(return_statement([binary(
+                    string("Hello, "),
+                    "..",
+                    name("name"),
+                )])),
+            ),
+        )
+        .into(),
+        function("Foo:baz", [], block([])).into(),
+        locals(["a", "b"], []).into(),
+        local(
+            "f",
+            closure(
+                ["x"],
+                block_with_last([], Some(return_statement([name("x")]))),
+            ),
+        )
+        .into(),
+        with_else(
+            with_else_if(
+                if_statement(name("a"), block([call("a", [number(1)]).into()])),
+                name("b"),
+                block([]),
+            ),
+            block([numerical_for(
+                "i",
+                number(1),
+                number(10),
+                Some(number(2)),
+                block([call("print", [name("i")]).into()]),
+            )
+            .into()]),
+        )
+        .into(),
+        generic_for(
+            ["k", "v"],
+            [call("pairs", [name("t")]).into()],
+            block([while_loop(
+                boolean(true),
+                block([repeat_until(block([]), name("done")).into()]),
+            )
+            .into()]),
+        )
+        .into(),
+        call(paren(name("f")), [number(1)]).into(),
+    ]));
+
+    let printed = code.try_print().unwrap();
+    assert_eq!(
+        printed,
+        "\
+local function greet(name, ...)
+    if name == nil then
+        name = \"world\"
+    end
+    return \"Hello, \" .. name
+end
+function Foo:baz() end
+local a, b
+local f = function(x)
+    return x
+end
+if a then
+    a(1)
+elseif b then
+else
+    for i = 1, 10, 2 do
+        print(i)
+    end
+end
+for k, v in pairs(t) do
+    while true do
+        repeat until done
+    end
+end;
+(f)(1)
+"
+    );
+    check(&printed);
+}
@@ -0,0 +1,118 @@
+#![cfg(test)]
+
+//! Tests for the structured [`Diagnostic`]s reported while parsing.
+
+use luau_parser::prelude::{
+    Diagnostic, DiagnosticCode, Parser, ParserOptions, Position, RelatedSpan, Severity,
+};
+
+/// Parse the passed code and get its diagnostics.
+fn diagnostics(code: &str) -> Vec<Diagnostic> {
+    Parser::new(code, ParserOptions::default())
+        .parse("")
+        .errors
+        .clone()
+}
+
+#[test]
+fn codes() {
+    let cases = [
+        ("if true print(1) end", DiagnosticCode::MissingThen),
+        ("if true then print(1)", DiagnosticCode::MissingEnd),
+        ("local x = if a then 1", DiagnosticCode::MissingElse),
+        ("repeat print(1)", DiagnosticCode::MissingUntil),
+        ("for i 1, 2 do end", DiagnosticCode::MissingEqual),
+        ("for i = 1 2 do end", DiagnosticCode::MissingComma),
+        ("print((1)", DiagnosticCode::MissingClosingBracket),
+        ("local x = 1 +", DiagnosticCode::MissingExpression),
+        ("type T = ", DiagnosticCode::MissingType),
+        (
+            "return 1 print(1)",
+            DiagnosticCode::StatementAfterTermination,
+        ),
+        (
+            "local x = a += 1",
+            DiagnosticCode::CompoundAssignmentInExpression,
+        ),
+        ("local x = `{}`", DiagnosticCode::EmptyInterpolation),
+        ("local x = '\\q'", DiagnosticCode::InvalidEscape),
+    ];
+
+    for (code, expected) in cases {
+        let diagnostics = diagnostics(code);
+
+        assert!(
+            diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.code == expected),
+            "{code}: {diagnostics:#?}"
+        );
+        assert!(
+            diagnostics.iter().all(Diagnostic::is_error),
+            "{code}: {diagnostics:#?}"
+        );
+    }
+}
+
+#[test]
+fn missing_then() {
+    let diagnostics = diagnostics("if true print(1) end");
+    let diagnostic = &diagnostics[0];
+
+    assert_eq!(diagnostic.code, DiagnosticCode::MissingThen);
+    assert_eq!(diagnostic.code.code(), "E0001");
+    assert_eq!(diagnostic.code.name(), "MissingThen");
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.message, "Expected `then` found <identifier>");
+    assert_eq!(diagnostic.expected.as_deref(), Some("<then>"));
+    assert_eq!(diagnostic.found.as_deref(), Some("<identifier>"));
+    assert_eq!(
+        diagnostic.related,
+        [RelatedSpan::new(
+            Position::new(0, 0),
+            Position::new(0, 2),
+            "`if` statement starts here."
+        )]
+    );
+    assert_eq!(
+        diagnostic.to_string(),
+        "error[E0001]: Expected `then` found <identifier> (1:9)"
+    );
+}
+
+#[test]
+fn missing_end() {
+    let diagnostics = diagnostics("local function foo()\n    if true then\n        print(1)\n");
+    let codes = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.code, diagnostic.related[0].start))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        codes,
+        [
+            (DiagnosticCode::MissingEnd, Position::new(1, 4)),
+            (DiagnosticCode::MissingEnd, Position::new(0, 6)),
+        ]
+    );
+}
+
+#[test]
+fn missing_expression() {
+    let diagnostics = diagnostics("while do end");
+    let diagnostic = &diagnostics[0];
+
+    assert_eq!(diagnostic.code, DiagnosticCode::MissingExpression);
+    assert_eq!(diagnostic.message, "Expected <expr>");
+    assert_eq!(diagnostic.expected.as_deref(), Some("<expr>"));
+    assert_eq!(diagnostic.found.as_deref(), Some("<do>"));
+}
+
+#[test]
+fn all_codes_are_unique() {
+    for (i, code) in DiagnosticCode::ALL.iter().enumerate() {
+        for other in &DiagnosticCode::ALL[i + 1..] {
+            assert_ne!(code.code(), other.code());
+        }
+    }
+}
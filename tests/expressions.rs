@@ -0,0 +1,175 @@
+#![cfg(test)]
+
+//! Tests for the shape of parsed expressions, mainly operator precedence and
+//! associativity, which can't be covered by printing the CST back.
+
+use luau_parser::{
+    format::{FormatConfig, format},
+    minify::MinifyOptions,
+    prelude::{
+        Associativity, BinaryOperator, DiagnosticCode, Expression, FunctionArgument,
+        FunctionArguments, OperatorCategory, Parser, ParserOptions, Print, Statement,
+    },
+};
+
+/// Display the passed expression as a fully parenthesized string.
+fn display(expression: &Expression) -> String {
+    match expression {
+        Expression::BinaryExpression {
+            left,
+            operator,
+            right,
+        } => format!(
+            "({} {} {})",
+            display(left),
+            operator.print().trim(),
+            display(right)
+        ),
+        Expression::UnaryExpression {
+            operator,
+            expression,
+        } => format!("({} {})", operator.print().trim(), display(expression)),
+        Expression::TypeCast { expression, .. } => format!("({} :: _)", display(expression)),
+        _ => expression.print().trim().to_string(),
+    }
+}
+
+/// Parse `local _ = <code>` and return the expression.
+fn parse_expression(code: &str) -> Expression {
+    let code = format!("local _ = {code}");
+    let cst = Parser::new(&code, ParserOptions::default()).parse("");
+    assert!(!cst.has_errors(), "{:#?}", cst.errors);
+
+    let Statement::LocalAssignment(local_assignment) = &*cst.block.statements[0].0 else {
+        panic!("Expected a local assignment.");
+    };
+
+    (**local_assignment.expressions[0]).clone()
+}
+
+/// Parse `local _ = <code>` and display the expression.
+fn parse(code: &str) -> String {
+    display(&parse_expression(code))
+}
+
+#[test]
+fn precedence() {
+    assert_eq!(parse("1 + 2 * 3 - 4"), "((1 + (2 * 3)) - 4)");
+    assert_eq!(parse("a or b and c"), "(a or (b and c))");
+    assert_eq!(parse("not a == b"), "((not a) == b)");
+    assert_eq!(parse("1 < 2 == true"), "((1 < 2) == true)");
+    assert_eq!(parse("#t + 1"), "((# t) + 1)");
+    assert_eq!(parse("-x ^ 2"), "(- (x ^ 2))");
+    assert_eq!(parse("x :: number + 1"), "((x :: _) + 1)");
+}
+
+#[test]
+fn associativity() {
+    assert_eq!(parse("a - b - c"), "((a - b) - c)");
+    assert_eq!(parse("a // b % c"), "((a // b) % c)");
+    assert_eq!(parse("a .. b .. c"), "(a .. (b .. c))");
+    assert_eq!(parse("2 ^ 3 ^ 2"), "(2 ^ (3 ^ 2))");
+}
+
+#[test]
+fn operators() {
+    let Expression::BinaryExpression { operator, .. } = parse_expression("a <= b") else {
+        panic!("Expected a binary expression.");
+    };
+    assert!(matches!(operator, BinaryOperator::LessThanOrEqualTo(_)));
+    assert_eq!(operator.category(), OperatorCategory::Comparison);
+    assert_eq!(operator.associativity(), Associativity::Left);
+
+    let Expression::BinaryExpression { operator, .. } = parse_expression("a .. b") else {
+        panic!("Expected a binary expression.");
+    };
+    assert_eq!(operator.category(), OperatorCategory::Concatenation);
+    assert_eq!(operator.associativity(), Associativity::Right);
+}
+
+#[test]
+fn compound_assignment_in_expression() {
+    let cst = Parser::new("local _ = a += 1 * 2", ParserOptions::default()).parse("");
+    assert_eq!(cst.errors.len(), 1, "{:#?}", cst.errors);
+    assert_eq!(
+        cst.errors[0].code,
+        DiagnosticCode::CompoundAssignmentInExpression
+    );
+
+    // `+=` isn't turned into a `+`, but the operator and its right side are kept.
+    let Statement::LocalAssignment(local_assignment) = &*cst.block.statements[0].0 else {
+        panic!("Expected a local assignment.");
+    };
+    let expression = &**local_assignment.expressions[0];
+    assert_eq!(display(expression), "(a += (1 * 2))");
+    assert!(matches!(
+        expression,
+        Expression::BinaryExpression {
+            operator: BinaryOperator::CompoundAssignment(_),
+            ..
+        }
+    ));
+
+    for code in [
+        "local x = a += 1",
+        "local x = a + b ..= c or d",
+        "print(a -= 1)",
+    ] {
+        let cst = Parser::new(code, ParserOptions::default()).parse("");
+        assert_eq!(cst.errors.len(), 1, "{code}: {:#?}", cst.errors);
+        assert_eq!(cst.block.print(), code);
+    }
+}
+
+#[test]
+fn variadic_values() {
+    assert!(matches!(
+        parse_expression("..."),
+        Expression::VariadicValues(_)
+    ));
+    assert_eq!(parse("... + 1"), "(... + 1)");
+
+    for code in [
+        "local x = ...",
+        "local a, b = ...",
+        "return ...",
+        "print(... + 1, ...)",
+        "local t = { ..., n = select('#', ...) }",
+    ] {
+        let cst = Parser::new(code, ParserOptions::default()).parse("");
+        assert!(!cst.has_errors(), "{code}: {:#?}", cst.errors);
+        assert_eq!(cst.block.print(), code);
+
+        let count = code.matches("...").count();
+        assert_eq!(
+            format(&cst, &FormatConfig::default())
+                .unwrap()
+                .matches("...")
+                .count(),
+            count,
+            "{code}"
+        );
+        assert_eq!(
+            cst.minify(MinifyOptions::default())
+                .unwrap()
+                .matches("...")
+                .count(),
+            count,
+            "{code}"
+        );
+        assert_eq!(cst.to_lua51().unwrap(), code);
+    }
+
+    // A lone `...` in a call keeps its own variant.
+    let cst = Parser::new("print(...)", ParserOptions::default()).parse("");
+    let Statement::FunctionCall(function_call) = &*cst.block.statements[0].0 else {
+        panic!("Expected a function call.");
+    };
+    let FunctionArguments::List(list) = &function_call.arguments else {
+        panic!("Expected an argument list.");
+    };
+    assert!(matches!(
+        &**list.item[0],
+        FunctionArgument::VariadicValues(_)
+    ));
+}
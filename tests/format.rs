@@ -0,0 +1,460 @@
+#![cfg(test)]
+
+//! Tests for the [`format`](luau_parser::format) module.
+
+use lsp_types::{Position, Range, TextEdit};
+use luau_parser::{
+    format::{
+        CallParentheses, FormatConfig, FormatError, IndentStyle, QuoteStyle, TrailingSeparators,
+        format, format_range,
+    },
+    prelude::{
+        Comment, LineIndex, Parser, ParserOptions, PositionEncoding, Token, Trivia, Visit, Visitor,
+    },
+};
+use std::{cmp::Reverse, fs};
+
+/// A [`Visitor`] collecting the text of all comments.
+#[derive(Default)]
+struct CommentCollector(Vec<String>);
+
+impl Visitor<'_> for CommentCollector {
+    fn visit_token(&mut self, token: &Token) {
+        for trivia in &token.leading_trivia {
+            if let Trivia::Comment(Comment::SingleLine(text) | Comment::MultiLine(text)) = trivia {
+                self.0.push(text.trim_end().to_string());
+            }
+        }
+    }
+}
+
+/// Get the sorted comments in the passed code.
+fn comments(code: &str) -> Vec<String> {
+    let mut collector = CommentCollector::default();
+    Parser::new(code, ParserOptions::default())
+        .parse("")
+        .block
+        .visit(&mut collector);
+
+    collector.0.sort();
+    collector.0
+}
+
+/// Format the passed code, checking that the output is valid, keeps all
+/// comments, and doesn't change when formatted again.
+fn format_with(code: &str, config: &FormatConfig) -> String {
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let formatted = format(&cst, config).unwrap();
+
+    let formatted_cst = Parser::new(&formatted, ParserOptions::default()).parse("");
+    assert!(
+        formatted_cst.errors.is_empty(),
+        "{formatted}\n{:#?}",
+        formatted_cst.errors
+    );
+    assert_eq!(comments(&formatted), comments(code), "{formatted}");
+    assert_eq!(format(&formatted_cst, config).unwrap(), formatted);
+
+    formatted
+}
+
+/// Format the passed code using the default configuration.
+#[inline]
+fn format_default(code: &str) -> String {
+    format_with(code, &FormatConfig::default())
+}
+
+#[test]
+fn statements() {
+    assert_eq!(
+        format_default(
+            r#"
+local   x=1;local y
+if x then return x elseif y then y() else end
+for i=1,10,2 do print(i) end
+for k,v in pairs(t) do end
+while true do break end
+repeat x-=1 until x<0
+do local _ = x end
+function foo.bar(a,b,...) return end
+local function baz<T>(a:T):T return a end
+export type Foo<T=string> ={x:T,[string]:number}
+"#
+        ),
+        r#"local x = 1
+local y
+if x then
+    return x
+elseif y then
+    y()
+else
+end
+for i = 1, 10, 2 do
+    print(i)
+end
+for k, v in pairs(t) do end
+while true do
+    break
+end
+repeat
+    x -= 1
+until x < 0
+do
+    local _ = x
+end
+function foo.bar(a, b, ...)
+    return
+end
+local function baz<T>(a: T): T
+    return a
+end
+export type Foo<T = string> = { x: T, [string]: number }
+"#
+    );
+}
+
+#[test]
+fn expressions() {
+    assert_eq!(
+        format_default(
+            "local _ = -(-x) + - -y .. not z\nlocal _ = if a then b elseif c then d else e\nlocal _ = `a {b}` :: string"
+        ),
+        "local _ = -(-x) + - -y .. not z\nlocal _ = if a then b elseif c then d else e\nlocal _ = `a {b}` :: string\n"
+    );
+    assert_eq!(
+        format_default("local _ = (function()return 1 end)()"),
+        "local _ = (function()\n    return 1\nend)()\n"
+    );
+}
+
+#[test]
+fn comments_are_kept() {
+    let code = r#"--!strict
+-- A comment before everything.
+
+local x = 1 -- After `x`.
+local t = { -- After `{`.
+    a = 1, --[[ After `a`. ]]
+    -- Before `b`.
+    b = 2,
+
+
+    c = 3
+    -- Before `}`.
+}
+
+local function f()
+    -- The body is only a comment.
+end
+-- The end of the file.
+"#;
+
+    assert_eq!(
+        format_default(code),
+        r#"--!strict
+-- A comment before everything.
+
+local x = 1 -- After `x`.
+local t = { -- After `{`.
+    a = 1, --[[ After `a`. ]]
+    -- Before `b`.
+    b = 2,
+    c = 3,
+    -- Before `}`.
+}
+
+local function f()
+    -- The body is only a comment.
+end
+-- The end of the file.
+"#
+    );
+}
+
+#[test]
+fn line_width() {
+    let config = FormatConfig {
+        line_width: 30,
+        ..FormatConfig::default()
+    };
+
+    assert_eq!(
+        format_with(
+            "local _ = foo(first, second, third)\nlocal _ = first and second or third and fourth\nfoo(a, { b = 1, c = 2, d = 3 })",
+            &config
+        ),
+        r#"local _ = foo(
+    first,
+    second,
+    third
+)
+local _ = first and second
+    or third and fourth
+foo(a, {
+    b = 1,
+    c = 2,
+    d = 3,
+})
+"#
+    );
+}
+
+#[test]
+fn indentation() {
+    let code = "do\nlocal _ = 1\nend";
+
+    assert_eq!(
+        format_with(
+            code,
+            &FormatConfig {
+                indent_style: IndentStyle::Tabs,
+                ..FormatConfig::default()
+            }
+        ),
+        "do\n\tlocal _ = 1\nend\n"
+    );
+    assert_eq!(
+        format_with(
+            code,
+            &FormatConfig {
+                indent_width: 2,
+                ..FormatConfig::default()
+            }
+        ),
+        "do\n  local _ = 1\nend\n"
+    );
+}
+
+#[test]
+fn quote_style() {
+    let code = r#"local _ = 'a', "b", 'it\'s', "say \"hi\"", 'say "hi"', [[c]]"#;
+    let with_style = |quote_style| {
+        format_with(
+            code,
+            &FormatConfig {
+                quote_style,
+                ..FormatConfig::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        with_style(QuoteStyle::AutoPreferDouble),
+        "local _ = \"a\", \"b\", \"it's\", 'say \"hi\"', 'say \"hi\"', [[c]]\n"
+    );
+    assert_eq!(
+        with_style(QuoteStyle::AutoPreferSingle),
+        "local _ = 'a', 'b', \"it's\", 'say \"hi\"', 'say \"hi\"', [[c]]\n"
+    );
+    assert_eq!(
+        with_style(QuoteStyle::ForceDouble),
+        "local _ = \"a\", \"b\", \"it's\", \"say \\\"hi\\\"\", \"say \\\"hi\\\"\", [[c]]\n"
+    );
+    assert_eq!(
+        with_style(QuoteStyle::ForceSingle),
+        "local _ = 'a', 'b', 'it\\'s', 'say \"hi\"', 'say \"hi\"', [[c]]\n"
+    );
+    assert_eq!(with_style(QuoteStyle::Keep), format!("{code}\n"));
+}
+
+#[test]
+fn call_parentheses() {
+    let code = "f(\"a\")\nf \"a\"\nf({})\nf {}\nf(a, {})";
+    let with_style = |call_parentheses| {
+        format_with(
+            code,
+            &FormatConfig {
+                call_parentheses,
+                ..FormatConfig::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        with_style(CallParentheses::Always),
+        "f(\"a\")\nf(\"a\")\nf({})\nf({})\nf(a, {})\n"
+    );
+    assert_eq!(
+        with_style(CallParentheses::NoSingleString),
+        "f \"a\"\nf \"a\"\nf({})\nf({})\nf(a, {})\n"
+    );
+    assert_eq!(
+        with_style(CallParentheses::NoSingleTable),
+        "f(\"a\")\nf(\"a\")\nf {}\nf {}\nf(a, {})\n"
+    );
+    assert_eq!(
+        with_style(CallParentheses::None),
+        "f \"a\"\nf \"a\"\nf {}\nf {}\nf(a, {})\n"
+    );
+    assert_eq!(with_style(CallParentheses::Input), format!("{code}\n"));
+}
+
+#[test]
+fn trailing_separators() {
+    let code = "local _ = { a = 1, b = 2, }\nlocal _ = { aaaaaaaaaa = 1, bbbbbbbbbb = 2 }";
+    let with_style = |trailing_separators| {
+        format_with(
+            code,
+            &FormatConfig {
+                line_width: 30,
+                trailing_separators,
+                ..FormatConfig::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        with_style(TrailingSeparators::Multiline),
+        "local _ = { a = 1, b = 2 }\nlocal _ = {\n    aaaaaaaaaa = 1,\n    bbbbbbbbbb = 2,\n}\n"
+    );
+    assert_eq!(
+        with_style(TrailingSeparators::Always),
+        "local _ = { a = 1, b = 2, }\nlocal _ = {\n    aaaaaaaaaa = 1,\n    bbbbbbbbbb = 2,\n}\n"
+    );
+    assert_eq!(
+        with_style(TrailingSeparators::Never),
+        "local _ = { a = 1, b = 2 }\nlocal _ = {\n    aaaaaaaaaa = 1,\n    bbbbbbbbbb = 2\n}\n"
+    );
+}
+
+#[test]
+fn semicolons() {
+    assert_eq!(format_default("a();b();\n(c)()"), "a()\nb();\n(c)()\n");
+}
+
+#[test]
+fn errors() {
+    let cst = Parser::new("if true print(1) end", ParserOptions::default()).parse("");
+
+    assert_eq!(
+        format(&cst, &FormatConfig::default()),
+        Err(FormatError::HasErrors)
+    );
+}
+
+#[test]
+fn test_code() {
+    for entry in fs::read_dir("test-code").unwrap() {
+        let code = fs::read_to_string(entry.unwrap().path()).unwrap();
+
+        for line_width in [20, 80, 120] {
+            format_with(
+                &code,
+                &FormatConfig {
+                    line_width,
+                    ..FormatConfig::default()
+                },
+            );
+        }
+    }
+}
+
+#[test]
+fn test_code_twice() {
+    let config = FormatConfig::default();
+
+    for entry in fs::read_dir("test-code").unwrap() {
+        let path = entry.unwrap().path();
+        let code = fs::read_to_string(&path).unwrap();
+
+        let once = format(
+            &Parser::new(&code, ParserOptions::default()).parse(""),
+            &config,
+        )
+        .unwrap();
+        let twice = format(
+            &Parser::new(&once, ParserOptions::default()).parse(""),
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(once, twice, "{}", path.display());
+        assert_eq!(comments(&twice), comments(&code), "{}", path.display());
+    }
+}
+
+/// Format the statements touched by `range` and apply the edits.
+fn format_range_with(code: &str, range: Range, config: &FormatConfig) -> String {
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let index = LineIndex::new(code);
+    let mut code = code.to_string();
+
+    let mut edits = format_range(&cst, range, config).unwrap();
+    edits.sort_by_key(|edit| Reverse(edit.range.start));
+    for edit in edits {
+        let start = index.offset(edit.range.start, PositionEncoding::Utf16);
+        let end = index.offset(edit.range.end, PositionEncoding::Utf16);
+        code.replace_range(start..end, &edit.new_text);
+    }
+
+    code
+}
+
+/// Create a range from the passed lines and columns.
+#[inline]
+fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+    Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+#[test]
+fn format_range_top_level() {
+    let code = "local   a=1\nlocal b   =2 -- b\nlocal  c=3;\n(f)()\nlocal d=4";
+    let config = FormatConfig::default();
+
+    assert_eq!(
+        format_range_with(code, range((1, 2), (1, 2)), &config),
+        "local   a=1\nlocal b = 2 -- b\nlocal  c=3;\n(f)()\nlocal d=4"
+    );
+    assert_eq!(
+        format_range_with(code, range((1, 8), (2, 3)), &config),
+        "local   a=1\nlocal b = 2 -- b\nlocal c = 3;\n(f)()\nlocal d=4"
+    );
+    assert_eq!(
+        format_range_with(code, range((0, 0), (0, 0)), &config),
+        "local a = 1\nlocal b   =2 -- b\nlocal  c=3;\n(f)()\nlocal d=4"
+    );
+}
+
+#[test]
+fn format_range_nested() {
+    let code =
+        "local function f()\n  -- comment\n  if x then return   1 end\nlocal y=2\nend\nlocal   z=3";
+    let config = FormatConfig::default();
+
+    assert_eq!(
+        format_range_with(code, range((2, 20), (2, 20)), &config),
+        "local function f()\n  -- comment\n  if x then return 1 end\nlocal y=2\nend\nlocal   z=3"
+    );
+    assert_eq!(
+        format_range_with(code, range((2, 2), (3, 3)), &config),
+        "local function f()\n    -- comment\n    if x then\n        return 1\n    end\n    local y = 2\nend\nlocal   z=3"
+    );
+    assert_eq!(
+        format_range_with(code, range((3, 0), (5, 1)), &config),
+        "local function f()\n    -- comment\n    if x then\n        return 1\n    end\n    local y = 2\nend\nlocal z = 3"
+    );
+}
+
+#[test]
+fn format_range_unchanged() {
+    let cst = Parser::new("local x = 1\n\nlocal   y=2", ParserOptions::default()).parse("");
+    let config = FormatConfig::default();
+
+    assert!(
+        format_range(&cst, range((0, 0), (0, 11)), &config)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        format_range(&cst, range((1, 0), (1, 0)), &config)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        format_range(&cst, range((2, 0), (2, 0)), &config).unwrap(),
+        vec![TextEdit::new(
+            range((2, 0), (2, 11)),
+            "local y = 2".to_string()
+        )]
+    );
+}
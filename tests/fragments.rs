@@ -0,0 +1,145 @@
+#![cfg(test)]
+
+//! Tests for parsing single nodes with [`Parser::parse_expression`] and co.
+
+use luau_parser::prelude::{
+    DiagnosticCode, Expression, GetRange, Parser, Position, PositionEncoding, Print, Range,
+    Statement, TerminationStatement, TypeValue,
+};
+
+#[test]
+fn expression() {
+    let parsed = Parser::default().parse_expression("foo(1) + 2");
+
+    assert!(!parsed.has_errors(), "{:#?}", parsed.errors);
+    let node = parsed.node.unwrap();
+    assert!(matches!(node, Expression::BinaryExpression { .. }));
+    assert_eq!(node.print(), "foo(1) + 2");
+}
+
+#[test]
+fn type_value() {
+    let parsed = Parser::default().parse_type("{ x: number }?");
+
+    assert!(!parsed.has_errors(), "{:#?}", parsed.errors);
+    assert!(matches!(parsed.node, Some(TypeValue::Optional { .. })));
+}
+
+#[test]
+fn statement() {
+    let parsed = Parser::default().parse_statement("local x = 1");
+
+    assert!(!parsed.has_errors(), "{:#?}", parsed.errors);
+    assert!(matches!(
+        parsed.node,
+        Some((Statement::LocalAssignment(_), None))
+    ));
+
+    let parsed = Parser::default().parse_statement("local x = 1; -- comment");
+
+    assert!(!parsed.has_errors(), "{:#?}", parsed.errors);
+    let node = parsed.node.unwrap();
+    assert!(node.1.is_some());
+    assert_eq!(node.print(), "local x = 1; -- comment");
+}
+
+#[test]
+fn last_statement() {
+    let parser = Parser::default();
+
+    let parsed = parser.parse_last_statement("return 1, 2;");
+    assert!(!parsed.has_errors(), "{:#?}", parsed.errors);
+    let node = parsed.node.unwrap();
+    assert!(matches!(
+        node,
+        (TerminationStatement::Return { .. }, Some(_))
+    ));
+    assert_eq!(node.print(), "return 1, 2;");
+
+    for code in ["break", "continue"] {
+        let parsed = parser.parse_last_statement(code);
+
+        assert!(!parsed.has_errors(), "{code}: {:#?}", parsed.errors);
+        assert_eq!(parsed.node.unwrap().print(), code);
+    }
+
+    let parsed = parser.parse_last_statement("local x = 1");
+    assert!(parsed.node.is_none());
+    assert_eq!(parsed.errors[0].code, DiagnosticCode::MissingStatement);
+}
+
+#[test]
+fn block() {
+    let parsed = Parser::default().parse_block("local x = 1\nreturn x");
+
+    assert!(!parsed.has_errors(), "{:#?}", parsed.errors);
+    let block = parsed.node.unwrap();
+    assert!(block.last_statement.is_some());
+    assert_eq!(block.print(), "local x = 1\nreturn x");
+}
+
+#[test]
+fn trailing_input() {
+    let parser = Parser::default();
+
+    let errors = parser.parse_expression("1 + 2 3").errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, DiagnosticCode::TrailingInput);
+    assert_eq!(errors[0].message, "Expected <eof> found <number>");
+    assert_eq!(
+        errors[0].range(),
+        Range::new(Position::new(0, 6), Position::new(0, 7))
+    );
+
+    let errors = parser.parse_type("number string").errors;
+    assert_eq!(errors[0].code, DiagnosticCode::TrailingInput);
+
+    let errors = parser.parse_statement("local x = 1 print(x)").errors;
+    assert_eq!(errors[0].code, DiagnosticCode::TrailingInput);
+
+    // Only one `;` belongs to the statement.
+    let errors = parser.parse_statement("local x = 1;;").errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, DiagnosticCode::TrailingInput);
+
+    // Trivia after the node is fine.
+    assert!(!parser.parse_expression("1 -- comment\n").has_errors());
+}
+
+#[test]
+fn missing_node() {
+    let parser = Parser::default();
+
+    let parsed = parser.parse_expression("");
+    assert!(parsed.node.is_none());
+    assert_eq!(parsed.errors[0].code, DiagnosticCode::MissingExpression);
+    assert_eq!(parsed.errors[0].found.as_deref(), Some("<eof>"));
+
+    let parsed = parser.parse_type("");
+    assert_eq!(parsed.errors[0].code, DiagnosticCode::MissingType);
+
+    // Termination statements aren't `Statement`s, they are parsed with
+    // `Parser::parse_last_statement`.
+    let parsed = parser.parse_statement("return 1");
+    assert!(parsed.node.is_none());
+    assert_eq!(parsed.errors[0].code, DiagnosticCode::MissingStatement);
+    assert_eq!(
+        parsed.errors[0].message,
+        "Expected <statement> found <return>"
+    );
+}
+
+#[test]
+fn position_encoding() {
+    let parsed = Parser::default()
+        .with_position_encoding(PositionEncoding::Utf16)
+        .parse_expression("'😀' .. x");
+
+    let Some(Expression::BinaryExpression { right, .. }) = parsed.node else {
+        panic!("{:#?}", parsed.node);
+    };
+    assert_eq!(
+        right.get_range().unwrap(),
+        Range::new(Position::new(0, 8), Position::new(0, 9))
+    );
+}
@@ -0,0 +1,406 @@
+#![cfg(test)]
+#![cfg(feature = "cache")]
+
+//! Tests for [`Parser::apply_changes`], every incremental reparse must produce
+//! the exact same CST as parsing the whole file again.
+
+use luau_parser::prelude::{
+    LanguageLevel, Parser, ParserOptions, Pointer, Position, PositionEncoding, Range, Statement,
+    TextDocumentContentChangeEvent,
+};
+use std::fs;
+
+const URI: &str = "test.luau";
+
+const CODE: &str = "local foo = bar(1)
+print(foo) -- comment
+
+local function qux(a: number): number
+    return a + 1
+end
+
+for i = 1, 10 do
+    print(qux(i))
+end
+";
+
+const NESTED_CODE: &str = "local x = 1
+local function foo(a)
+    local b = a
+    print(b)
+    if b then
+        print(1)
+        print(2)
+        print(3)
+    elseif a then
+        print(4)
+    end
+    repeat
+        b -= 1
+    until b < 0
+    -- comment
+end
+print(foo(x))
+";
+
+/// Create a change replacing the passed range with `text`.
+fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+    TextDocumentContentChangeEvent {
+        range: Some(Range::new(
+            Position::new(start.0, start.1),
+            Position::new(end.0, end.1),
+        )),
+        range_length: None,
+        text: text.to_string(),
+    }
+}
+
+/// Get the position of the character at `offset` in `text`.
+fn position_at(text: &str, offset: usize) -> Position {
+    let mut position = Position::default();
+    for character in text.chars().take(offset) {
+        if character == '\n' {
+            position.line += 1;
+            position.character = 0;
+        } else {
+            position.character += 1;
+        }
+    }
+
+    position
+}
+
+/// Get the byte offset of the passed position in `text`.
+fn byte_offset(text: &str, target: Position) -> usize {
+    let mut position = Position::default();
+    for (offset, character) in text.char_indices() {
+        if position == target {
+            return offset;
+        }
+
+        if character == '\n' {
+            position.line += 1;
+            position.character = 0;
+        } else {
+            position.character += 1;
+        }
+    }
+
+    text.len()
+}
+
+/// Apply the changes to both the parser and `code`, then check that the produced
+/// CST matches a full reparse of the new code.
+fn check(parser: &mut Parser, code: &mut String, changes: &[TextDocumentContentChangeEvent]) {
+    for change in changes {
+        let range = change.range.unwrap();
+        let start = byte_offset(code, range.start);
+        let end = byte_offset(code, range.end);
+
+        code.replace_range(start..end, &change.text);
+    }
+
+    let cst = parser.apply_changes(URI, changes);
+    let expected = Parser::new(code, parser.options()).parse(URI);
+
+    assert_eq!(cst, expected, "Failed for:\n{code}");
+}
+
+#[test]
+fn single_changes() {
+    let mut code = CODE.to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+
+    // Rename a variable in the middle.
+    check(&mut parser, &mut code, &[change((1, 6), (1, 9), "foobar")]);
+    // Add a new statement.
+    check(
+        &mut parser,
+        &mut code,
+        &[change((2, 0), (2, 0), "local x = 1\n")],
+    );
+    // Edit the first statement.
+    check(&mut parser, &mut code, &[change((0, 6), (0, 9), "f")]);
+    // Edit a comment.
+    check(&mut parser, &mut code, &[change((1, 17), (1, 24), "other")]);
+    // Remove lines.
+    check(&mut parser, &mut code, &[change((2, 0), (4, 0), "")]);
+    // Append to the end.
+    check(
+        &mut parser,
+        &mut code,
+        &[change((9, 0), (9, 0), "print(1)")],
+    );
+    // Edit inside a nested block.
+    check(
+        &mut parser,
+        &mut code,
+        &[change((7, 14), (7, 15), "\n\ti + 2")],
+    );
+}
+
+#[test]
+fn errors() {
+    let mut code = CODE.to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+
+    // Introduce an error.
+    check(&mut parser, &mut code, &[change((3, 0), (3, 5), "")]);
+    // Fix it.
+    check(&mut parser, &mut code, &[change((3, 0), (3, 0), "local")]);
+    // An error that's fixed by the same change list.
+    check(
+        &mut parser,
+        &mut code,
+        &[change((7, 0), (7, 3), ""), change((7, 0), (7, 0), "for")],
+    );
+}
+
+#[test]
+fn multiple_documents() {
+    const OTHER_URI: &str = "other.luau";
+
+    // Tokens dropped while parsing aren't printed back, so the source has to be
+    // kept when switching to another document.
+    let mut code = "local local x = 1\nprint(x)\n".to_string();
+    let mut other_code = CODE.to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+    parser.set_input(&other_code);
+    parser.parse(OTHER_URI);
+
+    check(&mut parser, &mut code, &[change((1, 6), (1, 7), "y")]);
+
+    let changes = [change((1, 6), (1, 9), "qux")];
+    other_code.replace_range(25..28, "qux");
+    assert_eq!(
+        parser.apply_changes(OTHER_URI, &changes),
+        Parser::new(&other_code, ParserOptions::default()).parse(OTHER_URI)
+    );
+}
+
+#[test]
+fn statements_depending_on_next_ones() {
+    let mut code = "local x = a\nlocal y = 1\n".to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+
+    // `a` now becomes a function call.
+    check(&mut parser, &mut code, &[change((1, 0), (1, 5), "(")]);
+    check(&mut parser, &mut code, &[change((1, 0), (1, 1), "local")]);
+    // `a` now becomes a binary expression.
+    check(&mut parser, &mut code, &[change((1, 0), (1, 5), "+ 2")]);
+}
+
+#[test]
+fn full_replacement() {
+    let mut parser = Parser::new(CODE, ParserOptions::default());
+    parser.parse(URI);
+
+    let cst = parser.apply_changes(
+        URI,
+        &[TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "print(1)".to_string(),
+        }],
+    );
+
+    assert_eq!(
+        cst,
+        Parser::new("print(1)", ParserOptions::default()).parse(URI)
+    );
+}
+
+#[test]
+fn reuses_statements() {
+    let mut parser = Parser::new(CODE, ParserOptions::default());
+    let old = parser.parse(URI);
+    let old_statements = old.block.statements.clone();
+    drop(old);
+
+    let cst = parser.apply_changes(URI, &[change((7, 11), (7, 13), "20")]);
+
+    assert!(Pointer::ptr_eq(
+        &cst.block.statements[0].0,
+        &old_statements[0].0
+    ));
+    assert!(Pointer::ptr_eq(
+        &cst.block.statements[1].0,
+        &old_statements[1].0
+    ));
+    assert!(!Pointer::ptr_eq(
+        &cst.block.statements[3].0,
+        &old_statements[3].0
+    ));
+}
+
+#[test]
+fn nested_blocks() {
+    let mut code = NESTED_CODE.to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+
+    // Edit a statement in the function body.
+    check(&mut parser, &mut code, &[change((3, 10), (3, 11), "a")]);
+    // Edit statements in the `if` body.
+    check(&mut parser, &mut code, &[change((7, 14), (7, 15), "x, 5")]);
+    check(
+        &mut parser,
+        &mut code,
+        &[change((6, 0), (6, 0), "        local c = 2\n")],
+    );
+    // Edit the first statement of a block.
+    check(&mut parser, &mut code, &[change((5, 14), (5, 15), "c")]);
+    // Edit the trivia right before `end`.
+    check(
+        &mut parser,
+        &mut code,
+        &[change((15, 7), (15, 14), "other")],
+    );
+    // Add tokens that end blocks.
+    check(&mut parser, &mut code, &[change((6, 8), (6, 8), "else ")]);
+    check(&mut parser, &mut code, &[change((6, 8), (6, 13), "")]);
+    check(
+        &mut parser,
+        &mut code,
+        &[change((13, 8), (13, 8), "until ")],
+    );
+    check(&mut parser, &mut code, &[change((13, 8), (13, 14), "")]);
+    check(&mut parser, &mut code, &[change((4, 13), (4, 13), " end")]);
+    check(&mut parser, &mut code, &[change((4, 13), (4, 17), "")]);
+    // Edit the `elseif` body.
+    check(&mut parser, &mut code, &[change((10, 14), (10, 15), "b")]);
+}
+
+#[test]
+fn reuses_nested_statements() {
+    let mut parser = Parser::new(NESTED_CODE, ParserOptions::default());
+    let old = parser.parse(URI);
+    let old_first = old.block.statements[0].0.clone();
+    let Statement::LocalFunction(old_function) = &*old.block.statements[1].0 else {
+        panic!("expected a local function");
+    };
+    let old_body = old_function.body.statements.clone();
+    drop(old);
+
+    let cst = parser.apply_changes(URI, &[change((7, 14), (7, 15), "30")]);
+    let Statement::LocalFunction(function) = &*cst.block.statements[1].0 else {
+        panic!("expected a local function");
+    };
+
+    // Only the `if` statement in the function is reparsed.
+    assert!(Pointer::ptr_eq(&cst.block.statements[0].0, &old_first));
+    assert!(Pointer::ptr_eq(
+        &function.body.statements[0].0,
+        &old_body[0].0
+    ));
+    assert!(Pointer::ptr_eq(
+        &function.body.statements[1].0,
+        &old_body[1].0
+    ));
+    assert!(!Pointer::ptr_eq(
+        &function.body.statements[2].0,
+        &old_body[2].0
+    ));
+}
+
+#[test]
+fn last_statements() {
+    let mut code = "local x = 1\nprint(x)\nreturn x\n".to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+
+    check(&mut parser, &mut code, &[change((1, 6), (1, 7), "2")]);
+    check(&mut parser, &mut code, &[change((2, 7), (2, 8), "x + 1")]);
+    // Statements after the last statement are errors.
+    check(
+        &mut parser,
+        &mut code,
+        &[change((3, 0), (3, 0), "print(x)\n")],
+    );
+    check(&mut parser, &mut code, &[change((3, 0), (4, 0), "")]);
+    check(&mut parser, &mut code, &[change((2, 0), (3, 0), "")]);
+    check(
+        &mut parser,
+        &mut code,
+        &[change((2, 0), (2, 0), "return\n")],
+    );
+
+    let mut code = "local function foo()\n\tprint(1)\n\tprint(2)\n\treturn 1\nend\n".to_string();
+    let mut parser = Parser::new(&code, ParserOptions::default());
+    parser.parse(URI);
+
+    check(&mut parser, &mut code, &[change((3, 8), (3, 9), "2")]);
+    check(&mut parser, &mut code, &[change((2, 7), (2, 8), "3")]);
+    check(
+        &mut parser,
+        &mut code,
+        &[change((3, 9), (3, 9), "\n\tprint(3)")],
+    );
+    check(&mut parser, &mut code, &[change((3, 9), (4, 9), "")]);
+}
+
+#[test]
+fn other_encodings() {
+    let options = ParserOptions {
+        position_encoding: PositionEncoding::Utf16,
+        ..ParserOptions::default()
+    };
+    let code =
+        "local s = \"😀\" print(s)\nlocal function foo()\n\tprint(\"😀\", 1)\n\tprint(2)\nend\n";
+    let mut parser = Parser::new(code, options);
+    let old_first = parser.parse(URI).block.statements[0].0.clone();
+
+    // `1` is at column 13 in UTF-16, after the emoji.
+    let cst = parser.apply_changes(URI, &[change((2, 13), (2, 14), "10")]);
+    let code = code.replace("\", 1)", "\", 10)");
+    assert_eq!(cst, Parser::new(&code, options).parse(URI));
+    assert!(Pointer::ptr_eq(&cst.block.statements[0].0, &old_first));
+
+    let cst = parser.apply_changes(URI, &[change((0, 21), (0, 22), "x")]);
+    let code = code.replace("print(s)", "print(x)");
+    assert_eq!(cst, Parser::new(&code, options).parse(URI));
+}
+
+#[test]
+fn disabled_features() {
+    let mut code = "local function foo()\n\tlocal x = 1\n\tprint(x)\n\tprint(x)\nend\n".to_string();
+    let mut parser = Parser::new(&code, LanguageLevel::Lua51.into());
+    parser.parse(URI);
+
+    check(&mut parser, &mut code, &[change((3, 8), (3, 9), "x + 1")]);
+    check(
+        &mut parser,
+        &mut code,
+        &[change((3, 0), (3, 0), "\tx += 1\n")],
+    );
+    check(&mut parser, &mut code, &[change((3, 0), (4, 0), "")]);
+}
+
+#[test]
+fn every_character() {
+    for entry in fs::read_dir("test-code").unwrap() {
+        let content = fs::read_to_string(entry.unwrap().path()).unwrap();
+
+        let mut code = content.clone();
+        let mut parser = Parser::new(&code, ParserOptions::default());
+        parser.parse(URI);
+
+        for (offset, removed) in content.chars().enumerate().step_by(13) {
+            let start = position_at(&code, offset);
+            let end = position_at(&code, offset + 1);
+            let removed = removed.to_string();
+
+            let start = (start.line, start.character);
+            check(
+                &mut parser,
+                &mut code,
+                &[change(start, (end.line, end.character), "")],
+            );
+            check(&mut parser, &mut code, &[change(start, start, &removed)]);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+#![cfg(test)]
+#![cfg(feature = "serde")]
+
+//! Tests for [`Cst::to_json_ast`](luau_parser::types::Cst::to_json_ast).
+
+use std::fs;
+
+use luau_parser::{
+    json_ast::{JSON_AST_VERSION, JsonAstOptions},
+    prelude::{Parser, ParserOptions},
+};
+use serde_json::json;
+
+#[test]
+fn statements() {
+    let code = "local x: number = 1 -- one
+print(x + y)
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let json = cst.to_json_ast();
+
+    assert_eq!(
+        json,
+        json!({
+            "version": JSON_AST_VERSION,
+            "root": {
+                "kind": "AstStatBlock",
+                "location": "0,0 - 2,0",
+                "hasEnd": false,
+                "body": [
+                    {
+                        "kind": "AstStatLocal",
+                        "location": "0,0 - 0,19",
+                        "vars": [{
+                            "kind": "AstLocal",
+                            "location": "0,6 - 0,7",
+                            "name": "x",
+                            "luauType": {
+                                "kind": "AstTypeReference",
+                                "location": "0,9 - 0,15",
+                                "name": "number",
+                                "nameLocation": "0,9 - 0,15",
+                                "prefix": null,
+                                "parameters": [],
+                            },
+                        }],
+                        "values": [{
+                            "kind": "AstExprConstantNumber",
+                            "location": "0,18 - 0,19",
+                            "value": 1.0,
+                        }],
+                    },
+                    {
+                        "kind": "AstStatExpr",
+                        "location": "1,0 - 1,12",
+                        "expr": {
+                            "kind": "AstExprCall",
+                            "location": "1,0 - 1,12",
+                            "func": {
+                                "kind": "AstExprGlobal",
+                                "location": "1,0 - 1,5",
+                                "global": "print",
+                            },
+                            "args": [{
+                                "kind": "AstExprBinary",
+                                "location": "1,6 - 1,11",
+                                "op": "Add",
+                                "left": {
+                                    "kind": "AstExprLocal",
+                                    "location": "1,6 - 1,7",
+                                    "local": {
+                                        "kind": "AstLocal",
+                                        "location": "0,6 - 0,7",
+                                        "name": "x",
+                                    },
+                                },
+                                "right": {
+                                    "kind": "AstExprGlobal",
+                                    "location": "1,10 - 1,11",
+                                    "global": "y",
+                                },
+                            }],
+                            "self": false,
+                            "argLocation": "1,5 - 1,12",
+                        },
+                    },
+                ],
+            },
+            "commentLocations": [{ "kind": "Comment", "location": "0,20 - 0,26" }],
+            "errors": [],
+        })
+    );
+}
+
+#[test]
+fn expressions_and_types() {
+    let code = "local t = { 1, a = `{b}c`, [2] = ... }
+type T<U...> = { [string]: U? } | (number) -> ...U
+if a then elseif b then else end
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let json = cst.to_json_ast();
+    let body = &json["root"]["body"];
+
+    let items = &body[0]["values"][0]["items"];
+    assert_eq!(items[0]["kind"], "item");
+    assert_eq!(items[1]["kind"], "record");
+    assert_eq!(items[1]["key"]["value"], "a");
+    assert_eq!(items[1]["value"]["kind"], "AstExprInterpString");
+    assert_eq!(items[1]["value"]["strings"], json!(["", "c"]));
+    assert_eq!(items[2]["kind"], "general");
+    assert_eq!(items[2]["value"]["kind"], "AstExprVarargs");
+
+    let alias = &body[1];
+    assert_eq!(alias["kind"], "AstStatTypeAlias");
+    assert_eq!(alias["genericPacks"][0]["name"], "U");
+    assert_eq!(alias["value"]["kind"], "AstTypeUnion");
+
+    let table = &alias["value"]["types"][0];
+    assert_eq!(table["indexer"]["indexType"]["name"], "string");
+    assert_eq!(
+        table["indexer"]["resultType"]["types"][1]["kind"],
+        "AstTypeOptional"
+    );
+
+    let function = &alias["value"]["types"][1];
+    assert_eq!(function["kind"], "AstTypeFunction");
+    assert_eq!(function["argTypes"]["types"][0]["name"], "number");
+    assert_eq!(function["returnTypes"]["kind"], "AstTypePackGeneric");
+
+    let if_statement = &body[2];
+    assert_eq!(if_statement["kind"], "AstStatIf");
+    assert_eq!(if_statement["elsebody"]["kind"], "AstStatIf");
+    assert_eq!(if_statement["elsebody"]["location"], "2,10 - 2,32");
+    assert_eq!(if_statement["elsebody"]["elsebody"]["kind"], "AstStatBlock");
+}
+
+#[test]
+fn errors() {
+    let cst = Parser::new("local x = \nprint(", ParserOptions::default()).parse("");
+    let json = cst.to_json_ast();
+
+    assert!(!json["errors"].as_array().unwrap().is_empty());
+    assert_eq!(
+        json["errors"][0]["location"],
+        format!(
+            "{},{} - {},{}",
+            cst.errors[0].start.line,
+            cst.errors[0].start.character,
+            cst.errors[0].end.line,
+            cst.errors[0].end.character,
+        )
+    );
+    assert_eq!(json["errors"][0]["code"], cst.errors[0].code.code());
+}
+
+#[test]
+fn trivia() {
+    let code = "local x = 1 --[[ block ]]\n-- line\n";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+
+    let json = cst.to_json_ast();
+    assert!(json.get("trivia").is_none());
+    assert_eq!(
+        json["commentLocations"],
+        json!([
+            { "kind": "BlockComment", "location": "0,12 - 0,25" },
+            { "kind": "Comment", "location": "1,0 - 1,7" },
+        ])
+    );
+
+    let json = cst.to_json_ast_with(JsonAstOptions {
+        include_trivia: true,
+    });
+    let trivia = json["trivia"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|trivia| trivia["text"].as_str().unwrap())
+        .collect::<String>();
+    assert_eq!(trivia, "    --[[ block ]]\n-- line\n");
+    assert_eq!(json["trivia"][5]["location"], "0,25 - 1,0");
+}
+
+#[test]
+fn test_code() {
+    for entry in fs::read_dir("test-code").unwrap() {
+        let content = fs::read_to_string(entry.unwrap().path()).unwrap();
+        let json = Parser::new(&content, ParserOptions::default())
+            .parse("")
+            .to_json_ast();
+
+        assert_eq!(json["version"], JSON_AST_VERSION);
+        assert_eq!(json["root"]["kind"], "AstStatBlock");
+    }
+}
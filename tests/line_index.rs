@@ -0,0 +1,230 @@
+#![cfg(test)]
+
+//! Tests for [`LineIndex`] and [`PositionEncoding`].
+
+use luau_parser::prelude::{
+    GetRange, LineIndex, Parser, ParserOptions, Position, PositionEncoding, PositionEncodingKind,
+    Range,
+};
+
+/// `é` takes 2 bytes and 1 UTF-16 code unit, `😀` takes 4 bytes and 2 UTF-16
+/// code units.
+const CODE: &str = "local s = 'é😀' -- 😀\nprint(s)\n";
+
+#[test]
+fn offsets() {
+    let index = LineIndex::new(CODE);
+
+    assert_eq!(index.len(), CODE.len());
+    assert_eq!(index.line_count(), 3);
+
+    // `'` after the string's characters.
+    let offset = CODE.find("' --").unwrap();
+    let positions = [
+        (PositionEncoding::Utf8, Position::new(0, 17)),
+        (PositionEncoding::Utf16, Position::new(0, 14)),
+        (PositionEncoding::Utf32, Position::new(0, 13)),
+    ];
+
+    for (encoding, position) in positions {
+        assert_eq!(index.position(offset, encoding), position, "{encoding:?}");
+        assert_eq!(index.offset(position, encoding), offset, "{encoding:?}");
+    }
+
+    let offset = CODE.find("print").unwrap();
+    for (encoding, _) in positions {
+        assert_eq!(index.position(offset, encoding), Position::new(1, 0));
+        assert_eq!(index.offset(Position::new(1, 0), encoding), offset);
+    }
+}
+
+#[test]
+fn convert() {
+    let index = LineIndex::new(CODE);
+    let position = Position::new(0, 13);
+
+    assert_eq!(
+        index.convert(position, PositionEncoding::Utf32, PositionEncoding::Utf16),
+        Position::new(0, 14)
+    );
+    assert_eq!(
+        index.convert(position, PositionEncoding::Utf32, PositionEncoding::Utf8),
+        Position::new(0, 17)
+    );
+    assert_eq!(
+        index.convert(
+            Position::new(0, 17),
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16
+        ),
+        Position::new(0, 14)
+    );
+
+    // Positions in the middle of a character are moved to its start.
+    assert_eq!(
+        index.convert(
+            Position::new(0, 13),
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32
+        ),
+        Position::new(0, 12)
+    );
+    assert_eq!(
+        index.position(CODE.find('😀').unwrap() + 1, PositionEncoding::Utf32),
+        Position::new(0, 12)
+    );
+}
+
+#[test]
+fn clamping() {
+    let index = LineIndex::new(CODE);
+
+    assert_eq!(
+        index.offset(Position::new(1, 100), PositionEncoding::Utf16),
+        CODE.len() - 1
+    );
+    assert_eq!(
+        index.offset(Position::new(100, 0), PositionEncoding::Utf16),
+        CODE.len()
+    );
+    assert_eq!(
+        index.position(1000, PositionEncoding::Utf16),
+        Position::new(2, 0)
+    );
+
+    assert_eq!(index.line_length(0, PositionEncoding::Utf8), 26);
+    assert_eq!(index.line_length(0, PositionEncoding::Utf16), 21);
+    assert_eq!(index.line_length(0, PositionEncoding::Utf32), 19);
+    assert_eq!(index.line_length(100, PositionEncoding::Utf32), 0);
+}
+
+#[test]
+fn from_cst() {
+    let cst = Parser::new(CODE, ParserOptions::default()).parse("");
+    assert_eq!(LineIndex::from_cst(&cst), LineIndex::new(CODE));
+
+    let cst = Parser::new(CODE, ParserOptions::default())
+        .with_position_encoding(PositionEncoding::Utf8)
+        .parse("");
+    assert_eq!(LineIndex::from_cst(&cst), LineIndex::new(CODE));
+}
+
+#[test]
+fn parser_option() {
+    let print_range = |encoding| {
+        let cst = Parser::new(CODE, ParserOptions::default())
+            .with_position_encoding(encoding)
+            .parse("");
+        assert_eq!(cst.position_encoding, encoding);
+
+        cst.block.statements[1].0.get_range().unwrap()
+    };
+    let comment_range = |encoding| {
+        let cst = Parser::new(CODE, ParserOptions::default())
+            .with_position_encoding(encoding)
+            .parse("");
+
+        cst.block.statements[0].0.get_range().unwrap()
+    };
+
+    // Positions on lines without wide characters are the same.
+    for encoding in [
+        PositionEncoding::Utf8,
+        PositionEncoding::Utf16,
+        PositionEncoding::Utf32,
+    ] {
+        assert_eq!(
+            print_range(encoding),
+            Range::new(Position::new(1, 0), Position::new(1, 8))
+        );
+    }
+
+    assert_eq!(
+        comment_range(PositionEncoding::Utf8),
+        Range::new(Position::new(0, 0), Position::new(0, 18))
+    );
+    assert_eq!(
+        comment_range(PositionEncoding::Utf16),
+        Range::new(Position::new(0, 0), Position::new(0, 15))
+    );
+    assert_eq!(
+        comment_range(PositionEncoding::Utf32),
+        Range::new(Position::new(0, 0), Position::new(0, 14))
+    );
+}
+
+#[test]
+fn errors_are_converted() {
+    let code = "local s = '😀' if true print(s) end";
+    let start = |encoding| {
+        Parser::new(code, ParserOptions::default())
+            .with_position_encoding(encoding)
+            .parse("")
+            .errors[0]
+            .start
+    };
+
+    assert_eq!(start(PositionEncoding::Utf32), Position::new(0, 22));
+    assert_eq!(start(PositionEncoding::Utf16), Position::new(0, 23));
+    assert_eq!(start(PositionEncoding::Utf8), Position::new(0, 25));
+
+    // LSP diagnostics are always in UTF-16.
+    let diagnostics = |encoding| {
+        Parser::new(code, ParserOptions::default())
+            .with_position_encoding(encoding)
+            .parse("")
+            .lsp_diagnostics(code)
+    };
+    assert_eq!(
+        diagnostics(PositionEncoding::Utf8),
+        diagnostics(PositionEncoding::Utf32)
+    );
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn apply_changes() {
+    use luau_parser::prelude::{Print, TextDocumentContentChangeEvent};
+
+    let mut parser =
+        Parser::new(CODE, ParserOptions::default()).with_position_encoding(PositionEncoding::Utf16);
+    parser.parse("test.luau");
+
+    // Replace the `s` in `print(s)`, then the emoji in the comment.
+    let cst = parser.apply_changes(
+        "test.luau",
+        &[
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 6), Position::new(1, 7))),
+                range_length: None,
+                text: "x".to_string(),
+            },
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(0, 19), Position::new(0, 21))),
+                range_length: None,
+                text: "y".to_string(),
+            },
+        ],
+    );
+
+    assert_eq!(cst.block.print(), "local s = 'é😀' -- y\nprint(x)\n");
+    assert_eq!(cst.position_encoding, PositionEncoding::Utf16);
+}
+
+#[test]
+fn encoding_kinds() {
+    for encoding in [
+        PositionEncoding::Utf8,
+        PositionEncoding::Utf16,
+        PositionEncoding::Utf32,
+    ] {
+        let kind = PositionEncodingKind::from(encoding);
+
+        assert_eq!(PositionEncoding::from_kind(&kind), Some(encoding));
+    }
+
+    assert_eq!(
+        PositionEncoding::from_kind(&PositionEncodingKind::new("utf-7")),
+        None
+    );
+}
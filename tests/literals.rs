@@ -0,0 +1,322 @@
+#![cfg(test)]
+
+//! Tests for parsing literal values.
+
+use luau_parser::prelude::{
+    Cst, DiagnosticCode, Expression, GetRange, InterpolatedStringSegment, LuauString,
+    ParseNumberError, ParsedNumber, ParsedString, Parser, ParserOptions, Position, Print, Range,
+    Statement,
+};
+
+#[test]
+fn numbers() {
+    let cases = [
+        ("0", ParsedNumber::Other(0.0)),
+        ("1_000_000", ParsedNumber::Other(1_000_000.0)),
+        ("1.5", ParsedNumber::Other(1.5)),
+        (".5", ParsedNumber::Other(0.5)),
+        ("5.", ParsedNumber::Other(5.0)),
+        ("1e3", ParsedNumber::Other(1000.0)),
+        ("2.5E-1", ParsedNumber::Other(0.25)),
+        ("1e+2", ParsedNumber::Other(100.0)),
+        ("0xFF", ParsedNumber::HexOrByte(255)),
+        ("0XdEaD_bEeF", ParsedNumber::HexOrByte(0xDEAD_BEEF)),
+        ("0b1010", ParsedNumber::HexOrByte(10)),
+        ("0B1111_0000", ParsedNumber::HexOrByte(240)),
+        (
+            "0xFFFFFFFFFFFFFFFF",
+            ParsedNumber::HexOrByte(u64::MAX as i128),
+        ),
+    ];
+
+    for (number, expected) in cases {
+        assert_eq!(ParsedNumber::parse(number), Ok(expected), "{number}");
+    }
+}
+
+#[test]
+fn number_errors() {
+    assert_eq!(
+        ParsedNumber::parse("0x1_0000_0000_0000_0000"),
+        Err(ParseNumberError::OutOfRange)
+    );
+    assert_eq!(
+        ParsedNumber::parse(&format!("0b1{}", "0".repeat(200))),
+        Err(ParseNumberError::OutOfRange)
+    );
+    assert_eq!(
+        ParsedNumber::parse("1e400"),
+        Ok(ParsedNumber::Other(f64::INFINITY))
+    );
+
+    for number in ["0x", "0xG", "0x-1", "0b2", "1.2.3", "inf", "nan", "1e"] {
+        assert!(
+            matches!(
+                ParsedNumber::parse(number),
+                Err(ParseNumberError::HexOrByte(_) | ParseNumberError::Other(_))
+            ),
+            "{number}"
+        );
+    }
+}
+
+#[test]
+fn number_tokens() {
+    let cst = Parser::new("local _ = 0x10", ParserOptions::default()).parse("");
+    let Statement::LocalAssignment(local_assignment) = &*cst.block.statements[0].0 else {
+        panic!("Expected a local assignment.");
+    };
+    let Expression::Number(token) = &**local_assignment.expressions[0] else {
+        panic!("Expected a number.");
+    };
+
+    assert_eq!(
+        ParsedNumber::from_token(token),
+        Some(Ok(ParsedNumber::HexOrByte(16)))
+    );
+    assert_eq!(
+        ParsedNumber::from_token(&local_assignment.local_token),
+        None
+    );
+}
+
+#[test]
+fn check_numbers() {
+    let code = "local a = 0x10000000000000000
+local b = 9007199254740993
+local c = 9007199254740992 + 1.5
+";
+    let mut cst = (*Parser::new(code, ParserOptions::default()).parse("")).clone();
+    assert!(cst.errors.is_empty());
+
+    cst.check_numbers();
+
+    let errors = cst
+        .errors
+        .iter()
+        .map(|error| (error.start, error.code, error.message.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (
+                Position::new(0, 10),
+                DiagnosticCode::NumberOutOfRange,
+                "Number is out of range.".to_string()
+            ),
+            (
+                Position::new(1, 10),
+                DiagnosticCode::ImpreciseNumber,
+                "Number can't be represented exactly and will be rounded.".to_string()
+            ),
+        ]
+    );
+    assert!(!cst.has_errors());
+}
+
+#[test]
+fn malformed_numbers() {
+    let mut cst = (*Parser::new("local a = 0x", ParserOptions::default()).parse("")).clone();
+    assert!(!cst.has_errors());
+
+    cst.check_numbers();
+
+    assert_eq!(cst.errors.len(), 1);
+    assert_eq!(cst.errors[0].code, DiagnosticCode::MalformedNumber);
+    assert!(cst.errors[0].is_error());
+    assert!(cst.has_errors());
+}
+
+/// Get the first expression of the first statement, which must be a local
+/// assignment.
+fn first_expression(cst: &Cst) -> &Expression {
+    let Statement::LocalAssignment(local_assignment) = &*cst.block.statements[0].0 else {
+        panic!("Expected a local assignment.");
+    };
+
+    &local_assignment.expressions[0]
+}
+
+#[test]
+fn strings() {
+    let cases: [(&str, &[u8]); 8] = [
+        (r#""abc""#, b"abc"),
+        (r#"'a\tb\nc\\d\"e\'f'"#, b"a\tb\nc\\d\"e'f"),
+        (r#""\a\b\f\r\v""#, b"\x07\x08\x0C\r\x0B"),
+        (r#""\65\066\0067""#, b"AB\x067"),
+        (r#""\x41\xfF""#, b"A\xFF"),
+        (r#""\u{48}\u{E9}\u{1F600}""#, "Hé😀".as_bytes()),
+        ("\"a\\z  \t  b\"", b"ab"),
+        ("[==[\nfoo]]\\n]==]", b"foo]]\\n"),
+    ];
+
+    for (code, expected) in cases {
+        let cst = Parser::new(&format!("local _ = {code}"), ParserOptions::default()).parse("");
+        assert!(cst.errors.is_empty(), "{code}: {:?}", cst.errors);
+
+        let Expression::String(token) = first_expression(&cst) else {
+            panic!("Expected a string.");
+        };
+        let (string, errors) = ParsedString::from_token(token).unwrap();
+
+        assert_eq!(string.0, expected, "{code}");
+        assert!(errors.is_empty());
+    }
+
+    // The lexer doesn't allow new lines in single line strings yet, even after
+    // `\` and `\z`.
+    for (string, expected) in [("\"a\\\nb\"", b"a\nb" as &[u8]), ("\"a\\z \r\n b\"", b"ab")] {
+        let (string, errors) = ParsedString::parse(
+            &LuauString::DoubleQuotes(string.into()),
+            Position::default(),
+        );
+
+        assert_eq!(string.0, expected);
+        assert!(errors.is_empty());
+    }
+}
+
+#[test]
+fn string_errors() {
+    let code = r#"local a = "\q"
+local b = "\x4"
+local c = "\u{110000}"
+local d = "\256"
+print("\u41")
+type T = "\g"
+"#;
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+
+    let errors = cst
+        .errors
+        .iter()
+        .map(|error| (error.start, error.end, error.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (
+                Position::new(0, 11),
+                Position::new(0, 13),
+                "Invalid escape sequence."
+            ),
+            (
+                Position::new(1, 11),
+                Position::new(1, 13),
+                "Hexadecimal escapes must have 2 hexadecimal digits."
+            ),
+            (
+                Position::new(2, 11),
+                Position::new(2, 21),
+                "Unicode escapes can't be larger than `10FFFF`."
+            ),
+            (
+                Position::new(3, 11),
+                Position::new(3, 15),
+                "Decimal escapes can't be larger than 255."
+            ),
+            (
+                Position::new(4, 7),
+                Position::new(4, 9),
+                "Expected `{` after `\\u`."
+            ),
+            (
+                Position::new(5, 10),
+                Position::new(5, 12),
+                "Invalid escape sequence."
+            ),
+        ]
+    );
+}
+
+#[test]
+fn interpolated_strings() {
+    let code = "local _ = `a\\{ {x + 1} b { y }\\u{7B}`";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    assert!(cst.errors.is_empty(), "{:?}", cst.errors);
+    assert_eq!(cst.block.print(), code);
+
+    let Expression::InterpolatedString(interpolated_string) = first_expression(&cst) else {
+        panic!("Expected an interpolated string.");
+    };
+    let segments = &interpolated_string.segments;
+    assert_eq!(segments.len(), 5);
+
+    let strings = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            InterpolatedStringSegment::String(token) => {
+                let (string, errors) = ParsedString::from_token(token).unwrap();
+                assert!(errors.is_empty());
+
+                Some(string.as_str().unwrap().to_string())
+            }
+            InterpolatedStringSegment::Expression(_) => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(strings, ["a{ ", " b ", "{"]);
+
+    // Segments end right after the `{`, without the whitespace before the
+    // expression.
+    let ranges = segments
+        .iter()
+        .step_by(2)
+        .map(|segment| segment.get_range().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranges,
+        [
+            Range::new(Position::new(0, 10), Position::new(0, 16)),
+            Range::new(Position::new(0, 21), Position::new(0, 26)),
+            Range::new(Position::new(0, 29), Position::new(0, 37)),
+        ]
+    );
+
+    let InterpolatedStringSegment::Expression(expression) = &segments[1] else {
+        panic!("Expected an expression.");
+    };
+    let Expression::BinaryExpression { left, .. } = &**expression else {
+        panic!("Expected a binary expression.");
+    };
+    assert_eq!(
+        left.get_range().unwrap(),
+        Range::new(Position::new(0, 16), Position::new(0, 17))
+    );
+
+    let InterpolatedStringSegment::Expression(expression) = &segments[3] else {
+        panic!("Expected an expression.");
+    };
+    assert!(matches!(**expression, Expression::Var(_)));
+    assert_eq!(
+        expression.get_range().unwrap(),
+        Range::new(Position::new(0, 27), Position::new(0, 28))
+    );
+}
+
+#[test]
+fn interpolated_string_errors() {
+    let cases = [
+        (
+            "local _ = `a {} b`",
+            "Expected an expression between `{` and `}`.",
+        ),
+        (
+            "local _ = `a {x y} b`",
+            "Expected `}` to close the interpolation.",
+        ),
+        ("local _ = `{\"\\q\"}`", "Invalid escape sequence."),
+        ("local _ = `\\q{x}`", "Invalid escape sequence."),
+    ];
+
+    for (code, message) in cases {
+        let cst = Parser::new(code, ParserOptions::default()).parse("");
+        let messages = cst
+            .errors
+            .iter()
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(messages, [message], "{code}");
+        assert_eq!(cst.block.print(), code);
+    }
+}
@@ -0,0 +1,174 @@
+#![cfg(test)]
+
+//! Tests for the LSP conversions of a [`Cst`](luau_parser::types::Cst).
+
+use luau_parser::prelude::{
+    Diagnostic, DiagnosticSeverity, NumberOrString, Parser, ParserOptions, Position, Range,
+};
+
+/// The URI used for all parsed files.
+const URI: &str = "file:///test.luau";
+
+/// Parse `code` and get its LSP diagnostics, along with the [`Cst`] errors.
+///
+/// [`Cst`]: luau_parser::types::Cst
+fn lsp_diagnostics(code: &str, uri: &str) -> (Vec<lsp_types::Diagnostic>, Vec<Diagnostic>) {
+    let cst = Parser::new(code, ParserOptions::default()).parse(uri);
+
+    (cst.lsp_diagnostics(code), cst.errors.clone())
+}
+
+#[test]
+fn diagnostics_ranges() {
+    let (diagnostics, _) = lsp_diagnostics("if true print(1) end", URI);
+
+    assert_eq!(diagnostics.len(), 1);
+
+    let diagnostic = &diagnostics[0];
+    assert_eq!(
+        diagnostic.range,
+        Range::new(Position::new(0, 8), Position::new(0, 9))
+    );
+    assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(
+        diagnostic.code,
+        Some(NumberOrString::String("E0001".to_string()))
+    );
+    assert_eq!(diagnostic.source.as_deref(), Some("luau-parser"));
+
+    let related = diagnostic.related_information.as_ref().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].location.uri.as_str(), URI);
+    assert_eq!(
+        related[0].location.range,
+        Range::new(Position::new(0, 0), Position::new(0, 2))
+    );
+}
+
+#[test]
+fn no_errors() {
+    assert!(lsp_diagnostics("local x = 1", URI).0.is_empty());
+}
+
+#[test]
+fn utf16_columns() {
+    // Each emoji is 1 character but 2 UTF-16 code units.
+    let (diagnostics, errors) = lsp_diagnostics("local s = '😀😀' if true print(1) end", URI);
+
+    assert_eq!(errors[0].start, Position::new(0, 23));
+    assert_eq!(
+        diagnostics[0].range,
+        Range::new(Position::new(0, 25), Position::new(0, 26))
+    );
+
+    // Related spans are converted too.
+    assert_eq!(
+        diagnostics[0].related_information.as_ref().unwrap()[0]
+            .location
+            .range,
+        Range::new(Position::new(0, 17), Position::new(0, 19))
+    );
+}
+
+#[test]
+fn utf16_columns_in_comments() {
+    let (diagnostics, _) = lsp_diagnostics("-- 😀\nlocal x = 1 -- 😀 😀\nlocal y = 1 +", URI);
+
+    // Wide characters on other lines don't affect the column.
+    assert_eq!(
+        diagnostics[0].range,
+        Range::new(Position::new(2, 12), Position::new(2, 13))
+    );
+}
+
+#[test]
+fn utf16_columns_after_dropped_tokens() {
+    // The emojis are in tokens dropped while recovering from errors, so they
+    // aren't in the CST.
+    let (diagnostics, errors) = lsp_diagnostics("😀😀 = 1 local x = (", URI);
+    let ranges = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.range))
+        .collect::<Vec<_>>();
+
+    assert!(errors.len() >= 2, "{errors:#?}");
+    assert!(
+        ranges.contains(&(
+            "Unexpected <error>",
+            Range::new(Position::new(0, 2), Position::new(0, 4))
+        )),
+        "{ranges:#?}"
+    );
+    assert!(
+        ranges.contains(&(
+            "Expected <expr>",
+            Range::new(Position::new(0, 19), Position::new(0, 20))
+        )),
+        "{ranges:#?}"
+    );
+
+    let (diagnostics, _) = lsp_diagnostics("local s = \"😀\" local x = (", URI);
+    assert_eq!(
+        diagnostics.last().unwrap().range,
+        Range::new(Position::new(0, 25), Position::new(0, 26))
+    );
+}
+
+#[test]
+fn errors_without_end() {
+    // The error is at the end of the line, so it's widened backwards.
+    let (diagnostics, errors) = lsp_diagnostics("local x = 1 +", URI);
+
+    assert_eq!(errors[0].start, errors[0].end);
+    assert_eq!(
+        diagnostics[0].range,
+        Range::new(Position::new(0, 12), Position::new(0, 13))
+    );
+
+    // An empty file has nothing to widen to.
+    let (diagnostics, _) = lsp_diagnostics("if", URI);
+
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.range.start <= diagnostic.range.end)
+    );
+}
+
+#[test]
+fn merge_duplicates() {
+    let (diagnostics, errors) = lsp_diagnostics(
+        "local function foo()\n    if true then\n        print(1)\n",
+        URI,
+    );
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].start, errors[1].start);
+    assert_eq!(diagnostics.len(), 1);
+
+    // The message isn't repeated but both related spans are kept.
+    assert_eq!(diagnostics[0].message, errors[0].message);
+    assert_eq!(
+        diagnostics[0].related_information.as_ref().unwrap().len(),
+        2
+    );
+}
+
+#[test]
+fn different_codes_stay_separate() {
+    let (diagnostics, errors) = lsp_diagnostics("if x", URI);
+
+    assert_eq!(errors.len(), 2);
+    assert_ne!(errors[0].code, errors[1].code);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].message, errors[0].message);
+    assert_eq!(diagnostics[1].message, errors[1].message);
+}
+
+#[test]
+fn invalid_uri() {
+    let (diagnostics, _) = lsp_diagnostics("if true print(1) end", "");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].related_information.is_none());
+}
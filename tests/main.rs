@@ -5,7 +5,7 @@
 //! and checks if the printed text is the same as the input. If parsing was
 //! successful, both should perfectly match.
 
-use luau_parser::prelude::Parser;
+use luau_parser::prelude::{Parser, ParserOptions, Print};
 use std::{
     fs::{self, File},
     io::{self, Read},
@@ -24,13 +24,31 @@ fn process_files(src_dir: &Path) -> io::Result<()> {
             let mut content = String::new();
             file.read_to_string(&mut content)?;
 
-            let mut parser = Parser::new(&content);
+            let mut parser = Parser::new(&content, ParserOptions::default());
             let cst = parser.parse(path.to_string_lossy().as_ref());
 
             if cst.try_print().unwrap() != content {
                 println!("{:#?}\n", cst);
                 panic!("File at '{}' failed.", path.display());
             }
+
+            let mut string = String::new();
+            cst.block.print_to(&mut string).unwrap();
+            assert_eq!(
+                string,
+                content,
+                "`print_to` failed for '{}'.",
+                path.display()
+            );
+
+            let mut bytes = Vec::new();
+            cst.block.print_to_io(&mut bytes)?;
+            assert_eq!(
+                bytes,
+                content.as_bytes(),
+                "`print_to_io` failed for '{}'.",
+                path.display()
+            );
         }
     }
 
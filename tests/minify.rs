@@ -0,0 +1,163 @@
+#![cfg(test)]
+
+//! Tests for [`Cst::minify`](luau_parser::types::Cst::minify).
+
+use luau_parser::{
+    minify::{MinifyError, MinifyOptions},
+    prelude::{Parser, ParserOptions, Symbol, Token, TokenType, Visit, Visitor},
+    semantic::{Resolution, SemanticModel},
+};
+use std::fs;
+
+/// A [`Visitor`] collecting the types of all tokens, except for semicolons.
+#[derive(Default)]
+struct TokenCollector(Vec<TokenType>);
+
+impl Visitor<'_> for TokenCollector {
+    fn visit_token(&mut self, token: &Token) {
+        if token.token_type != TokenType::Symbol(Symbol::Semicolon) {
+            self.0.push(token.token_type.clone());
+        }
+    }
+}
+
+/// Get the types of all tokens in the passed code, with identifiers replaced by
+/// `_` if `rename_locals` is set.
+fn tokens(code: &str, rename_locals: bool) -> Vec<TokenType> {
+    let mut collector = TokenCollector::default();
+    Parser::new(code, ParserOptions::default())
+        .parse("")
+        .visit(&mut collector);
+
+    if rename_locals {
+        for token_type in &mut collector.0 {
+            if let TokenType::Identifier(_) = token_type {
+                *token_type = TokenType::Identifier("_".into());
+            }
+        }
+    }
+
+    collector.0
+}
+
+/// Get how each reference in the passed code resolves, globals by name and
+/// locals by the index of their binding.
+fn resolutions(code: &str) -> Vec<Result<usize, String>> {
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    model
+        .references
+        .iter()
+        .map(|reference| match reference.resolution {
+            Resolution::Binding(binding) => Ok(binding.0),
+            Resolution::Global => Err(reference
+                .token
+                .token_type
+                .try_as_string()
+                .unwrap_or_default()),
+        })
+        .collect()
+}
+
+/// Minify the passed code, checking that the output parses back to the same
+/// tree with the same resolutions.
+fn minify(code: &str, rename_locals: bool) -> String {
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let minified = cst.minify(MinifyOptions { rename_locals }).unwrap();
+
+    let minified_cst = Parser::new(&minified, ParserOptions::default()).parse("");
+    assert!(
+        minified_cst.errors.is_empty(),
+        "{minified}\n{:#?}",
+        minified_cst.errors
+    );
+    assert_eq!(
+        tokens(&minified, rename_locals),
+        tokens(code, rename_locals),
+        "{minified}"
+    );
+    assert_eq!(resolutions(&minified), resolutions(code), "{minified}");
+
+    minified
+}
+
+#[test]
+fn spaces() {
+    assert_eq!(
+        minify("local x = a - -b .. 1 .. c", false),
+        "local x=a- -b..1 ..c"
+    );
+    assert_eq!(minify("print(x .. .5, ...)", false), "print(x.. .5,...)");
+    assert_eq!(
+        minify("local t: Foo<Bar<Baz>> = {[ [[x]] ] = 1}", false),
+        "local t:Foo<Bar<Baz> > ={[ [[x]]]=1}"
+    );
+    assert_eq!(
+        minify(
+            "if not x then return 'a' elseif y then return\"b\" end",
+            false
+        ),
+        "if not x then return'a'elseif y then return\"b\"end"
+    );
+}
+
+#[test]
+fn comments_and_semicolons() {
+    assert_eq!(
+        minify(
+            "-- A comment.\na(); --[[ Another one. ]] b();\n(c)()\nd()\n(e)()",
+            false
+        ),
+        "a()b();(c)()d()(e)()"
+    );
+    assert_eq!(
+        minify("local s = `a {b} c` -- x", false),
+        "local s=`a {b} c`"
+    );
+}
+
+#[test]
+fn renaming() {
+    assert_eq!(
+        minify(
+            "local first = 1\nlocal second = first + 1\nprint(second)\nlocal third = a",
+            true
+        ),
+        "local b=1 local b=b+1 print(b)local b=a"
+    );
+    assert_eq!(
+        minify(
+            "local outer = 1\nlocal function f(x, ...)\n    return outer + x\nend\nfor i, v in pairs(t) do print(i, v) end\nprint(outer)",
+            true
+        ),
+        "local a=1 local function b(b,...)return a+b end for b,c in pairs(t)do print(b,c)end print(a)"
+    );
+    assert_eq!(
+        minify(
+            "local t = {}\nfunction t:method(value)\n    self.value = value\nend",
+            true
+        ),
+        "local a={}function a:method(a)self.value=a end"
+    );
+}
+
+#[test]
+fn errors() {
+    let cst = Parser::new("local = 1", ParserOptions::default()).parse("");
+
+    assert_eq!(
+        cst.minify(MinifyOptions::default()),
+        Err(MinifyError::HasErrors)
+    );
+}
+
+#[test]
+fn test_code() {
+    for entry in fs::read_dir("test-code").unwrap() {
+        let code = fs::read_to_string(entry.unwrap().path()).unwrap();
+
+        minify(&code, false);
+        minify(&code, true);
+    }
+}
@@ -0,0 +1,212 @@
+#![cfg(test)]
+
+//! Tests for [`ParserOptions`] and disabled [`LanguageFeatures`].
+
+use luau_parser::prelude::{
+    Diagnostic, DiagnosticCode, LanguageFeatures, LanguageLevel, Parser, ParserOptions, Position,
+    PositionEncoding, Print,
+};
+
+/// Parse the passed code with the passed options and return its errors.
+fn errors(code: &str, options: ParserOptions) -> Vec<Diagnostic> {
+    Parser::new(code, options).parse("").errors.clone()
+}
+
+/// Get the messages of the passed errors, all of which must be for disabled
+/// features.
+fn disabled(errors: &[Diagnostic]) -> Vec<&str> {
+    errors
+        .iter()
+        .map(|error| {
+            assert_eq!(error.code, DiagnosticCode::DisabledFeature, "{error:#?}");
+            &*error.message
+        })
+        .collect()
+}
+
+const LUAU_CODE: &str = r#"
+type Foo<T = string> = { x: T }
+type function bar(t)
+    return t
+end
+
+@native
+local function baz(x: number): number
+    for i = 1, 10 do
+        if i == x then
+            continue
+        end
+    end
+
+    x += 1
+    x //= 2
+
+    return if x > 1 then x // 2 else `{x}`
+end
+"#;
+
+#[test]
+fn default_enables_everything() {
+    assert_eq!(
+        ParserOptions::default(),
+        LanguageLevel::LuauNewSolver.into()
+    );
+    assert!(errors(LUAU_CODE, ParserOptions::default()).is_empty());
+}
+
+#[test]
+fn luau() {
+    let errors = errors(LUAU_CODE, LanguageLevel::Luau.into());
+
+    assert_eq!(
+        disabled(&errors),
+        ["The `type_functions` feature is disabled."]
+    );
+    assert_eq!(errors[0].start, Position::new(2, 0));
+    assert_eq!(errors[0].end, Position::new(4, 3));
+}
+
+#[test]
+fn lua51() {
+    let errors = errors(LUAU_CODE, LanguageLevel::Lua51.into());
+
+    assert_eq!(
+        disabled(&errors),
+        [
+            "The `types` feature is disabled.",
+            "The `type_functions` feature is disabled.",
+            "The `attributes` feature is disabled.",
+            "The `types` feature is disabled.",
+            "The `types` feature is disabled.",
+            "The `continue_statement` feature is disabled.",
+            "The `compound_assignment` feature is disabled.",
+            "The `compound_assignment` feature is disabled.",
+            "The `if_expressions` feature is disabled.",
+            "The `floor_division` feature is disabled.",
+            "The `string_interpolation` feature is disabled.",
+        ]
+    );
+
+    // The whole type definition is reported once, not each type in it.
+    assert_eq!(errors[0].start, Position::new(1, 0));
+    assert_eq!(errors[0].end, Position::new(1, 31));
+
+    // `continue`
+    assert_eq!(errors[5].start, Position::new(10, 12));
+    assert_eq!(errors[5].end, Position::new(10, 20));
+}
+
+#[test]
+fn lua51_code() {
+    let code = "local continue = 1\nlocal x = continue // 2\nx = x + 1";
+    let errors = errors(code, LanguageLevel::Lua51.into());
+
+    assert_eq!(
+        disabled(&errors),
+        ["The `floor_division` feature is disabled."]
+    );
+    assert_eq!(errors[0].start, Position::new(1, 19));
+}
+
+#[test]
+fn lua51_literals() {
+    let code = "local a = `plain`\nlocal b = 0b101\nlocal c = 1_000 + 0.000_1\nlocal d = `{a}`";
+    assert!(errors(code, LanguageLevel::Luau.into()).is_empty());
+
+    let errors = errors(code, LanguageLevel::Lua51.into());
+    assert_eq!(
+        disabled(&errors),
+        [
+            "The `string_interpolation` feature is disabled.",
+            "The `binary_numbers` feature is disabled.",
+            "The `number_separators` feature is disabled.",
+            "The `number_separators` feature is disabled.",
+            "The `string_interpolation` feature is disabled.",
+        ]
+    );
+    assert_eq!(errors[0].start, Position::new(0, 10));
+    assert_eq!(errors[0].end, Position::new(0, 17));
+}
+
+#[test]
+fn has_errors() {
+    let cst = Parser::new("x += 1", LanguageLevel::Lua51.into()).parse("");
+    assert!(cst.has_errors());
+    assert!(cst.try_print().is_err());
+
+    let cst = Parser::new("x += 1", ParserOptions::default()).parse("");
+    assert!(!cst.has_errors());
+
+    // Feature errors are merged with syntax errors in position order.
+    let errors = errors(
+        "x += 1
+if true print(1) end
+y //= 2",
+        LanguageLevel::Lua51.into(),
+    );
+    let starts = errors.iter().map(|error| error.start).collect::<Vec<_>>();
+    let mut sorted = starts.clone();
+    sorted.sort();
+    assert_eq!(starts, sorted);
+    assert_eq!(errors[0].code, DiagnosticCode::DisabledFeature);
+    assert_eq!(errors.last().unwrap().code, DiagnosticCode::DisabledFeature);
+}
+
+#[test]
+fn single_features() {
+    let features = LanguageFeatures {
+        floor_division: false,
+        generic_defaults: false,
+        ..LanguageFeatures::ALL
+    };
+    let options = ParserOptions::default().with_features(features);
+
+    assert_eq!(
+        disabled(&errors(LUAU_CODE, options)),
+        [
+            "The `generic_defaults` feature is disabled.",
+            "The `floor_division` feature is disabled.",
+            "The `floor_division` feature is disabled.",
+        ]
+    );
+}
+
+#[test]
+fn tree_is_unchanged() {
+    let cst = Parser::new(LUAU_CODE, LanguageLevel::Lua51.into()).parse("");
+
+    assert_eq!(cst.block.print(), LUAU_CODE);
+    assert_eq!(
+        cst.block,
+        Parser::new(LUAU_CODE, ParserOptions::default())
+            .parse("")
+            .block
+    );
+}
+
+#[test]
+fn fragments() {
+    let parser = Parser::new("", LanguageLevel::Lua51.into());
+
+    let parsed = parser.parse_expression("a // b");
+    assert_eq!(
+        disabled(&parsed.errors),
+        ["The `floor_division` feature is disabled."]
+    );
+
+    let parsed = parser.parse_block("local x: number = 1");
+    assert_eq!(
+        disabled(&parsed.errors),
+        ["The `types` feature is disabled."]
+    );
+}
+
+#[test]
+fn position_encoding() {
+    let options =
+        ParserOptions::new(LanguageLevel::Lua51).with_position_encoding(PositionEncoding::Utf8);
+    let errors = errors("local s = '😀' x += 1", options);
+
+    assert_eq!(errors[0].start, Position::new(0, 19));
+    assert_eq!(errors[0].end, Position::new(0, 21));
+}
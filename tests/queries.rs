@@ -0,0 +1,122 @@
+#![cfg(test)]
+
+//! Tests for position and range based queries on the CST.
+
+use luau_parser::prelude::{
+    Literal, LuauNumber, Node, Parser, ParserOptions, Position, Print, Range, Statement, TokenType,
+};
+
+const CODE: &str = "local foo = bar(1)
+print(foo)
+
+local function qux()
+    return foo + 1
+end
+";
+
+/// Get the variant names of the passed nodes.
+fn kinds(nodes: &[Node]) -> Vec<&'static str> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Cst(_) => "Cst",
+            Node::Block(_) => "Block",
+            Node::Statement(_) => "Statement",
+            Node::TerminationStatement(_) => "TerminationStatement",
+            Node::LocalAssignment(_) => "LocalAssignment",
+            Node::LocalFunction(_) => "LocalFunction",
+            Node::Expression(_) => "Expression",
+            Node::BinaryOperator(_) => "BinaryOperator",
+            Node::FunctionCall(_) => "FunctionCall",
+            Node::FunctionCallInvoked(_) => "FunctionCallInvoked",
+            Node::PrefixExp(_) => "PrefixExp",
+            Node::Var(_) => "Var",
+            Node::Token(_) => "Token",
+            _ => "Other",
+        })
+        .collect()
+}
+
+#[test]
+fn node_at() {
+    let cst = Parser::new(CODE, ParserOptions::default()).parse("");
+
+    let nodes = cst.node_at(Position::new(0, 13));
+    assert_eq!(
+        kinds(&nodes),
+        [
+            "Cst",
+            "Block",
+            "Statement",
+            "LocalAssignment",
+            "Expression",
+            "FunctionCall",
+            "FunctionCallInvoked",
+            "PrefixExp",
+            "Var",
+            "Token"
+        ]
+    );
+    let Some(Node::Token(token)) = nodes.last() else {
+        panic!("Expected a token.");
+    };
+    assert_eq!(token.token_type, TokenType::Identifier("bar".into()));
+
+    let nodes = cst.node_at(Position::new(4, 15));
+    assert_eq!(
+        kinds(&nodes[..5]),
+        ["Cst", "Block", "Statement", "LocalFunction", "Block"]
+    );
+    assert_eq!(
+        kinds(&nodes[5..]),
+        [
+            "TerminationStatement",
+            "Expression",
+            "BinaryOperator",
+            "Token"
+        ]
+    );
+
+    assert!(cst.node_at(Position::new(100, 0)).is_empty());
+}
+
+#[test]
+fn nodes_in() {
+    let cst = Parser::new(CODE, ParserOptions::default()).parse("");
+
+    let nodes = cst.nodes_in(Range::new(Position::new(1, 0), Position::new(1, 10)));
+    assert_eq!(kinds(&nodes), ["Statement"]);
+    let Node::Statement(statement) = nodes[0] else {
+        unreachable!()
+    };
+    assert!(matches!(statement, Statement::FunctionCall(_)));
+    assert_eq!(statement.print().trim(), "print(foo)");
+
+    let nodes = cst.nodes_in(Range::new(Position::new(0, 6), Position::new(0, 15)));
+    assert_eq!(kinds(&nodes), ["Other", "Token", "FunctionCallInvoked"]);
+}
+
+#[test]
+fn token_ends() {
+    let cst = Parser::new("local x = 1 -- one\nprint(x)", ParserOptions::default()).parse("");
+    let Statement::LocalAssignment(local_assignment) = &*cst.block.statements[0].0 else {
+        panic!("Expected a local assignment.");
+    };
+
+    // Tokens end at their text, not after their trailing trivia.
+    assert_eq!(local_assignment.local_token.end, Position::new(0, 5));
+
+    let nodes = cst.node_at(Position::new(0, 11));
+    let Some(Node::Token(one)) = nodes.last() else {
+        panic!("Expected a token.");
+    };
+    assert_eq!(
+        one.token_type,
+        TokenType::Literal(Literal::Number(LuauNumber::Plain("1".into())))
+    );
+    assert_eq!(one.end, Position::new(0, 11));
+    assert!(!one.trailing_trivia.is_empty());
+
+    // The comment is after the statement, not in it.
+    assert_eq!(kinds(&cst.node_at(Position::new(0, 14))), ["Cst", "Block"]);
+}
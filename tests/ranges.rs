@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+//! Tests for the [`ranges`](luau_parser::ranges) module.
+
+use luau_parser::prelude::{FoldingRangeKind, Parser, ParserOptions, Position, Range};
+
+/// Get `(start line, end line, kind)` for each folding range of the code.
+fn folds(code: &str) -> Vec<(u32, u32, Option<FoldingRangeKind>)> {
+    Parser::new(code, ParserOptions::default())
+        .parse("")
+        .folding_ranges()
+        .into_iter()
+        .map(|range| (range.start_line, range.end_line, range.kind))
+        .collect()
+}
+
+#[test]
+fn folding() {
+    let code = "\
+--[[
+    A long comment.
+]]
+--#region Setup
+local t = {
+    a = 1,
+    f = function()
+        return 1
+    end,
+}
+--#endregion
+if t.a then
+    print(1)
+elseif t.b then
+    print(2)
+else
+    print(3)
+end
+for i = 1, 10
+do
+    while true do
+        break
+    end
+end
+do end
+repeat
+    local x = 1
+until x
+local short = { 1, 2 }
+";
+
+    assert_eq!(
+        folds(code),
+        [
+            (0, 2, Some(FoldingRangeKind::Comment)),
+            (3, 10, Some(FoldingRangeKind::Region)),
+            (4, 8, None),
+            (6, 7, None),
+            (11, 12, None),
+            (13, 14, None),
+            (15, 16, None),
+            (18, 22, None),
+            (20, 21, None),
+            (25, 26, None),
+        ]
+    );
+}
+
+#[test]
+fn unclosed_regions() {
+    assert_eq!(
+        folds("--#endregion\n--#region\nlocal x = 1\n--#region\n\n--#endregion"),
+        [(3, 5, Some(FoldingRangeKind::Region))]
+    );
+}
+
+#[test]
+fn selection() {
+    let code = "local function f(a, b)\n    return a + b\nend";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+
+    let selections = cst.selection_ranges(&[Position::new(1, 15), Position::new(5, 0)]);
+    assert_eq!(selections.len(), 2);
+
+    let mut chain = Vec::new();
+    let mut selection = Some(&selections[0]);
+    while let Some(current) = selection {
+        chain.push(current.range);
+        selection = current.parent.as_deref();
+    }
+
+    let range = |start: (u32, u32), end: (u32, u32)| {
+        Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+    };
+    assert_eq!(
+        chain,
+        [
+            // `b`
+            range((1, 15), (1, 16)),
+            // `a + b`
+            range((1, 11), (1, 16)),
+            // `return a + b`
+            range((1, 4), (1, 16)),
+            // The function
+            range((0, 0), (2, 3)),
+        ]
+    );
+
+    assert_eq!(selections[1].range, range((5, 0), (5, 0)));
+    assert!(selections[1].parent.is_none());
+}
@@ -0,0 +1,168 @@
+#![cfg(test)]
+
+//! Tests for [`rename`], every reference in scope and nothing else must be
+//! renamed.
+
+use luau_parser::{
+    prelude::{Parser, ParserOptions, Position},
+    rename::{RenameError, rename},
+};
+
+/// Rename what's at `(line, character)` in the code and return the new code.
+fn renamed(code: &str, line: u32, character: u32, new_name: &str) -> Result<String, RenameError> {
+    let cst = Parser::new(code, ParserOptions::default()).parse("file:///main.luau");
+    let edit = rename(&cst, Position::new(line, character), new_name)?;
+
+    let mut edits = edit.changes.unwrap().into_values().next().unwrap();
+    edits.sort_by_key(|edit| edit.range.start);
+
+    let mut lines = code.split('\n').map(str::to_string).collect::<Vec<_>>();
+    for edit in edits.iter().rev() {
+        let line = &mut lines[edit.range.start.line as usize];
+        line.replace_range(
+            edit.range.start.character as usize..edit.range.end.character as usize,
+            &edit.new_text,
+        );
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[test]
+fn values() {
+    let code = "\
+local x = 1
+local function f(x)
+    return x
+end
+print(x, f(x))";
+
+    assert_eq!(
+        renamed(code, 0, 6, "y").unwrap(),
+        "\
+local y = 1
+local function f(x)
+    return x
+end
+print(y, f(y))"
+    );
+    assert_eq!(
+        renamed(code, 2, 11, "value").unwrap(),
+        "\
+local x = 1
+local function f(value)
+    return value
+end
+print(x, f(x))"
+    );
+    assert_eq!(
+        renamed(code, 1, 15, "g").unwrap(),
+        "\
+local x = 1
+local function g(x)
+    return x
+end
+print(x, g(x))"
+    );
+}
+
+#[test]
+fn types() {
+    let code = "\
+local function make(): Point
+    return { x = 0 }
+end
+type Point = { x: number }
+local Point = 1
+local p: Point = make()";
+
+    assert_eq!(
+        renamed(code, 3, 6, "Vector").unwrap(),
+        "\
+local function make(): Vector
+    return { x = 0 }
+end
+type Vector = { x: number }
+local Point = 1
+local p: Vector = make()"
+    );
+    assert_eq!(
+        renamed(code, 4, 7, "Origin").unwrap(),
+        "\
+local function make(): Point
+    return { x = 0 }
+end
+type Point = { x: number }
+local Origin = 1
+local p: Point = make()"
+    );
+}
+
+#[test]
+fn generics() {
+    let code = "\
+type Pair<T> = { first: T, second: T }
+local function id<T>(value: T): T
+    return value
+end";
+
+    assert_eq!(
+        renamed(code, 0, 10, "Item").unwrap(),
+        "\
+type Pair<Item> = { first: Item, second: Item }
+local function id<T>(value: T): T
+    return value
+end"
+    );
+    assert_eq!(
+        renamed(code, 1, 28, "U").unwrap(),
+        "\
+type Pair<T> = { first: T, second: T }
+local function id<U>(value: U): U
+    return value
+end"
+    );
+}
+
+#[test]
+fn errors() {
+    let code = "\
+local a = 1
+local b = 2
+do
+    local c = a
+    print(c)
+end
+type T = number";
+
+    assert_eq!(renamed(code, 0, 6, "end"), Err(RenameError::Keyword));
+    assert_eq!(renamed(code, 0, 6, "1a"), Err(RenameError::InvalidName));
+    assert_eq!(renamed(code, 0, 6, "a b"), Err(RenameError::InvalidName));
+    // `a` would refer to `b` in `local c = a`.
+    assert_eq!(renamed(code, 0, 6, "b"), Err(RenameError::Shadowing));
+    assert_eq!(renamed(code, 1, 6, "a"), Err(RenameError::Shadowing));
+    assert_eq!(renamed(code, 3, 10, "print"), Err(RenameError::Shadowing));
+    assert_eq!(renamed(code, 6, 5, "string"), Err(RenameError::Shadowing));
+    assert_eq!(renamed(code, 4, 4, "x"), Err(RenameError::NothingToRename));
+    assert_eq!(renamed(code, 2, 0, "x"), Err(RenameError::NothingToRename));
+
+    let generic = "type T = number\nlocal function f<U>(x: U, y: T) end";
+    assert_eq!(renamed(generic, 1, 17, "T"), Err(RenameError::Shadowing));
+
+    // Types don't clash with values.
+    assert!(renamed(code, 6, 5, "a").is_ok());
+    // No reference would change, but one `a` would shadow the other.
+    assert_eq!(renamed(code, 0, 6, "c"), Err(RenameError::Shadowing));
+    assert_eq!(renamed(code, 3, 10, "a"), Err(RenameError::Shadowing));
+
+    let code = "local a = 1 do local b = 2 print(b) end print(a)";
+    assert_eq!(renamed(code, 0, 21, "a"), Err(RenameError::Shadowing));
+    assert_eq!(renamed(code, 0, 6, "b"), Err(RenameError::Shadowing));
+
+    // Bindings in unrelated scopes can share a name.
+    let code = "do local a = 1 print(a) end do local b = 2 print(b) end";
+    assert_eq!(
+        renamed(code, 0, 37, "a").unwrap(),
+        "do local a = 1 print(a) end do local a = 2 print(a) end"
+    );
+}
@@ -0,0 +1,181 @@
+#![cfg(test)]
+
+//! Tests for the [`rewrite`](luau_parser::rewrite) module.
+
+use luau_parser::{
+    build::{call, local, name, number, string},
+    prelude::{
+        Cst, Expression, FunctionArgument, FunctionArguments, ListItem, Parser, ParserOptions,
+        Pointer, Position, Range, Statement, TextEdit,
+    },
+    rewrite::{RewriteError, Rewriter},
+};
+
+/// Parse the passed code.
+fn parse(code: &str) -> Pointer<Cst> {
+    Parser::new(code, ParserOptions::default()).parse("")
+}
+
+/// Get the `index`th statement of the main block.
+fn statement(cst: &Cst, index: usize) -> &Statement {
+    &cst.block.statements[index].0
+}
+
+#[test]
+fn replace() {
+    let cst = parse("local function foo()\nend\nfoo()\n");
+    let mut rewriter = Rewriter::new(&cst);
+
+    let Statement::LocalFunction(function) = statement(&cst, 0) else {
+        panic!("expected a local function");
+    };
+    rewriter
+        .replace_text(&function.function_name, "bar")
+        .unwrap();
+    rewriter
+        .replace(statement(&cst, 1), &call("bar", [number(1)]))
+        .unwrap();
+
+    assert_eq!(
+        rewriter.edits().unwrap(),
+        vec![
+            TextEdit::new(
+                Range::new(Position::new(0, 15), Position::new(0, 18)),
+                "bar".to_string()
+            ),
+            // The common `)` isn't edited.
+            TextEdit::new(
+                Range::new(Position::new(2, 0), Position::new(2, 4)),
+                "bar(1".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        rewriter.apply().unwrap(),
+        "local function bar()\nend\nbar(1)\n"
+    );
+}
+
+#[test]
+fn remove() {
+    let code = "\
+local a = 1
+
+-- About b.
+-- More about b.
+local b = 2 -- Two.
+local c = 3; local d = 4
+print(a, b, c)
+";
+    let cst = parse(code);
+    let mut rewriter = Rewriter::new(&cst);
+
+    rewriter.remove(statement(&cst, 1)).unwrap();
+    rewriter.remove(statement(&cst, 2)).unwrap();
+
+    let Statement::FunctionCall(print) = statement(&cst, 4) else {
+        panic!("expected a function call");
+    };
+    let FunctionArguments::List(arguments) = &print.arguments else {
+        panic!("expected arguments");
+    };
+    let ListItem::Trailing { item, .. } = &arguments.item.items[1] else {
+        panic!("expected a trailing item");
+    };
+    rewriter.remove(&**item).unwrap();
+
+    assert_eq!(
+        rewriter.apply().unwrap(),
+        "local a = 1\n\nlocal d = 4\nprint(a, c)\n"
+    );
+}
+
+#[test]
+fn remove_last() {
+    let cst = parse("local a = 1\nprint(a, b)");
+    let mut rewriter = Rewriter::new(&cst);
+
+    rewriter.remove(statement(&cst, 1)).unwrap();
+    assert_eq!(rewriter.apply().unwrap(), "local a = 1");
+
+    let Statement::FunctionCall(print) = statement(&cst, 1) else {
+        panic!("expected a function call");
+    };
+    let FunctionArguments::List(arguments) = &print.arguments else {
+        panic!("expected arguments");
+    };
+    let ListItem::NonTrailing(item) = &arguments.item.items[1] else {
+        panic!("expected a non trailing item");
+    };
+    let FunctionArgument::Expression(expression) = &**item else {
+        panic!("expected an expression");
+    };
+    assert!(matches!(**expression, Expression::Var(_)));
+
+    let mut rewriter = Rewriter::new(&cst);
+    rewriter.remove(&**item).unwrap();
+    assert_eq!(rewriter.apply().unwrap(), "local a = 1\nprint(a)");
+}
+
+#[test]
+fn insert() {
+    let code = "\
+do
+    -- A comment.
+    foo() -- Another one.
+    bar()
+end
+";
+    let cst = parse(code);
+    let Statement::DoBlock(do_block) = statement(&cst, 0) else {
+        panic!("expected a do block");
+    };
+    let foo = &do_block.body.statements[0].0;
+
+    let mut rewriter = Rewriter::new(&cst);
+    rewriter
+        .insert_before(&**foo, &local("x", string("x")))
+        .unwrap();
+    rewriter
+        .insert_after(&**foo, &call("print", [name("x")]))
+        .unwrap();
+
+    assert_eq!(
+        rewriter.apply().unwrap(),
+        "\
+do
+    local x = \"x\"
+    -- A comment.
+    foo() -- Another one.
+    print(x)
+    bar()
+end
+"
+    );
+
+    let cst = parse("foo(); bar()");
+    let mut rewriter = Rewriter::new(&cst);
+    rewriter
+        .insert_after(statement(&cst, 0), &call("baz", Vec::<Expression>::new()))
+        .unwrap();
+
+    assert_eq!(rewriter.apply().unwrap(), "foo(); baz() bar()");
+}
+
+#[test]
+fn errors() {
+    let cst = parse("local a = 1\n");
+    let mut rewriter = Rewriter::new(&cst);
+
+    let Statement::LocalAssignment(assignment) = statement(&cst, 0) else {
+        panic!("expected a local assignment");
+    };
+    assert_eq!(
+        rewriter.remove(&local("b", number(2))),
+        Err(RewriteError::UnknownNode)
+    );
+
+    rewriter.remove(statement(&cst, 0)).unwrap();
+    rewriter.replace_text(&assignment.local_token, "").unwrap();
+    assert_eq!(rewriter.edits(), Err(RewriteError::Overlapping));
+}
@@ -0,0 +1,271 @@
+#![cfg(test)]
+
+//! Tests for the [`SemanticModel`], every name must resolve to the binding
+//! Luau would use for it.
+
+use luau_parser::{
+    prelude::{Parser, ParserOptions, Position},
+    semantic::{BindingKind, ReferenceKind, Resolution, ScopeKind, SemanticModel, TypeBindingKind},
+};
+
+/// Get `(name, line of the declaring token)` for what each reference resolves
+/// to, globals have [`None`] as their line.
+fn resolutions(model: &SemanticModel) -> Vec<(String, Option<u32>)> {
+    model
+        .references
+        .iter()
+        .map(|reference| {
+            let name = reference.token.token_type.try_as_string().unwrap();
+            let line = match reference.resolution {
+                Resolution::Binding(id) => Some(model.binding(id).token.start.line),
+                Resolution::Global => None,
+            };
+
+            (name, line)
+        })
+        .collect()
+}
+
+/// Helper to build the expected output of [`resolutions`].
+fn expected(items: &[(&str, Option<u32>)]) -> Vec<(String, Option<u32>)> {
+    items
+        .iter()
+        .map(|(name, line)| (name.to_string(), *line))
+        .collect()
+}
+
+#[test]
+fn shadowing() {
+    let code = "local x = 1
+do
+    local x = x + 1
+    print(x)
+end
+print(x)
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    assert_eq!(
+        resolutions(&model),
+        expected(&[
+            ("x", Some(0)),
+            ("print", None),
+            ("x", Some(2)),
+            ("print", None),
+            ("x", Some(0)),
+        ])
+    );
+    assert_eq!(model.bindings.len(), 2);
+    assert_eq!(model.references_of(model.bindings[0].id).count(), 2);
+}
+
+#[test]
+fn functions() {
+    let code = "local function fact(n)
+    return if n == 0 then 1 else n * fact(n - 1)
+end
+local f = function() return f end
+function Foo:bar(a)
+    return self, a
+end
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    assert_eq!(
+        resolutions(&model),
+        expected(&[
+            ("n", Some(0)),
+            ("n", Some(0)),
+            ("fact", Some(0)),
+            ("n", Some(0)),
+            ("f", None),
+            ("Foo", None),
+            ("self", Some(4)),
+            ("a", Some(4)),
+        ])
+    );
+
+    let kinds = model
+        .bindings
+        .iter()
+        .map(|binding| binding.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            BindingKind::LocalFunction,
+            BindingKind::Parameter,
+            BindingKind::Local,
+            BindingKind::ImplicitSelf,
+            BindingKind::Parameter,
+        ]
+    );
+}
+
+#[test]
+fn loops() {
+    let code = "local i = 10
+for i = i, 1, -1 do print(i) end
+for k, v in pairs(t) do print(k, v) end
+repeat local done = true until done
+print(i, k, done)
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    assert_eq!(
+        resolutions(&model),
+        expected(&[
+            ("i", Some(0)),
+            ("print", None),
+            ("i", Some(1)),
+            ("pairs", None),
+            ("t", None),
+            ("print", None),
+            ("k", Some(2)),
+            ("v", Some(2)),
+            ("done", Some(3)),
+            ("print", None),
+            ("i", Some(0)),
+            ("k", None),
+            ("done", None),
+        ])
+    );
+}
+
+#[test]
+fn reference_kinds() {
+    let code = "local a, b = 1
+a, b = b, a
+a += 1
+c.d = a
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    let kinds = model
+        .references
+        .iter()
+        .map(|reference| reference.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            ReferenceKind::Write,
+            ReferenceKind::Write,
+            ReferenceKind::Read,
+            ReferenceKind::Read,
+            ReferenceKind::ReadWrite,
+            ReferenceKind::Read,
+            ReferenceKind::Read,
+        ]
+    );
+    assert_eq!(model.globals().count(), 1);
+}
+
+#[test]
+fn queries() {
+    let code = "local x = 1
+local function foo(y)
+    return x + y
+end
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    let scope = model.scope(model.scope_at(Position::new(2, 10)));
+    assert_eq!(scope.kind, ScopeKind::Block);
+    assert_eq!(model.scope(scope.parent.unwrap()).kind, ScopeKind::Function);
+
+    let x = model.binding_at(Position::new(0, 6)).unwrap();
+    assert_eq!(model.binding_at(Position::new(2, 11)), Some(x));
+    assert_eq!(
+        model.lookup("x", Position::new(2, 0)),
+        Resolution::Binding(x)
+    );
+    assert_eq!(model.lookup("x", Position::new(0, 8)), Resolution::Global);
+    assert_eq!(model.lookup("y", Position::new(4, 0)), Resolution::Global);
+
+    let reference = model.reference_at(Position::new(2, 15)).unwrap();
+    assert_eq!(reference.token.token_type.try_as_string().unwrap(), "y");
+}
+
+#[test]
+fn interpolated_strings() {
+    let code = "local name = \"world\"
+print(`hello {name}, {#name} {other}`)
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    assert_eq!(
+        resolutions(&model),
+        expected(&[
+            ("print", None),
+            ("name", Some(0)),
+            ("name", Some(0)),
+            ("other", None),
+        ])
+    );
+}
+
+#[test]
+fn types() {
+    let code = "local function make(): Point
+    return {}
+end
+type Point = { x: number }
+type Box<T> = { value: T, point: Point }
+local function unbox<T>(box: Box<T>): T
+    return box.value
+end
+";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let model = SemanticModel::new(&cst);
+
+    let resolutions = model
+        .type_references
+        .iter()
+        .map(|reference| {
+            let name = reference.token.token_type.try_as_string().unwrap();
+            let binding = reference.resolution.map(|id| model.type_binding(id));
+
+            (
+                name,
+                binding.map(|binding| (binding.token.start.line, binding.kind)),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        resolutions,
+        [
+            // Type definitions are visible before their declaration.
+            (
+                "Point".to_string(),
+                Some((3, TypeBindingKind::TypeDefinition))
+            ),
+            ("number".to_string(), None),
+            ("T".to_string(), Some((4, TypeBindingKind::Generic))),
+            (
+                "Point".to_string(),
+                Some((3, TypeBindingKind::TypeDefinition))
+            ),
+            (
+                "Box".to_string(),
+                Some((4, TypeBindingKind::TypeDefinition))
+            ),
+            ("T".to_string(), Some((5, TypeBindingKind::Generic))),
+            ("T".to_string(), Some((5, TypeBindingKind::Generic))),
+        ]
+    );
+
+    // Values and types don't share names.
+    assert_eq!(model.binding_at(Position::new(3, 6)), None);
+    assert_eq!(
+        model.lookup_type("T", Position::new(6, 0)),
+        model.type_binding_at(Position::new(5, 21))
+    );
+    assert_eq!(model.lookup_type("T", Position::new(8, 0)), None);
+}
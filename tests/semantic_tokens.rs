@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+//! Tests for the [`semantic_tokens`](luau_parser::semantic_tokens) module.
+
+use luau_parser::{
+    prelude::{Parser, ParserOptions, Position, Range, SemanticTokens},
+    semantic_tokens::{TOKEN_MODIFIERS, TOKEN_TYPES, semantic_tokens, semantic_tokens_range},
+};
+
+/// Decode the tokens into `text: type [modifiers]` strings.
+fn decode(code: &str, tokens: SemanticTokens) -> Vec<String> {
+    let lines = code.lines().collect::<Vec<_>>();
+    let (mut line, mut character) = (0, 0);
+
+    tokens
+        .data
+        .iter()
+        .map(|token| {
+            if token.delta_line == 0 {
+                character += token.delta_start;
+            } else {
+                line += token.delta_line;
+                character = token.delta_start;
+            }
+
+            let start = character as usize;
+            let text = &lines[line as usize][start..start + token.length as usize];
+            let modifiers = TOKEN_MODIFIERS
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| token.token_modifiers_bitset & (1 << index) != 0)
+                .map(|(_, modifier)| format!(" {}", modifier.as_str()))
+                .collect::<String>();
+
+            format!(
+                "{text}: {}{modifiers}",
+                TOKEN_TYPES[token.token_type as usize].as_str()
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn classification() {
+    let code = "\
+local Point = {}
+type Point<T> = { x: T }
+@native
+function Point.new(x: number): Point<number>
+    local point = setmetatable({ x = x }, Point)
+    point:move(x)
+    count += 1
+    return point
+end
+function Point:move(by: number)
+    self.x += by
+end";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+
+    assert_eq!(
+        decode(code, semantic_tokens(&cst)),
+        [
+            "Point: variable declaration",
+            "Point: type declaration",
+            "T: typeParameter declaration",
+            "x: property",
+            "T: typeParameter",
+            "@: decorator",
+            "native: decorator",
+            "Point: variable",
+            "new: function declaration",
+            "x: parameter declaration",
+            "number: type defaultLibrary",
+            "Point: type",
+            "number: type defaultLibrary",
+            "point: variable declaration",
+            "setmetatable: variable global",
+            "x: property",
+            "x: parameter",
+            "Point: variable",
+            "point: variable",
+            "move: method",
+            "x: parameter",
+            "count: variable modification global",
+            "point: variable",
+            "Point: variable",
+            "move: method declaration",
+            "by: parameter declaration",
+            "number: type defaultLibrary",
+            "self: parameter",
+            "x: property",
+            "by: parameter",
+        ]
+    );
+}
+
+#[test]
+fn range() {
+    let code = "local a = 1\nlocal b = a\nprint(b)";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let range = Range::new(Position::new(1, 8), Position::new(2, 3));
+
+    assert_eq!(
+        decode(code, semantic_tokens_range(&cst, range)),
+        ["a: variable", "print: variable global"]
+    );
+}
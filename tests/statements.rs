@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+//! Tests for the shape of parsed statements, which can't be covered by
+//! printing the CST back.
+
+use luau_parser::prelude::{GlobalFunctionName, Parser, Print, Statement};
+
+/// Parse the passed global function and return its name.
+fn function_name(code: &str) -> GlobalFunctionName {
+    let parsed = Parser::default().parse_statement(code);
+    assert!(!parsed.has_errors(), "{code}: {:#?}", parsed.errors);
+
+    let Some((Statement::GlobalFunction(function), _)) = parsed.node else {
+        panic!("Expected a global function: {code}");
+    };
+
+    function.function_name.clone()
+}
+
+#[test]
+fn global_function_names() {
+    assert!(matches!(
+        function_name("function foo() end"),
+        GlobalFunctionName::SimpleName(_)
+    ));
+
+    let cases = [
+        ("function foo.bar() end", ".bar", None),
+        ("function foo:bar() end", "", Some("bar")),
+        ("function foo.bar.qux() end", ".bar.qux", None),
+        // The method comes after all the keys.
+        ("function foo.bar.qux:Test() end", ".bar.qux", Some("Test")),
+    ];
+
+    for (code, expected_keys, expected_method) in cases {
+        let GlobalFunctionName::Table {
+            table,
+            keys,
+            method,
+        } = function_name(code)
+        else {
+            panic!("Expected a table function name: {code}");
+        };
+
+        assert_eq!(table.print().trim(), "foo", "{code}");
+        assert_eq!(keys.print().trim(), expected_keys, "{code}");
+        assert_eq!(
+            method
+                .as_ref()
+                .map(|method| method.1.print())
+                .as_deref()
+                .map(str::trim),
+            expected_method,
+            "{code}"
+        );
+    }
+}
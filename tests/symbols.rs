@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+//! Tests for [`Cst::document_symbols`](luau_parser::types::Cst::document_symbols).
+
+use luau_parser::prelude::{DocumentSymbol, Parser, ParserOptions, Position, Range};
+
+/// Get the outline of the code, one `name (kind)` per line, indented by depth.
+fn outline(code: &str) -> String {
+    /// Write the passed symbols and their children.
+    fn write(symbols: &[DocumentSymbol], depth: usize, out: &mut String) {
+        for symbol in symbols {
+            out.push_str(&format!(
+                "{}{} ({:?})\n",
+                "  ".repeat(depth),
+                symbol.name,
+                symbol.kind
+            ));
+            write(
+                symbol.children.as_deref().unwrap_or_default(),
+                depth + 1,
+                out,
+            );
+        }
+    }
+
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let mut out = String::new();
+    write(&cst.document_symbols(), 0, &mut out);
+
+    out
+}
+
+#[test]
+fn outline_of_a_module() {
+    let code = "\
+export type Point = { x: number, y: number }
+type function keys(t)
+    return t
+end
+
+local M = {
+    version = 1,
+    new = function(x, y)
+        local function clamp(value) return value end
+        return clamp(x)
+    end,
+    nested = { deep = true },
+    [\"skipped\"] = 2,
+}
+local a, b = 1
+
+function M.util.helper() end
+function M.nested.make() end
+function Foo.bar:baz() end
+function M:method()
+    if true then
+        local function inner() end
+    end
+end
+local function main()
+    local notASymbol = 1
+end
+
+return M";
+
+    assert_eq!(
+        outline(code),
+        "\
+Point (Struct)
+keys (Function)
+M (Object)
+  version (Field)
+  new (Function)
+    clamp (Function)
+  nested (Object)
+    deep (Field)
+    make (Function)
+  util.helper (Function)
+  method (Method)
+    inner (Function)
+a (Variable)
+b (Variable)
+Foo.bar:baz (Method)
+main (Function)
+"
+    );
+}
+
+#[test]
+fn ranges() {
+    let cst = Parser::new(
+        "local function foo()\nend\nlocal t = { bar = 1 }",
+        ParserOptions::default(),
+    )
+    .parse("");
+    let symbols = cst.document_symbols();
+
+    assert_eq!(
+        symbols[0].range,
+        Range::new(Position::new(0, 0), Position::new(1, 3))
+    );
+    assert_eq!(
+        symbols[0].selection_range,
+        Range::new(Position::new(0, 15), Position::new(0, 18))
+    );
+
+    let field = &symbols[1].children.as_ref().unwrap()[0];
+    assert_eq!(
+        field.range,
+        Range::new(Position::new(2, 12), Position::new(2, 19))
+    );
+    assert_eq!(
+        field.selection_range,
+        Range::new(Position::new(2, 12), Position::new(2, 15))
+    );
+}
+
+#[test]
+fn children_are_inside_their_parent() {
+    /// Check that the children of the passed symbols are inside them.
+    fn check(symbols: &[DocumentSymbol]) {
+        for symbol in symbols {
+            let children = symbol.children.as_deref().unwrap_or_default();
+            for child in children {
+                assert!(
+                    symbol.range.start <= child.range.start && child.range.end <= symbol.range.end,
+                    "{} isn't inside {}",
+                    child.name,
+                    symbol.name
+                );
+            }
+
+            check(children);
+        }
+    }
+
+    let code = "\
+local M = {}
+local Foo = { bar = {} }
+
+function Foo.bar:baz()
+    return 1
+end
+
+function M.f()
+end";
+    let symbols = Parser::new(code, ParserOptions::default())
+        .parse("")
+        .document_symbols();
+    check(&symbols);
+
+    // `M` and `Foo` are extended to the end of the functions nested in them.
+    assert_eq!(
+        symbols[0].range,
+        Range::new(Position::new(0, 0), Position::new(8, 3))
+    );
+    assert_eq!(
+        symbols[1].range,
+        Range::new(Position::new(1, 0), Position::new(5, 3))
+    );
+    assert_eq!(symbols[1].children.as_ref().unwrap()[0].name, "bar");
+    assert_eq!(
+        symbols[1].children.as_ref().unwrap()[0].range,
+        Range::new(Position::new(1, 14), Position::new(5, 3))
+    );
+}
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+//! Tests for [`Cst::to_lua51`](luau_parser::types::Cst::to_lua51).
+
+use luau_parser::{
+    prelude::{LanguageLevel, Parser, ParserOptions, Position},
+    transpile::TranspileError,
+};
+use std::fs;
+
+/// Transpile the passed code, which must have no errors.
+fn transpile(code: &str) -> String {
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+    let lua = cst.to_lua51().unwrap();
+
+    let lua_cst = Parser::new(&lua, LanguageLevel::Lua51.into()).parse("");
+    assert!(lua_cst.errors.is_empty(), "{lua}\n{:#?}", lua_cst.errors);
+    assert_eq!(lua.lines().count(), code.lines().count(), "{lua}");
+
+    lua
+}
+
+#[test]
+fn types() {
+    let code = r#"
+export type Foo<T = string> = {
+    x: T,
+};
+type function bar(t)
+    return t
+end
+@native
+local function baz<T>(x: T, ...: number): (T, number)
+    local y: Foo<number> = { x = 1 }
+    local z = (y :: any).x :: number
+    return x, z
+end
+local f = function<T>(x: T): T return x end
+for k: string, v: number in pairs({}) do end
+"#;
+
+    assert_eq!(
+        transpile(code),
+        r#"
+
+
+
+
+
+
+
+local function baz(x, ...)
+    local y = { x = 1 }
+    local z = (y).x
+    return x, z
+end
+local f = function(x) return x end
+for k, v in pairs({}) do end
+"#
+    );
+}
+
+#[test]
+fn continue_statement() {
+    let code = r#"
+for i = 1, 10 do
+    if i % 2 == 0 then
+        continue
+    end
+    print(i)
+end
+while true do
+    if a then continue end
+    if b then break end
+    for _ = 1, 2 do break end
+end
+"#;
+
+    assert_eq!(
+        transpile(code),
+        r#"
+for i = 1, 10 do repeat
+    if i % 2 == 0 then
+        break
+    end
+    print(i)
+until true end
+while true do local __luau_break = false repeat
+    if a then break end
+    if b then do __luau_break = true break end end
+    for _ = 1, 2 do break end
+until true if __luau_break then break end end
+"#
+    );
+}
+
+#[test]
+fn compound_assignment() {
+    assert_eq!(transpile("x += 1"), "x = x + (1)");
+    assert_eq!(transpile("x ..= 'a' .. b"), "x = x .. ('a' .. b)");
+    assert_eq!(transpile("x //= 2"), "x = math.floor(x / (2))");
+    assert_eq!(
+        transpile("t.a.b -= 1"),
+        "do local __luau_table, __luau_key = t.a, \"b\" __luau_table[__luau_key] = __luau_table[__luau_key] - (1) end"
+    );
+    assert_eq!(
+        transpile("t[f()] *= 2"),
+        "do local __luau_table, __luau_key = t, f() __luau_table[__luau_key] = __luau_table[__luau_key] * (2) end"
+    );
+}
+
+#[test]
+fn if_expressions() {
+    assert_eq!(
+        transpile("local x = if a then b else c"),
+        "local x = ((a) and {b} or {c})[1]"
+    );
+    assert_eq!(
+        transpile("local x = if a or b then f() elseif c then nil else if d then 1 else 2"),
+        "local x = ((a or b) and {f()} or (c) and {nil} or {((d) and {1} or {2})[1]})[1]"
+    );
+}
+
+#[test]
+fn strings() {
+    assert_eq!(
+        transpile("local _ = `Hello, {name}! {1 + 2}`"),
+        "local _ = (\"Hello, \" .. tostring(name) .. \"! \" .. tostring(1 + 2))"
+    );
+    assert_eq!(
+        transpile("local _ = `{a}{b}`"),
+        "local _ = (tostring(a) .. tostring(b))"
+    );
+    assert_eq!(
+        transpile("local _ = `\"plain\"`"),
+        r#"local _ = "\"plain\"""#
+    );
+    assert_eq!(
+        transpile(r#"local _ = "\x41\u{e9}" .. 'a\nb'"#),
+        r#"local _ = "Aé" .. 'a\nb'"#
+    );
+}
+
+#[test]
+fn numbers_and_floor_division() {
+    assert_eq!(
+        transpile("local _ = 0b1010 + 1_000 + 0xFF"),
+        "local _ = 10 + 1000 + 0xFF"
+    );
+    assert_eq!(
+        transpile("local _ = a // b * c // (d // e)"),
+        "local _ = math.floor(math.floor(a / b) * c / (math.floor(d / e)))"
+    );
+}
+
+#[test]
+fn errors() {
+    let cst = Parser::new("if true print(1) end", ParserOptions::default()).parse("");
+
+    assert_eq!(cst.to_lua51(), Err(TranspileError::HasErrors));
+}
+
+#[test]
+fn continue_in_repeat() {
+    // The body is wrapped in another loop, which would hide `done` from the
+    // condition.
+    let code = "repeat\n    local done = f()\n    if x then continue end\nuntil done";
+    let cst = Parser::new(code, ParserOptions::default()).parse("");
+
+    assert_eq!(
+        cst.to_lua51(),
+        Err(TranspileError::ContinueWithUntilLocal(Position::new(3, 6)))
+    );
+
+    // Locals declared outside of the body, or used only in the body, are fine.
+    assert_eq!(
+        transpile("local done repeat local x = f() if x then continue end until done"),
+        "local done repeat repeat local x = f() if x then break end until true until done"
+    );
+    assert_eq!(
+        transpile("repeat local done = f() until done"),
+        "repeat local done = f() until done"
+    );
+}
+
+#[test]
+fn test_code() {
+    for entry in fs::read_dir("test-code").unwrap() {
+        let code = fs::read_to_string(entry.unwrap().path()).unwrap();
+
+        transpile(&code);
+    }
+}
@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+//! Tests for [`Visitor`] and [`VisitorMut`], the visitors must see every token
+//! and trivia exactly once and in source order.
+
+use luau_parser::prelude::{
+    Expression, Parser, ParserOptions, Print, Token, TokenType, Trivia, Visit, VisitMut, Visitor,
+    VisitorMut,
+};
+use std::{fs, io, mem, path::Path};
+
+/// A visitor rebuilding the source code from the tokens and trivia it visits.
+#[derive(Default)]
+struct SourceBuilder {
+    /// The source code built so far.
+    source: String,
+
+    /// The number of expressions visited.
+    expressions: usize,
+}
+
+impl Visitor<'_> for SourceBuilder {
+    fn visit_trivia(&mut self, trivia: &Trivia) {
+        self.source += &trivia.print();
+    }
+
+    fn leave_token(&mut self, token: &Token) {
+        self.source += &token.token_type.try_as_string().unwrap_or_default();
+    }
+
+    fn visit_expression(&mut self, _: &Expression) {
+        self.expressions += 1;
+    }
+}
+
+/// A visitor renaming all identifiers called `from` to `to`.
+struct Renamer {
+    /// The old name.
+    from: &'static str,
+
+    /// The new name.
+    to: &'static str,
+}
+
+impl VisitorMut for Renamer {
+    fn visit_token(&mut self, token: &mut Token) {
+        if token.token_type == TokenType::Identifier(self.from.into()) {
+            token.token_type = TokenType::Identifier(self.to.into());
+        }
+    }
+}
+
+#[test]
+fn visits_all_tokens_and_trivia() -> io::Result<()> {
+    for entry in fs::read_dir(Path::new("test-code"))? {
+        let path = entry?.path();
+        let content = fs::read_to_string(&path)?;
+        let cst = Parser::new(&content, ParserOptions::default()).parse("");
+
+        let mut builder = SourceBuilder::default();
+        cst.visit(&mut builder);
+
+        assert_eq!(
+            builder.source,
+            content,
+            "File at '{}' failed.",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn visits_nested_nodes() {
+    let cst = Parser::new("local _ = foo(1 + 2, { a = -b })", ParserOptions::default()).parse("");
+
+    let mut builder = SourceBuilder::default();
+    cst.visit(&mut builder);
+
+    // `foo(...)`, `1 + 2`, `1`, `2`, `{ a = -b }`, `-b` and `b`.
+    assert_eq!(builder.expressions, 7);
+}
+
+/// A visitor counting expressions, without looking into tables.
+#[derive(Default)]
+struct TableSkipper {
+    /// The number of expressions visited.
+    expressions: usize,
+
+    /// Whether or not the last visited expression is a table.
+    is_table: bool,
+}
+
+impl Visitor<'_> for TableSkipper {
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.expressions += 1;
+        self.is_table = matches!(expression, Expression::Table(_));
+    }
+
+    fn skip_children(&mut self) -> bool {
+        mem::take(&mut self.is_table)
+    }
+}
+
+#[test]
+fn skips_children() {
+    let cst = Parser::new("local _ = foo(1 + 2, { a = -b })", ParserOptions::default()).parse("");
+
+    let mut skipper = TableSkipper::default();
+    cst.visit(&mut skipper);
+
+    // `foo(...)`, `1 + 2`, `1`, `2`, and `{ a = -b }`.
+    assert_eq!(skipper.expressions, 5);
+}
+
+#[test]
+fn visit_mut() {
+    let code = "local foo = 1\nprint(foo, { foo = foo })\n";
+    let mut cst = (*Parser::new(code, ParserOptions::default()).parse("")).clone();
+
+    cst.visit_mut(&mut Renamer {
+        from: "foo",
+        to: "bar",
+    });
+
+    assert_eq!(
+        cst.try_print().unwrap(),
+        "local bar = 1\nprint(bar, { bar = bar })\n"
+    );
+}